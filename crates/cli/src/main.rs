@@ -1,14 +1,15 @@
 use std::fs;
 use std::env;
 use std::path::Path;
-use coding_guide_helper_core::{Lexer, Parser, Formatter, Item, diagnose, DiagnosticConfig, DiagnosticSeverity, LoadedProjectConfig};
+use coding_guide_helper_core::{Lexer, Parser, Formatter, Item, diagnose, analyze_with_preprocessor, CStandard, DiagnosticConfig, LoadedProjectConfig};
 use coding_guide_helper_core::token::*;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     
-    // 引数解析: [プログラム名] [--project-root <path>] <filename>
+    // 引数解析: [プログラム名] [--project-root <path>] [--std <c89|c99|c11|c23>] <filename>
     let mut project_root: Option<String> = None;
+    let mut standard: Option<CStandard> = None;
     let mut filename: Option<String> = None;
     
     let mut i = 1;
@@ -16,6 +17,15 @@ fn main() {
         if args[i] == "--project-root" && i + 1 < args.len() {
             project_root = Some(args[i + 1].clone());
             i += 2;
+        } else if args[i] == "--std" && i + 1 < args.len() {
+            match args[i + 1].parse() {
+                Ok(value) => standard = Some(value),
+                Err(message) => {
+                    eprintln!("{}", message);
+                    std::process::exit(2);
+                }
+            }
+            i += 2;
         } else {
             filename = Some(args[i].clone());
             i += 1;
@@ -28,13 +38,17 @@ fn main() {
         .unwrap_or_else(|_| Path::new(filename).to_path_buf());
     
     // プロジェクト設定を読み込む
-    let loaded_config = if let Some(root) = project_root {
+    let mut loaded_config = if let Some(root) = project_root {
         LoadedProjectConfig::find_and_load_with_root(&root)
     } else {
         // ファイルのディレクトリから検索
         let file_dir = source_path.parent().unwrap_or(Path::new("."));
         LoadedProjectConfig::find_and_load_with_root(file_dir)
     };
+    // --std は設定ファイルの [preprocessor] standard より優先する
+    if let Some(standard) = standard {
+        loaded_config.config.preprocessor.standard = Some(standard);
+    }
     let config = &loaded_config.config;
     
     println!("[Project Configuration]");
@@ -42,130 +56,76 @@ fn main() {
     println!("Check function format: {}", config.diagnostics.check_function_format);
     println!();
     
-    lexer_sample(filename);
+    lexer_sample(filename, config.preprocessor.standard);
     parser_sample(filename, &loaded_config, &source_path);
     diagnostics_sample_with_config(filename, &loaded_config, &source_path);
     formatter_sample(filename, &loaded_config, &source_path);
 }
 
 // lexer_sample() 関数を修正
-fn lexer_sample(filename: &str) {
+fn lexer_sample(filename: &str, standard: Option<CStandard>) {
     println!("[Lexer Sample]");
     let contents = fs::read_to_string(filename).unwrap();
     let mut lx = Lexer::new(&contents);
+    if let Some(standard) = standard {
+        lx.set_standard(standard);
+    }
     
     while let Some(token) = lx.next_token() {
-        match token {
-            Token::BlockComment(BlockCommentToken { span }) => {
-                println!("Block comment from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::LineComment(LineCommentToken { span }) => {
-                println!("Line comment from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Include(IncludeToken { span, filename }) => {
-                println!("Include from ({}, {}) to ({}, {}): {:?} (filename: {})", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx], filename);
-            },
-            Token::Define(DefineToken { span, macro_name, macro_value }) => {
-                println!("Define from ({}, {}) to ({}, {}): {:?} (macro: {}, value: {})", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx], macro_name, macro_value);
-            },
-            Token::Typedef(TypedefToken { span }) => {
-                println!("Typedef from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Semicolon(SemicolonToken { span }) => {
-                println!("Semicolon from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Equal(EqualToken { span }) => {
-                println!("Equal from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Asterisk(AsteriskToken { span }) => {
-                println!("Asterisk from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::NumberLiteral(NumberLiteralToken { span, value }) => {
-                println!("Number literal from ({}, {}) to ({}, {}): {:?} (value: {})", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx], value);
-            },
-            Token::FloatLiteral(FloatLiteralToken { span, value }) => {
-                println!("Float literal from ({}, {}) to ({}, {}): {:?} (value: {})", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx], value);
-            },
-            Token::Ident(IdentToken { span, name }) => {
-                println!("Ident from ({}, {}) to ({}, {}): {:?} (name: {})", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx], name);
-            },
-            // 記憶域クラス指定子
-            Token::Auto(AutoToken { span }) | Token::Register(RegisterToken { span }) | Token::Static(StaticToken { span }) | 
-            Token::Extern(ExternToken { span }) => {
-                println!("Storage class from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            // 型修飾子
-            Token::Const(ConstToken { span }) | Token::Volatile(VolatileToken { span }) | Token::Restrict(RestrictToken { span }) | 
-            Token::Atomic(AtomicToken { span }) => {
-                println!("Type qualifier from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            // 型指定子
-            Token::Int(IntToken { span }) | Token::Char(CharToken { span }) | Token::Float(FloatToken { span }) | 
-            Token::Double(DoubleToken { span }) | Token::Void(VoidToken { span }) | Token::Long(LongToken { span }) | 
-            Token::Short(ShortToken { span }) | Token::Signed(SignedToken { span }) | Token::Unsigned(UnsignedToken { span }) => {
-                println!("Type specifier from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Struct(StructToken { span }) => {
-                println!("Struct from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Enum(EnumToken { span }) => {
-                println!("Enum from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Union(UnionToken { span }) => {
-                println!("Union from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::LeftBrace(LeftBraceToken { span }) => {
-                println!("LeftBrace from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::RightBrace(RightBraceToken { span }) => {
-                println!("RightBrace from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::LeftParen(LeftParenToken { span }) => {
-                println!("LeftParen from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::RightParen(RightParenToken { span }) => {
-                println!("RightParen from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Return(ReturnToken { span }) => {
-                println!("Return from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::IfKeyword(IfKeywordToken { span }) => {
-                println!("IfKeyword from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::ElseKeyword(ElseKeywordToken { span }) => {
-                println!("ElseKeyword from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::While(WhileToken { span }) => {
-                println!("While from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::For(ForToken { span }) => {
-                println!("For from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Ifdef(IfdefToken { span }) | Token::Ifndef(IfndefToken { span }) | Token::If(IfToken { span }) |
-            Token::Elif(ElifToken { span }) | Token::Else(ElseToken { span }) | Token::Endif(EndifToken { span }) => {
-                println!("Conditional directive from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            // 演算子トークン
-            Token::Plus(PlusToken { span }) | Token::Minus(MinusToken { span }) | Token::Slash(SlashToken { span }) |
-            Token::Percent(PercentToken { span }) | Token::EqualEqual(EqualEqualToken { span }) | Token::NotEqual(NotEqualToken { span }) |
-            Token::LessThan(LessThanToken { span }) | Token::LessThanOrEqual(LessThanOrEqualToken { span }) |
-            Token::GreaterThan(GreaterThanToken { span }) | Token::GreaterThanOrEqual(GreaterThanOrEqualToken { span }) |
-            Token::Ampersand(AmpersandToken { span }) | Token::AmpersandAmpersand(AmpersandAmpersandToken { span }) |
-            Token::Pipe(PipeToken { span }) | Token::PipePipe(PipePipeToken { span }) | Token::Caret(CaretToken { span }) |
-            Token::Tilde(TildeToken { span }) | Token::Exclamation(ExclamationToken { span }) |
-            Token::LeftShift(LeftShiftToken { span }) | Token::RightShift(RightShiftToken { span }) |
-            Token::LeftBracket(LeftBracketToken { span }) | Token::RightBracket(RightBracketToken { span }) |
-            Token::Question(QuestionToken { span }) | Token::Colon(ColonToken { span }) | Token::Comma(CommaToken { span }) |
-            Token::Dot(DotToken { span }) | Token::Arrow(ArrowToken { span }) |
-            Token::PlusPlus(PlusPlusToken { span }) | Token::MinusMinus(MinusMinusToken { span }) => {
-                println!("Operator from ({}, {}) to ({}, {}): {:?}", span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx]);
-            },
-            Token::Error(coding_guide_helper_core::token::ErrorToken { span, message }) => {
-                eprintln!("[LEXER ERROR] Line {}, Column {}: {}", span.start_line, span.start_column, message);
-                eprintln!("  Code: {:?}", &contents[span.byte_start_idx..span.byte_end_idx]);
-                std::process::exit(1);
-            },
+        let span = token.span();
+        if let Token::Error(ErrorToken { message, .. }) = &token {
+            eprintln!("[LEXER ERROR] Line {}, Column {}: {}", span.start_line, span.start_column, message);
+            eprintln!("  Code: {:?}", &contents[span.byte_start_idx..span.byte_end_idx]);
+            std::process::exit(1);
         }
+
+        // ペイロードを持つトークンは追加情報を表示
+        let detail = match &token {
+            Token::Include(IncludeToken { filename, .. }) => format!(" (filename: {})", filename),
+            Token::Define(DefineToken { macro_name, macro_value, .. }) => format!(" (macro: {}, value: {})", macro_name, macro_value),
+            Token::NumberLiteral(NumberLiteralToken { value, .. }) | Token::FloatLiteral(FloatLiteralToken { value, .. }) |
+            Token::StringLiteral(StringLiteralToken { value, .. }) |
+            Token::CharLiteral(CharLiteralToken { value, .. }) => format!(" (value: {})", value),
+            Token::Ident(IdentToken { name, .. }) => format!(" (name: {})", name),
+            Token::Attribute(AttributeToken { attributes, .. }) => format!(" (attributes: {:?})", attributes),
+            Token::Line(LineToken { line_number, file, .. }) => format!(" (line: {}, file: {:?})", line_number, file),
+            _ => String::new(),
+        };
+        println!("{} from ({}, {}) to ({}, {}): {:?}{}", token_kind_label(token.kind()), span.start_line, span.start_column, span.end_line, span.end_column, &contents[span.byte_start_idx..span.byte_end_idx], detail);
+    }
+}
+
+// トークンの種類を表示用のラベルに変換（同じ分類のトークンはまとめる）
+fn token_kind_label(kind: TokenKind) -> String {
+    match kind {
+        TokenKind::BlockComment => "Block comment".to_string(),
+        TokenKind::LineComment => "Line comment".to_string(),
+        TokenKind::NumberLiteral => "Number literal".to_string(),
+        TokenKind::FloatLiteral => "Float literal".to_string(),
+        TokenKind::StringLiteral => "String literal".to_string(),
+        TokenKind::CharLiteral => "Character literal".to_string(),
+        TokenKind::Attribute => "Attribute".to_string(),
+        // 記憶域クラス指定子
+        TokenKind::Auto | TokenKind::Register | TokenKind::Static | TokenKind::Extern => "Storage class".to_string(),
+        // 型修飾子
+        TokenKind::Const | TokenKind::Volatile | TokenKind::Restrict | TokenKind::Atomic => "Type qualifier".to_string(),
+        // 型指定子
+        TokenKind::Int | TokenKind::Char | TokenKind::Float | TokenKind::Double | TokenKind::Void |
+        TokenKind::Long | TokenKind::Short | TokenKind::Signed | TokenKind::Unsigned |
+        TokenKind::Bool | TokenKind::Complex | TokenKind::Imaginary => "Type specifier".to_string(),
+        TokenKind::Ifdef | TokenKind::Ifndef | TokenKind::If | TokenKind::Elif | TokenKind::Else |
+        TokenKind::Endif => "Conditional directive".to_string(),
+        // 演算子トークン
+        TokenKind::Plus | TokenKind::Minus | TokenKind::Slash | TokenKind::Percent | TokenKind::EqualEqual |
+        TokenKind::NotEqual | TokenKind::LessThan | TokenKind::LessThanOrEqual | TokenKind::GreaterThan |
+        TokenKind::GreaterThanOrEqual | TokenKind::Ampersand | TokenKind::AmpersandAmpersand | TokenKind::Pipe |
+        TokenKind::PipePipe | TokenKind::Caret | TokenKind::Tilde | TokenKind::Exclamation | TokenKind::LeftShift |
+        TokenKind::RightShift | TokenKind::LeftBracket | TokenKind::RightBracket | TokenKind::Question |
+        TokenKind::Colon | TokenKind::Comma | TokenKind::Dot | TokenKind::Arrow | TokenKind::PlusPlus |
+        TokenKind::MinusMinus | TokenKind::PlusEqual | TokenKind::MinusEqual | TokenKind::AsteriskEqual |
+        TokenKind::SlashEqual | TokenKind::PercentEqual | TokenKind::AmpersandEqual | TokenKind::PipeEqual |
+        TokenKind::CaretEqual | TokenKind::LeftShiftEqual | TokenKind::RightShiftEqual => "Operator".to_string(),
+        _ => format!("{:?}", kind),
     }
 }
 
@@ -190,14 +150,34 @@ fn print_item(item: &Item, indent: usize) {
         Item::Include { span, text, filename, .. } => {
             println!("{}Include from ({}, {}) to ({}, {}): {:?} (filename: {})", indent_str, span.start_line, span.start_column, span.end_line, span.end_column, text, filename);
         },
-        Item::Define { span, text, macro_name, macro_value, .. } => {
-            println!("{}Define from ({}, {}) to ({}, {}): {:?} (macro: {}, value: {})", indent_str, span.start_line, span.start_column, span.end_line, span.end_column, text, macro_name, macro_value);
+        Item::Define { span, text, macro_name, params, macro_value, .. } => {
+            let params = params.as_ref().map(|p| format!(", params: [{}]", p.join(", "))).unwrap_or_default();
+            println!("{}Define from ({}, {}) to ({}, {}): {:?} (macro: {}{}, value: {})", indent_str, span.start_line, span.start_column, span.end_line, span.end_column, text, macro_name, params, macro_value);
+        },
+        Item::ErrorDirective { span, text, message, .. } | Item::WarningDirective { span, text, message, .. } => {
+            let kind = if matches!(item, Item::ErrorDirective { .. }) { "ErrorDirective" } else { "WarningDirective" };
+            println!("{}{} from ({}, {}) to ({}, {}): {:?} (message: {})", indent_str, kind, span.start_line, span.start_column, span.end_line, span.end_column, text, message);
         },
-        Item::ConditionalBlock { directive_type, condition, items, start_span: _, end_span, .. } => {
+        Item::LineDirective { span, text, line_number, file, .. } => {
+            println!("{}LineDirective from ({}, {}) to ({}, {}): {:?} (line: {}, file: {:?})", indent_str, span.start_line, span.start_column, span.end_line, span.end_column, text, line_number, file);
+        },
+        Item::ConditionalBlock { directive_type, condition, items, elif_branches, else_branch, end_span, .. } => {
             println!("{}ConditionalBlock #{} {} {{", indent_str, directive_type, condition);
             for inner_item in items {
                 print_item(inner_item, indent + 1);
             }
+            for branch in elif_branches {
+                println!("{}}} #elif {} {{", indent_str, branch.condition);
+                for inner_item in &branch.items {
+                    print_item(inner_item, indent + 1);
+                }
+            }
+            if let Some(else_items) = else_branch {
+                println!("{}}} #else {{", indent_str);
+                for inner_item in else_items {
+                    print_item(inner_item, indent + 1);
+                }
+            }
             println!("{}}} // #endif at ({}, {})", indent_str, end_span.end_line, end_span.end_column);
         },
         Item::TypedefDecl { span, text, .. } => {
@@ -210,9 +190,8 @@ fn print_item(item: &Item, indent: usize) {
             println!("{}StructDecl from ({}, {}) to ({}, {}): {:?} (struct_name: {:?}, has_typedef: {})", 
                 indent_str, span.start_line, span.start_column, span.end_line, span.end_column, text, struct_name, has_typedef);
         },
-        Item::FunctionDecl { span, return_type, function_name, parameters, storage_class, .. } => {
-            let storage_str = storage_class.as_deref().unwrap_or("");
-            let prefix = if storage_str.is_empty() { String::new() } else { format!("{} ", storage_str) };
+        Item::FunctionDecl { span, return_type, function_name, parameters, storage_class, specifiers, .. } => {
+            let prefix: String = storage_class.iter().chain(specifiers).map(|word| format!("{} ", word)).collect();
             println!("{}FunctionDecl from ({}, {}) to ({}, {}): {}{} {} {}",
                 indent_str, span.start_line, span.start_column, span.end_line, span.end_column, 
                 prefix, return_type, function_name, parameters);
@@ -243,12 +222,7 @@ fn diagnostics_sample(filename: &str) {
         println!("No issues found.");
     } else {
         for diag in diagnostics {
-            let severity_str = match diag.severity {
-                DiagnosticSeverity::Error => "ERROR",
-                DiagnosticSeverity::Warning => "WARNING",
-                DiagnosticSeverity::Information => "INFO",
-                DiagnosticSeverity::Hint => "HINT",
-            };
+            let severity_str = diag.severity.as_str().to_uppercase();
             println!("[{}] {}: {} (line {}, column {})", 
                 diag.code, 
                 severity_str, 
@@ -267,33 +241,34 @@ fn diagnostics_sample_with_config(
 ) {
     println!("\n[Diagnostics Sample]");
     let contents = fs::read_to_string(filename).unwrap();
-    let lx = Lexer::new(&contents);
-    let mut parser = Parser::new_with_config(lx, project_config.to_preprocessor_config());
-    if let Some(dir) = source_path.parent() {
-        parser.set_current_file_dir(dir);
-    }
-    let tu = parser.parse();
     
     let config = project_config.to_diagnostic_config_with_path(Some(source_path));
-    let diagnostics = diagnose(&tu, &config);
+    let result = analyze_with_preprocessor(&contents, &config, project_config.to_preprocessor_config());
+    let tu = &result.translation_unit;
+    let diagnostics: Vec<_> = result.parse_diagnostics.iter().chain(&result.diagnostics).collect();
     
     if diagnostics.is_empty() {
         println!("No issues found.");
     } else {
         for diag in diagnostics {
-            let severity_str = match diag.severity {
-                DiagnosticSeverity::Error => "ERROR",
-                DiagnosticSeverity::Warning => "WARNING",
-                DiagnosticSeverity::Information => "INFO",
-                DiagnosticSeverity::Hint => "HINT",
+            let severity_str = diag.severity.as_str().to_uppercase();
+            // #line ディレクティブがある場合は論理行番号で表示する
+            let (line, file) = tu.logical_position(diag.span.start_line);
+            let location = match file {
+                Some(file) => format!("{}: line {}", file, line),
+                None => format!("line {}", line),
             };
-            println!("[{}] {}: {} (line {}, column {})", 
+            println!("[{}] {}: {} ({}, column {})", 
                 diag.code, 
                 severity_str, 
                 diag.message,
-                diag.span.start_line,
+                location,
                 diag.span.start_column
             );
+            for (span, message) in &diag.related {
+                let (line, _) = tu.logical_position(span.start_line);
+                println!("    note: {} (line {}, column {})", message, line, span.start_column);
+            }
         }
     }
 }
@@ -308,7 +283,7 @@ fn formatter_sample(filename: &str, project_config: &LoadedProjectConfig, source
     }
     let tu = parser.parse();
     
-    let formatter = Formatter::new();
+    let formatter = Formatter::new_with_config(&project_config.config.formatting);
     let formatted = formatter.format_tu(&tu);
     println!("{}", formatted);
 }
@@ -1,5 +1,26 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use coding_guide_helper_core::{lexer::Lexer, parser::Parser};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId, Throughput};
+use coding_guide_helper_core::{lexer::Lexer, parser::Parser, token::Token};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// 確保回数を数えるアロケータ（識別子のインターンによる確保回数の削減を確認するため）
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
 
 // 小規模なCコードのパース
 fn bench_parse_small(c: &mut Criterion) {
@@ -171,6 +192,85 @@ int main() {
     });
 }
 
+// 識別子が多いコードのトークン化
+// 同じ識別子が繰り返し現れるため、インターンにより識別子ごとの文字列確保が初出時のみになる
+fn bench_lexer_identifier_heavy(c: &mut Criterion) {
+    let mut input = String::new();
+    for i in 0..2_000 {
+        input.push_str(&format!("result = counter + offset * scale - base_value_{};\n", i % 20));
+    }
+
+    // 1 回のトークン化での確保回数と識別子トークン数を表示する
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let mut lexer = Lexer::from_borrowed(&input);
+    let mut identifiers = 0;
+    while let Some(token) = lexer.next_token() {
+        if matches!(token, Token::Ident(_)) {
+            identifiers += 1;
+        }
+    }
+    drop(lexer);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!("lexer_identifier_heavy: {} identifier tokens, {} allocations", identifiers, allocations);
+
+    c.bench_function("lexer_identifier_heavy", |b| {
+        b.iter(|| {
+            let mut lexer = Lexer::from_borrowed(black_box(&input));
+            let mut count = 0;
+            while lexer.next_token().is_some() {
+                count += 1;
+            }
+            count
+        })
+    });
+}
+
+// 大きな合成ファイルのトークン化（入力サイズに対して線形であることの確認用）
+fn bench_lexer_large_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lexer_large_file");
+
+    for lines in [1_000, 10_000].iter() {
+        let input = generate_source(*lines);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(lines), &input, |b, input| {
+            b.iter(|| {
+                let mut lexer = Lexer::from_borrowed(black_box(input));
+                let mut count = 0;
+                while lexer.next_token().is_some() {
+                    count += 1;
+                }
+                count
+            })
+        });
+    }
+    group.finish();
+}
+
+// 関数・構造体・プリプロセッサ・コメントを含む lines 行の C コードを生成する
+// （src/tests/lexer_performance_tests.rs の生成器と同じ内容）
+fn generate_source(lines: usize) -> String {
+    const BLOCK: [&str; 10] = [
+        "/* 関数 {i} */",
+        "#define VALUE_{i} ({i} + 1)",
+        "struct S{i} { int x; char name[16]; };",
+        "static int func_{i}(int a, int b) {",
+        "    // 加算",
+        "    if (a > b) {",
+        "        return a - b;",
+        "    }",
+        "    return a + b * VALUE_{i};",
+        "}",
+    ];
+
+    let mut input = String::new();
+    for line in 0..lines {
+        let template = BLOCK[line % BLOCK.len()];
+        input.push_str(&template.replace("{i}", &(line / BLOCK.len()).to_string()));
+        input.push('\n');
+    }
+    input
+}
+
 criterion_group!(
     benches,
     bench_parse_small,
@@ -178,6 +278,8 @@ criterion_group!(
     bench_parse_structs,
     bench_parse_preprocessor,
     bench_parse_by_size,
-    bench_lexer_only
+    bench_lexer_only,
+    bench_lexer_identifier_heavy,
+    bench_lexer_large_file
 );
 criterion_main!(benches);
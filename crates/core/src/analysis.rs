@@ -0,0 +1,61 @@
+use crate::ast::TranslationUnit;
+use crate::config::PreprocessorConfig;
+use crate::diagnostics::{
+    apply_rule_overrides, diagnose_missing_prototypes, diagnose_with_source, sort_by_position, Diagnostic, DiagnosticConfig,
+};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::type_table::TypeTable;
+use std::collections::HashMap;
+
+/// 解析結果（AST・コーディング規約の診断・パース時の診断をまとめたもの）
+#[derive(Debug)]
+pub struct AnalysisResult {
+    pub translation_unit: TranslationUnit,
+    pub diagnostics: Vec<Diagnostic>,        // コーディング規約の診断
+    pub parse_diagnostics: Vec<Diagnostic>,  // 字句解析・構文解析で検出したエラー
+}
+
+/// ソースコードに対して 字句解析 → 構文解析 → 診断 を一括で実行する
+///
+/// CLI / LSP などの組み込み側はこの関数を入口として使う。
+pub fn analyze(source: &str, config: &DiagnosticConfig) -> AnalysisResult {
+    analyze_with_preprocessor(source, config, PreprocessorConfig::default())
+}
+
+/// ソースコードを構文解析し、AST・型テーブル・マクロ（マクロ名 → 値）を返す
+///
+/// 診断は行わない。typedef 名の解決結果を使うツール向けの入口。
+pub fn parse_with_types(source: &str) -> (TranslationUnit, TypeTable, HashMap<String, String>) {
+    Parser::new(Lexer::from_borrowed(source)).into_parts()
+}
+
+/// プリプロセッサ設定（define / includeパス）を指定して解析する
+pub fn analyze_with_preprocessor(
+    source: &str,
+    config: &DiagnosticConfig,
+    preprocessor_config: PreprocessorConfig,
+) -> AnalysisResult {
+    let mut parser = Parser::new_with_config(Lexer::from_borrowed(source), preprocessor_config);
+    // 診断対象ファイルのディレクトリを基準に #include を解決する
+    if let Some(dir) = config.source_path.as_ref().and_then(|p| p.parent()) {
+        parser.set_current_file_dir(dir);
+    }
+
+    let translation_unit = parser.parse();
+    let mut parse_diagnostics = parser.take_diagnostics();
+    apply_rule_overrides(&mut parse_diagnostics, config);
+    let mut diagnostics = diagnose_with_source(&translation_unit, config, source);
+    // ヘッダーの関数宣言を使う診断は、ヘッダーを解析したパーサーの情報が必要なためここで行う
+    let prototype_diagnostics = diagnose_missing_prototypes(&translation_unit, config, parser.header_functions());
+    if !prototype_diagnostics.is_empty() {
+        diagnostics.extend(prototype_diagnostics);
+        sort_by_position(&mut diagnostics);
+    }
+
+    AnalysisResult {
+        translation_unit,
+        diagnostics,
+        parse_diagnostics,
+    }
+}
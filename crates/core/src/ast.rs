@@ -2,9 +2,11 @@ use crate::span::Span;
 use crate::trivia::Trivia;
 use crate::type_system::Type;
 use crate::expression::Expression;
+use std::collections::HashMap;
 
 /// ステートメント（文）
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Statement {
     /// 変数宣言文
     VarDecl {
@@ -36,6 +38,12 @@ pub enum Statement {
         body: Vec<Statement>,
         span: Span,
     },
+    /// do-while文
+    DoWhile {
+        body: Vec<Statement>,
+        condition: Expression,
+        span: Span,
+    },
     /// for文
     For {
         init: Option<Box<Statement>>,
@@ -55,8 +63,87 @@ pub enum Statement {
     },
 }
 
+impl Statement {
+    pub fn span(&self) -> &Span {
+        match self {
+            Statement::VarDecl { span, .. }
+            | Statement::Expression { span, .. }
+            | Statement::Return { span, .. }
+            | Statement::If { span, .. }
+            | Statement::While { span, .. }
+            | Statement::DoWhile { span, .. }
+            | Statement::For { span, .. }
+            | Statement::Block { span, .. }
+            | Statement::Empty { span } => span,
+        }
+    }
+
+    /// デバッグ表示用に、文をC言語のソースとして1行で出力する
+    ///
+    /// 例: `if (x) { return 1; } else { y = 2; }`、`for (int i = 0; i < n; i++) { sum += i; }`
+    pub fn pretty(&self) -> String {
+        match self {
+            Statement::VarDecl { var_type, var_name, initializer, .. } => {
+                let mut text = match var_type {
+                    Some(ty) => format!("{} {}", ty, var_name),
+                    None => var_name.clone(),
+                };
+                if let Some(init) = initializer {
+                    text.push_str(&format!(" = {}", init.pretty()));
+                }
+                text.push(';');
+                text
+            }
+            Statement::Expression { expr, .. } => format!("{};", expr.pretty()),
+            Statement::Return { value: Some(value), .. } => format!("return {};", value.pretty()),
+            Statement::Return { value: None, .. } => "return;".to_string(),
+            Statement::If { condition, then_block, else_block, .. } => {
+                let mut text = format!("if ({}) {}", condition.pretty(), pretty_block(then_block));
+                match else_block.as_deref() {
+                    // else if はブロックで囲まずに続ける
+                    Some([nested @ Statement::If { .. }]) => text.push_str(&format!(" else {}", nested.pretty())),
+                    Some(else_block) => text.push_str(&format!(" else {}", pretty_block(else_block))),
+                    None => {}
+                }
+                text
+            }
+            Statement::While { condition, body, .. } => {
+                format!("while ({}) {}", condition.pretty(), pretty_block(body))
+            }
+            Statement::DoWhile { body, condition, .. } => {
+                format!("do {} while ({});", pretty_block(body), condition.pretty())
+            }
+            Statement::For { init, condition, update, body, .. } => {
+                // 初期化部の文は末尾の ';' を取り除いて埋め込む
+                let init = init.as_ref().map(|init| init.pretty()).unwrap_or_default();
+                let condition = condition.as_ref().map(|c| format!(" {}", c.pretty())).unwrap_or_default();
+                let update = update.as_ref().map(|u| format!(" {}", u.pretty())).unwrap_or_default();
+                format!(
+                    "for ({};{};{}) {}",
+                    init.trim_end_matches(';'),
+                    condition,
+                    update,
+                    pretty_block(body)
+                )
+            }
+            Statement::Block { statements, .. } => pretty_block(statements),
+            Statement::Empty { .. } => ";".to_string(),
+        }
+    }
+}
+
+/// `{ 文 文 }` の形でブロックを1行に出力する（空のブロックは `{}`）
+fn pretty_block(statements: &[Statement]) -> String {
+    if statements.is_empty() {
+        return "{}".to_string();
+    }
+    let body: Vec<String> = statements.iter().map(Statement::pretty).collect();
+    format!("{{ {} }}", body.join(" "))
+}
+
 /// struct のメンバー情報
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StructMember {
     pub name: String,
     pub member_type: Option<Type>,
@@ -65,7 +152,8 @@ pub struct StructMember {
 }
 
 /// union のメンバー情報
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnionMember {
     pub name: String,
     pub member_type: Option<Type>,
@@ -73,20 +161,89 @@ pub struct UnionMember {
 }
 
 /// enum の列挙子情報
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumVariant {
     pub name: String,
     pub value: Option<i64>,  // 明示的な値指定（例: RED = 0）
+    pub has_explicit_value: bool,  // = で値を指定しているか（定数として評価できなかった場合も true）
     pub span: Span,
 }
 
-#[derive(Debug)]
+/// 宣言の初期化子（`=` の右側）
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Initializer {
+    /// 単一の値（式のテキスト。指示付き初期化子は `.x = 1` や `[2] = 5` 全体）
+    Value {
+        text: String,
+        span: Span,
+    },
+    /// 波括弧で囲まれた初期化子リスト `{1, 2, {3, 4}}`
+    List {
+        elements: Vec<Initializer>,
+        span: Span,
+    },
+}
+
+impl Initializer {
+    /// 初期化子リストの要素数（単一の値は None）
+    pub fn element_count(&self) -> Option<usize> {
+        match self {
+            Initializer::List { elements, .. } => Some(elements.len()),
+            Initializer::Value { .. } => None,
+        }
+    }
+
+    pub fn span(&self) -> &Span {
+        match self {
+            Initializer::Value { span, .. } | Initializer::List { span, .. } => span,
+        }
+    }
+}
+
+/// 条件コンパイルブロックの #elif ブランチ
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElifBranch {
+    pub condition: String,
+    pub condition_result: bool,  // 条件評価結果
+    pub span: Span,  // #elif ディレクティブの位置
+    pub items: Vec<Item>,
+}
+
+/// ファイルが宣言するシンボルの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SymbolKind {
+    Function,
+    Variable,
+    Struct,
+    Union,
+    Enum,
+    Typedef,
+    EnumVariant,
+    Macro,
+}
+
+/// ファイルが宣言するシンボル（インデックス作成やドキュメントシンボル用）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub span: Span,  // 宣言全体の範囲（列挙子は列挙子自身の範囲）
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TranslationUnit {
     pub items: Vec<Item>,
     pub leading_trivia: Trivia,  // ファイル先頭のコメント（ヘッダーコメントなど）
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Item {
     // BlockComment と LineComment は削除（triviaに移行）
     Include { 
@@ -99,21 +256,45 @@ pub enum Item {
         span: Span, 
         text: String, 
         macro_name: String, 
+        params: Option<Vec<String>>,  // 関数形式マクロの引数（オブジェクト形式マクロは None）
         macro_value: String,
         trivia: Trivia,
     },
+    ErrorDirective {
+        span: Span,
+        text: String,
+        message: String,
+        trivia: Trivia,
+    },
+    WarningDirective {
+        span: Span,
+        text: String,
+        message: String,
+        trivia: Trivia,
+    },
+    LineDirective {
+        span: Span,
+        text: String,
+        line_number: usize,  // 次の行の論理行番号（1始まり）
+        file: Option<String>,
+        trivia: Trivia,
+    },
     ConditionalBlock { 
-        directive_type: String,
+        directive_type: String,  // ifdef / ifndef / if
         condition: String,
         condition_result: bool,  // 条件評価結果
-        items: Vec<Item>,
+        items: Vec<Item>,  // 最初のブランチ（#ifdef / #ifndef / #if）のアイテム
+        elif_branches: Vec<ElifBranch>,
+        else_branch: Option<Vec<Item>>,
+        else_span: Option<Span>,  // #else ディレクティブの位置
         start_span: Span,
-        end_span: Span,
+        end_span: Span,  // #endif ディレクティブの位置（#endif がない場合は start_span）
         trivia: Trivia,
     },
     TypedefDecl { 
         span: Span, 
         text: String,
+        attributes: Vec<String>,  // `__attribute__((...))` / `[[...]]` で指定された属性（"packed" など）
         trivia: Trivia,
     },
     VarDecl { 
@@ -121,7 +302,10 @@ pub enum Item {
         text: String,
         var_name: String,
         has_initializer: bool,
+        initializer: Option<Initializer>,  // 解析できた初期化子（has_initializer が true でも None の場合がある）
         var_type: Option<Type>,
+        specifiers: Vec<String>,  // inline / _Noreturn / _Thread_local
+        attributes: Vec<String>,  // `__attribute__((...))` / `[[...]]` で指定された属性（"packed" など）
         trivia: Trivia,
     },
     StructDecl {
@@ -131,6 +315,7 @@ pub enum Item {
         has_typedef: bool,
         variable_names: Vec<String>,
         members: Vec<StructMember>,  // メンバー情報
+        attributes: Vec<String>,  // `__attribute__((...))` / `[[...]]` で指定された属性（"packed" など）
         trivia: Trivia,
     },
     EnumDecl {
@@ -140,6 +325,7 @@ pub enum Item {
         has_typedef: bool,
         variable_names: Vec<String>,
         variants: Vec<EnumVariant>,  // 列挙子情報
+        attributes: Vec<String>,  // `__attribute__((...))` / `[[...]]` で指定された属性（"packed" など）
         trivia: Trivia,
     },
     UnionDecl {
@@ -149,6 +335,7 @@ pub enum Item {
         has_typedef: bool,
         variable_names: Vec<String>,
         members: Vec<UnionMember>,  // メンバー情報
+        attributes: Vec<String>,  // `__attribute__((...))` / `[[...]]` で指定された属性（"packed" など）
         trivia: Trivia,
     },
     FunctionDecl {
@@ -157,8 +344,196 @@ pub enum Item {
         return_type: String,
         function_name: String,
         parameters: String,
+        parameter_list: Vec<(Type, String)>,  // 引数を型と名前に分解したもの（名前のない引数・解析できない引数は含まない）
         storage_class: Option<String>,
+        specifiers: Vec<String>,  // inline / _Noreturn / _Thread_local
         body: Option<Vec<Statement>>,  // 関数本体（定義の場合のみ）
+        attributes: Vec<String>,  // `__attribute__((...))` / `[[...]]` で指定された属性（"packed" など）
         trivia: Trivia,
     },
 }
+
+impl Item {
+    /// 配列変数の要素数
+    ///
+    /// 最初の次元の要素数が定数式ならその値、`[]` なら初期化子リストの要素数から推定する。
+    /// 配列でない場合や、要素数がマクロを含む式で決まらない場合は None。
+    pub fn array_length(&self) -> Option<usize> {
+        let Item::VarDecl { var_type: Some(ty), initializer, .. } = self else {
+            return None;
+        };
+        let dimension = ty.array_dimensions.first()?;
+        match &dimension.size {
+            Some(size) => crate::const_eval::eval_const_str(size, &HashMap::new()).and_then(|n| usize::try_from(n).ok()),
+            None => initializer.as_ref()?.element_count(),
+        }
+    }
+
+    /// ConditionalBlock の各ブランチ（#if / #elif / #else の順）のアイテムを返す
+    /// ConditionalBlock 以外は空
+    pub fn branches(&self) -> Vec<&[Item]> {
+        let Item::ConditionalBlock { items, elif_branches, else_branch, .. } = self else {
+            return Vec::new();
+        };
+        let mut branches = vec![items.as_slice()];
+        branches.extend(elif_branches.iter().map(|branch| branch.items.as_slice()));
+        branches.extend(else_branch.as_deref());
+        branches
+    }
+}
+
+impl TranslationUnit {
+    /// 条件ブロック（#ifdef など）の中も含めた全アイテムを出現順に列挙する
+    ///
+    /// `ConditionalBlock` 自身も列挙に含まれ、その直後に全ブランチの子アイテムが続く。
+    pub fn all_items(&self) -> Vec<&Item> {
+        fn collect<'a>(items: &'a [Item], result: &mut Vec<&'a Item>) {
+            for item in items {
+                result.push(item);
+                for children in item.branches() {
+                    collect(children, result);
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        collect(&self.items, &mut result);
+        result
+    }
+
+    /// 条件ブロックを、条件が成立した分岐のアイテムだけに置き換える（入れ子のブロックも同様）
+    ///
+    /// 最初に条件が成立した分岐（#if → #elif → #else の順）を採用し、
+    /// どの分岐も成立しない場合はブロックごと取り除く。
+    pub fn prune_inactive_branches(&mut self) {
+        fn prune(items: Vec<Item>) -> Vec<Item> {
+            let mut result = Vec::with_capacity(items.len());
+            for item in items {
+                match item {
+                    Item::ConditionalBlock { condition_result, items, elif_branches, else_branch, .. } => {
+                        let taken = if condition_result {
+                            Some(items)
+                        } else if let Some(branch) = elif_branches.into_iter().find(|b| b.condition_result) {
+                            Some(branch.items)
+                        } else {
+                            else_branch
+                        };
+                        if let Some(taken) = taken {
+                            result.extend(prune(taken));
+                        }
+                    }
+                    other => result.push(other),
+                }
+            }
+            result
+        }
+
+        self.items = prune(std::mem::take(&mut self.items));
+    }
+
+    /// #line ディレクティブを反映した論理行番号（0始まり）とファイル名を返す
+    ///
+    /// 物理行番号（0始まり）を受け取り、それより前にある最後の #line を基準に換算する。
+    /// #line より前の行は物理行番号をそのまま返す。
+    pub fn logical_position(&self, physical_line: usize) -> (usize, Option<&str>) {
+        let mut logical_line = physical_line;
+        let mut file = None;
+        for item in self.all_items() {
+            if let Item::LineDirective { span, line_number, file: directive_file, .. } = item {
+                // ディレクティブの行自体や、それより前の行には影響しない
+                if span.start_line >= physical_line || span.end_line > physical_line {
+                    break;
+                }
+                logical_line = line_number.saturating_sub(1) + (physical_line - span.end_line);
+                if let Some(name) = directive_file {
+                    file = Some(name.as_str());
+                }
+            }
+        }
+        (logical_line, file)
+    }
+
+    /// ファイルが宣言するシンボル（関数・グローバル変数・型・列挙子・マクロ）を出現順に列挙する
+    ///
+    /// 条件ブロックの中は分岐の有効・無効に関係なくすべて含む。
+    /// struct / union / enum は、タグ名・typedef 名・同時に宣言した変数名をそれぞれ別のシンボルとして返す。
+    pub fn symbols(&self) -> Vec<Symbol> {
+        let mut symbols = Vec::new();
+        let mut push = |name: &str, kind: SymbolKind, span: &Span| {
+            symbols.push(Symbol { name: name.to_string(), kind, span: span.clone() });
+        };
+
+        for item in self.all_items() {
+            match item {
+                Item::Define { span, macro_name, .. } => push(macro_name, SymbolKind::Macro, span),
+                Item::TypedefDecl { span, text, .. } => {
+                    for name in extract_typedef_names(text) {
+                        push(&name, SymbolKind::Typedef, span);
+                    }
+                }
+                Item::VarDecl { span, var_name, .. } => push(var_name, SymbolKind::Variable, span),
+                Item::FunctionDecl { span, function_name, .. } => push(function_name, SymbolKind::Function, span),
+                Item::StructDecl { span, text, struct_name: tag, has_typedef, variable_names, .. }
+                | Item::UnionDecl { span, text, union_name: tag, has_typedef, variable_names, .. }
+                | Item::EnumDecl { span, text, enum_name: tag, has_typedef, variable_names, .. } => {
+                    let tag_kind = match item {
+                        Item::StructDecl { .. } => SymbolKind::Struct,
+                        Item::UnionDecl { .. } => SymbolKind::Union,
+                        _ => SymbolKind::Enum,
+                    };
+                    if let Some(tag) = tag {
+                        push(tag, tag_kind, span);
+                    }
+                    if *has_typedef {
+                        for name in extract_typedef_names(text) {
+                            push(&name, SymbolKind::Typedef, span);
+                        }
+                    } else {
+                        for name in variable_names {
+                            push(name, SymbolKind::Variable, span);
+                        }
+                    }
+                    if let Item::EnumDecl { variants, .. } = item {
+                        for variant in variants {
+                            push(&variant.name, SymbolKind::EnumVariant, &variant.span);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        symbols
+    }
+}
+
+/// typedef 宣言のテキストから定義される型名を取り出す
+/// 例: "typedef struct { int x; } Point, *PointPtr;" -> ["Point", "PointPtr"]
+/// 例: "typedef void (*Callback)(int);" -> ["Callback"]
+pub(crate) fn extract_typedef_names(text: &str) -> Vec<String> {
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let body = text.trim().trim_end_matches(';');
+    let declarators = match body.rfind('}') {
+        Some(close) => &body[close + 1..],
+        None => body,
+    };
+
+    declarators
+        .split(',')
+        .filter_map(|declarator| {
+            // 関数ポインタは "(*" の直後が型名
+            let candidate = match declarator.find("(*") {
+                Some(pos) => declarator[pos + 2..].split(|c: char| !is_ident_char(c)).find(|word| !word.is_empty()),
+                None => declarator
+                    .split('[')
+                    .next()
+                    .unwrap_or("")
+                    .split(|c: char| !is_ident_char(c))
+                    .rfind(|word| !word.is_empty()),
+            };
+            candidate
+                .filter(|name| !matches!(*name, "typedef" | "const" | "volatile"))
+                .map(|name| name.to_string())
+        })
+        .collect()
+}
@@ -1,9 +1,9 @@
-use serde::{Deserialize, Serialize};
+use crate::editorconfig::EditorConfigIndent;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use std::fs;
 
 /// Windows拡張パスプレフィックス (\\?\) を削除
-#[cfg(windows)]
+#[cfg(all(windows, feature = "config"))]
 fn strip_extended_path_prefix(path: PathBuf) -> PathBuf {
     path.to_string_lossy()
         .strip_prefix(r"\\?\")
@@ -11,19 +11,23 @@ fn strip_extended_path_prefix(path: PathBuf) -> PathBuf {
         .unwrap_or(path)
 }
 
-#[cfg(not(windows))]
+#[cfg(all(not(windows), feature = "config"))]
 fn strip_extended_path_prefix(path: PathBuf) -> PathBuf {
     path
 }
 
 /// プロジェクト設定ファイル (coding-guide.toml)
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(default)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(default))]
 pub struct ProjectConfig {
     pub diagnostics: DiagnosticsConfig,
     pub file_header: FileHeaderConfig,
     pub formatting: FormattingConfig,
     pub preprocessor: PreprocessorConfig,
+    pub rules: BTreeMap<String, RuleLevel>,  // 診断コードごとの有効・無効と重大度（例: CGH006 = "off"）
+    #[cfg_attr(feature = "config", serde(skip))]
+    pub formatting_keys: Vec<String>,  // 設定ファイルの [formatting] で明示的に指定されたキー（.editorconfig より優先する）
 }
 
 impl Default for ProjectConfig {
@@ -33,22 +37,81 @@ impl Default for ProjectConfig {
             file_header: FileHeaderConfig::default(),
             formatting: FormattingConfig::default(),
             preprocessor: PreprocessorConfig::default(),
+            rules: BTreeMap::new(),
+            formatting_keys: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(default)]
+/// [rules] テーブルで診断コードごとに指定する値
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "snake_case"))]
+pub enum RuleLevel {
+    Off,      // チェックを無効にし、そのコードの診断を出さない
+    On,       // チェックを有効にする（重大度は既定のまま）
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(default))]
 pub struct DiagnosticsConfig {
     pub check_file_header: bool,
     pub check_function_format: bool,
     pub check_type_safety: bool,
     pub check_storage_class_order: bool,
     pub check_macro_parentheses: bool,
+    pub check_duplicate_macros: bool,
+    pub check_include_guard: bool,
     pub check_global_var_naming: bool,
     pub check_global_var_type_prefix: bool,
     pub check_local_var_type_prefix: bool,
+    pub check_empty_loop_body: bool,
+    pub max_function_statements: Option<usize>,
+    pub max_cyclomatic_complexity: Option<usize>,
+    pub max_nesting_depth: Option<usize>,
+    pub check_const_pointer_params: bool,
+    pub check_function_naming: bool,
+    pub function_naming_style: NamingStyle,
+    pub check_type_naming: bool,
+    pub type_naming_style: NamingStyle,
+    pub check_enum_monotonic: bool,
+    pub check_mixed_indentation: bool,
+    pub check_implicit_int: bool,
+    pub check_empty_aggregate: bool,
+    pub check_short_identifiers: bool,
+    pub min_identifier_length: usize,
+    pub allow_loop_index_names: bool,
+    pub warn_partial_array_init: bool,
+    pub warn_empty_return: bool,
+    pub operator_spacing_classes: Vec<OperatorClass>,
+    pub check_dead_preprocessor: bool,
+    pub check_macro_argument_parentheses: bool,
+    pub check_include_order: bool,
+    pub check_typedef_shadowing: bool,
+    pub check_redundant_qualifiers: bool,
+    pub check_missing_prototypes: bool,
+    pub check_array_init_size: bool,
+    pub check_plain_char_numeric: bool,
+    pub check_declarations_after_statements: bool,
+    pub check_continuation_indent: bool,
+    pub check_void_return_value: bool,
+    pub check_unused_parameters: bool,
+    pub check_unused_locals: bool,
+    pub check_use_before_declaration: bool,
+    pub check_hex_unsigned_suffix: bool,
+    pub check_octal_literals: bool,
+    pub check_consistent_brace_style: bool,
+    pub check_operator_spacing: bool,
+    pub check_no_tabs: bool,
+    pub check_packed_struct_comment: bool,
+    pub check_non_ascii: bool,
     pub check_preprocessor_indent: bool,
+    pub preprocessor_indent_style: PreprocessorIndentStyle,
     pub check_indent_style: bool,
     pub check_include_dir: bool,
     pub check_src_dir: bool,
@@ -63,10 +126,53 @@ impl Default for DiagnosticsConfig {
             check_type_safety: true,
             check_storage_class_order: true,
             check_macro_parentheses: true,
+            check_duplicate_macros: true,
+            check_include_guard: true,
             check_global_var_naming: true,
             check_global_var_type_prefix: true,
             check_local_var_type_prefix: true,
+            check_empty_loop_body: true,
+            max_function_statements: None,
+            max_cyclomatic_complexity: None,
+            max_nesting_depth: None,
+            check_const_pointer_params: true,
+            check_function_naming: false,
+            function_naming_style: NamingStyle::SnakeCase,
+            check_type_naming: false,
+            type_naming_style: NamingStyle::PascalCase,
+            check_enum_monotonic: false,
+            check_mixed_indentation: true,
+            check_implicit_int: true,
+            check_empty_aggregate: true,
+            check_short_identifiers: false,
+            min_identifier_length: 3,
+            allow_loop_index_names: true,
+            warn_partial_array_init: false,
+            warn_empty_return: false,
+            operator_spacing_classes: OperatorClass::ALL.to_vec(),
+            check_dead_preprocessor: true,
+            check_macro_argument_parentheses: true,
+            check_include_order: false,
+            check_typedef_shadowing: true,
+            check_redundant_qualifiers: true,
+            check_missing_prototypes: false,
+            check_array_init_size: true,
+            check_plain_char_numeric: false,
+            check_declarations_after_statements: false,
+            check_continuation_indent: false,
+            check_void_return_value: true,
+            check_unused_parameters: true,
+            check_unused_locals: true,
+            check_use_before_declaration: true,
+            check_hex_unsigned_suffix: false,
+            check_octal_literals: true,
+            check_consistent_brace_style: false,
+            check_operator_spacing: false,
+            check_no_tabs: false,
+            check_packed_struct_comment: false,
+            check_non_ascii: false,
             check_preprocessor_indent: true,
+            preprocessor_indent_style: PreprocessorIndentStyle::ColumnZero,
             check_indent_style: true,
             check_include_dir: true,
             check_src_dir: true,
@@ -75,8 +181,9 @@ impl Default for DiagnosticsConfig {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(default)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(default))]
 pub struct FileHeaderConfig {
     pub required_fields: Vec<String>,
     pub template: Option<String>,
@@ -95,8 +202,9 @@ impl Default for FileHeaderConfig {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "snake_case"))]
 pub enum IndentStyle {
     Tabs,
     Spaces,
@@ -108,13 +216,137 @@ impl Default for IndentStyle {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(default)]
+/// 宣言でのポインタの `*` の位置
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "snake_case"))]
+pub enum PointerStyle {
+    RightOfType,  // int* p
+    LeftOfName,   // int *p
+    Spaced,       // int * p
+}
+
+/// プリプロセッサディレクティブのインデントのスタイル（CGH008）
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "snake_case"))]
+pub enum PreprocessorIndentStyle {
+    #[default]
+    ColumnZero,      // #if を常に行頭に書く
+    HashColumnZero,  // # は行頭、キーワードをネストに合わせてインデント（#  if）
+    Indented,        // # ごとネストに合わせてインデント（  #if）
+}
+
+/// 前後の空白をチェックする二項演算子の分類（CGH047）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "snake_case"))]
+pub enum OperatorClass {
+    Arithmetic,  // + - * / %
+    Comparison,  // == != < <= > >=
+    Logical,     // && ||
+    Bitwise,     // & | ^ << >>
+    Assignment,  // = += -= など
+}
+
+impl OperatorClass {
+    pub const ALL: [OperatorClass; 5] = [
+        OperatorClass::Arithmetic,
+        OperatorClass::Comparison,
+        OperatorClass::Logical,
+        OperatorClass::Bitwise,
+        OperatorClass::Assignment,
+    ];
+}
+
+/// 識別子の命名規則
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "snake_case"))]
+pub enum NamingStyle {
+    SnakeCase,       // my_function
+    CamelCase,       // myFunction
+    PascalCase,      // MyFunction
+    UpperSnakeCase,  // MY_FUNCTION
+    Regex(NamingPattern),   // 任意の正規表現（名前全体に一致する必要がある）
+}
+
+impl std::fmt::Display for NamingStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NamingStyle::SnakeCase => write!(f, "snake_case"),
+            NamingStyle::CamelCase => write!(f, "camelCase"),
+            NamingStyle::PascalCase => write!(f, "PascalCase"),
+            NamingStyle::UpperSnakeCase => write!(f, "UPPER_SNAKE_CASE"),
+            NamingStyle::Regex(pattern) => write!(f, "正規表現 '{}'", pattern.as_str()),
+        }
+    }
+}
+
+/// 命名規則の正規表現
+///
+/// 設定の読み込み時にコンパイルするため、不正なパターンは設定ファイルのエラーになる。
+/// 比較・シリアライズは元のパターン文字列で行う。
+#[derive(Debug, Clone)]
+pub struct NamingPattern {
+    pattern: String,
+    regex: regex::Regex,  // 名前全体に一致させるため ^(?:...)$ で囲んだもの
+}
+
+impl NamingPattern {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let regex = regex::Regex::new(&format!("^(?:{})$", pattern))
+            .map_err(|e| format!("命名規則の正規表現 '{}' が不正です: {}", pattern, e))?;
+        Ok(NamingPattern { pattern: pattern.to_string(), regex })
+    }
+
+    /// 設定ファイルに書かれたパターン
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// 名前全体がパターンに一致するか
+    pub fn is_match(&self, name: &str) -> bool {
+        self.regex.is_match(name)
+    }
+}
+
+impl PartialEq for NamingPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+#[cfg(feature = "config")]
+impl serde::Serialize for NamingPattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.pattern)
+    }
+}
+
+#[cfg(feature = "config")]
+impl<'de> serde::Deserialize<'de> for NamingPattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = <String as serde::Deserialize>::deserialize(deserializer)?;
+        NamingPattern::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(default))]
 pub struct FormattingConfig {
     pub add_file_header: bool,
     pub use_tabs: bool,  // 4スペースをタブに変換
     pub indent_style: IndentStyle,  // インデントにタブまたはスペースを使用
     pub indent_width: usize,  // スペース使用時のインデント幅
+    pub use_editorconfig: bool,  // .editorconfig の indent_style / indent_size を取り込む
+    pub align_struct_members: bool,  // 構造体メンバーの名前と行末コメントの開始位置を揃える
+    pub pointer_style: Option<PointerStyle>,  // ポインタの `*` の位置（未指定の場合は元のまま）
+    pub sort_includes: bool,  // 連続する #include をファイル名順に並べ替える
+    pub group_includes: bool,  // 並べ替え時に <...> を "..." より前にまとめ、空行で区切る
+    pub space_binary_operators: bool,  // 関数本体の文と初期化子の二項演算子の前後に空白を入れる
+    pub blank_lines_between_items: Option<usize>,  // トップレベルのアイテム間の空行の最大数（未指定の場合は元のまま）
 }
 
 impl Default for FormattingConfig {
@@ -124,18 +356,91 @@ impl Default for FormattingConfig {
             use_tabs: false,  // デフォルトはスペースのまま
             indent_style: IndentStyle::Spaces,
             indent_width: 4,
+            use_editorconfig: true,
+            align_struct_members: false,
+            pointer_style: None,
+            sort_includes: false,
+            group_includes: false,
+            space_binary_operators: false,
+            blank_lines_between_items: None,
+        }
+    }
+}
+
+/// 解析対象とする C 言語の規格
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "snake_case"))]
+pub enum CStandard {
+    C89,
+    C99,
+    C11,
+    C23,
+}
+
+impl CStandard {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CStandard::C89 => "c89",
+            CStandard::C99 => "c99",
+            CStandard::C11 => "c11",
+            CStandard::C23 => "c23",
+        }
+    }
+
+    /// この規格ではキーワードではない（識別子として扱う）語
+    pub fn disabled_keywords(&self) -> &'static [&'static str] {
+        match self {
+            CStandard::C89 => &[
+                "restrict", "inline", "_Bool", "_Complex", "_Imaginary",
+                "_Atomic", "_Noreturn", "_Thread_local",
+            ],
+            CStandard::C99 => &["_Atomic", "_Noreturn", "_Thread_local"],
+            CStandard::C11 | CStandard::C23 => &[],
+        }
+    }
+
+    /// 二進数リテラル（0b101）を使えるか
+    pub fn allows_binary_literals(&self) -> bool {
+        *self >= CStandard::C23
+    }
+}
+
+impl std::fmt::Display for CStandard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for CStandard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "c89" | "c90" => Ok(CStandard::C89),
+            "c99" => Ok(CStandard::C99),
+            "c11" => Ok(CStandard::C11),
+            "c23" => Ok(CStandard::C23),
+            _ => Err(format!("不明な C 規格です: {}", s)),
         }
     }
 }
 
 /// プリプロセッサ設定
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(default)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "config", serde(default))]
 pub struct PreprocessorConfig {
     /// 定義済みマクロのリスト（例: ["_WIN32", "DEBUG"]）
     pub defines: Vec<String>,
     /// ヘッダーファイル検索パス（例: ["./include", "C:/SDK/include"]）
     pub include_paths: Vec<PathBuf>,
+    /// #include されたヘッダーを読み込んで型情報を取り込むか
+    pub resolve_includes: bool,
+    /// 条件付きコンパイルブロックを、条件が成立した分岐のアイテムだけに置き換えるか
+    pub prune_inactive_branches: bool,
+    /// 解析対象の C 規格（未指定の場合はすべてのキーワード・リテラルを受け付ける）
+    pub standard: Option<CStandard>,
 }
 
 impl Default for PreprocessorConfig {
@@ -143,6 +448,9 @@ impl Default for PreprocessorConfig {
         PreprocessorConfig {
             defines: Vec::new(),
             include_paths: vec![PathBuf::from("include"), PathBuf::from(".")],
+            resolve_includes: true,
+            prune_inactive_branches: false,
+            standard: None,
         }
     }
 }
@@ -175,6 +483,9 @@ impl PreprocessorConfig {
         PreprocessorConfig {
             defines: self.defines.clone(),
             include_paths,
+            resolve_includes: self.resolve_includes,
+            prune_inactive_branches: self.prune_inactive_branches,
+            standard: self.standard,
         }
     }
 }
@@ -182,22 +493,34 @@ impl PreprocessorConfig {
 impl ProjectConfig {
     /// 設定ファイルを読み込む
     /// 見つからない場合はデフォルト設定を返す
+    #[cfg(feature = "config")]
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(path)?;
-        let config: ProjectConfig = toml::from_str(&content)?;
+        let content = std::fs::read_to_string(path)?;
+        let mut config: ProjectConfig = toml::from_str(&content)?;
+        let value: toml::Value = toml::from_str(&content)?;
+        if let Some(formatting) = value.get("formatting").and_then(|f| f.as_table()) {
+            config.formatting_keys = formatting.keys().cloned().collect();
+        }
         Ok(config)
     }
 
     /// プロジェクトルートから設定ファイルを検索
     /// 現在のディレクトリから親ディレクトリへ遡って "coding-guide.toml" を探す
+    /// ファイルシステムのルートか、`.git` を含むディレクトリ（リポジトリのルート）で探索を打ち切る
+    #[cfg(feature = "config")]
     pub fn find_and_load<P: AsRef<Path>>(start_dir: P) -> Self {
         Self::find_and_load_with_root(start_dir).config
     }
 
     /// 設定ファイルを検索してプロジェクトルート情報付きで返す
+    #[cfg(feature = "config")]
     pub fn find_and_load_with_root<P: AsRef<Path>>(start_dir: P) -> LoadedProjectConfig {
         let start_path = start_dir.as_ref().to_path_buf();
-        let mut current = start_path.clone();
+        // 相対パス（"." など）でも親ディレクトリを辿れるよう絶対パスにしてから探索する
+        let mut current = start_path
+            .canonicalize()
+            .map(strip_extended_path_prefix)
+            .unwrap_or(start_path.clone());
         
         loop {
             let config_path = current.join("coding-guide.toml");
@@ -205,6 +528,9 @@ impl ProjectConfig {
                 match Self::load_from_file(&config_path) {
                     Ok(config) => {
                         eprintln!("Loaded config from: {}", config_path.display());
+                        for code in config.unknown_rule_codes() {
+                            eprintln!("Warning: Unknown diagnostic code in [rules]: {}", code);
+                        }
                         let project_root = config_path
                             .parent()
                             .map(|p| p.to_path_buf())
@@ -235,6 +561,11 @@ impl ProjectConfig {
                 }
             }
             
+            // リポジトリのルートより上の設定ファイルは読み込まない
+            if current.join(".git").exists() {
+                break;
+            }
+
             // 親ディレクトリへ
             if !current.pop() {
                 // ルートディレクトリに到達
@@ -256,28 +587,83 @@ impl ProjectConfig {
 
     /// DiagnosticConfigに変換
     pub fn to_diagnostic_config(&self) -> crate::diagnostics::DiagnosticConfig {
-        crate::diagnostics::DiagnosticConfig {
-            check_file_header: self.diagnostics.check_file_header,
-            check_function_format: self.diagnostics.check_function_format,
-            check_type_safety: self.diagnostics.check_type_safety,
-            check_storage_class_order: self.diagnostics.check_storage_class_order,
-            check_macro_parentheses: self.diagnostics.check_macro_parentheses,
-            check_global_var_naming: self.diagnostics.check_global_var_naming,
-            check_global_var_type_prefix: self.diagnostics.check_global_var_type_prefix,
-            check_local_var_type_prefix: self.diagnostics.check_local_var_type_prefix,
-            check_preprocessor_indent: self.diagnostics.check_preprocessor_indent,
-            check_indent_style: self.diagnostics.check_indent_style,
-            check_include_dir: self.diagnostics.check_include_dir,
-            check_src_dir: self.diagnostics.check_src_dir,
-            indent_style: self.formatting.indent_style.clone(),
-            indent_width: self.formatting.indent_width,
-            project_root: None,
-            source_path: None,
-            exclude_paths: self.diagnostics.exclude_paths.clone(),
-            check_cert_c: true,
-            check_cwe_c: true,
-            check_misra_c: true,
-        }
+        let d = &self.diagnostics;
+        let checks = [
+            ("CGH001", d.check_file_header),
+            ("CGH002", d.check_function_format),
+            ("CGH003", d.check_type_safety),
+            ("CGH004", d.check_storage_class_order),
+            ("CGH005", d.check_macro_parentheses),
+            ("CGH006", d.check_global_var_naming),
+            ("CGH007", d.check_global_var_type_prefix),
+            ("CGH008", d.check_preprocessor_indent),
+            ("CGH009", d.check_indent_style),
+            ("CGH010", d.check_local_var_type_prefix),
+            ("CGH011", d.check_include_dir),
+            ("CGH012", d.check_src_dir),
+            ("CGH015", d.check_duplicate_macros),
+            ("CGH016", d.check_include_guard),
+            ("CGH017", d.check_empty_loop_body),
+            ("CGH021", d.check_const_pointer_params),
+            ("CGH022", d.check_function_naming),
+            ("CGH023", d.check_type_naming),
+            ("CGH024", d.check_enum_monotonic),
+            ("CGH025", d.check_mixed_indentation),
+            ("CGH026", d.check_implicit_int),
+            ("CGH027", d.check_empty_aggregate),
+            ("CGH028", d.check_short_identifiers),
+            ("CGH030", d.check_dead_preprocessor),
+            ("CGH031", d.check_macro_argument_parentheses),
+            ("CGH032", d.check_include_order),
+            ("CGH033", d.check_typedef_shadowing),
+            ("CGH034", d.check_redundant_qualifiers),
+            ("CGH035", d.check_missing_prototypes),
+            ("CGH036", d.check_array_init_size),
+            ("CGH037", d.check_plain_char_numeric),
+            ("CGH038", d.check_declarations_after_statements),
+            ("CGH039", d.check_continuation_indent),
+            ("CGH040", d.check_void_return_value),
+            ("CGH041", d.check_unused_parameters),
+            ("CGH042", d.check_unused_locals),
+            ("CGH043", d.check_use_before_declaration),
+            ("CGH044", d.check_hex_unsigned_suffix),
+            ("CGH045", d.check_octal_literals),
+            ("CGH046", d.check_consistent_brace_style),
+            ("CGH047", d.check_operator_spacing),
+            ("CGH048", d.check_no_tabs),
+            ("CGH049", d.check_packed_struct_comment),
+            ("CGH051", d.check_non_ascii),
+        ];
+        checks
+            .into_iter()
+            .try_fold(crate::diagnostics::DiagnosticConfigBuilder::new(), |builder, (code, enabled)| builder.set(code, enabled))
+            .expect("[diagnostics] の各チェックは設定可能な診断コードに対応する")
+            .max_function_statements(d.max_function_statements)
+            .max_cyclomatic_complexity(d.max_cyclomatic_complexity)
+            .max_nesting_depth(d.max_nesting_depth)
+            .function_naming_style(d.function_naming_style.clone())
+            .type_naming_style(d.type_naming_style.clone())
+            .min_identifier_length(d.min_identifier_length)
+            .allow_loop_index_names(d.allow_loop_index_names)
+            .warn_partial_array_init(d.warn_partial_array_init)
+            .warn_empty_return(d.warn_empty_return)
+            .operator_spacing_classes(d.operator_spacing_classes.clone())
+            .preprocessor_indent_style(d.preprocessor_indent_style.clone())
+            .indent_style(self.formatting.indent_style.clone())
+            .indent_width(self.formatting.indent_width)
+            .exclude_paths(d.exclude_paths.clone())
+            .rules(self.rules.iter().filter(|(code, _)| crate::diagnostics::is_known_code(code)))
+            .build()
+    }
+
+    /// [rules] テーブルに含まれる未知の診断コードを返す
+    /// 未知のコードはエラーにせず、変換時には無視する
+    pub fn unknown_rule_codes(&self) -> Vec<&str> {
+        self.rules
+            .keys()
+            .map(String::as_str)
+            .filter(|code| !crate::diagnostics::is_known_code(code))
+            .collect()
     }
 }
 
@@ -289,6 +675,7 @@ pub struct LoadedProjectConfig {
 }
 
 impl LoadedProjectConfig {
+    #[cfg(feature = "config")]
     pub fn find_and_load_with_root<P: AsRef<Path>>(start_dir: P) -> Self {
         ProjectConfig::find_and_load_with_root(start_dir)
     }
@@ -308,10 +695,23 @@ impl LoadedProjectConfig {
             }
         });
 
+        let mut config = self.config.to_diagnostic_config();
+        if let Some(path) = source_path.as_ref().filter(|_| self.config.formatting.use_editorconfig) {
+            // 設定ファイルで明示的に指定した値を .editorconfig より優先する
+            let editorconfig = EditorConfigIndent::find_for_file(path);
+            let is_explicit = |key: &str| self.config.formatting_keys.iter().any(|k| k == key);
+            if let Some(style) = editorconfig.indent_style.filter(|_| !is_explicit("indent_style")) {
+                config.indent_style = style;
+            }
+            if let Some(width) = editorconfig.indent_width.filter(|_| !is_explicit("indent_width")) {
+                config.indent_width = width;
+            }
+        }
+
         crate::diagnostics::DiagnosticConfig {
             project_root: Some(project_root),
             source_path,
-            ..self.config.to_diagnostic_config()
+            ..config
         }
     }
 
@@ -354,6 +754,30 @@ add_file_header = false
         assert!(!config.formatting.add_file_header);
     }
 
+    #[test]
+    fn test_parse_pointer_style() {
+        let config: ProjectConfig = toml::from_str("[formatting]\npointer_style = \"left_of_name\"\n").unwrap();
+        assert_eq!(config.formatting.pointer_style, Some(PointerStyle::LeftOfName));
+        assert_eq!(ProjectConfig::default().formatting.pointer_style, None);
+    }
+
+    #[test]
+    fn test_parse_standard() {
+        let config: ProjectConfig = toml::from_str("[preprocessor]\nstandard = \"c99\"\n").unwrap();
+        assert_eq!(config.preprocessor.standard, Some(CStandard::C99));
+        assert_eq!(ProjectConfig::default().preprocessor.standard, None);
+        assert_eq!("C23".parse::<CStandard>(), Ok(CStandard::C23));
+        assert!("c++17".parse::<CStandard>().is_err());
+    }
+
+    #[test]
+    fn test_parse_sort_includes() {
+        let config: ProjectConfig = toml::from_str("[formatting]\nsort_includes = true\ngroup_includes = true\n").unwrap();
+        assert!(config.formatting.sort_includes);
+        assert!(config.formatting.group_includes);
+        assert!(!ProjectConfig::default().formatting.sort_includes);
+    }
+
     #[test]
     fn test_partial_config() {
         // 一部のみ指定した場合、残りはデフォルト値
@@ -407,6 +831,80 @@ include_paths = ["deps/include"]
         assert_eq!(config.preprocessor.include_paths, vec![PathBuf::from("deps/include")]);
     }
 
+    #[test]
+    fn test_rules_table_disables_and_overrides_severity() {
+        use crate::diagnostics::{diagnose, DiagnosticSeverity};
+        use crate::lexer::Lexer;
+        use crate::parser::Parser;
+
+        let toml_str = r#"
+[diagnostics]
+check_file_header = false
+
+[rules]
+CGH101 = "off"
+CGH006 = "error"
+"#;
+
+        let config: ProjectConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.rules.get("CGH101"), Some(&RuleLevel::Off));
+        assert!(config.unknown_rule_codes().is_empty());
+
+        let diag_config = config.to_diagnostic_config();
+        let tu = Parser::new(Lexer::new("void v;\nint counter;\n")).parse();
+        let defaults = diagnose(&tu, &ProjectConfig::default().to_diagnostic_config());
+        assert!(defaults.iter().any(|d| d.code.to_string() == "CGH101"));
+
+        let diagnostics = diagnose(&tu, &diag_config);
+        assert!(!diagnostics.iter().any(|d| d.code.to_string() == "CGH101"));
+        let naming: Vec<_> = diagnostics.iter().filter(|d| d.code.to_string() == "CGH006").collect();
+        assert!(!naming.is_empty());
+        assert!(naming.iter().all(|d| d.severity == DiagnosticSeverity::Error));
+    }
+
+    #[test]
+    fn test_rules_table_overrides_standard_diagnostic_severity() {
+        use crate::analysis::analyze_with_preprocessor;
+        use crate::diagnostics::DiagnosticSeverity;
+
+        let toml_str = r#"
+[preprocessor]
+standard = "c89"
+
+[rules]
+CGH050 = "warning"
+"#;
+
+        let config: ProjectConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.unknown_rule_codes().is_empty());
+
+        let result = analyze_with_preprocessor(
+            "int x = 0b101;\n",
+            &config.to_diagnostic_config(),
+            config.preprocessor.clone(),
+        );
+        let standard: Vec<_> = result.parse_diagnostics.iter().filter(|d| d.code.to_string() == "CGH050").collect();
+        assert_eq!(standard.len(), 1);
+        assert_eq!(standard[0].severity, DiagnosticSeverity::Warning);
+    }
+
+    #[test]
+    fn test_rules_table_unknown_code_is_not_an_error() {
+        let toml_str = r#"
+[rules]
+CGH999 = "off"
+CGH002 = "error"
+"#;
+
+        let config: ProjectConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.unknown_rule_codes(), vec!["CGH999"]);
+
+        let diag_config = config.to_diagnostic_config();
+        assert!(diag_config.disabled_codes.is_empty());
+        assert!(diag_config.check_function_format);
+        assert_eq!(diag_config.severity_overrides.len(), 1);
+    }
+
     #[test]
     fn test_find_and_load_with_root_no_config() {
         // 一時ディレクトリを作成
@@ -462,4 +960,39 @@ include_paths = ["deps/include"]
         // クリーンアップ
         let _ = std::fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    fn test_find_and_load_with_root_two_levels_up() {
+        let temp_dir = std::env::temp_dir().join("coding_guide_test_two_levels_up");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let nested_dir = temp_dir.join("src").join("layer1");
+        std::fs::create_dir_all(&nested_dir).unwrap();
+        std::fs::write(temp_dir.join("coding-guide.toml"), "[diagnostics]\ncheck_file_header = false\n").unwrap();
+
+        let loaded = ProjectConfig::find_and_load_with_root(&nested_dir);
+
+        assert_eq!(loaded.project_root, temp_dir.canonicalize().unwrap());
+        assert!(!loaded.config.diagnostics.check_file_header);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_find_and_load_with_root_stops_at_git_root() {
+        // 設定ファイルがリポジトリ（.git を含むディレクトリ）の外にある場合は読み込まない
+        let temp_dir = std::env::temp_dir().join("coding_guide_test_git_root");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let repo_dir = temp_dir.join("repo");
+        let src_dir = repo_dir.join("src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::fs::create_dir_all(repo_dir.join(".git")).unwrap();
+        std::fs::write(temp_dir.join("coding-guide.toml"), "[diagnostics]\ncheck_file_header = false\n").unwrap();
+
+        let loaded = ProjectConfig::find_and_load_with_root(&src_dir);
+
+        assert_eq!(loaded.project_root, src_dir.canonicalize().unwrap());
+        assert!(loaded.config.diagnostics.check_file_header);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
 }
@@ -0,0 +1,261 @@
+use crate::expression::{BinaryOperator, Expression, UnaryOperator};
+use crate::expression_parser::ExpressionParser;
+use crate::lexer::Lexer;
+use std::collections::HashMap;
+
+/// マクロ展開の最大深さ（自己参照マクロによる無限再帰を防ぐ）
+const MAX_MACRO_DEPTH: usize = 32;
+
+/// 整数定数式を評価する
+///
+/// リテラル、単項 `+` `-` `~` `!`、整数の二項演算子、三項演算子を畳み込む。
+/// 識別子は `macros`（マクロ名 → 置換値）で展開し、定数にならない場合は `None` を返す。
+pub fn eval_const_expr(expr: &Expression, macros: &HashMap<String, String>) -> Option<i64> {
+    Evaluator { macros, undefined_as_zero: false }.eval(expr, 0)
+}
+
+/// 文字列で与えられた定数式を評価する（マクロの置換値、enumの値など）
+pub fn eval_const_str(text: &str, macros: &HashMap<String, String>) -> Option<i64> {
+    Evaluator { macros, undefined_as_zero: false }.eval_text(text, 0)
+}
+
+/// `#if` / `#elif` の条件式を評価する
+///
+/// `defined(X)` / `defined X` を展開し、未定義の識別子は C の規則どおり 0 として扱う。
+pub fn eval_preprocessor_condition(condition: &str, macros: &HashMap<String, String>) -> Option<i64> {
+    let expanded = replace_defined(condition, macros);
+    Evaluator { macros, undefined_as_zero: true }.eval_text(&expanded, 0)
+}
+
+struct Evaluator<'a> {
+    macros: &'a HashMap<String, String>,
+    undefined_as_zero: bool,  // #if の評価では未定義の識別子を 0 とみなす
+}
+
+impl Evaluator<'_> {
+    fn eval_text(&self, text: &str, depth: usize) -> Option<i64> {
+        let text = strip_comments(text);
+        let mut lexer = Lexer::new(&text);
+        let mut expr_parser = ExpressionParser::new(&mut lexer);
+        let expr = expr_parser.parse_expression();
+        expr_parser.finish();
+
+        // 式の後ろに余分なトークンが残っている場合は定数式ではない
+        if lexer.next_token().is_some() {
+            return None;
+        }
+        self.eval(&expr?, depth)
+    }
+
+    fn eval(&self, expr: &Expression, depth: usize) -> Option<i64> {
+        match expr {
+            Expression::IntLiteral { value, .. } => Some(*value),
+            Expression::Identifier { name, .. } => {
+                match self.macros.get(name) {
+                    Some(value) if depth < MAX_MACRO_DEPTH => self.eval_text(value, depth + 1),
+                    Some(_) => None,
+                    None if self.undefined_as_zero => Some(0),
+                    None => None,
+                }
+            }
+            Expression::UnaryOp { op, operand, .. } => {
+                let value = self.eval(operand, depth)?;
+                match op {
+                    UnaryOperator::Plus => Some(value),
+                    UnaryOperator::Negate => value.checked_neg(),
+                    UnaryOperator::BitwiseNot => Some(!value),
+                    UnaryOperator::LogicalNot => Some((value == 0) as i64),
+                    _ => None,
+                }
+            }
+            Expression::BinaryOp { op, left, right, .. } => {
+                let lhs = self.eval(left, depth)?;
+
+                // 論理演算子は短絡評価（右辺が評価できなくても結果が決まる）
+                match op {
+                    BinaryOperator::LogicalAnd if lhs == 0 => return Some(0),
+                    BinaryOperator::LogicalOr if lhs != 0 => return Some(1),
+                    _ => {}
+                }
+
+                let rhs = self.eval(right, depth)?;
+                match op {
+                    BinaryOperator::Add => lhs.checked_add(rhs),
+                    BinaryOperator::Subtract => lhs.checked_sub(rhs),
+                    BinaryOperator::Multiply => lhs.checked_mul(rhs),
+                    BinaryOperator::Divide => lhs.checked_div(rhs),
+                    BinaryOperator::Modulo => lhs.checked_rem(rhs),
+                    BinaryOperator::BitwiseAnd => Some(lhs & rhs),
+                    BinaryOperator::BitwiseOr => Some(lhs | rhs),
+                    BinaryOperator::BitwiseXor => Some(lhs ^ rhs),
+                    BinaryOperator::LeftShift => u32::try_from(rhs).ok().and_then(|n| lhs.checked_shl(n)),
+                    BinaryOperator::RightShift => u32::try_from(rhs).ok().and_then(|n| lhs.checked_shr(n)),
+                    BinaryOperator::Equal => Some((lhs == rhs) as i64),
+                    BinaryOperator::NotEqual => Some((lhs != rhs) as i64),
+                    BinaryOperator::LessThan => Some((lhs < rhs) as i64),
+                    BinaryOperator::LessThanOrEq => Some((lhs <= rhs) as i64),
+                    BinaryOperator::GreaterThan => Some((lhs > rhs) as i64),
+                    BinaryOperator::GreaterThanOrEq => Some((lhs >= rhs) as i64),
+                    BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => Some((rhs != 0) as i64),
+                    // 代入は定数式ではない
+                    _ => None,
+                }
+            }
+            Expression::Conditional { condition, then_expr, else_expr, .. } => {
+                if self.eval(condition, depth)? != 0 {
+                    self.eval(then_expr, depth)
+                } else {
+                    self.eval(else_expr, depth)
+                }
+            }
+            // 整数型へのキャストは値をそのまま使う（切り詰めは考慮しない）
+            Expression::Cast { operand, .. } => self.eval(operand, depth),
+            _ => None,
+        }
+    }
+}
+
+/// `defined(X)` / `defined X` を 1 または 0 に置き換える
+fn replace_defined(condition: &str, macros: &HashMap<String, String>) -> String {
+    let mut result = String::new();
+    let mut rest = condition;
+
+    while let Some(pos) = find_identifier(rest, "defined") {
+        result.push_str(&rest[..pos]);
+        let after = rest[pos + "defined".len()..].trim_start();
+        let (name, remaining) = if let Some(inner) = after.strip_prefix('(') {
+            match inner.find(')') {
+                Some(close) => (inner[..close].trim(), &inner[close + 1..]),
+                None => (inner.trim(), ""),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        result.push_str(if macros.contains_key(name) { " 1 " } else { " 0 " });
+        rest = remaining;
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// 識別子として独立して現れる `word` の位置を探す
+fn find_identifier(text: &str, word: &str) -> Option<usize> {
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut start = 0;
+    while let Some(offset) = text[start..].find(word) {
+        let pos = start + offset;
+        let before_ok = text[..pos].chars().next_back().is_none_or(|c| !is_ident_char(c));
+        let after_ok = text[pos + word.len()..].chars().next().is_none_or(|c| !is_ident_char(c));
+        if before_ok && after_ok {
+            return Some(pos);
+        }
+        start = pos + word.len();
+    }
+    None
+}
+
+/// 行コメント・ブロックコメントを取り除く（マクロ値の末尾コメント対策）
+fn strip_comments(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    loop {
+        let line_comment = rest.find("//");
+        let block_comment = rest.find("/*");
+        match (line_comment, block_comment) {
+            (Some(l), b) if b.is_none_or(|b| l < b) => {
+                result.push_str(&rest[..l]);
+                match rest[l..].find('\n') {
+                    Some(nl) => rest = &rest[l + nl..],
+                    None => return result,
+                }
+            }
+            (_, Some(b)) => {
+                result.push_str(&rest[..b]);
+                result.push(' ');
+                match rest[b + 2..].find("*/") {
+                    Some(end) => rest = &rest[b + 2 + end + 2..],
+                    None => return result,
+                }
+            }
+            _ => {
+                result.push_str(rest);
+                return result;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(text: &str) -> Option<i64> {
+        eval_const_str(text, &HashMap::new())
+    }
+
+    #[test]
+    fn test_eval_arithmetic() {
+        assert_eq!(eval("1 + 2 * 3"), Some(7));
+        assert_eq!(eval("(1 + 2) * 3"), Some(9));
+        assert_eq!(eval("10 / 3"), Some(3));
+        assert_eq!(eval("10 % 3"), Some(1));
+        assert_eq!(eval("-5 + +2"), Some(-3));
+        assert_eq!(eval("0x10 - 010"), Some(8));
+    }
+
+    #[test]
+    fn test_eval_bitwise() {
+        assert_eq!(eval("1 << 4"), Some(16));
+        assert_eq!(eval("0xF0 >> 4"), Some(15));
+        assert_eq!(eval("0xF0 | 0x0F"), Some(255));
+        assert_eq!(eval("0xFF & 0x0F"), Some(15));
+        assert_eq!(eval("0xFF ^ 0x0F"), Some(240));
+        assert_eq!(eval("~0"), Some(-1));
+    }
+
+    #[test]
+    fn test_eval_logical_short_circuit() {
+        // 右辺が定数でなくても短絡評価で結果が決まる
+        assert_eq!(eval("0 && unknown"), Some(0));
+        assert_eq!(eval("1 || unknown"), Some(1));
+        assert_eq!(eval("1 && unknown"), None);
+        assert_eq!(eval("1 && 2"), Some(1));
+        assert_eq!(eval("!0"), Some(1));
+        assert_eq!(eval("3 > 2 && 2 >= 2"), Some(1));
+    }
+
+    #[test]
+    fn test_eval_non_constant() {
+        assert_eq!(eval("x + 1"), None);
+        assert_eq!(eval("1 / 0"), None);
+        assert_eq!(eval("1.5"), None);
+        assert_eq!(eval("1 2"), None);
+    }
+
+    #[test]
+    fn test_eval_with_macros() {
+        let mut macros = HashMap::new();
+        macros.insert("BASE".to_string(), "0x100 // base".to_string());
+        macros.insert("SIZE".to_string(), "(BASE + 4)".to_string());
+        macros.insert("LOOP".to_string(), "LOOP".to_string());
+
+        assert_eq!(eval_const_str("SIZE * 2", &macros), Some(520));
+        assert_eq!(eval_const_str("LOOP", &macros), None);
+    }
+
+    #[test]
+    fn test_eval_preprocessor_condition() {
+        let mut macros = HashMap::new();
+        macros.insert("VERSION".to_string(), "3".to_string());
+        macros.insert("DEBUG".to_string(), String::new());
+
+        assert_eq!(eval_preprocessor_condition("defined(DEBUG) && VERSION >= 2", &macros), Some(1));
+        assert_eq!(eval_preprocessor_condition("defined RELEASE || VERSION == 1", &macros), Some(0));
+        assert_eq!(eval_preprocessor_condition("UNDEFINED_MACRO", &macros), Some(0));
+        assert_eq!(eval_preprocessor_condition("!defined(RELEASE)", &macros), Some(1));
+    }
+}
@@ -1,9 +1,16 @@
-﻿use crate::ast::{TranslationUnit, Item, Statement};
+﻿use crate::ast::{extract_typedef_names, Initializer, TranslationUnit, Item, Statement};
+use crate::expression::{BinaryOperator, Expression, UnaryOperator};
 use crate::span::Span;
-use crate::type_system::{BaseType, TypeQualifier};
+use crate::token::Token;
+use crate::type_system::{BaseType, Type, TypeQualifier};
+use crate::config::{NamingStyle, RuleLevel};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, PartialEq)]
+/// 診断コード
+///
+/// 順序は Custom（CGHxxx）→ CERT-C → CWE → MISRA-C の順で、同じ種類の中では規則の名前・番号順。
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DiagnosticCode {
     Custom(String),
     CertC(String),
@@ -22,13 +29,18 @@ impl std::fmt::Display for DiagnosticCode {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// 診断結果
+///
+/// `Ord` は `ordering_key`（Span → 診断コード）で比較し、同じ場合は重大度（高い順）・メッセージ・
+/// 注記・関連位置の順に比較する全順序。スナップショットテストや診断のマージで並びを固定するのに使う。
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Diagnostic {
     pub span: Span,
     pub severity: DiagnosticSeverity,
     pub message: String,
     pub code: DiagnosticCode,
     pub notes: Vec<String>,
+    pub related: Vec<(Span, String)>,  // 関連する別の位置（「最初の定義」など）とその説明
 }
 
 impl Diagnostic {
@@ -39,6 +51,7 @@ impl Diagnostic {
             message,
             code,
             notes: Vec::new(),
+            related: Vec::new(),
         }
     }
 
@@ -46,9 +59,41 @@ impl Diagnostic {
         self.notes.push(note.into());
         self
     }
+
+    /// 関連する別の位置を追加する（LSP では DiagnosticRelatedInformation として表示される）
+    pub fn with_related(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.related.push((span, message.into()));
+        self
+    }
+
+    /// 並べ替えに使うキー（ソース上の範囲、診断コードの順）
+    pub fn ordering_key(&self) -> (&Span, &DiagnosticCode) {
+        (&self.span, &self.code)
+    }
+}
+
+impl Ord for Diagnostic {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.ordering_key()
+            .cmp(&other.ordering_key())
+            .then_with(|| other.severity.cmp(&self.severity))
+            .then_with(|| self.message.cmp(&other.message))
+            .then_with(|| self.notes.cmp(&other.notes))
+            .then_with(|| self.related.cmp(&other.related))
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl PartialOrd for Diagnostic {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// 診断の重大度
+///
+/// `Ord` は重大度の高さで比較する（Error > Warning > Information > Hint）。
+/// 文字列との変換は設定ファイルの [rules] と同じ "error" / "warning" / "info" / "hint" を使う。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DiagnosticSeverity {
     Error,
     Warning,
@@ -56,6 +101,60 @@ pub enum DiagnosticSeverity {
     Hint,
 }
 
+impl DiagnosticSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Information => "info",
+            DiagnosticSeverity::Hint => "hint",
+        }
+    }
+
+    /// 重大度の高さ（大きいほど重大）
+    fn rank(&self) -> u8 {
+        match self {
+            DiagnosticSeverity::Error => 3,
+            DiagnosticSeverity::Warning => 2,
+            DiagnosticSeverity::Information => 1,
+            DiagnosticSeverity::Hint => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 大文字・小文字は区別しない。LSP の表記に合わせて "information" も受け付ける
+impl std::str::FromStr for DiagnosticSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(DiagnosticSeverity::Error),
+            "warning" => Ok(DiagnosticSeverity::Warning),
+            "info" | "information" => Ok(DiagnosticSeverity::Information),
+            "hint" => Ok(DiagnosticSeverity::Hint),
+            _ => Err(format!("不明な重大度です: {}", s)),
+        }
+    }
+}
+
+impl Ord for DiagnosticSeverity {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl PartialOrd for DiagnosticSeverity {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DiagnosticConfig {
     pub check_file_header: bool,
@@ -63,10 +162,53 @@ pub struct DiagnosticConfig {
     pub check_function_format: bool,
     pub check_type_safety: bool,
     pub check_macro_parentheses: bool,  // マクロ定義の値が括弧で囲まれているかチェック
+    pub check_duplicate_macros: bool,  // 同じマクロが異なる値で再定義されていないかチェック
+    pub check_include_guard: bool,  // ヘッダーファイル（.h）のインクルードガードチェック
     pub check_global_var_naming: bool,  // グローバル変数の命名規則チェック（大文字）
     pub check_global_var_type_prefix: bool,  // グローバル変数の型名プレフィックスチェック
     pub check_local_var_type_prefix: bool,  // ローカル変数の型名プレフィックスチェック
+    pub check_empty_loop_body: bool,  // ループ本体が空文（;）のみになっていないかチェック
+    pub max_function_statements: Option<usize>,  // 関数あたりの最大ステートメント数（None で無効）
+    pub max_cyclomatic_complexity: Option<usize>,  // 関数あたりの循環的複雑度の上限（None で無効）
+    pub max_nesting_depth: Option<usize>,  // 関数内の制御文・ブロックのネストの上限（None で無効）
+    pub check_const_pointer_params: bool,  // ポインタ引数の指す先を変更しない場合に const を提案
+    pub check_function_naming: bool,  // 関数名の命名規則チェック
+    pub function_naming_style: NamingStyle,  // 期待される関数名の命名規則
+    pub check_type_naming: bool,  // struct/union/enum のタグ名と typedef 名の命名規則チェック
+    pub type_naming_style: NamingStyle,  // 期待される型名の命名規則
+    pub check_enum_monotonic: bool,  // 列挙子の値が単調増加になっているかチェック
+    pub check_mixed_indentation: bool,  // 行頭の空白にタブとスペースが混在していないかチェック
+    pub check_implicit_int: bool,  // 戻り値の型を省略した関数定義（暗黙の int）をチェックするかどうか
+    pub check_empty_aggregate: bool,  // メンバーを持たない struct / union の定義をチェックするかどうか
+    pub check_short_identifiers: bool,  // 短すぎるグローバル変数名をチェックするかどうか
+    pub min_identifier_length: usize,  // グローバル変数名の最小文字数
+    pub allow_loop_index_names: bool,  // ループ変数として慣用的な名前（i / j / k）を短い名前のチェックから除外するかどうか
+    pub warn_partial_array_init: bool,  // 初期化子が配列の要素数より少ない場合も警告するかどうか（CGH036）
+    pub warn_empty_return: bool,  // void でない関数での値のない return も警告するかどうか（CGH040）
+    pub operator_spacing_classes: Vec<crate::config::OperatorClass>,  // 前後の空白をチェックする二項演算子の分類（CGH047）
+    pub check_dead_preprocessor: bool,  // どの分岐も有効にならない条件付きブロック（#if 0 など）をチェックするかどうか
+    pub check_macro_argument_parentheses: bool,  // 関数形式マクロの置換値の中で引数が括弧で囲まれているかをチェックするかどうか
+    pub check_include_order: bool,  // #include がシステムヘッダー→ローカルヘッダーの順にまとめられ、ファイル名順に並んでいるかをチェックするかどうか
+    pub check_typedef_shadowing: bool,  // ローカル変数が typedef 名を隠していないかをチェックするかどうか
+    pub check_redundant_qualifiers: bool,  // 同じ位置で重複した型修飾子（const const など）をチェックするかどうか
+    pub check_missing_prototypes: bool,  // static でない関数定義のプロトタイプ宣言が解析したヘッダーにあるかチェックするかどうか
+    pub check_array_init_size: bool,  // 配列の要素数より多い初期化子をチェックするかどうか
+    pub check_plain_char_numeric: bool,  // 符号を明示しない char 型の変数を数値演算に使っていないかチェックするかどうか
+    pub check_declarations_after_statements: bool,  // ブロックの途中（文の後）での変数宣言をチェックするかどうか（C89 スタイル）
+    pub check_continuation_indent: bool,  // 複数行にわたる文の継続行のインデントをチェックするかどうか
+    pub check_void_return_value: bool,  // void 関数での値付き return をチェックするかどうか
+    pub check_unused_parameters: bool,  // 関数本体で使われていない引数をチェックするかどうか
+    pub check_unused_locals: bool,  // 宣言後に使われていないローカル変数をチェックするかどうか
+    pub check_use_before_declaration: bool,  // ブロック内で宣言より前に変数を使っていないかチェックするかどうか
+    pub check_hex_unsigned_suffix: bool,  // ビット演算に使う16進定数に U サフィックスがあるかチェックするかどうか
+    pub check_octal_literals: bool,  // 8進数リテラル（先頭が 0 の整数定数）をチェックするかどうか
+    pub check_consistent_brace_style: bool,  // 開き括弧の配置（Allman / K&R）がファイル内で統一されているかチェックするかどうか
+    pub check_operator_spacing: bool,  // 二項演算子の前後に空白があるかチェックするかどうか
+    pub check_no_tabs: bool,  // indent_style がスペースのとき、行中を含むすべてのタブ文字をチェックするかどうか
+    pub check_packed_struct_comment: bool,  // __attribute__((packed)) の構造体に理由を説明するコメントがあるかをチェックするかどうか
+    pub check_non_ascii: bool,  // コメント・文字列リテラルの外にある ASCII 以外の文字とファイル先頭の UTF-8 BOM をチェックするかどうか
     pub check_preprocessor_indent: bool,  // プリプロセッサディレクティブのインデントチェック
+    pub preprocessor_indent_style: crate::config::PreprocessorIndentStyle,  // 期待するディレクティブのインデントのスタイル
     pub check_indent_style: bool,  // インデントスタイル（タブ/スペース）のチェック
     pub indent_style: crate::config::IndentStyle,  // 期待されるインデントスタイル
     pub indent_width: usize,  // スペース使用時のインデント幅
@@ -78,6 +220,8 @@ pub struct DiagnosticConfig {
     pub check_cert_c: bool,
     pub check_cwe_c: bool,
     pub check_misra_c: bool,
+    pub disabled_codes: Vec<String>, // 出力しない診断コード（[rules] で "off" を指定したもの）
+    pub severity_overrides: HashMap<String, DiagnosticSeverity>, // 診断コードごとの重大度の上書き
 }
 
 impl Default for DiagnosticConfig {
@@ -88,10 +232,53 @@ impl Default for DiagnosticConfig {
             check_function_format: true,
             check_type_safety: true,
             check_macro_parentheses: true,
+            check_duplicate_macros: true,
+            check_include_guard: true,
             check_global_var_naming: true,
             check_global_var_type_prefix: true,
             check_local_var_type_prefix: true,
+            check_empty_loop_body: true,
+            max_function_statements: None,
+            max_cyclomatic_complexity: None,
+            max_nesting_depth: None,
+            check_const_pointer_params: true,
+            check_function_naming: false,
+            function_naming_style: NamingStyle::SnakeCase,
+            check_type_naming: false,
+            type_naming_style: NamingStyle::PascalCase,
+            check_enum_monotonic: false,
+            check_mixed_indentation: true,
+            check_implicit_int: true,
+            check_empty_aggregate: true,
+            check_short_identifiers: false,
+            min_identifier_length: 3,
+            allow_loop_index_names: true,
+            warn_partial_array_init: false,
+            warn_empty_return: false,
+            operator_spacing_classes: crate::config::OperatorClass::ALL.to_vec(),
+            check_dead_preprocessor: true,
+            check_macro_argument_parentheses: true,
+            check_include_order: false,
+            check_typedef_shadowing: true,
+            check_redundant_qualifiers: true,
+            check_missing_prototypes: false,
+            check_array_init_size: true,
+            check_plain_char_numeric: false,
+            check_declarations_after_statements: false,
+            check_continuation_indent: false,
+            check_void_return_value: true,
+            check_unused_parameters: true,
+            check_unused_locals: true,
+            check_use_before_declaration: true,
+            check_hex_unsigned_suffix: false,
+            check_octal_literals: true,
+            check_consistent_brace_style: false,
+            check_operator_spacing: false,
+            check_no_tabs: false,
+            check_packed_struct_comment: false,
+            check_non_ascii: false,
             check_preprocessor_indent: true,
+            preprocessor_indent_style: crate::config::PreprocessorIndentStyle::ColumnZero,
             check_indent_style: true,
             indent_style: crate::config::IndentStyle::Spaces,
             indent_width: 4,
@@ -103,7 +290,269 @@ impl Default for DiagnosticConfig {
             check_cert_c: true,
             check_cwe_c: true,
             check_misra_c: true,
+            disabled_codes: Vec::new(),
+            severity_overrides: HashMap::new(),
+        }
+    }
+}
+
+/// DiagnosticConfig をチェーン形式で組み立てるビルダー
+/// 各チェックは診断コード（"CGH001" など）で有効・無効を切り替える
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticConfigBuilder {
+    config: DiagnosticConfig,
+}
+
+/// 個別のフラグを持たず、他のチェックから報告される診断コード
+/// CGH013 / CGH014 / CGH029 / CGH050 はパース時、CGH101〜CGH104 は型安全性チェック（CGH003）から報告される
+const REPORT_ONLY_CODES: [&str; 8] = ["CGH013", "CGH014", "CGH029", "CGH050", "CGH101", "CGH102", "CGH103", "CGH104"];
+
+/// [rules] などで指定できる既知の診断コードかどうか
+pub fn is_known_code(code: &str) -> bool {
+    CONFIGURABLE_CODES.contains(&code) || REPORT_ONLY_CODES.contains(&code)
+}
+
+type CheckFlag = fn(&mut DiagnosticConfig) -> &mut bool;
+type CheckLimit = fn(&mut DiagnosticConfig) -> &mut Option<usize>;
+
+/// 有効・無効のフラグを持つチェックの診断コードと、そのフラグ
+/// CGH003 は CGH101〜CGH104 もあわせて切り替える（型安全性チェックから報告されるため）
+const CHECK_FLAGS: [(&str, CheckFlag); 44] = [
+    ("CGH001", |config| &mut config.check_file_header),
+    ("CGH002", |config| &mut config.check_function_format),
+    ("CGH003", |config| &mut config.check_type_safety),
+    ("CGH004", |config| &mut config.check_storage_class_order),
+    ("CGH005", |config| &mut config.check_macro_parentheses),
+    ("CGH006", |config| &mut config.check_global_var_naming),
+    ("CGH007", |config| &mut config.check_global_var_type_prefix),
+    ("CGH008", |config| &mut config.check_preprocessor_indent),
+    ("CGH009", |config| &mut config.check_indent_style),
+    ("CGH010", |config| &mut config.check_local_var_type_prefix),
+    ("CGH011", |config| &mut config.check_include_dir),
+    ("CGH012", |config| &mut config.check_src_dir),
+    ("CGH015", |config| &mut config.check_duplicate_macros),
+    ("CGH016", |config| &mut config.check_include_guard),
+    ("CGH017", |config| &mut config.check_empty_loop_body),
+    ("CGH021", |config| &mut config.check_const_pointer_params),
+    ("CGH022", |config| &mut config.check_function_naming),
+    ("CGH023", |config| &mut config.check_type_naming),
+    ("CGH024", |config| &mut config.check_enum_monotonic),
+    ("CGH025", |config| &mut config.check_mixed_indentation),
+    ("CGH026", |config| &mut config.check_implicit_int),
+    ("CGH027", |config| &mut config.check_empty_aggregate),
+    ("CGH028", |config| &mut config.check_short_identifiers),
+    ("CGH030", |config| &mut config.check_dead_preprocessor),
+    ("CGH031", |config| &mut config.check_macro_argument_parentheses),
+    ("CGH032", |config| &mut config.check_include_order),
+    ("CGH033", |config| &mut config.check_typedef_shadowing),
+    ("CGH034", |config| &mut config.check_redundant_qualifiers),
+    ("CGH035", |config| &mut config.check_missing_prototypes),
+    ("CGH036", |config| &mut config.check_array_init_size),
+    ("CGH037", |config| &mut config.check_plain_char_numeric),
+    ("CGH038", |config| &mut config.check_declarations_after_statements),
+    ("CGH039", |config| &mut config.check_continuation_indent),
+    ("CGH040", |config| &mut config.check_void_return_value),
+    ("CGH041", |config| &mut config.check_unused_parameters),
+    ("CGH042", |config| &mut config.check_unused_locals),
+    ("CGH043", |config| &mut config.check_use_before_declaration),
+    ("CGH044", |config| &mut config.check_hex_unsigned_suffix),
+    ("CGH045", |config| &mut config.check_octal_literals),
+    ("CGH046", |config| &mut config.check_consistent_brace_style),
+    ("CGH047", |config| &mut config.check_operator_spacing),
+    ("CGH048", |config| &mut config.check_no_tabs),
+    ("CGH049", |config| &mut config.check_packed_struct_comment),
+    ("CGH051", |config| &mut config.check_non_ascii),
+];
+
+/// 上限値を持つチェックの診断コード・上限値を指定するセッター名・上限値
+/// 上限値がないと有効にできないため、disable でのみ指定できる
+const CHECK_LIMITS: [(&str, &str, CheckLimit); 3] = [
+    ("CGH018", "max_function_statements", |config| &mut config.max_function_statements),
+    ("CGH019", "max_cyclomatic_complexity", |config| &mut config.max_cyclomatic_complexity),
+    ("CGH020", "max_nesting_depth", |config| &mut config.max_nesting_depth),
+];
+
+/// enable / disable で指定できる診断コード（CHECK_FLAGS と CHECK_LIMITS のコード）
+/// 上限値を持つチェック（CGH018〜CGH020）は disable でのみ指定でき、有効化は max_* セッターで行う
+pub const CONFIGURABLE_CODES: [&str; CHECK_FLAGS.len() + CHECK_LIMITS.len()] = {
+    let mut codes = [""; CHECK_FLAGS.len() + CHECK_LIMITS.len()];
+    let mut i = 0;
+    while i < CHECK_FLAGS.len() {
+        codes[i] = CHECK_FLAGS[i].0;
+        i += 1;
+    }
+    while i < codes.len() {
+        codes[i] = CHECK_LIMITS[i - CHECK_FLAGS.len()].0;
+        i += 1;
+    }
+    codes
+};
+
+impl DiagnosticConfigBuilder {
+    /// デフォルト設定から組み立てを開始する
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 診断コードに対応するチェックを有効にする
+    ///
+    /// 未知のコードと、上限値を持つチェック（CGH018〜CGH020）はエラーになる。
+    pub fn enable(self, code: &str) -> Result<Self, String> {
+        self.set(code, true)
+    }
+
+    /// 診断コードに対応するチェックを無効にする（未知のコードはエラーになる）
+    pub fn disable(self, code: &str) -> Result<Self, String> {
+        self.set(code, false)
+    }
+
+    /// 診断コードに対応するチェックの有効・無効を設定する
+    pub fn set(mut self, code: &str, enabled: bool) -> Result<Self, String> {
+        if let Some((_, flag)) = CHECK_FLAGS.iter().find(|(flag_code, _)| *flag_code == code) {
+            *flag(&mut self.config) = enabled;
+            return Ok(self);
+        }
+        match CHECK_LIMITS.iter().find(|(limit_code, ..)| *limit_code == code) {
+            Some((_, _, limit)) if !enabled => {
+                *limit(&mut self.config) = None;
+                Ok(self)
+            }
+            Some((_, setter, _)) => Err(format!(
+                "診断コード {} は上限値を持つチェックのため enable では有効にできません。{} で上限値を指定してください",
+                code, setter
+            )),
+            None => Err(format!("不明な診断コードです: {}", code)),
+        }
+    }
+
+    /// すべてのチェックを無効にする（CERT C / CWE / MISRA C を含む）
+    pub fn disable_all(mut self) -> Self {
+        for (_, flag) in CHECK_FLAGS {
+            *flag(&mut self.config) = false;
+        }
+        for (_, _, limit) in CHECK_LIMITS {
+            *limit(&mut self.config) = None;
+        }
+        self.config.check_cert_c = false;
+        self.config.check_cwe_c = false;
+        self.config.check_misra_c = false;
+        self
+    }
+
+    /// 診断コードごとの設定（[rules] テーブル）を反映する
+    /// "off" はチェックを無効にしたうえでそのコードの診断を除外し、重大度の指定はチェックを有効にして重大度を上書きする
+    /// 上限値を持つチェックと個別のフラグを持たないコードは、重大度の上書きと診断の除外のみ行う
+    pub fn rules<'a>(mut self, rules: impl IntoIterator<Item = (&'a String, &'a RuleLevel)>) -> Self {
+        for (code, level) in rules {
+            let flag = CHECK_FLAGS.iter().find(|(flag_code, _)| flag_code == code).map(|(_, flag)| flag);
+            let severity = match level {
+                RuleLevel::Off => {
+                    if let Some(flag) = flag {
+                        *flag(&mut self.config) = false;
+                    } else if let Some((_, _, limit)) = CHECK_LIMITS.iter().find(|(limit_code, ..)| limit_code == code) {
+                        *limit(&mut self.config) = None;
+                    }
+                    self.config.disabled_codes.push(code.clone());
+                    continue;
+                }
+                RuleLevel::On => None,
+                RuleLevel::Error => Some(DiagnosticSeverity::Error),
+                RuleLevel::Warning => Some(DiagnosticSeverity::Warning),
+                RuleLevel::Info => Some(DiagnosticSeverity::Information),
+                RuleLevel::Hint => Some(DiagnosticSeverity::Hint),
+            };
+            if let Some(flag) = flag {
+                *flag(&mut self.config) = true;
+            }
+            if let Some(severity) = severity {
+                self.config.severity_overrides.insert(code.clone(), severity);
+            }
         }
+        self
+    }
+
+    pub fn max_function_statements(mut self, limit: Option<usize>) -> Self {
+        self.config.max_function_statements = limit;
+        self
+    }
+
+    pub fn max_cyclomatic_complexity(mut self, limit: Option<usize>) -> Self {
+        self.config.max_cyclomatic_complexity = limit;
+        self
+    }
+
+    pub fn max_nesting_depth(mut self, limit: Option<usize>) -> Self {
+        self.config.max_nesting_depth = limit;
+        self
+    }
+
+    pub fn function_naming_style(mut self, style: NamingStyle) -> Self {
+        self.config.function_naming_style = style;
+        self
+    }
+
+    pub fn type_naming_style(mut self, style: NamingStyle) -> Self {
+        self.config.type_naming_style = style;
+        self
+    }
+
+    pub fn min_identifier_length(mut self, length: usize) -> Self {
+        self.config.min_identifier_length = length;
+        self
+    }
+
+    pub fn allow_loop_index_names(mut self, allow: bool) -> Self {
+        self.config.allow_loop_index_names = allow;
+        self
+    }
+
+    pub fn warn_partial_array_init(mut self, warn: bool) -> Self {
+        self.config.warn_partial_array_init = warn;
+        self
+    }
+
+    pub fn warn_empty_return(mut self, warn: bool) -> Self {
+        self.config.warn_empty_return = warn;
+        self
+    }
+
+    pub fn operator_spacing_classes(mut self, classes: Vec<crate::config::OperatorClass>) -> Self {
+        self.config.operator_spacing_classes = classes;
+        self
+    }
+
+    pub fn preprocessor_indent_style(mut self, style: crate::config::PreprocessorIndentStyle) -> Self {
+        self.config.preprocessor_indent_style = style;
+        self
+    }
+
+    pub fn indent_style(mut self, style: crate::config::IndentStyle) -> Self {
+        self.config.indent_style = style;
+        self
+    }
+
+    pub fn indent_width(mut self, width: usize) -> Self {
+        self.config.indent_width = width;
+        self
+    }
+
+    pub fn project_root(mut self, root: Option<PathBuf>) -> Self {
+        self.config.project_root = root;
+        self
+    }
+
+    pub fn source_path(mut self, path: Option<PathBuf>) -> Self {
+        self.config.source_path = path;
+        self
+    }
+
+    pub fn exclude_paths(mut self, paths: Vec<PathBuf>) -> Self {
+        self.config.exclude_paths = paths;
+        self
+    }
+
+    pub fn build(self) -> DiagnosticConfig {
+        self.config
     }
 }
 
@@ -207,6 +656,26 @@ pub fn diagnose_with_source(tu: &TranslationUnit, config: &DiagnosticConfig, sou
     if config.check_function_format {
         diagnostics.extend(check_function_format(tu));
     }
+
+    if config.check_storage_class_order {
+        diagnostics.extend(check_storage_class_order(tu));
+    }
+
+    if config.check_implicit_int {
+        diagnostics.extend(check_implicit_int(tu));
+    }
+
+    if config.check_empty_aggregate {
+        diagnostics.extend(check_empty_aggregate(tu));
+    }
+
+    if config.check_short_identifiers {
+        diagnostics.extend(check_short_identifiers(tu, config.min_identifier_length, config.allow_loop_index_names));
+    }
+
+    if config.check_dead_preprocessor {
+        diagnostics.extend(check_dead_preprocessor(tu));
+    }
     
     if config.check_type_safety {
         diagnostics.extend(check_type_safety(tu));
@@ -216,6 +685,68 @@ pub fn diagnose_with_source(tu: &TranslationUnit, config: &DiagnosticConfig, sou
         diagnostics.extend(check_macro_parentheses(tu));
     }
     
+    if config.check_macro_argument_parentheses {
+        diagnostics.extend(check_macro_argument_parentheses(tu));
+    }
+    
+    if config.check_include_order {
+        diagnostics.extend(check_include_order(tu));
+    }
+    
+    if config.check_typedef_shadowing {
+        diagnostics.extend(check_typedef_shadowing(tu));
+    }
+    
+    if config.check_redundant_qualifiers {
+        diagnostics.extend(check_redundant_qualifiers(tu));
+    }
+    
+    if config.check_array_init_size {
+        diagnostics.extend(check_array_init_size(tu, config.warn_partial_array_init));
+    }
+    
+    if config.check_plain_char_numeric {
+        diagnostics.extend(check_plain_char_numeric(tu));
+    }
+    
+    if config.check_declarations_after_statements {
+        diagnostics.extend(check_declarations_after_statements(tu));
+    }
+    
+    if config.check_void_return_value {
+        diagnostics.extend(check_void_return_value(tu, config.warn_empty_return));
+    }
+    
+    if config.check_unused_parameters {
+        diagnostics.extend(check_unused_parameters(tu));
+    }
+    
+    if config.check_unused_locals {
+        diagnostics.extend(check_unused_locals(tu));
+    }
+    
+    if config.check_use_before_declaration {
+        diagnostics.extend(check_use_before_declaration(tu));
+    }
+    
+    if config.check_consistent_brace_style {
+        diagnostics.extend(check_consistent_brace_style(tu));
+    }
+    
+    if config.check_packed_struct_comment {
+        diagnostics.extend(check_packed_struct_comment(tu));
+    }
+    
+    if config.check_duplicate_macros {
+        diagnostics.extend(check_duplicate_macros(tu));
+    }
+    
+    if config.check_include_guard && is_header_file(config) {
+        if let Some(diag) = check_include_guard(tu) {
+            diagnostics.push(diag);
+        }
+    }
+    
     if config.check_global_var_naming {
         diagnostics.extend(check_global_var_naming(tu));
     }
@@ -228,12 +759,82 @@ pub fn diagnose_with_source(tu: &TranslationUnit, config: &DiagnosticConfig, sou
         diagnostics.extend(check_local_var_type_prefix(tu));
     }
     
+    if config.check_empty_loop_body {
+        diagnostics.extend(check_empty_loop_body(tu));
+    }
+
+    if let Some(max_statements) = config.max_function_statements {
+        diagnostics.extend(check_function_length(tu, max_statements));
+    }
+
+    if let Some(max_complexity) = config.max_cyclomatic_complexity {
+        diagnostics.extend(check_cyclomatic_complexity(tu, max_complexity));
+    }
+
+    if let Some(max_depth) = config.max_nesting_depth {
+        diagnostics.extend(check_max_nesting_depth(tu, max_depth));
+    }
+
+    if config.check_const_pointer_params {
+        diagnostics.extend(check_const_pointer_params(tu));
+    }
+
+    if config.check_function_naming {
+        diagnostics.extend(check_function_naming(tu, &config.function_naming_style));
+    }
+
+    if config.check_type_naming {
+        diagnostics.extend(check_type_naming(tu, &config.type_naming_style));
+    }
+
+    if config.check_enum_monotonic {
+        diagnostics.extend(check_enum_monotonic(tu));
+    }
+    
     if config.check_preprocessor_indent {
-        diagnostics.extend(check_preprocessor_indent(tu));
+        diagnostics.extend(check_preprocessor_indent(
+            tu,
+            source,
+            &config.preprocessor_indent_style,
+            config.indent_width,
+        ));
+    }
+    
+    if config.check_mixed_indentation && !source.is_empty() {
+        diagnostics.extend(check_mixed_indentation(source));
     }
     
     if config.check_indent_style && !source.is_empty() {
-        diagnostics.extend(check_indent_style(source, &config.indent_style, config.indent_width));
+        diagnostics.extend(check_indent_style(
+            source,
+            &config.indent_style,
+            config.indent_width,
+            !config.check_mixed_indentation,
+        ));
+    }
+    
+    if config.check_continuation_indent && !source.is_empty() {
+        diagnostics.extend(check_continuation_indent(tu, source, config.indent_width));
+    }
+    
+    if config.check_hex_unsigned_suffix && !source.is_empty() {
+        diagnostics.extend(check_hex_unsigned_suffix(source));
+    }
+    
+    if config.check_octal_literals && !source.is_empty() {
+        diagnostics.extend(check_octal_literals(source));
+    }
+    
+    if config.check_operator_spacing && !source.is_empty() {
+        diagnostics.extend(check_operator_spacing(tu, source, &config.operator_spacing_classes));
+    }
+    
+    if config.check_no_tabs && !source.is_empty() {
+        diagnostics.extend(check_no_tabs(source, &config.indent_style));
+    }
+
+    if config.check_non_ascii && !source.is_empty() {
+        diagnostics.extend(check_non_ascii(source));
     }
     
     if config.check_cert_c {
@@ -247,10 +848,38 @@ pub fn diagnose_with_source(tu: &TranslationUnit, config: &DiagnosticConfig, sou
     if config.check_misra_c {
         diagnostics.extend(check_misra_c(tu, source));
     }
-    
+
+    apply_rule_overrides(&mut diagnostics, config);
+    sort_by_position(&mut diagnostics);
+    dedup_diagnostics(&mut diagnostics);
     diagnostics
 }
 
+/// 診断をソース上の位置（`Diagnostic::ordering_key`）、診断コードの順に並べる
+/// 同じ位置・同じコードの診断はチェックを実行した順のまま残す（安定ソート）
+pub(crate) fn sort_by_position(diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by(|a, b| a.ordering_key().cmp(&b.ordering_key()));
+}
+
+/// コード・範囲・メッセージがすべて同じ診断を1つにまとめる（最初に現れたものを残す）
+pub(crate) fn dedup_diagnostics(diagnostics: &mut Vec<Diagnostic>) {
+    let mut seen = HashSet::new();
+    diagnostics.retain(|d| seen.insert((d.code.to_string(), d.span.clone(), d.message.clone())));
+}
+
+/// [rules] による診断コードごとの無効化と重大度の上書きを適用する
+pub(crate) fn apply_rule_overrides(diagnostics: &mut Vec<Diagnostic>, config: &DiagnosticConfig) {
+    if config.disabled_codes.is_empty() && config.severity_overrides.is_empty() {
+        return;
+    }
+    diagnostics.retain(|d| !config.disabled_codes.iter().any(|code| *code == d.code.to_string()));
+    for diagnostic in diagnostics.iter_mut() {
+        if let Some(severity) = config.severity_overrides.get(&diagnostic.code.to_string()) {
+            diagnostic.severity = *severity;
+        }
+    }
+}
+
 /// ファイルヘッダーコメントの存在をチェック
 fn check_file_header(tu: &TranslationUnit) -> Option<Diagnostic> {
     use crate::trivia::Comment;
@@ -303,44 +932,34 @@ fn check_file_header(tu: &TranslationUnit) -> Option<Diagnostic> {
 
 /// 関数定義のフォーマットをチェック
 /// 戻り値・修飾子が1行、関数名と引数が1行、開き括弧が1行に分かれているか確認
+///
+/// 関数定義のテキストを字句解析したトークン列から、関数名と本体の `{` の直前のトークンと同じ行に
+/// あるかを判定する（コメントは無視する）
 fn check_function_format(tu: &TranslationUnit) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
-    
-    for item in &tu.items {
-        if let Item::FunctionDecl { span, text, function_name, return_type, storage_class, .. } = item {
-            // textの内容を行ごとに分割して解析
-            // まず、行コメントを除去してから解析
-            let clean_text: String = text.lines()
-                .filter(|line| {
-                    let trimmed = line.trim();
-                    // 行コメントのみの行は除外
-                    !trimmed.starts_with("//")
-                })
-                .collect::<Vec<_>>()
-                .join("\n");
-            
-            let lines: Vec<&str> = clean_text.lines()
-                .filter(|line| !line.trim().is_empty()) // 空行を除外
-                .collect();
-            
-            if lines.is_empty() {
+
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { span, text, function_name, .. } = item {
+            let Some(layout) = FunctionLayout::of(text, function_name) else {
                 continue;
+            };
+
+            // チェック1: 関数名と同じ行に戻り値の型・修飾子が書かれていないか
+            if !layout.starts_line(layout.name) {
+                diagnostics.push(Diagnostic::new(
+                    layout.span(span, layout.name),
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "関数 '{}' の名前は戻り値の型とは別の行に記述してください。\n例: int\n    {}(...)",
+                        function_name, function_name
+                    ),
+                    DiagnosticCode::Custom("CGH002".to_string()),
+                ));
             }
-            
-            // 関数名が含まれる行を探す
-            let mut function_line_idx = None;
-            for (i, line) in lines.iter().enumerate() {
-                if line.contains(function_name) {
-                    function_line_idx = Some(i);
-                    break;
-                }
-            }
-            
-            if let Some(fn_idx) = function_line_idx {
-                let fn_line = lines[fn_idx];
-                
-                // チェック1: 関数名の行に開き括弧 '{' が含まれているか
-                if fn_line.contains('{') {
+
+            // チェック2: 関数本体の開き括弧 '{' が単独の行にあるか
+            if let Some(body_open) = layout.body_open {
+                if !layout.starts_line(body_open) {
                     diagnostics.push(Diagnostic::new(
                         span.clone(),
                         DiagnosticSeverity::Warning,
@@ -351,39 +970,867 @@ fn check_function_format(tu: &TranslationUnit) -> Vec<Diagnostic> {
                         DiagnosticCode::Custom("CGH002".to_string()),
                     ));
                 }
-                
-                // チェック2: 戻り値の型と関数名が同じ行にあるか
-                // 戻り値の型（return_type）が関数名と同じ行にあるかチェック
-                let has_return_type_on_same_line = if let Some(storage) = storage_class {
-                    // storage classがある場合、return_typeかstorageのいずれかが同じ行にあるかチェック
-                    fn_line.contains(return_type) || fn_line.contains(storage)
-                } else {
-                    fn_line.contains(return_type)
-                };
-                
-                if has_return_type_on_same_line {
-                    diagnostics.push(Diagnostic::new(
-                        span.clone(),
-                        DiagnosticSeverity::Warning,
-                        format!(
-                            "関数 '{}' の名前は戻り値の型とは別の行に記述してください。\n例: int\n    {}(...)",
-                            function_name, function_name
-                        ),
-                        DiagnosticCode::Custom("CGH002".to_string()),
-                    ));
-                }
             }
         }
     }
-    
+
     diagnostics
 }
 
-/// 型の安全性をチェック
-fn check_type_safety(tu: &TranslationUnit) -> Vec<Diagnostic> {
+/// 関数定義のテキストを字句解析したトークン列（空白・コメントを除く）と、関数名・本体の `{` の位置
+struct FunctionLayout<'a> {
+    text: &'a str,
+    tokens: Vec<Token>,
+    name: usize,  // 関数名（直後に `(` が続く識別子）のトークンの添字
+    body_open: Option<usize>,  // 引数リストの後の最初の `{` のトークンの添字（プロトタイプ宣言では None）
+}
+
+impl<'a> FunctionLayout<'a> {
+    fn of(text: &'a str, function_name: &str) -> Option<Self> {
+        let mut lexer = crate::lexer::Lexer::from_borrowed(text);
+        lexer.preserve_whitespace(true);
+        let tokens: Vec<Token> = std::iter::from_fn(|| lexer.next_token())
+            .filter(|token| !matches!(token, Token::Whitespace(..) | Token::BlockComment(..) | Token::LineComment(..)))
+            .collect();
+
+        let name = tokens.windows(2).position(|pair| {
+            matches!(&pair[0], Token::Ident(ident) if &*ident.name == function_name)
+                && matches!(pair[1], Token::LeftParen(..))
+        })?;
+
+        // 引数リストの閉じ括弧を探す
+        let mut depth = 0usize;
+        let mut params_end = None;
+        for (i, token) in tokens.iter().enumerate().skip(name + 1) {
+            match token {
+                Token::LeftParen(..) => depth += 1,
+                Token::RightParen(..) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        params_end = Some(i);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let body_open = (params_end? + 1..tokens.len()).find(|&i| matches!(tokens[i], Token::LeftBrace(..)));
+
+        Some(FunctionLayout { text, tokens, name, body_open })
+    }
+
+    /// `index` のトークンが行の最初のトークンか（直前のトークンとの間に改行があるか）
+    fn starts_line(&self, index: usize) -> bool {
+        let Some(prev) = index.checked_sub(1) else {
+            return true;
+        };
+        self.text[self.tokens[prev].span().byte_end_idx..self.tokens[index].span().byte_start_idx].contains('\n')
+    }
+
+    /// `index` のトークンのソース上の Span（`span` は関数定義全体の Span）
+    fn span(&self, span: &Span, index: usize) -> Span {
+        let token_span = self.tokens[index].span();
+        text_offset_span(span, self.text, token_span.byte_start_idx, token_span.byte_end_idx - token_span.byte_start_idx)
+    }
+}
+
+/// 記憶域クラス指定子の順序をチェック（CGH004）
+/// `const static int X;` のように型や型修飾子の後に記憶域クラス指定子がある宣言を検出する
+fn check_storage_class_order(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    const STORAGE_CLASSES: [&str; 5] = ["static", "extern", "auto", "register", "_Thread_local"];
     let mut diagnostics = Vec::new();
-    
-    for item in &tu.items {
+
+    for item in tu.all_items() {
+        let (span, text) = match item {
+            Item::VarDecl { span, text, .. }
+            | Item::FunctionDecl { span, text, .. }
+            | Item::StructDecl { span, text, .. }
+            | Item::UnionDecl { span, text, .. }
+            | Item::EnumDecl { span, text, .. } => (span, text),
+            _ => continue,
+        };
+
+        // 宣言指定子の部分（宣言子・初期化子・本体より前）だけを見る
+        let specifiers_end = text.find(['(', '=', ';', '{', '[', '*']).unwrap_or(text.len());
+        let specifiers: Vec<&str> = text[..specifiers_end].split_whitespace().collect();
+
+        let Some(position) = specifiers.iter().position(|word| STORAGE_CLASSES.contains(word)) else {
+            continue;
+        };
+        if position == 0 {
+            continue;
+        }
+
+        let storage_class = specifiers[position];
+        let mut reordered = specifiers.clone();
+        reordered.remove(position);
+        reordered.insert(0, storage_class);
+
+        diagnostics.push(Diagnostic::new(
+            span.clone(),
+            DiagnosticSeverity::Warning,
+            format!(
+                "記憶域クラス指定子 '{}' は型や型修飾子（'{}'）より前に記述してください。例: '{}'",
+                storage_class,
+                specifiers[..position].join(" "),
+                reordered.join(" ")
+            ),
+            DiagnosticCode::Custom("CGH004".to_string()),
+        ));
+    }
+
+    diagnostics
+}
+
+/// 重複した型修飾子のチェック（CGH034）
+/// 基本型・各ポインタ層ごとに、同じ const / volatile / restrict が2回以上書かれていないかを確認する
+fn check_redundant_qualifiers(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    fn check_type(ty: &Type, name: &str, span: &Span, diagnostics: &mut Vec<Diagnostic>) {
+        // typedef 名を使った型は typedef 側の修飾子が基本型の修飾子に合成されているため、基本型の重複は判定しない
+        let base = Some(ty.base_qualifiers.as_slice()).filter(|_| ty.alias.is_none());
+        let levels = base.into_iter().chain(ty.pointer_layers.iter().map(|layer| layer.qualifiers.as_slice()));
+        for qualifiers in levels {
+            for qualifier in [TypeQualifier::Const, TypeQualifier::Volatile, TypeQualifier::Restrict] {
+                if qualifiers.iter().filter(|q| **q == qualifier).count() > 1 {
+                    diagnostics.push(Diagnostic::new(
+                        span.clone(),
+                        DiagnosticSeverity::Warning,
+                        format!("'{}' の型で修飾子 '{}' が重複しています。", name, qualifier.to_string()),
+                        DiagnosticCode::Custom("CGH034".to_string()),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn visit_statements(statements: &[Statement], diagnostics: &mut Vec<Diagnostic>) {
+        for stmt in statements {
+            match stmt {
+                Statement::VarDecl { var_type: Some(ty), var_name, span, .. } => check_type(ty, var_name, span, diagnostics),
+                Statement::If { then_block, else_block, .. } => {
+                    visit_statements(then_block, diagnostics);
+                    if let Some(else_block) = else_block {
+                        visit_statements(else_block, diagnostics);
+                    }
+                }
+                Statement::For { init, body, .. } => {
+                    if let Some(init) = init {
+                        visit_statements(std::slice::from_ref(init.as_ref()), diagnostics);
+                    }
+                    visit_statements(body, diagnostics);
+                }
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } => visit_statements(body, diagnostics),
+                Statement::Block { statements, .. } => visit_statements(statements, diagnostics),
+                _ => {}
+            }
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for item in tu.all_items() {
+        match item {
+            Item::VarDecl { var_type: Some(ty), var_name, span, .. } => check_type(ty, var_name, span, &mut diagnostics),
+            Item::StructDecl { members, .. } => {
+                for member in members {
+                    if let Some(ty) = &member.member_type {
+                        check_type(ty, &member.name, &member.span, &mut diagnostics);
+                    }
+                }
+            }
+            Item::UnionDecl { members, .. } => {
+                for member in members {
+                    if let Some(ty) = &member.member_type {
+                        check_type(ty, &member.name, &member.span, &mut diagnostics);
+                    }
+                }
+            }
+            Item::FunctionDecl { body: Some(body), .. } => visit_statements(body, &mut diagnostics),
+            _ => {}
+        }
+    }
+    diagnostics
+}
+
+/// ヘッダーにプロトタイプ宣言のない関数定義のチェック（CGH035）
+///
+/// `header_functions` は #include で解析したヘッダーで宣言された関数名（`Parser::header_functions`）。
+/// ヘッダーを1つも解析していない場合（None）やヘッダーファイル自体の診断では何も報告しない。
+/// 他の診断と同様に [rules] の上書きを適用した結果を返す。
+pub(crate) fn diagnose_missing_prototypes(
+    tu: &TranslationUnit,
+    config: &DiagnosticConfig,
+    header_functions: Option<&HashSet<String>>,
+) -> Vec<Diagnostic> {
+    let Some(header_functions) = header_functions else {
+        return Vec::new();
+    };
+    if !config.check_missing_prototypes || is_header_file(config) {
+        return Vec::new();
+    }
+
+    let mut diagnostics = check_missing_prototypes(tu, header_functions);
+    apply_rule_overrides(&mut diagnostics, config);
+    diagnostics
+}
+
+/// static でない関数定義のうち、プロトタイプ宣言がヘッダーにないものを報告する
+/// `main` はヘッダーで宣言しないのが普通なので対象外とする
+fn check_missing_prototypes(tu: &TranslationUnit, header_functions: &HashSet<String>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for item in tu.all_items() {
+        let Item::FunctionDecl { span, text, function_name, storage_class, body: Some(_), .. } = item else {
+            continue;
+        };
+        if storage_class.as_deref() == Some("static")
+            || function_name == "main"
+            || header_functions.contains(function_name)
+        {
+            continue;
+        }
+        diagnostics.push(Diagnostic::new(
+            function_name_span(span, text, function_name),
+            DiagnosticSeverity::Information,
+            format!(
+                "関数 '{}' のプロトタイプ宣言がインクルードしたヘッダーにありません。ヘッダーで宣言するか、ファイル内でのみ使う場合は static にしてください",
+                function_name
+            ),
+            DiagnosticCode::Custom("CGH035".to_string()),
+        ));
+    }
+
+    diagnostics
+}
+
+/// 配列の要素数と初期化子リストの要素数が合っているかチェック
+///
+/// 要素数より多い初期化子はエラー。`warn_partial` が true なら少ない場合も警告する（`{0}` は除く）。
+/// 要素数を省略した `[]` と、指示付き初期化子（`.x = ` / `[2] = `）を含むリストは対象外。
+fn check_array_init_size(tu: &TranslationUnit, warn_partial: bool) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // 要素数にはオブジェクト形式マクロ（#define SIZE 4）も使えるようにする
+    let macros: HashMap<String, String> = tu
+        .all_items()
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::Define { macro_name, params: None, macro_value, .. } => Some((macro_name.clone(), macro_value.clone())),
+            _ => None,
+        })
+        .collect();
+
+    for item in tu.all_items() {
+        let Item::VarDecl { span, var_name, var_type: Some(ty), initializer: Some(Initializer::List { elements, .. }), .. } = item else {
+            continue;
+        };
+        let Some(size) = ty.array_dimensions.first().and_then(|dimension| dimension.size.as_deref()) else {
+            continue;
+        };
+        let is_designated = elements.iter().any(|element| {
+            matches!(element, Initializer::Value { text, .. } if text.starts_with('.') || text.starts_with('['))
+        });
+        if is_designated {
+            continue;
+        }
+        let Some(size) = crate::const_eval::eval_const_str(size, &macros).and_then(|n| usize::try_from(n).ok()) else {
+            continue;
+        };
+
+        let count = elements.len();
+        if count > size {
+            diagnostics.push(Diagnostic::new(
+                span.clone(),
+                DiagnosticSeverity::Error,
+                format!("配列 '{}' の要素数 {} に対して初期化子が {} 個あります", var_name, size, count),
+                DiagnosticCode::Custom("CGH036".to_string()),
+            ));
+        } else if warn_partial && count < size && !is_zero_initializer(elements) {
+            diagnostics.push(Diagnostic::new(
+                span.clone(),
+                DiagnosticSeverity::Warning,
+                format!(
+                    "配列 '{}' の要素数 {} に対して初期化子が {} 個しかありません。残りの要素は 0 で初期化されます",
+                    var_name, size, count
+                ),
+                DiagnosticCode::Custom("CGH036".to_string()),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// `{0}` によるゼロ初期化の慣用句かどうか
+fn is_zero_initializer(elements: &[Initializer]) -> bool {
+    matches!(elements, [Initializer::Value { text, .. }] if text == "0")
+}
+
+/// 符号を明示しない char 型の変数を数値として演算に使っていないかチェック（CGH037）
+///
+/// char の符号は処理系定義のため、数値を格納する変数は signed char / unsigned char を明示すべき。
+/// 算術演算の初期化式を持つ、または関数内で算術演算・インクリメントの被演算子になる変数を報告する。
+fn check_plain_char_numeric(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    fn is_plain_char(ty: &Option<Type>) -> bool {
+        ty.as_ref().is_some_and(|ty| {
+            ty.base_type == BaseType::Char && ty.alias.is_none() && !ty.is_pointer() && !ty.is_array()
+        })
+    }
+
+    fn is_arithmetic(expr: &Expression) -> bool {
+        matches!(
+            expr,
+            Expression::BinaryOp {
+                op: BinaryOperator::Add
+                    | BinaryOperator::Subtract
+                    | BinaryOperator::Multiply
+                    | BinaryOperator::Divide
+                    | BinaryOperator::Modulo
+                    | BinaryOperator::AddAssign
+                    | BinaryOperator::SubtractAssign
+                    | BinaryOperator::MultiplyAssign
+                    | BinaryOperator::DivideAssign
+                    | BinaryOperator::ModuloAssign,
+                ..
+            } | Expression::UnaryOp {
+                op: UnaryOperator::Negate
+                    | UnaryOperator::PreIncrement
+                    | UnaryOperator::PreDecrement
+                    | UnaryOperator::PostIncrement
+                    | UnaryOperator::PostDecrement,
+                ..
+            }
+        )
+    }
+
+    /// 関数内で宣言された char 変数（名前・宣言のSpan・算術演算で初期化しているか）を集める
+    fn collect_char_locals(statements: &[Statement], locals: &mut Vec<(String, Span, bool)>) {
+        for stmt in statements {
+            match stmt {
+                Statement::VarDecl { var_type, var_name, initializer, span } if is_plain_char(var_type) => {
+                    let arithmetic_init = initializer.as_ref().is_some_and(is_arithmetic);
+                    locals.push((var_name.clone(), span.clone(), arithmetic_init));
+                }
+                Statement::If { then_block, else_block, .. } => {
+                    collect_char_locals(then_block, locals);
+                    if let Some(else_block) = else_block {
+                        collect_char_locals(else_block, locals);
+                    }
+                }
+                Statement::For { init, body, .. } => {
+                    if let Some(init) = init {
+                        collect_char_locals(std::slice::from_ref(init.as_ref()), locals);
+                    }
+                    collect_char_locals(body, locals);
+                }
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } => collect_char_locals(body, locals),
+                Statement::Block { statements, .. } => collect_char_locals(statements, locals),
+                _ => {}
+            }
+        }
+    }
+
+    let globals: Vec<(String, Span)> = tu
+        .all_items()
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::VarDecl { span, var_name, var_type, .. } if is_plain_char(var_type) => Some((var_name.clone(), span.clone())),
+            _ => None,
+        })
+        .collect();
+
+    let mut flagged: Vec<(String, Span)> = Vec::new();
+    for item in tu.all_items() {
+        let Item::FunctionDecl { body: Some(statements), .. } = item else {
+            continue;
+        };
+
+        // 算術演算の被演算子として直接使われている変数名
+        let mut operands: HashSet<String> = HashSet::new();
+        visit_expressions(statements, &mut |expr| {
+            if !is_arithmetic(expr) {
+                return;
+            }
+            let sides: Vec<&Expression> = match expr {
+                Expression::BinaryOp { left, right, .. } => vec![left, right],
+                Expression::UnaryOp { operand, .. } => vec![operand],
+                _ => Vec::new(),
+            };
+            for side in sides {
+                if let Expression::Identifier { name, .. } = side {
+                    operands.insert(name.clone());
+                }
+            }
+        });
+
+        let mut locals = Vec::new();
+        collect_char_locals(statements, &mut locals);
+        for (name, span, arithmetic_init) in &locals {
+            if *arithmetic_init || operands.contains(name) {
+                flagged.push((name.clone(), span.clone()));
+            }
+        }
+        // ローカル変数に隠されていないグローバル変数
+        for (name, span) in &globals {
+            let shadowed = locals.iter().any(|(local, ..)| local == name);
+            if !shadowed && operands.contains(name) && !flagged.iter().any(|(n, s)| n == name && s == span) {
+                flagged.push((name.clone(), span.clone()));
+            }
+        }
+    }
+
+    flagged
+        .into_iter()
+        .map(|(name, span)| {
+            Diagnostic::new(
+                span,
+                DiagnosticSeverity::Warning,
+                format!(
+                    "char 型の変数 '{}' が数値として演算に使われています。char の符号は処理系定義のため、signed char / unsigned char を明示してください",
+                    name
+                ),
+                DiagnosticCode::Custom("CGH037".to_string()),
+            )
+        })
+        .collect()
+}
+
+/// ブロックの途中での変数宣言をチェック（CGH038）
+/// C89 ではブロック内の宣言は先頭にまとめる必要があり、文の後に現れる宣言を報告する
+fn check_declarations_after_statements(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    fn visit_block(statements: &[Statement], diagnostics: &mut Vec<Diagnostic>) {
+        let mut seen_statement = false;
+        for stmt in statements {
+            match stmt {
+                Statement::VarDecl { var_name, span, .. } => {
+                    if seen_statement {
+                        diagnostics.push(Diagnostic::new(
+                            span.clone(),
+                            DiagnosticSeverity::Information,
+                            format!(
+                                "変数 '{}' の宣言が文の後にあります。宣言はブロックの先頭にまとめてください（C89）",
+                                var_name
+                            ),
+                            DiagnosticCode::Custom("CGH038".to_string()),
+                        ));
+                    }
+                    continue;
+                }
+                Statement::If { then_block, else_block, .. } => {
+                    visit_block(then_block, diagnostics);
+                    if let Some(else_block) = else_block {
+                        visit_block(else_block, diagnostics);
+                    }
+                }
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } | Statement::For { body, .. } => {
+                    visit_block(body, diagnostics);
+                }
+                Statement::Block { statements, .. } => visit_block(statements, diagnostics),
+                Statement::Expression { .. } | Statement::Return { .. } | Statement::Empty { .. } => {}
+            }
+            seen_statement = true;
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { body: Some(statements), .. } = item {
+            visit_block(statements, &mut diagnostics);
+        }
+    }
+    diagnostics
+}
+
+/// 戻り値の型と return 文の対応をチェック（CGH040）
+/// void 関数での `return expr;` はエラー。`warn_empty_return` が true なら void でない関数での `return;` も警告する
+fn check_void_return_value(tu: &TranslationUnit, warn_empty: bool) -> Vec<Diagnostic> {
+    fn visit_statements(statements: &[Statement], returns: &mut Vec<(bool, Span)>) {
+        for stmt in statements {
+            match stmt {
+                Statement::Return { value, span } => returns.push((value.is_some(), span.clone())),
+                Statement::If { then_block, else_block, .. } => {
+                    visit_statements(then_block, returns);
+                    if let Some(else_block) = else_block {
+                        visit_statements(else_block, returns);
+                    }
+                }
+                Statement::For { body, .. } | Statement::While { body, .. } | Statement::DoWhile { body, .. } => {
+                    visit_statements(body, returns);
+                }
+                Statement::Block { statements, .. } => visit_statements(statements, returns),
+                Statement::VarDecl { .. } | Statement::Expression { .. } | Statement::Empty { .. } => {}
+            }
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for item in tu.all_items() {
+        let Item::FunctionDecl { function_name, return_type, body: Some(statements), .. } = item else {
+            continue;
+        };
+        // 修飾子を除いて void だけが残る場合（void * は値を返す）
+        let is_void = !return_type.contains('*')
+            && return_type
+                .split_whitespace()
+                .filter(|word| !matches!(*word, "const" | "volatile" | "inline" | "static" | "extern"))
+                .eq(["void"]);
+
+        let mut returns = Vec::new();
+        visit_statements(statements, &mut returns);
+        for (has_value, span) in returns {
+            if is_void && has_value {
+                diagnostics.push(Diagnostic::new(
+                    span,
+                    DiagnosticSeverity::Error,
+                    format!("void 関数 '{}' で値を返しています", function_name),
+                    DiagnosticCode::Custom("CGH040".to_string()),
+                ));
+            } else if warn_empty && !is_void && !has_value {
+                diagnostics.push(Diagnostic::new(
+                    span,
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "関数 '{}' の戻り値の型は '{}' ですが、値のない return があります",
+                        function_name,
+                        return_type.trim()
+                    ),
+                    DiagnosticCode::Custom("CGH040".to_string()),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+/// 関数本体で使われていない引数をチェック（CGH041）
+/// 名前が `_` で始まる引数は意図的に使わないものとして対象外にする
+fn check_unused_parameters(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for item in tu.all_items() {
+        let Item::FunctionDecl { span, text, function_name, parameter_list, body: Some(statements), .. } = item else {
+            continue;
+        };
+
+        let mut referenced = HashSet::new();
+        visit_expressions(statements, &mut |expr| {
+            if let Expression::Identifier { name, .. } = expr {
+                referenced.insert(name.clone());
+            }
+        });
+
+        for (_, name) in parameter_list {
+            if name.starts_with('_') || referenced.contains(name) {
+                continue;
+            }
+            diagnostics.push(Diagnostic::new(
+                function_name_span(span, text, function_name),
+                DiagnosticSeverity::Hint,
+                format!(
+                    "関数 '{}' の引数 '{}' は使われていません。意図的に使わない場合は '(void){};' と書いてください",
+                    function_name, name, name
+                ),
+                DiagnosticCode::Custom("CGH041".to_string()),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// 宣言後に使われていないローカル変数をチェック（CGH042）
+/// 同じブロックの後続の文（入れ子のブロックを含む）で名前が参照されなければ報告する。
+/// 初期化式は使用に含めず、`&x` など読み書き以外の参照も使用とみなす。
+fn check_unused_locals(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    fn is_referenced(statements: &[Statement], expressions: &[&Expression], name: &str) -> bool {
+        let mut found = false;
+        let mut check = |expr: &Expression| {
+            if matches!(expr, Expression::Identifier { name: referenced, .. } if referenced == name) {
+                found = true;
+            }
+        };
+        visit_expressions(statements, &mut check);
+        expressions.iter().for_each(|expr| visit_expression(expr, &mut check));
+        found
+    }
+
+    fn unused(var_name: &str, span: &Span) -> Diagnostic {
+        Diagnostic::new(
+            span.clone(),
+            DiagnosticSeverity::Warning,
+            format!("ローカル変数 '{}' は宣言されていますが使われていません", var_name),
+            DiagnosticCode::Custom("CGH042".to_string()),
+        )
+    }
+
+    fn visit_block(statements: &[Statement], diagnostics: &mut Vec<Diagnostic>) {
+        for (i, stmt) in statements.iter().enumerate() {
+            match stmt {
+                Statement::VarDecl { var_name, span, .. } => {
+                    if !is_referenced(&statements[i + 1..], &[], var_name) {
+                        diagnostics.push(unused(var_name, span));
+                    }
+                }
+                Statement::If { then_block, else_block, .. } => {
+                    visit_block(then_block, diagnostics);
+                    if let Some(else_block) = else_block {
+                        visit_block(else_block, diagnostics);
+                    }
+                }
+                Statement::For { init, condition, update, body, .. } => {
+                    // for の初期化部の宣言は条件・更新式・本体がスコープ
+                    if let Some(Statement::VarDecl { var_name, span, .. }) = init.as_deref() {
+                        let expressions: Vec<&Expression> = condition.iter().chain(update.iter()).collect();
+                        if !is_referenced(body, &expressions, var_name) {
+                            diagnostics.push(unused(var_name, span));
+                        }
+                    }
+                    visit_block(body, diagnostics);
+                }
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } => visit_block(body, diagnostics),
+                Statement::Block { statements, .. } => visit_block(statements, diagnostics),
+                Statement::Expression { .. } | Statement::Return { .. } | Statement::Empty { .. } => {}
+            }
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { body: Some(statements), .. } = item {
+            visit_block(statements, &mut diagnostics);
+        }
+    }
+    diagnostics
+}
+
+/// ブロック内で宣言より前に変数を使っていないかチェック（CGH043）
+///
+/// 1回目で各ブロックの宣言を集め、2回目でそれより前の文からの参照を探す。
+/// 引数・グローバルなシンボル・外側のブロックで宣言済みの名前は、前の文から正しく参照できるため対象外。
+fn check_use_before_declaration(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    /// 文の中で名前を参照しているか（その名前を宣言し直す入れ子のブロックでは、宣言以降を見ない）
+    fn references(stmt: &Statement, name: &str) -> bool {
+        let mut found = false;
+        let mut check = |expr: &Expression| {
+            if matches!(expr, Expression::Identifier { name: referenced, .. } if referenced == name) {
+                found = true;
+            }
+        };
+        match stmt {
+            Statement::VarDecl { initializer: Some(expr), .. }
+            | Statement::Expression { expr, .. }
+            | Statement::Return { value: Some(expr), .. } => visit_expression(expr, &mut check),
+            Statement::If { condition, then_block, else_block, .. } => {
+                visit_expression(condition, &mut check);
+                return found
+                    || block_references(then_block, name)
+                    || else_block.as_deref().is_some_and(|block| block_references(block, name));
+            }
+            Statement::While { condition, body, .. } | Statement::DoWhile { condition, body, .. } => {
+                visit_expression(condition, &mut check);
+                return found || block_references(body, name);
+            }
+            Statement::For { init, condition, update, body, .. } => {
+                if let Some(init) = init.as_deref() {
+                    if references(init, name) {
+                        return true;
+                    }
+                    if matches!(init, Statement::VarDecl { var_name, .. } if var_name == name) {
+                        return false;
+                    }
+                }
+                condition.iter().chain(update.iter()).for_each(|expr| visit_expression(expr, &mut check));
+                return found || block_references(body, name);
+            }
+            Statement::Block { statements, .. } => return block_references(statements, name),
+            _ => {}
+        }
+        found
+    }
+
+    fn block_references(statements: &[Statement], name: &str) -> bool {
+        for stmt in statements {
+            if references(stmt, name) {
+                return true;
+            }
+            if matches!(stmt, Statement::VarDecl { var_name, .. } if var_name == name) {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn visit_block(statements: &[Statement], visible: &HashSet<String>, diagnostics: &mut Vec<Diagnostic>) {
+        // 1回目: このブロックで新しく宣言される名前
+        let declarations: Vec<(usize, &String, &Span)> = statements
+            .iter()
+            .enumerate()
+            .filter_map(|(i, stmt)| match stmt {
+                Statement::VarDecl { var_name, span, .. } if !visible.contains(var_name) => Some((i, var_name, span)),
+                _ => None,
+            })
+            .collect();
+
+        // 2回目: 宣言より前の文からの参照
+        for (i, stmt) in statements.iter().enumerate() {
+            for (_, name, decl_span) in declarations.iter().filter(|(decl_index, ..)| *decl_index > i) {
+                if references(stmt, name) {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            stmt.span().clone(),
+                            DiagnosticSeverity::Error,
+                            format!("変数 '{}' が宣言より前で使われています", name),
+                            DiagnosticCode::Custom("CGH043".to_string()),
+                        )
+                        .with_related((*decl_span).clone(), format!("'{}' の宣言", name)),
+                    );
+                }
+            }
+
+            // 入れ子のブロックからは、それまでに宣言された名前が見える
+            let mut inner_visible = visible.clone();
+            inner_visible.extend(declarations.iter().filter(|(decl_index, ..)| *decl_index < i).map(|(_, name, _)| (*name).clone()));
+            match stmt {
+                Statement::If { then_block, else_block, .. } => {
+                    visit_block(then_block, &inner_visible, diagnostics);
+                    if let Some(else_block) = else_block {
+                        visit_block(else_block, &inner_visible, diagnostics);
+                    }
+                }
+                Statement::For { init, body, .. } => {
+                    if let Some(Statement::VarDecl { var_name, .. }) = init.as_deref() {
+                        inner_visible.insert(var_name.clone());
+                    }
+                    visit_block(body, &inner_visible, diagnostics);
+                }
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } => {
+                    visit_block(body, &inner_visible, diagnostics);
+                }
+                Statement::Block { statements, .. } => visit_block(statements, &inner_visible, diagnostics),
+                _ => {}
+            }
+        }
+    }
+
+    let globals: HashSet<String> = tu.symbols().into_iter().map(|symbol| symbol.name).collect();
+    let mut diagnostics = Vec::new();
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { parameter_list, body: Some(statements), .. } = item {
+            let mut visible = globals.clone();
+            visible.extend(parameter_list.iter().map(|(_, name)| name.clone()));
+            visit_block(statements, &visible, &mut diagnostics);
+        }
+    }
+    diagnostics
+}
+
+/// 開き括弧の配置がファイル内で統一されているかチェック（CGH046）
+/// 関数本体と制御文（`if (...) {` / `else {` / `do {` など）のブロックをそれぞれ別に比較し、
+/// Allman スタイル（`{` を単独の行に置く）と K&R スタイル（`{` を行末に置く）のうち少数派の箇所を報告する。
+/// 同数の場合は先に現れたスタイルに合わせる
+fn check_consistent_brace_style(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    // (単独の行にあるか, 開き括弧の位置, 対象の説明)
+    let mut function_braces: Vec<(bool, Span, String)> = Vec::new();
+    let mut block_braces: Vec<(bool, Span, String)> = Vec::new();
+
+    for item in tu.all_items() {
+        let Item::FunctionDecl { span, text, function_name, body: Some(_), .. } = item else {
+            continue;
+        };
+        let Some(layout) = FunctionLayout::of(text, function_name) else {
+            continue;
+        };
+        let Some(body_open) = layout.body_open else {
+            continue;
+        };
+
+        function_braces.push((
+            layout.starts_line(body_open),
+            layout.span(span, body_open),
+            format!("関数 '{}' の本体", function_name),
+        ));
+
+        // 直前が `)` / `else` / `do` の `{` を制御文のブロックとみなす（初期化子の `{` などは対象外）
+        for i in body_open + 1..layout.tokens.len() {
+            let is_block_open = matches!(layout.tokens[i], Token::LeftBrace(..))
+                && matches!(layout.tokens[i - 1], Token::RightParen(..) | Token::ElseKeyword(..) | Token::Do(..));
+            if is_block_open {
+                block_braces.push((
+                    layout.starts_line(i),
+                    layout.span(span, i),
+                    format!("関数 '{}' 内のブロック", function_name),
+                ));
+            }
+        }
+    }
+
+    let style_name = |own_line: bool| if own_line { "Allman スタイル（単独の行）" } else { "K&R スタイル（行末）" };
+    let mut diagnostics = Vec::new();
+    for braces in [function_braces, block_braces] {
+        let Some(&(first_style, _, _)) = braces.first() else {
+            continue;
+        };
+        let own_line_count = braces.iter().filter(|(own_line, _, _)| *own_line).count();
+        let end_of_line_count = braces.len() - own_line_count;
+        let majority = match own_line_count.cmp(&end_of_line_count) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => first_style,
+        };
+        for (own_line, span, target) in braces {
+            if own_line != majority {
+                diagnostics.push(Diagnostic::new(
+                    span,
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "{}の開き括弧が {} になっています。ファイル内の他の箇所に合わせて {} にしてください",
+                        target,
+                        style_name(own_line),
+                        style_name(majority)
+                    ),
+                    DiagnosticCode::Custom("CGH046".to_string()),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+/// CGH049: パックした構造体（`__attribute__((packed))`）に理由を説明するコメントがあるかをチェック
+/// パックはメンバーのアラインメントを崩し移植性に影響するため、直前のコメントで理由を残すことを求める
+fn check_packed_struct_comment(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for item in tu.all_items() {
+        let Item::StructDecl { span, struct_name, attributes, trivia, .. } = item else {
+            continue;
+        };
+        let is_packed = attributes
+            .iter()
+            .any(|attribute| matches!(attribute.trim_start_matches("gnu::"), "packed" | "__packed__"));
+        if !is_packed || !trivia.leading.is_empty() {
+            continue;
+        }
+
+        let message = match struct_name {
+            Some(name) => format!("パックした構造体 '{}' にパックする理由を説明するコメントがありません。", name),
+            None => "パックした構造体にパックする理由を説明するコメントがありません。".to_string(),
+        };
+        diagnostics.push(Diagnostic::new(
+            span.clone(),
+            DiagnosticSeverity::Warning,
+            message,
+            DiagnosticCode::Custom("CGH049".to_string()),
+        ));
+    }
+
+    diagnostics
+}
+
+/// 型の安全性をチェック
+fn check_type_safety(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    
+    for item in tu.all_items() {
         if let Item::VarDecl { span, var_name, var_type, text, .. } = item {
             if let Some(ty) = var_type {
                 // チェック1: void型の変数宣言（void*は除く）
@@ -445,70 +1892,289 @@ fn check_type_safety(tu: &TranslationUnit) -> Vec<Diagnostic> {
             }
         }
     }
-    
-    diagnostics
+    
+    diagnostics
+}
+
+/// #defineマクロの置換値が括弧で囲まれているかチェック
+fn check_macro_parentheses(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    
+    fn check_items(items: &[Item], diagnostics: &mut Vec<Diagnostic>) {
+        for item in items {
+            match item {
+                Item::Define { span, macro_name, params, macro_value, .. } => {
+                    let macro_value = macro_value.trim();
+                    
+                    // 空の値や数値リテラルのみはスキップ
+                    if macro_value.is_empty() || is_simple_literal(macro_value) {
+                        continue;
+                    }
+                    
+                    // 関数形式マクロで文（do { ... } while (0) やブロック）に展開されるものは対象外
+                    if params.is_some() && is_statement_macro_body(macro_value) {
+                        continue;
+                    }
+                    
+                    // 演算子を含まなければ優先順位の問題は起きない
+                    if !contains_operator(macro_value) {
+                        continue;
+                    }
+                    
+                    // 括弧で囲まれているかチェック
+                    if !is_wrapped_in_parentheses(macro_value) {
+                        diagnostics.push(Diagnostic::new(
+                            span.clone(),
+                            DiagnosticSeverity::Warning,
+                            format!(
+                                "マクロ '{}' の置換値 '{}' は演算子を含んでいますが、括弧で囲まれていません。意図しない演算子の優先順位問題を避けるため、括弧で囲むことを推奨します。",
+                                macro_name,
+                                macro_value
+                            ),
+                            DiagnosticCode::Custom("CGH005".to_string()),
+                        ));
+                    }
+                },
+                Item::ConditionalBlock { .. } => {
+                    // 再帰的にチェック
+                    for branch in item.branches() {
+                        check_items(branch, diagnostics);
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+    
+    check_items(&tu.items, &mut diagnostics);
+    diagnostics
+}
+
+/// 関数形式マクロの引数の括弧チェック（CGH031）
+/// `#define SQ(x) x*x` のように引数が括弧で囲まれていないと、`SQ(a + 1)` が `a + 1*a + 1` に展開される
+fn check_macro_argument_parentheses(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for item in tu.all_items() {
+        let Item::Define { span, macro_name, params: Some(params), macro_value, .. } = item else {
+            continue;
+        };
+        // 文に展開されるマクロや演算子を含まないマクロは優先順位の問題が起きない
+        if is_statement_macro_body(macro_value) || !contains_operator(macro_value) {
+            continue;
+        }
+
+        let unwrapped: Vec<&str> = params
+            .iter()
+            .map(String::as_str)
+            .filter(|param| has_unwrapped_macro_param(macro_value, param))
+            .collect();
+        if unwrapped.is_empty() {
+            continue;
+        }
+        diagnostics.push(Diagnostic::new(
+            span.clone(),
+            DiagnosticSeverity::Warning,
+            format!(
+                "マクロ '{}' の引数 '{}' が置換値の中で括弧で囲まれていません。引数に式を渡したときの優先順位問題を避けるため、'({})' のように括弧で囲むことを推奨します。",
+                macro_name,
+                unwrapped.join("', '"),
+                unwrapped[0]
+            ),
+            DiagnosticCode::Custom("CGH031".to_string()),
+        ));
+    }
+
+    diagnostics
+}
+
+/// 同じマクロが異なる値で再定義されていないかチェック
+/// #if/#else など排他的な分岐での定義は別々に扱い、#undef 後の再定義は許可する
+fn check_duplicate_macros(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    use std::collections::HashMap;
+
+    // マクロ名 -> (置換値, 最初の定義の位置)
+    type MacroMap = HashMap<String, (String, Span)>;
+
+    fn normalize_value(value: &str) -> String {
+        value.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn undef_name(text: &str) -> Option<&str> {
+        let rest = text.trim().strip_prefix('#')?.trim_start().strip_prefix("undef")?;
+        rest.split_whitespace().next()
+    }
+
+    fn check_items(items: &[Item], defined: &mut MacroMap, diagnostics: &mut Vec<Diagnostic>) {
+        for item in items {
+            match item {
+                Item::Define { span, macro_name, macro_value, .. } => {
+                    if let Some((first_value, first_span)) = defined.get(macro_name) {
+                        if normalize_value(first_value) != normalize_value(macro_value) {
+                            diagnostics.push(Diagnostic::new(
+                                span.clone(),
+                                DiagnosticSeverity::Warning,
+                                format!(
+                                    "マクロ '{}' が異なる値で再定義されています（'{}' → '{}'）。最初の定義は {} 行目です。",
+                                    macro_name,
+                                    first_value.trim(),
+                                    macro_value.trim(),
+                                    first_span.start_line + 1
+                                ),
+                                DiagnosticCode::Custom("CGH015".to_string()),
+                            ).with_related(first_span.clone(), format!("マクロ '{}' の最初の定義", macro_name)));
+                        }
+                    } else {
+                        defined.insert(macro_name.clone(), (macro_value.clone(), span.clone()));
+                    }
+                },
+                Item::Include { text, .. } => {
+                    // #undef はIncludeトークンとして扱われている
+                    if let Some(name) = undef_name(text) {
+                        defined.remove(name);
+                    }
+                },
+                Item::ConditionalBlock { .. } => {
+                    // 各分岐は分岐前の定義状態から独立して調べ、結果をまとめる
+                    let before = defined.clone();
+                    for branch_items in item.branches() {
+                        let mut branch = before.clone();
+                        check_items(branch_items, &mut branch, diagnostics);
+                        for (name, value) in branch {
+                            defined.entry(name).or_insert(value);
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    let mut defined = MacroMap::new();
+    check_items(&tu.items, &mut defined, &mut diagnostics);
+    diagnostics
+}
+
+/// 診断対象がヘッダーファイル（拡張子 .h）かどうか
+fn is_header_file(config: &DiagnosticConfig) -> bool {
+    config
+        .source_path
+        .as_ref()
+        .and_then(|path| path.extension())
+        .is_some_and(|ext| ext == "h")
+}
+
+/// ヘッダーファイル全体が #ifndef X / #define X ... #endif で囲まれているかチェック
+fn check_include_guard(tu: &TranslationUnit) -> Option<Diagnostic> {
+    let guard_diagnostic = |span: Span, message: String| {
+        Diagnostic::new(
+            span,
+            DiagnosticSeverity::Warning,
+            message,
+            DiagnosticCode::Custom("CGH016".to_string()),
+        )
+    };
+
+    // コメントはtriviaとして扱われるため、先頭のItemがガードの #ifndef であるべき
+    let first = tu.items.first()?;
+
+    let Item::ConditionalBlock { directive_type, condition, items, elif_branches, else_branch, start_span, end_span, .. } = first else {
+        return Some(guard_diagnostic(
+            Span::new(0, 0, 0, 0),
+            "ヘッダーファイルにインクルードガードがありません。ファイル全体を #ifndef / #define / #endif で囲んでください。".to_string(),
+        ));
+    };
+
+    if directive_type != "ifndef" {
+        return Some(guard_diagnostic(
+            start_span.clone(),
+            "ヘッダーファイルにインクルードガードがありません。ファイル全体を #ifndef / #define / #endif で囲んでください。".to_string(),
+        ));
+    }
+
+    // #ifndef の直後に同じマクロ名の #define が必要
+    let guard_name = condition.trim();
+    let defines_guard = matches!(
+        items.first(),
+        Some(Item::Define { macro_name, .. }) if macro_name == guard_name
+    );
+    if !defines_guard {
+        return Some(guard_diagnostic(
+            start_span.clone(),
+            format!(
+                "インクルードガード '#ifndef {}' の直後に '#define {}' がありません。",
+                guard_name, guard_name
+            ),
+        ));
+    }
+
+    // #else / #elif を持たず、対応する #endif で閉じられている必要がある
+    let has_other_branch = !elif_branches.is_empty() || else_branch.is_some();
+    if has_other_branch || end_span == start_span {
+        return Some(guard_diagnostic(
+            start_span.clone(),
+            format!("インクルードガード '{}' が対応する #endif で正しく閉じられていません。", guard_name),
+        ));
+    }
+
+    // ガードの外側に宣言があってはならない
+    if let Some(outside) = tu.items.get(1) {
+        return Some(guard_diagnostic(
+            item_span(outside).clone(),
+            format!("インクルードガード '{}' の外側に宣言があります。ファイル全体をガードで囲んでください。", guard_name),
+        ));
+    }
+
+    None
 }
 
-/// #defineマクロの置換値が括弧で囲まれているかチェック
-fn check_macro_parentheses(tu: &TranslationUnit) -> Vec<Diagnostic> {
-    let mut diagnostics = Vec::new();
-    
-    fn check_items(items: &[Item], diagnostics: &mut Vec<Diagnostic>) {
-        for item in items {
-            match item {
-                Item::Define { text, span, .. } => {
-                    // #define MACRO value の形式から value 部分を抽出
-                    // テキストから "#define" を除去し、マクロ名と値を取得
-                    let content = text.trim();
-                    
-                    // #define を除去
-                    if let Some(after_define) = content.strip_prefix("#define") {
-                        let parts: Vec<&str> = after_define.trim().splitn(2, char::is_whitespace).collect();
-                        
-                        if parts.len() == 2 {
-                            let macro_name = parts[0];
-                            let macro_value = parts[1].trim();
-                            
-                            // 関数マクロ（括弧を含む名前）はスキップ
-                            if macro_name.contains('(') {
-                                continue;
-                            }
-                            
-                            // 空の値や数値リテラルのみはスキップ
-                            if macro_value.is_empty() || is_simple_literal(macro_value) {
-                                continue;
-                            }
-                            
-                            // 演算子を含むかチェック
-                            if contains_operator(macro_value) {
-                                // 括弧で囲まれているかチェック
-                                if !is_wrapped_in_parentheses(macro_value) {
-                                    diagnostics.push(Diagnostic::new(
-                                        span.clone(),
-                                        DiagnosticSeverity::Warning,
-                                        format!(
-                                            "マクロ '{}' の置換値 '{}' は演算子を含んでいますが、括弧で囲まれていません。意図しない演算子の優先順位問題を避けるため、括弧で囲むことを推奨します。",
-                                            macro_name,
-                                            macro_value
-                                        ),
-                                        DiagnosticCode::Custom("CGH005".to_string()),
-                                    ));
-                                }
-                            }
-                        }
-                    }
-                },
-                Item::ConditionalBlock { items, .. } => {
-                    // 再帰的にチェック
-                    check_items(items, diagnostics);
-                },
-                _ => {}
-            }
-        }
+/// Itemの先頭位置を表すSpanを取得
+fn item_span(item: &Item) -> &Span {
+    match item {
+        Item::Include { span, .. }
+        | Item::Define { span, .. }
+        | Item::LineDirective { span, .. }
+        | Item::ErrorDirective { span, .. }
+        | Item::WarningDirective { span, .. }
+        | Item::TypedefDecl { span, .. }
+        | Item::VarDecl { span, .. }
+        | Item::StructDecl { span, .. }
+        | Item::EnumDecl { span, .. }
+        | Item::UnionDecl { span, .. }
+        | Item::FunctionDecl { span, .. } => span,
+        Item::ConditionalBlock { start_span, .. } => start_span,
+    }
+}
+
+/// 関数定義のテキストから関数名の位置を求める（見つからない場合は関数全体の Span）
+fn function_name_span(span: &Span, text: &str, function_name: &str) -> Span {
+    match FunctionLayout::of(text, function_name) {
+        Some(layout) => layout.span(span, layout.name),
+        None => span.clone(),
+    }
+}
+
+/// 項目のテキスト内の位置 `offset` から `len` バイトの範囲を、ソース上の Span に変換する
+fn text_offset_span(span: &Span, text: &str, offset: usize, len: usize) -> Span {
+    let end = offset + len;
+
+    // 先頭の改行は span.start_line に既に数えられている
+    let prefix = &text[..offset];
+    let newlines = prefix.matches('\n').count() - usize::from(text.starts_with('\n'));
+    let line = span.start_line + newlines;
+    let column = match prefix.rfind('\n') {
+        Some(nl) => prefix[nl + 1..].chars().count(),
+        None => span.start_column + prefix.chars().count(),
+    };
+    Span {
+        start_line: line,
+        start_column: column,
+        end_line: line,
+        end_column: column + text[offset..end].chars().count(),
+        byte_start_idx: span.byte_start_idx + offset,
+        byte_end_idx: span.byte_start_idx + end,
     }
-    
-    check_items(&tu.items, &mut diagnostics);
-    diagnostics
 }
 
 /// 単純なリテラル（数値、文字列）かどうか判定
@@ -552,6 +2218,46 @@ fn contains_operator(value: &str) -> bool {
     false
 }
 
+/// 関数形式マクロの置換値が式ではなく文（do { ... } while (0) やブロック）かどうか
+fn is_statement_macro_body(value: &str) -> bool {
+    let trimmed = value.trim_start();
+    trimmed.starts_with('{') || (trimmed.starts_with("do") && trimmed[2..].trim_start().starts_with('{'))
+}
+
+/// 置換値の中に、括弧で囲まれていない引数の出現があるかチェック
+/// `(a)` のほか、関数呼び出しの実引数（`f(a, b)`）としての出現も括弧で区切られているとみなす。
+/// `#a`（文字列化）や `a ## b`（トークン連結）、関数として呼び出している引数（`f(x)`）は対象外
+fn has_unwrapped_macro_param(body: &str, param: &str) -> bool {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut search_from = 0;
+
+    while let Some(pos) = body[search_from..].find(param) {
+        let start = search_from + pos;
+        let end = start + param.len();
+        search_from = end;
+
+        // 識別子の一部（例: 引数 a に対する abc）は除外
+        if body[..start].chars().next_back().is_some_and(is_ident_char)
+            || body[end..].chars().next().is_some_and(is_ident_char)
+        {
+            continue;
+        }
+
+        let before = body[..start].trim_end();
+        let after = body[end..].trim_start();
+        if before.ends_with('#') || after.starts_with("##") || after.starts_with('(') {
+            continue;
+        }
+        let wrapped_before = before.ends_with('(') || before.ends_with(',');
+        let wrapped_after = after.starts_with(')') || after.starts_with(',');
+        if !(wrapped_before && wrapped_after) {
+            return true;
+        }
+    }
+
+    false
+}
+
 /// 値全体が括弧で囲まれているかチェック
 fn is_wrapped_in_parentheses(value: &str) -> bool {
     let trimmed = value.trim();
@@ -581,8 +2287,8 @@ fn is_wrapped_in_parentheses(value: &str) -> bool {
 fn check_global_var_naming(tu: &TranslationUnit) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
     
-    // トップレベルのアイテムのみチェック（関数内部は除外）
-    for item in &tu.items {
+    // ファイルスコープのアイテムのみチェック（関数内部は除外、条件ブロック内は含む）
+    for item in tu.all_items() {
         match item {
             Item::VarDecl { span, var_name, var_type, .. } => {
                 // extern宣言やtypedefは除外（var_typeがあるものだけチェック）
@@ -636,90 +2342,471 @@ fn check_global_var_naming(tu: &TranslationUnit) -> Vec<Diagnostic> {
                         }
                     }
                 }
-            },
-            Item::EnumDecl { span, text, variable_names, variants, has_typedef, .. } => {
-                // typedefでない場合のみ変数名をチェック
-                if !has_typedef {
-                    // variable_namesが空の場合、textから抽出（変数宣言のみの場合）
-                    if variable_names.is_empty() && variants.is_empty() {
-                        if let Some(var_name) = extract_var_name_from_struct_decl(text) {
-                            if !is_uppercase_with_underscores(&var_name) {
-                                diagnostics.push(Diagnostic::new(
-                                    span.clone(),
-                                    DiagnosticSeverity::Warning,
-                                    format!(
-                                        "グローバル変数 '{}' は大文字とアンダースコアで命名することを推奨します。例: '{}'",
-                                        var_name,
-                                        to_uppercase_with_underscores(&var_name)
-                                    ),
-                                    DiagnosticCode::Custom("CGH006".to_string()),
-                                ));
-                            }
-                        }
-                    } else {
-                        // variable_namesがある場合はそれをチェック
-                        for var_name in variable_names {
-                            if !is_uppercase_with_underscores(var_name) {
-                                diagnostics.push(Diagnostic::new(
-                                    span.clone(),
-                                    DiagnosticSeverity::Warning,
-                                    format!(
-                                        "グローバル変数 '{}' は大文字とアンダースコアで命名することを推奨します。例: '{}'",
-                                        var_name,
-                                        to_uppercase_with_underscores(var_name)
-                                    ),
-                                    DiagnosticCode::Custom("CGH006".to_string()),
-                                ));
-                            }
-                        }
+            },
+            Item::EnumDecl { span, text, variable_names, variants, has_typedef, .. } => {
+                // typedefでない場合のみ変数名をチェック
+                if !has_typedef {
+                    // variable_namesが空の場合、textから抽出（変数宣言のみの場合）
+                    if variable_names.is_empty() && variants.is_empty() {
+                        if let Some(var_name) = extract_var_name_from_struct_decl(text) {
+                            if !is_uppercase_with_underscores(&var_name) {
+                                diagnostics.push(Diagnostic::new(
+                                    span.clone(),
+                                    DiagnosticSeverity::Warning,
+                                    format!(
+                                        "グローバル変数 '{}' は大文字とアンダースコアで命名することを推奨します。例: '{}'",
+                                        var_name,
+                                        to_uppercase_with_underscores(&var_name)
+                                    ),
+                                    DiagnosticCode::Custom("CGH006".to_string()),
+                                ));
+                            }
+                        }
+                    } else {
+                        // variable_namesがある場合はそれをチェック
+                        for var_name in variable_names {
+                            if !is_uppercase_with_underscores(var_name) {
+                                diagnostics.push(Diagnostic::new(
+                                    span.clone(),
+                                    DiagnosticSeverity::Warning,
+                                    format!(
+                                        "グローバル変数 '{}' は大文字とアンダースコアで命名することを推奨します。例: '{}'",
+                                        var_name,
+                                        to_uppercase_with_underscores(var_name)
+                                    ),
+                                    DiagnosticCode::Custom("CGH006".to_string()),
+                                ));
+                            }
+                        }
+                    }
+                }
+            },
+            Item::UnionDecl { span, text, variable_names, members, has_typedef, .. } => {
+                // typedefでない場合のみ変数名をチェック
+                if !has_typedef {
+                    // variable_namesが空の場合、textから抽出（変数宣言のみの場合）
+                    if variable_names.is_empty() && members.is_empty() {
+                        if let Some(var_name) = extract_var_name_from_struct_decl(text) {
+                            if !is_uppercase_with_underscores(&var_name) {
+                                diagnostics.push(Diagnostic::new(
+                                    span.clone(),
+                                    DiagnosticSeverity::Warning,
+                                    format!(
+                                        "グローバル変数 '{}' は大文字とアンダースコアで命名することを推奨します。例: '{}'",
+                                        var_name,
+                                        to_uppercase_with_underscores(&var_name)
+                                    ),
+                                    DiagnosticCode::Custom("CGH006".to_string()),
+                                ));
+                            }
+                        }
+                    } else {
+                        // variable_namesがある場合はそれをチェック
+                        for var_name in variable_names {
+                            if !is_uppercase_with_underscores(var_name) {
+                                diagnostics.push(Diagnostic::new(
+                                    span.clone(),
+                                    DiagnosticSeverity::Warning,
+                                    format!(
+                                        "グローバル変数 '{}' は大文字とアンダースコアで命名することを推奨します。例: '{}'",
+                                        var_name,
+                                        to_uppercase_with_underscores(var_name)
+                                    ),
+                                    DiagnosticCode::Custom("CGH006".to_string()),
+                                ));
+                            }
+                        }
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+    
+    diagnostics
+}
+
+/// 戻り値の型を省略した関数定義（暗黙の int）をチェック（CGH026）
+fn check_implicit_int(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { span, text, function_name, return_type, .. } = item {
+            if !return_type.trim().is_empty() {
+                continue;
+            }
+            diagnostics.push(Diagnostic::new(
+                function_name_span(span, text, function_name),
+                DiagnosticSeverity::Warning,
+                format!(
+                    "関数 '{}' の戻り値の型が省略されています（暗黙の int）。戻り値の型を明示してください。例: 'int {}'",
+                    function_name, function_name
+                ),
+                DiagnosticCode::Custom("CGH026".to_string()),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// メンバーを持たない struct / union 定義のチェック（CGH027）
+/// 空の集成体は C では未定義動作であり、コンパイラ拡張に依存するため警告する
+fn check_empty_aggregate(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for item in tu.all_items() {
+        let (span, text, kind, tag_name, member_count) = match item {
+            Item::StructDecl { span, text, struct_name, members, .. } => (span, text, "構造体", struct_name, members.len()),
+            Item::UnionDecl { span, text, union_name, members, .. } => (span, text, "共用体", union_name, members.len()),
+            _ => continue,
+        };
+        if member_count > 0 || !is_empty_aggregate_body(text) {
+            continue;
+        }
+
+        let message = match tag_name {
+            Some(name) => format!("{} '{}' にメンバーがありません。空の{}は移植性がありません。", kind, name, kind),
+            None => format!("{}にメンバーがありません。空の{}は移植性がありません。", kind, kind),
+        };
+        diagnostics.push(Diagnostic::new(
+            span.clone(),
+            DiagnosticSeverity::Warning,
+            message,
+            DiagnosticCode::Custom("CGH027".to_string()),
+        ));
+    }
+
+    diagnostics
+}
+
+/// 宣言テキストの { } の中にメンバー宣言が 1 つもないかどうか
+/// 前方宣言（{ を含まない）は false。typedef 付きの定義は members が解析されないため、
+/// メンバー宣言の終端である ';' の有無で判定する
+fn is_empty_aggregate_body(text: &str) -> bool {
+    let (Some(open), Some(close)) = (text.find('{'), text.rfind('}')) else {
+        return false;
+    };
+    open < close && !text[open + 1..close].contains(';')
+}
+
+/// ループ変数として慣用的に使われる 1 文字の名前
+const LOOP_INDEX_NAMES: [&str; 3] = ["i", "j", "k"];
+
+/// 短すぎるグローバル変数名のチェック（CGH028）
+fn check_short_identifiers(tu: &TranslationUnit, min_length: usize, allow_loop_index_names: bool) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for item in tu.all_items() {
+        if let Item::VarDecl { span, var_name, .. } = item {
+            if var_name.chars().count() >= min_length {
+                continue;
+            }
+            if allow_loop_index_names && LOOP_INDEX_NAMES.contains(&var_name.as_str()) {
+                continue;
+            }
+            diagnostics.push(Diagnostic::new(
+                span.clone(),
+                DiagnosticSeverity::Warning,
+                format!(
+                    "グローバル変数 '{}' の名前が短すぎます。{} 文字以上の意味のある名前を付けてください。",
+                    var_name, min_length
+                ),
+                DiagnosticCode::Custom("CGH028".to_string()),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// どの分岐も有効にならない条件付きブロックのチェック（CGH030）
+/// #if 0 のように常に偽となるブロックは読み手を混乱させるデッドコードとして報告する
+fn check_dead_preprocessor(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    fn visit(items: &[Item], diagnostics: &mut Vec<Diagnostic>) {
+        for item in items {
+            let Item::ConditionalBlock { condition_result, elif_branches, else_branch, start_span, end_span, directive_type, condition, .. } = item else {
+                continue;
+            };
+            let taken = *condition_result
+                || elif_branches.iter().any(|branch| branch.condition_result)
+                || else_branch.is_some();
+            if !taken {
+                diagnostics.push(Diagnostic::new(
+                    start_span.merge(end_span),
+                    DiagnosticSeverity::Hint,
+                    format!("#{} {} のブロックはどの分岐も有効にならないため、デッドコードです。", directive_type, condition),
+                    DiagnosticCode::Custom("CGH030".to_string()),
+                ));
+                // ブロック全体を報告済みのため、内側の条件ブロックは報告しない
+                continue;
+            }
+            for children in item.branches() {
+                visit(children, diagnostics);
+            }
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    visit(&tu.items, &mut diagnostics);
+    diagnostics
+}
+
+/// #include の並び順のチェック（CGH032）
+/// 連続する #include の中で、<...> のシステムヘッダーが "..." のローカルヘッダーより前にまとめられ、
+/// 空行で区切られたまとまりごとにファイル名順に並んでいるかを確認する。条件付きブロックの分岐をまたいでは比較しない
+fn check_include_order(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    fn visit(items: &[Item], diagnostics: &mut Vec<Diagnostic>) {
+        // 直前の #include（システムヘッダーかどうか・ファイル名）。#include 以外のアイテムで区切る
+        let mut previous: Option<(bool, &str)> = None;
+        for item in items {
+            let Item::Include { span, text, filename, .. } = item else {
+                previous = None;
+                for children in item.branches() {
+                    visit(children, diagnostics);
+                }
+                continue;
+            };
+            let directive = text.trim_start();
+            let is_system = directive.trim_start_matches('#').trim_start().trim_start_matches("include").trim_start().starts_with('<');
+            let after_blank_line = text.len() != directive.len() && text[..text.len() - directive.len()].contains('\n');
+
+            if let Some((previous_is_system, previous_filename)) = previous {
+                let message = if is_system && !previous_is_system {
+                    Some(format!("システムヘッダー <{}> はローカルヘッダーより前にまとめてください。", filename))
+                } else if is_system == previous_is_system && !after_blank_line && filename.as_str() < previous_filename {
+                    Some(format!("#include \"{}\" は \"{}\" より前に記述してください（ファイル名順）。", filename, previous_filename))
+                } else {
+                    None
+                };
+                if let Some(message) = message {
+                    diagnostics.push(Diagnostic::new(
+                        span.clone(),
+                        DiagnosticSeverity::Hint,
+                        message,
+                        DiagnosticCode::Custom("CGH032".to_string()),
+                    ));
+                }
+            }
+            previous = Some((is_system, filename));
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    visit(&tu.items, &mut diagnostics);
+    diagnostics
+}
+
+/// 関数名の命名規則チェック（CGH022）
+fn check_function_naming(tu: &TranslationUnit, style: &NamingStyle) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let matcher = NamingMatcher { style };
+
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { span, text, function_name, .. } = item {
+            // main はエントリーポイントとして名前が決まっているため対象外
+            if function_name == "main" || matcher.is_match(function_name) {
+                continue;
+            }
+
+            let mut message = format!("関数 '{}' の名前は {} で記述してください。", function_name, style);
+            if let Some(suggested) = matcher.suggest(function_name) {
+                message.push_str(&format!("例: '{}'", suggested));
+            }
+            diagnostics.push(Diagnostic::new(
+                function_name_span(span, text, function_name),
+                DiagnosticSeverity::Warning,
+                message,
+                DiagnosticCode::Custom("CGH022".to_string()),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// struct / union / enum のタグ名と typedef 名の命名規則チェック（CGH023）
+fn check_type_naming(tu: &TranslationUnit, style: &NamingStyle) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let matcher = NamingMatcher { style };
+
+    for item in tu.all_items() {
+        let (span, text, tag_kind, tag_name, has_typedef) = match item {
+            Item::StructDecl { span, text, struct_name, has_typedef, .. } => (span, text, "構造体タグ", struct_name, *has_typedef),
+            Item::UnionDecl { span, text, union_name, has_typedef, .. } => (span, text, "共用体タグ", union_name, *has_typedef),
+            Item::EnumDecl { span, text, enum_name, has_typedef, .. } => (span, text, "列挙型タグ", enum_name, *has_typedef),
+            Item::TypedefDecl { span, text, .. } => (span, text, "", &None, true),
+            _ => continue,
+        };
+
+        let mut names: Vec<(&str, String)> = Vec::new();
+        if let Some(tag_name) = tag_name {
+            names.push((tag_kind, tag_name.clone()));
+        }
+        if has_typedef {
+            names.extend(extract_typedef_names(text).into_iter().map(|name| ("typedef 名", name)));
+        }
+
+        for (kind, name) in names {
+            if matcher.is_match(&name) {
+                continue;
+            }
+            let mut message = format!("{} '{}' は {} で記述してください。", kind, name, style);
+            if let Some(suggested) = matcher.suggest(&name) {
+                message.push_str(&format!("例: '{}'", suggested));
+            }
+            diagnostics.push(Diagnostic::new(
+                span.clone(),
+                DiagnosticSeverity::Warning,
+                message,
+                DiagnosticCode::Custom("CGH023".to_string()),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// ローカル変数による typedef 名の隠蔽チェック（CGH033）
+/// 関数本体で typedef 名と同じ名前のローカル変数を宣言すると、そのスコープでは型名として使えなくなる
+fn check_typedef_shadowing(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    fn visit_statements(statements: &[Statement], typedefs: &HashMap<String, Span>, diagnostics: &mut Vec<Diagnostic>) {
+        for stmt in statements {
+            match stmt {
+                Statement::VarDecl { var_name, span, .. } => {
+                    if let Some(typedef_span) = typedefs.get(var_name) {
+                        diagnostics.push(Diagnostic::new(
+                            span.clone(),
+                            DiagnosticSeverity::Warning,
+                            format!(
+                                "ローカル変数 '{}' が同じ名前の typedef 名を隠しています。このスコープでは型 '{}' を使用できません。",
+                                var_name, var_name
+                            ),
+                            DiagnosticCode::Custom("CGH033".to_string()),
+                        ).with_related(typedef_span.clone(), format!("typedef 名 '{}' の定義", var_name)));
+                    }
+                }
+                Statement::If { then_block, else_block, .. } => {
+                    visit_statements(then_block, typedefs, diagnostics);
+                    if let Some(else_block) = else_block {
+                        visit_statements(else_block, typedefs, diagnostics);
                     }
                 }
-            },
-            Item::UnionDecl { span, text, variable_names, members, has_typedef, .. } => {
-                // typedefでない場合のみ変数名をチェック
-                if !has_typedef {
-                    // variable_namesが空の場合、textから抽出（変数宣言のみの場合）
-                    if variable_names.is_empty() && members.is_empty() {
-                        if let Some(var_name) = extract_var_name_from_struct_decl(text) {
-                            if !is_uppercase_with_underscores(&var_name) {
-                                diagnostics.push(Diagnostic::new(
-                                    span.clone(),
-                                    DiagnosticSeverity::Warning,
-                                    format!(
-                                        "グローバル変数 '{}' は大文字とアンダースコアで命名することを推奨します。例: '{}'",
-                                        var_name,
-                                        to_uppercase_with_underscores(&var_name)
-                                    ),
-                                    DiagnosticCode::Custom("CGH006".to_string()),
-                                ));
-                            }
-                        }
-                    } else {
-                        // variable_namesがある場合はそれをチェック
-                        for var_name in variable_names {
-                            if !is_uppercase_with_underscores(var_name) {
-                                diagnostics.push(Diagnostic::new(
-                                    span.clone(),
-                                    DiagnosticSeverity::Warning,
-                                    format!(
-                                        "グローバル変数 '{}' は大文字とアンダースコアで命名することを推奨します。例: '{}'",
-                                        var_name,
-                                        to_uppercase_with_underscores(var_name)
-                                    ),
-                                    DiagnosticCode::Custom("CGH006".to_string()),
-                                ));
-                            }
-                        }
+                Statement::For { init, body, .. } => {
+                    if let Some(init) = init {
+                        visit_statements(std::slice::from_ref(init.as_ref()), typedefs, diagnostics);
                     }
+                    visit_statements(body, typedefs, diagnostics);
                 }
-            },
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } => {
+                    visit_statements(body, typedefs, diagnostics);
+                }
+                Statement::Block { statements, .. } => {
+                    visit_statements(statements, typedefs, diagnostics);
+                }
+                Statement::Expression { .. } | Statement::Return { .. } | Statement::Empty { .. } => {}
+            }
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    // 関数より前で定義された typedef 名 -> 定義の位置
+    let mut typedefs: HashMap<String, Span> = HashMap::new();
+    for item in tu.all_items() {
+        match item {
+            Item::TypedefDecl { span, text, .. }
+            | Item::StructDecl { span, text, has_typedef: true, .. }
+            | Item::UnionDecl { span, text, has_typedef: true, .. }
+            | Item::EnumDecl { span, text, has_typedef: true, .. } => {
+                for name in extract_typedef_names(text) {
+                    typedefs.entry(name).or_insert_with(|| span.clone());
+                }
+            }
+            Item::FunctionDecl { body: Some(body), .. } => {
+                visit_statements(body, &typedefs, &mut diagnostics);
+            }
             _ => {}
         }
     }
-    
     diagnostics
 }
 
+/// 命名規則に一致するかの判定と、規則に沿った名前の提案を行う
+struct NamingMatcher<'a> {
+    style: &'a NamingStyle,
+}
+
+impl NamingMatcher<'_> {
+    fn is_match(&self, name: &str) -> bool {
+        let is_lower_or_digit = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit();
+        let is_upper_or_digit = |c: char| c.is_ascii_uppercase() || c.is_ascii_digit();
+        let starts_with = |pred: fn(&char) -> bool| name.chars().next().as_ref().is_some_and(pred);
+        match self.style {
+            NamingStyle::SnakeCase => {
+                starts_with(char::is_ascii_lowercase) && name.chars().all(|c| is_lower_or_digit(c) || c == '_')
+            }
+            NamingStyle::UpperSnakeCase => {
+                starts_with(char::is_ascii_uppercase) && name.chars().all(|c| is_upper_or_digit(c) || c == '_')
+            }
+            NamingStyle::CamelCase => starts_with(char::is_ascii_lowercase) && name.chars().all(|c| c.is_ascii_alphanumeric()),
+            NamingStyle::PascalCase => starts_with(char::is_ascii_uppercase) && name.chars().all(|c| c.is_ascii_alphanumeric()),
+            NamingStyle::Regex(pattern) => pattern.is_match(name),
+        }
+    }
+
+    /// 規則に沿った名前を提案する（正規表現の場合は提案しない）
+    fn suggest(&self, name: &str) -> Option<String> {
+        let words = split_identifier_words(name);
+        if words.is_empty() {
+            return None;
+        }
+        let capitalize = |word: &String| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        };
+        let suggested = match self.style {
+            NamingStyle::SnakeCase => words.join("_"),
+            NamingStyle::UpperSnakeCase => words.join("_").to_ascii_uppercase(),
+            NamingStyle::CamelCase => {
+                words[0].clone() + &words[1..].iter().map(capitalize).collect::<String>()
+            }
+            NamingStyle::PascalCase => words.iter().map(capitalize).collect(),
+            NamingStyle::Regex(_) => return None,
+        };
+        Some(suggested)
+    }
+}
+
+/// 識別子を単語（小文字）に分割する
+/// 例: "MyHTTPServer_init" -> ["my", "http", "server", "init"]
+fn split_identifier_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if ch.is_ascii_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|c| c.is_ascii_lowercase());
+            // "myFunc" の F、"HTTPServer" の S の前で区切る
+            if prev.is_ascii_lowercase() || prev.is_ascii_digit() || (prev.is_ascii_uppercase() && next_is_lower) {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(ch.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
 /// struct宣言のtextフィールドから変数名を抽出
 /// 例: "struct Point myPoint;" -> Some("myPoint")
 fn extract_var_name_from_struct_decl(text: &str) -> Option<String> {
@@ -823,7 +2910,7 @@ fn check_global_var_type_prefix(tu: &TranslationUnit) -> Vec<Diagnostic> {
         }
     }
 
-    for item in &tu.items {
+    for item in tu.all_items() {
         if let Item::VarDecl { span, var_name, var_type, text, .. } = item {
             if let Some(ty) = var_type {
                 // typedef名があればそれを優先
@@ -926,85 +3013,683 @@ fn check_local_var_type_prefix(tu: &TranslationUnit) -> Vec<Diagnostic> {
                         visit_statements(else_block, diagnostics, type_prefixes);
                     }
                 }
-                Statement::While { body, .. } => {
-                    visit_statements(body, diagnostics, type_prefixes);
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } => {
+                    visit_statements(body, diagnostics, type_prefixes);
+                }
+                Statement::For { init, body, .. } => {
+                    if let Some(init_stmt) = init.as_deref() {
+                        visit_statements(std::slice::from_ref(init_stmt), diagnostics, type_prefixes);
+                    }
+                    visit_statements(body, diagnostics, type_prefixes);
+                }
+                Statement::Block { statements, .. } => {
+                    visit_statements(statements, diagnostics, type_prefixes);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { body: Some(statements), .. } = item {
+            visit_statements(statements, &mut diagnostics, &type_prefixes);
+        }
+    }
+
+    diagnostics
+}
+
+/// ループ本体が空文（;）のみになっていないかチェック（CGH017）
+/// `while (cond);` のような誤記を検出する。空のブロック `{}` は意図的なものとして許可する
+fn check_empty_loop_body(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    fn is_empty_statement_body(body: &[Statement]) -> bool {
+        matches!(body, [Statement::Empty { .. }])
+    }
+
+    fn visit_statements(statements: &[Statement], diagnostics: &mut Vec<Diagnostic>) {
+        for stmt in statements {
+            let empty_loop = match stmt {
+                Statement::While { body, span, .. } if is_empty_statement_body(body) => Some(("while", span)),
+                Statement::For { body, span, .. } if is_empty_statement_body(body) => Some(("for", span)),
+                Statement::DoWhile { body, span, .. } if is_empty_statement_body(body) => Some(("do-while", span)),
+                _ => None,
+            };
+
+            if let Some((kind, span)) = empty_loop {
+                diagnostics.push(Diagnostic::new(
+                    span.clone(),
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "{} ループの本体が空文（;）のみです。意図的な場合は空のブロック {{}} を使用してください。",
+                        kind
+                    ),
+                    DiagnosticCode::Custom("CGH017".to_string()),
+                ));
+            }
+
+            match stmt {
+                Statement::If { then_block, else_block, .. } => {
+                    visit_statements(then_block, diagnostics);
+                    if let Some(else_block) = else_block {
+                        visit_statements(else_block, diagnostics);
+                    }
+                }
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } | Statement::For { body, .. } => {
+                    visit_statements(body, diagnostics);
+                }
+                Statement::Block { statements, .. } => {
+                    visit_statements(statements, diagnostics);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { body: Some(statements), .. } = item {
+            visit_statements(statements, &mut diagnostics);
+        }
+    }
+
+    diagnostics
+}
+
+/// 列挙子の値が宣言順に単調増加しているかチェック（CGH024）
+/// 値を省略した列挙子は直前の値 + 1、定数として評価できない値の後は比較しない
+fn check_enum_monotonic(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for item in tu.all_items() {
+        if let Item::EnumDecl { variants, .. } = item {
+            // 最初の列挙子の暗黙値が 0 になるよう -1 から始める
+            let mut previous: Option<(&str, i64)> = None;
+            let mut previous_value = Some(-1);
+
+            for variant in variants {
+                let value = if variant.has_explicit_value {
+                    if let (Some(value), Some((prev_name, prev_value))) = (variant.value, previous) {
+                        if value <= prev_value {
+                            diagnostics.push(Diagnostic::new(
+                                variant.span.clone(),
+                                DiagnosticSeverity::Warning,
+                                format!(
+                                    "列挙子 '{}' の値（{}）が直前の列挙子 '{}' の値（{}）以下です。値は宣言順に増加させてください。",
+                                    variant.name, value, prev_name, prev_value
+                                ),
+                                DiagnosticCode::Custom("CGH024".to_string()),
+                            ));
+                        }
+                    }
+                    variant.value
+                } else {
+                    previous_value.and_then(|prev: i64| prev.checked_add(1))
+                };
+
+                previous = value.map(|value| (variant.name.as_str(), value));
+                previous_value = value;
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// 関数本体のステートメント数が上限を超えていないかチェック（CGH018）
+/// ネストしたブロック・制御文の中の文も数える（ブロック `{}` と空文 `;` 自体は数えない）
+fn check_function_length(tu: &TranslationUnit, max_statements: usize) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    fn count_statements(statements: &[Statement]) -> usize {
+        statements
+            .iter()
+            .map(|stmt| match stmt {
+                Statement::If { then_block, else_block, .. } => {
+                    1 + count_statements(then_block) + else_block.as_deref().map_or(0, count_statements)
+                }
+                Statement::While { body, .. } | Statement::DoWhile { body, .. } | Statement::For { body, .. } => {
+                    1 + count_statements(body)
+                }
+                Statement::Block { statements, .. } => count_statements(statements),
+                Statement::Empty { .. } => 0,
+                _ => 1,
+            })
+            .sum()
+    }
+
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { span, text, function_name, body: Some(statements), .. } = item {
+            let count = count_statements(statements);
+            if count > max_statements {
+                diagnostics.push(Diagnostic::new(
+                    function_name_span(span, text, function_name),
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "関数 '{}' のステートメント数（{}）が上限（{}）を超えています。関数を分割することを検討してください。",
+                        function_name, count, max_statements
+                    ),
+                    DiagnosticCode::Custom("CGH018".to_string()),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// 関数の循環的複雑度が上限を超えていないかチェック（CGH019）
+/// 複雑度 = 1 + 分岐（if / while / for / do）の数 + 論理演算子（&& / ||）の数
+/// switch / case は現状の構文解析器が未対応のため数えない
+fn check_cyclomatic_complexity(tu: &TranslationUnit, max_complexity: usize) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    fn count_logical_operators(expr: &Expression) -> usize {
+        match expr {
+            Expression::BinaryOp { op, left, right, .. } => {
+                let own = usize::from(matches!(op, BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr));
+                own + count_logical_operators(left) + count_logical_operators(right)
+            }
+            Expression::UnaryOp { operand, .. } | Expression::Cast { operand, .. } => count_logical_operators(operand),
+            Expression::FunctionCall { function, arguments, .. } => {
+                count_logical_operators(function) + arguments.iter().map(count_logical_operators).sum::<usize>()
+            }
+            Expression::ArrayAccess { array, index, .. } => count_logical_operators(array) + count_logical_operators(index),
+            Expression::MemberAccess { object, .. } | Expression::PointerMemberAccess { object, .. } => {
+                count_logical_operators(object)
+            }
+            Expression::Conditional { condition, then_expr, else_expr, .. } => {
+                count_logical_operators(condition) + count_logical_operators(then_expr) + count_logical_operators(else_expr)
+            }
+            Expression::Assignment { left, right, .. } => count_logical_operators(left) + count_logical_operators(right),
+            Expression::IntLiteral { .. } | Expression::FloatLiteral { .. } | Expression::StringLiteral { .. }
+                | Expression::CharLiteral { .. } | Expression::Identifier { .. } => 0,
+        }
+    }
+
+    fn count_decisions(statements: &[Statement]) -> usize {
+        let expr_count = |expr: Option<&Expression>| expr.map_or(0, count_logical_operators);
+        statements
+            .iter()
+            .map(|stmt| match stmt {
+                Statement::VarDecl { initializer, .. } => expr_count(initializer.as_ref()),
+                Statement::Expression { expr, .. } => count_logical_operators(expr),
+                Statement::Return { value, .. } => expr_count(value.as_ref()),
+                Statement::If { condition, then_block, else_block, .. } => {
+                    1 + count_logical_operators(condition)
+                        + count_decisions(then_block)
+                        + else_block.as_deref().map_or(0, count_decisions)
+                }
+                Statement::While { condition, body, .. } | Statement::DoWhile { condition, body, .. } => {
+                    1 + count_logical_operators(condition) + count_decisions(body)
+                }
+                Statement::For { init, condition, update, body, .. } => {
+                    1 + init.as_deref().map_or(0, |init| count_decisions(std::slice::from_ref(init)))
+                        + expr_count(condition.as_ref())
+                        + expr_count(update.as_ref())
+                        + count_decisions(body)
+                }
+                Statement::Block { statements, .. } => count_decisions(statements),
+                Statement::Empty { .. } => 0,
+            })
+            .sum()
+    }
+
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { span, text, function_name, body: Some(statements), .. } = item {
+            let complexity = 1 + count_decisions(statements);
+            if complexity > max_complexity {
+                diagnostics.push(Diagnostic::new(
+                    function_name_span(span, text, function_name),
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "関数 '{}' の循環的複雑度は {} で、上限（{}）を超えています。条件分岐を整理するか関数を分割してください。",
+                        function_name, complexity, max_complexity
+                    ),
+                    DiagnosticCode::Custom("CGH019".to_string()),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// 関数内の制御文・ブロックのネストが深すぎないかチェック（CGH020）
+/// 関数本体の直下にある制御文の深さを 1 とし、上限を超えた最も外側の文のみ報告する
+fn check_max_nesting_depth(tu: &TranslationUnit, max_depth: usize) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    fn visit_statements(
+        statements: &[Statement],
+        depth: usize,
+        max_depth: usize,
+        function_name: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        for stmt in statements {
+            // `else if` は元の if と同じ深さの連鎖として扱う
+            let mut else_if: Option<&[Statement]> = None;
+            let (span, children): (&Span, Vec<&[Statement]>) = match stmt {
+                Statement::If { then_block, else_block, span, .. } => {
+                    let mut children = vec![then_block.as_slice()];
+                    match else_block.as_deref() {
+                        Some(chained @ [Statement::If { .. }]) => else_if = Some(chained),
+                        Some(else_block) => children.push(else_block),
+                        None => {}
+                    }
+                    (span, children)
+                }
+                Statement::While { body, span, .. }
+                | Statement::DoWhile { body, span, .. }
+                | Statement::For { body, span, .. } => (span, vec![body.as_slice()]),
+                Statement::Block { statements, span } => (span, vec![statements.as_slice()]),
+                _ => continue,
+            };
+
+            let stmt_depth = depth + 1;
+            if stmt_depth > max_depth {
+                diagnostics.push(Diagnostic::new(
+                    span.clone(),
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "関数 '{}' のネストの深さ（{}）が上限（{}）を超えています。早期リターンや関数の分割を検討してください。",
+                        function_name, stmt_depth, max_depth
+                    ),
+                    DiagnosticCode::Custom("CGH020".to_string()),
+                ));
+                continue;
+            }
+
+            for child in children {
+                visit_statements(child, stmt_depth, max_depth, function_name, diagnostics);
+            }
+            if let Some(chained) = else_if {
+                visit_statements(chained, depth, max_depth, function_name, diagnostics);
+            }
+        }
+    }
+
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { function_name, body: Some(statements), .. } = item {
+            visit_statements(statements, 0, max_depth, function_name, &mut diagnostics);
+        }
+    }
+
+    diagnostics
+}
+
+/// 指す先を変更していないポインタ引数に const を提案（CGH021）
+/// `*p = ...` / `p->field = ...` / `p[i] = ...` とそれらへの ++ / -- を書き込みとみなす。
+/// 関数呼び出しの引数に渡す・別の変数に代入する・return で返すと、その先で変更される
+/// 可能性があるため書き込みとみなす（呼び出し先の変更までは追跡しない）
+fn check_const_pointer_params(tu: &TranslationUnit) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // 参照先（`*p` / `p->field` / `p[i]`）の式から、アクセスしているポインタ変数名を求める
+    fn accessed_pointer(target: &Expression) -> Option<&str> {
+        match target {
+            Expression::UnaryOp { op: UnaryOperator::Dereference, operand, .. } => match operand.as_ref() {
+                Expression::Identifier { name, .. } => Some(name),
+                other => accessed_pointer(other),
+            },
+            Expression::PointerMemberAccess { object, .. } | Expression::ArrayAccess { array: object, .. } => {
+                match object.as_ref() {
+                    Expression::Identifier { name, .. } => Some(name),
+                    other => accessed_pointer(other),
+                }
+            }
+            Expression::MemberAccess { object, .. } => accessed_pointer(object),
+            _ => None,
+        }
+    }
+
+    // 式の値として外に渡るポインタ変数名（`p` / `p + 1` / `(char *)p` / `&p->field` など）を集める
+    fn escaping_pointers(expr: &Expression, names: &mut Vec<String>) {
+        match expr {
+            Expression::Identifier { name, .. } => names.push(name.clone()),
+            Expression::BinaryOp { left, right, .. } => {
+                escaping_pointers(left, names);
+                escaping_pointers(right, names);
+            }
+            Expression::Cast { operand, .. } => escaping_pointers(operand, names),
+            Expression::Assignment { right, .. } => escaping_pointers(right, names),
+            Expression::Conditional { then_expr, else_expr, .. } => {
+                escaping_pointers(then_expr, names);
+                escaping_pointers(else_expr, names);
+            }
+            Expression::UnaryOp { op: UnaryOperator::AddressOf, operand, .. } => {
+                names.extend(accessed_pointer(operand).map(str::to_string));
+            }
+            _ => {}
+        }
+    }
+
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { span, text, function_name, parameter_list, body: Some(statements), .. } = item {
+            let candidates: Vec<&str> = parameter_list
+                .iter()
+                .filter(|(param_type, _)| is_non_const_single_pointer(param_type, span))
+                .map(|(_, name)| name.as_str())
+                .collect();
+            if candidates.is_empty() {
+                continue;
+            }
+
+            let mut written: Vec<String> = Vec::new();
+            visit_expressions(statements, &mut |expr| match expr {
+                Expression::Assignment { left, right, .. } => {
+                    written.extend(accessed_pointer(left).map(str::to_string));
+                    // 別の変数への代入（エイリアス）
+                    escaping_pointers(right, &mut written);
+                }
+                Expression::BinaryOp { op, left, .. } if op.is_assignment() => {
+                    written.extend(accessed_pointer(left).map(str::to_string));
                 }
-                Statement::For { init, body, .. } => {
-                    if let Some(init_stmt) = init.as_deref() {
-                        visit_statements(std::slice::from_ref(init_stmt), diagnostics, type_prefixes);
-                    }
-                    visit_statements(body, diagnostics, type_prefixes);
+                Expression::UnaryOp {
+                    op: UnaryOperator::PreIncrement
+                        | UnaryOperator::PreDecrement
+                        | UnaryOperator::PostIncrement
+                        | UnaryOperator::PostDecrement,
+                    operand,
+                    ..
+                } => written.extend(accessed_pointer(operand).map(str::to_string)),
+                Expression::FunctionCall { arguments, .. } => {
+                    arguments.iter().for_each(|arg| escaping_pointers(arg, &mut written));
                 }
-                Statement::Block { statements, .. } => {
-                    visit_statements(statements, diagnostics, type_prefixes);
+                _ => {}
+            });
+            // 初期化による別の変数へのエイリアスと、return で返すポインタ
+            for_each_statement(statements, &mut |stmt| match stmt {
+                Statement::VarDecl { initializer: Some(expr), .. } | Statement::Return { value: Some(expr), .. } => {
+                    escaping_pointers(expr, &mut written);
                 }
                 _ => {}
+            });
+
+            for param in candidates.into_iter().filter(|param| !written.iter().any(|name| name == param)) {
+                diagnostics.push(Diagnostic::new(
+                    function_name_span(span, text, function_name),
+                    DiagnosticSeverity::Information,
+                    format!(
+                        "関数 '{}' の引数 '{}' の指す先は変更されていません。const を付けることを検討してください。",
+                        function_name, param
+                    ),
+                    DiagnosticCode::Custom("CGH021".to_string()),
+                ));
             }
         }
     }
 
-    for item in &tu.items {
-        if let Item::FunctionDecl { body: Some(statements), .. } = item {
-            visit_statements(statements, &mut diagnostics, &type_prefixes);
+    diagnostics
+}
+
+/// 指す先が const でない1段ポインタ引数か（例: `char *dst` は対象、`const char *src` / `int **pp` / `int a[]` は対象外）
+/// typedef から引き継いだポインタ層（`typedef char *Str;` の `Str s`）は const の付け方が異なるため対象外とし、
+/// 関数定義の中に書かれた `*` だけを数える
+fn is_non_const_single_pointer(param_type: &crate::type_system::Type, function_span: &Span) -> bool {
+    let declared_here = |layer: &crate::type_system::PointerLayer| {
+        (function_span.byte_start_idx..function_span.byte_end_idx).contains(&layer.span.byte_start_idx)
+    };
+    param_type.pointer_level() == 1
+        && declared_here(&param_type.pointer_layers[0])
+        && !param_type.is_array()
+        && !param_type.has_base_qualifier(crate::type_system::TypeQualifier::Const)
+}
+
+/// 式とその全ての部分式を訪問する
+fn visit_expression(expr: &Expression, f: &mut dyn FnMut(&Expression)) {
+    f(expr);
+    match expr {
+        Expression::BinaryOp { left, right, .. } | Expression::Assignment { left, right, .. } => {
+            visit_expression(left, f);
+            visit_expression(right, f);
         }
+        Expression::UnaryOp { operand, .. } | Expression::Cast { operand, .. } => visit_expression(operand, f),
+        Expression::FunctionCall { function, arguments, .. } => {
+            visit_expression(function, f);
+            arguments.iter().for_each(|arg| visit_expression(arg, f));
+        }
+        Expression::ArrayAccess { array, index, .. } => {
+            visit_expression(array, f);
+            visit_expression(index, f);
+        }
+        Expression::MemberAccess { object, .. } | Expression::PointerMemberAccess { object, .. } => {
+            visit_expression(object, f)
+        }
+        Expression::Conditional { condition, then_expr, else_expr, .. } => {
+            visit_expression(condition, f);
+            visit_expression(then_expr, f);
+            visit_expression(else_expr, f);
+        }
+        Expression::IntLiteral { .. } | Expression::FloatLiteral { .. } | Expression::StringLiteral { .. }
+            | Expression::CharLiteral { .. } | Expression::Identifier { .. } => {}
     }
+}
 
-    diagnostics
+/// 文のリストに含まれる全ての文（ネストした文を含む）を訪問する
+fn for_each_statement(statements: &[Statement], f: &mut dyn FnMut(&Statement)) {
+    for stmt in statements {
+        f(stmt);
+        match stmt {
+            Statement::If { then_block, else_block, .. } => {
+                for_each_statement(then_block, f);
+                if let Some(else_block) = else_block {
+                    for_each_statement(else_block, f);
+                }
+            }
+            Statement::While { body, .. } | Statement::DoWhile { body, .. } => for_each_statement(body, f),
+            Statement::For { init, body, .. } => {
+                if let Some(init) = init.as_deref() {
+                    for_each_statement(std::slice::from_ref(init), f);
+                }
+                for_each_statement(body, f);
+            }
+            Statement::Block { statements, .. } => for_each_statement(statements, f),
+            _ => {}
+        }
+    }
+}
+
+/// 文のリストに含まれる全ての式（部分式を含む）を訪問する
+fn visit_expressions(statements: &[Statement], f: &mut dyn FnMut(&Expression)) {
+    for stmt in statements {
+        match stmt {
+            Statement::VarDecl { initializer: Some(expr), .. }
+            | Statement::Expression { expr, .. }
+            | Statement::Return { value: Some(expr), .. } => visit_expression(expr, f),
+            Statement::If { condition, then_block, else_block, .. } => {
+                visit_expression(condition, f);
+                visit_expressions(then_block, f);
+                if let Some(else_block) = else_block {
+                    visit_expressions(else_block, f);
+                }
+            }
+            Statement::While { condition, body, .. } | Statement::DoWhile { condition, body, .. } => {
+                visit_expression(condition, f);
+                visit_expressions(body, f);
+            }
+            Statement::For { init, condition, update, body, .. } => {
+                if let Some(init) = init.as_deref() {
+                    visit_expressions(std::slice::from_ref(init), f);
+                }
+                if let Some(condition) = condition {
+                    visit_expression(condition, f);
+                }
+                if let Some(update) = update {
+                    visit_expression(update, f);
+                }
+                visit_expressions(body, f);
+            }
+            Statement::Block { statements, .. } => visit_expressions(statements, f),
+            _ => {}
+        }
+    }
 }
 
 /// プリプロセッサディレクティブのインデントをチェック（CGH008）
-/// プリプロセッサディレクティブは行頭（0列目）から始まるべき
-fn check_preprocessor_indent(tu: &TranslationUnit) -> Vec<Diagnostic> {
-    let mut diagnostics = Vec::new();
-    
-    fn check_items(items: &[Item], diagnostics: &mut Vec<Diagnostic>) {
+///
+/// スタイルごとに、条件ブロックのネストの深さ × indent_width の位置を期待する:
+/// - ColumnZero: `#` もキーワードも行頭（`#if`）
+/// - HashColumnZero: `#` は行頭、キーワードの前にネスト分の空白（`#  if`）
+/// - Indented: `#` の前にネスト分の空白（`  #if`）
+///
+/// `#` とキーワードの間の空白はソースから読み取るため、ソースがない場合は `#` の位置のみ確認する。
+fn check_preprocessor_indent(
+    tu: &TranslationUnit,
+    source: &str,
+    style: &crate::config::PreprocessorIndentStyle,
+    indent_width: usize,
+) -> Vec<Diagnostic> {
+    use crate::config::PreprocessorIndentStyle;
+
+    struct Context<'a> {
+        lines: Vec<&'a str>,
+        style: &'a PreprocessorIndentStyle,
+        indent_width: usize,
+    }
+
+    /// ディレクティブの行から `#` の列と、`#` とキーワードの間の空白の幅を読み取る
+    fn directive_layout(line: &str) -> Option<(usize, usize)> {
+        let hash_column = line.chars().take_while(|c| *c == ' ' || *c == '\t').count();
+        let rest = line.trim_start_matches([' ', '\t']).strip_prefix('#')?;
+        let keyword_indent = rest.chars().take_while(|c| *c == ' ' || *c == '\t').count();
+        Some((hash_column, keyword_indent))
+    }
+
+    fn check_directive(span: &Span, text: Option<&str>, depth: usize, ctx: &Context, diagnostics: &mut Vec<Diagnostic>) {
+        let layout = ctx
+            .lines
+            .get(span.start_line)
+            .copied()
+            .or(text)
+            .and_then(directive_layout);
+        let (hash_column, keyword_indent) = match layout {
+            Some((hash_column, keyword_indent)) => (hash_column, Some(keyword_indent)),
+            None => (span.start_column, None),
+        };
+
+        let nested = depth * ctx.indent_width;
+        let (expected_hash, expected_keyword) = match ctx.style {
+            PreprocessorIndentStyle::ColumnZero => (0, 0),
+            PreprocessorIndentStyle::HashColumnZero => (0, nested),
+            PreprocessorIndentStyle::Indented => (nested, 0),
+        };
+
+        let message = if hash_column != expected_hash {
+            if expected_hash == 0 {
+                format!(
+                    "プリプロセッサディレクティブの前にスペースがあります（{}文字）。行頭から始めてください。",
+                    hash_column
+                )
+            } else {
+                format!(
+                    "プリプロセッサディレクティブの前のスペースが{}文字です。条件ブロックのネストに合わせて{}文字にしてください。",
+                    hash_column, expected_hash
+                )
+            }
+        } else if let Some(keyword_indent) = keyword_indent.filter(|indent| *indent != expected_keyword) {
+            if expected_keyword == 0 {
+                format!(
+                    "'#' とディレクティブ名の間にスペースがあります（{}文字）。'#' の直後に書いてください。",
+                    keyword_indent
+                )
+            } else {
+                format!(
+                    "'#' とディレクティブ名の間のスペースが{}文字です。条件ブロックのネストに合わせて{}文字にしてください。",
+                    keyword_indent, expected_keyword
+                )
+            }
+        } else {
+            return;
+        };
+
+        diagnostics.push(Diagnostic::new(
+            span.clone(),
+            DiagnosticSeverity::Warning,
+            message,
+            DiagnosticCode::Custom("CGH008".to_string()),
+        ));
+    }
+
+    fn check_items(items: &[Item], depth: usize, ctx: &Context, diagnostics: &mut Vec<Diagnostic>) {
         for item in items {
             match item {
-                Item::Include { span, .. } |
-                Item::Define { span, .. } => {
-                    // プリプロセッサディレクティブの前にスペースがあるかチェック
-                    if span.start_column > 0 {
-                        diagnostics.push(Diagnostic::new(
-                            span.clone(),
-                            DiagnosticSeverity::Warning,
-                            format!(
-                                "プリプロセッサディレクティブの前にスペースがあります（{}文字）。行頭から始めてください。",
-                                span.start_column
-                            ),
-                            DiagnosticCode::Custom("CGH008".to_string()),
-                        ));
-                    }
+                Item::Include { span, text, .. } |
+                Item::Define { span, text, .. } |
+                Item::LineDirective { span, text, .. } |
+                Item::ErrorDirective { span, text, .. } |
+                Item::WarningDirective { span, text, .. } => {
+                    check_directive(span, Some(text), depth, ctx, diagnostics);
                 },
-                Item::ConditionalBlock { start_span, items: nested_items, .. } => {
+                Item::ConditionalBlock { start_span, end_span, elif_branches, else_span, .. } => {
                     // ifdef/ifndef/if/elif/else/endifもチェック
-                    if start_span.start_column > 0 {
-                        diagnostics.push(Diagnostic::new(
-                            start_span.clone(),
-                            DiagnosticSeverity::Warning,
-                            format!(
-                                "プリプロセッサディレクティブの前にスペースがあります（{}文字）。行頭から始めてください。",
-                                start_span.start_column
-                            ),
-                            DiagnosticCode::Custom("CGH008".to_string()),
-                        ));
+                    let directive_spans = std::iter::once(start_span)
+                        .chain(elif_branches.iter().map(|branch| &branch.span))
+                        .chain(else_span.as_ref())
+                        .chain(Some(end_span).filter(|span| *span != start_span));
+                    for span in directive_spans {
+                        check_directive(span, None, depth, ctx, diagnostics);
+                    }
+                    // ネストされたアイテムは1段深い位置を期待する
+                    for branch in item.branches() {
+                        check_items(branch, depth + 1, ctx, diagnostics);
                     }
-                    // ネストされたアイテムも再帰的にチェック
-                    check_items(nested_items, diagnostics);
                 },
                 _ => {}
             }
         }
     }
-    
-    check_items(&tu.items, &mut diagnostics);
+
+    let ctx = Context {
+        lines: source.lines().collect(),
+        style,
+        indent_width,
+    };
+    let mut diagnostics = Vec::new();
+    check_items(&tu.items, 0, &ctx, &mut diagnostics);
+    diagnostics
+}
+
+/// CGH025: 行頭の空白にタブとスペースが混在していないかチェック
+fn check_mixed_indentation(source: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut line_start = 0;
+
+    for (line_idx, line) in source.split('\n').enumerate() {
+        let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let indent = &line[..indent_len];
+
+        // 空白のみの行は対象外
+        if indent.contains('\t') && indent.contains(' ') && indent_len < line.trim_end_matches('\r').len() {
+            diagnostics.push(Diagnostic::new(
+                Span {
+                    start_line: line_idx,
+                    start_column: 0,
+                    end_line: line_idx,
+                    end_column: indent_len,
+                    byte_start_idx: line_start,
+                    byte_end_idx: line_start + indent_len,
+                },
+                DiagnosticSeverity::Warning,
+                "行頭の空白にタブとスペースが混在しています。どちらか一方に統一してください。".to_string(),
+                DiagnosticCode::Custom("CGH025".to_string()),
+            ));
+        }
+
+        line_start += line.len() + 1;
+    }
+
     diagnostics
 }
 
 /// CGH009: インデントスタイル（タブ/スペース）のチェック
+/// 混在した行は `report_mixed` が true の場合のみ報告する（通常は CGH025 で報告）
 fn check_indent_style(
     source: &str,
     expected_style: &crate::config::IndentStyle,
     indent_width: usize,
+    report_mixed: bool,
 ) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
     
@@ -1027,6 +3712,9 @@ fn check_indent_style(
         
         // タブとスペースの混在をチェック
         if has_tabs && has_spaces {
+            if !report_mixed {
+                continue;
+            }
             let span = Span {
                 start_line: line_idx + 1,
                 start_column: 0,
@@ -1100,6 +3788,417 @@ fn check_indent_style(
     diagnostics
 }
 
+/// 複数行にわたる文の継続行のインデントをチェック（CGH039）
+/// 継続行は文の開始行より indent_width の倍数だけ深くインデントする（タブは indent_width 文字分として数える）
+fn check_continuation_indent(tu: &TranslationUnit, source: &str, indent_width: usize) -> Vec<Diagnostic> {
+    fn visit_statements(statements: &[Statement], spans: &mut Vec<Span>) {
+        for stmt in statements {
+            match stmt {
+                Statement::VarDecl { span, .. } | Statement::Expression { span, .. } | Statement::Return { span, .. } => {
+                    if span.end_line > span.start_line {
+                        spans.push(span.clone());
+                    }
+                }
+                Statement::If { then_block, else_block, .. } => {
+                    visit_statements(then_block, spans);
+                    if let Some(else_block) = else_block {
+                        visit_statements(else_block, spans);
+                    }
+                }
+                Statement::For { body, .. } | Statement::While { body, .. } | Statement::DoWhile { body, .. } => {
+                    visit_statements(body, spans);
+                }
+                Statement::Block { statements, .. } => visit_statements(statements, spans),
+                Statement::Empty { .. } => {}
+            }
+        }
+    }
+
+    // 行頭の空白の幅（タブは indent_width 文字分）と、空白のバイト長
+    let indent_of = |line: &str| -> (usize, usize) {
+        let leading: String = line.chars().take_while(|c| *c == ' ' || *c == '\t').collect();
+        let width = leading.chars().map(|c| if c == '\t' { indent_width } else { 1 }).sum();
+        (width, leading.len())
+    };
+
+    let mut spans = Vec::new();
+    for item in tu.all_items() {
+        if let Item::FunctionDecl { body: Some(statements), .. } = item {
+            visit_statements(statements, &mut spans);
+        }
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let mut diagnostics = Vec::new();
+    for span in spans {
+        // 位置が不明な（0 行 0 列のまま途中のバイトを指す）スパンでは開始行を決められない
+        if (span.start_line, span.start_column) == (0, 0) && span.byte_start_idx > 0 {
+            continue;
+        }
+        let Some(first_line) = lines.get(span.start_line) else {
+            continue;
+        };
+        let (base, _) = indent_of(first_line);
+        let continuation_lines = lines.iter().enumerate().take(span.end_line + 1).skip(span.start_line + 1);
+        for (line_idx, line) in continuation_lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (width, leading_len) = indent_of(line);
+            if width > base && indent_width > 0 && (width - base) % indent_width == 0 {
+                continue;
+            }
+            diagnostics.push(Diagnostic::new(
+                Span {
+                    start_line: line_idx,
+                    start_column: 0,
+                    end_line: line_idx,
+                    end_column: leading_len,
+                    byte_start_idx: 0,
+                    byte_end_idx: leading_len,
+                },
+                DiagnosticSeverity::Warning,
+                format!(
+                    "継続行のインデントが文の開始行から {} 文字の倍数だけ深くなっていません（開始行: {} 文字、継続行: {} 文字）",
+                    indent_width, base, width
+                ),
+                DiagnosticCode::Custom("CGH039".to_string()),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// ソースを字句解析し、コメントを除いたトークン列を返す（リテラルの元の表記を見る診断で使う）
+fn code_tokens(source: &str) -> Vec<crate::token::Token> {
+    use crate::token::Token;
+
+    let mut lexer = crate::lexer::Lexer::from_borrowed(source);
+    std::iter::from_fn(|| lexer.next_token())
+        .filter(|token| !matches!(token, Token::BlockComment(..) | Token::LineComment(..)))
+        .collect()
+}
+
+/// ビット演算に使う16進定数に U サフィックスがあるかチェック（CGH044）
+/// 誤検出を避けるため、直前か直後のトークンがビット演算子の16進定数だけを対象にする
+fn check_hex_unsigned_suffix(source: &str) -> Vec<Diagnostic> {
+    use crate::token::{NumberLiteralToken, Token};
+
+    fn is_bitwise_operator(token: &Token) -> bool {
+        matches!(
+            token,
+            Token::Ampersand(..) | Token::Pipe(..) | Token::Caret(..) | Token::Tilde(..)
+                | Token::LeftShift(..) | Token::RightShift(..)
+                | Token::AmpersandEqual(..) | Token::PipeEqual(..) | Token::CaretEqual(..)
+                | Token::LeftShiftEqual(..) | Token::RightShiftEqual(..)
+        )
+    }
+
+    let tokens = code_tokens(source);
+    let mut diagnostics = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let Token::NumberLiteral(NumberLiteralToken { span, value }) = token else {
+            continue;
+        };
+        // 16進数の桁に u / U は現れないので、含まれていればサフィックス
+        let is_hex = value.starts_with("0x") || value.starts_with("0X");
+        if !is_hex || value.contains(['u', 'U']) {
+            continue;
+        }
+        let before = i.checked_sub(1).map(|j| &tokens[j]);
+        if before.is_some_and(is_bitwise_operator) || tokens.get(i + 1).is_some_and(is_bitwise_operator) {
+            diagnostics.push(Diagnostic::new(
+                span.clone(),
+                DiagnosticSeverity::Warning,
+                format!("ビット演算に使う16進定数 '{}' に U サフィックスがありません（'{}U' とする）", value, value),
+                DiagnosticCode::Custom("CGH044".to_string()),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// 8進数リテラル（先頭が 0 の整数定数）をチェック（CGH045）
+/// `010` は 10 ではなく 8 になるため、意図しない8進数として警告する（`0` と16進数は対象外）
+fn check_octal_literals(source: &str) -> Vec<Diagnostic> {
+    use crate::token::Token;
+
+    code_tokens(source)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::NumberLiteral(literal) if literal.is_octal() => Some(Diagnostic::new(
+                literal.span,
+                DiagnosticSeverity::Warning,
+                format!("整数定数 '{}' は8進数として解釈されます（10進数なら先頭の 0 を外す）", literal.value),
+                DiagnosticCode::Custom("CGH045".to_string()),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 二項演算子の前後に空白があるかチェック（CGH047）
+/// 直前が被演算子の終わり（識別子・リテラル・`)`・`]`）で直後が被演算子の始まりの演算子だけを二項演算子とみなし、
+/// 単項演算子（`-x` `&x` `*p`）は対象外にする。キャストの直後（`(int)-x`）と、
+/// 型名の直後の `*`（`Node*next` のようなポインタ宣言）も対象外
+fn check_operator_spacing(
+    tu: &TranslationUnit,
+    source: &str,
+    classes: &[crate::config::OperatorClass],
+) -> Vec<Diagnostic> {
+    use crate::config::OperatorClass;
+    use crate::token::Token;
+
+    fn operator_class(token: &Token) -> Option<OperatorClass> {
+        match token {
+            Token::Plus(..) | Token::Minus(..) | Token::Asterisk(..) | Token::Slash(..) | Token::Percent(..) => {
+                Some(OperatorClass::Arithmetic)
+            }
+            Token::EqualEqual(..) | Token::NotEqual(..) | Token::LessThan(..) | Token::LessThanOrEqual(..)
+            | Token::GreaterThan(..) | Token::GreaterThanOrEqual(..) => Some(OperatorClass::Comparison),
+            Token::AmpersandAmpersand(..) | Token::PipePipe(..) => Some(OperatorClass::Logical),
+            Token::Ampersand(..) | Token::Pipe(..) | Token::Caret(..) | Token::LeftShift(..) | Token::RightShift(..) => {
+                Some(OperatorClass::Bitwise)
+            }
+            Token::Equal(..) | Token::PlusEqual(..) | Token::MinusEqual(..) | Token::AsteriskEqual(..)
+            | Token::SlashEqual(..) | Token::PercentEqual(..) | Token::AmpersandEqual(..) | Token::PipeEqual(..)
+            | Token::CaretEqual(..) | Token::LeftShiftEqual(..) | Token::RightShiftEqual(..) => Some(OperatorClass::Assignment),
+            _ => None,
+        }
+    }
+
+    fn ends_operand(token: &Token) -> bool {
+        matches!(
+            token,
+            Token::Ident(..) | Token::NumberLiteral(..) | Token::FloatLiteral(..) | Token::StringLiteral(..)
+                | Token::CharLiteral(..) | Token::RightParen(..) | Token::RightBracket(..)
+        )
+    }
+
+    fn starts_operand(token: &Token) -> bool {
+        matches!(
+            token,
+            Token::Ident(..) | Token::NumberLiteral(..) | Token::FloatLiteral(..) | Token::StringLiteral(..)
+                | Token::CharLiteral(..) | Token::LeftParen(..) | Token::LeftBrace(..) | Token::Plus(..) | Token::Minus(..)
+                | Token::Exclamation(..) | Token::Tilde(..) | Token::Asterisk(..) | Token::Ampersand(..)
+                | Token::PlusPlus(..) | Token::MinusMinus(..)
+        )
+    }
+
+    fn is_type_token(token: &Token) -> bool {
+        matches!(
+            token,
+            Token::Void(..) | Token::Char(..) | Token::Short(..) | Token::Int(..) | Token::Long(..)
+                | Token::Float(..) | Token::Double(..) | Token::Signed(..) | Token::Unsigned(..) | Token::Bool(..)
+                | Token::Const(..) | Token::Volatile(..) | Token::Asterisk(..)
+        )
+    }
+
+    let typedef_names: HashSet<String> = tu
+        .symbols()
+        .into_iter()
+        .filter(|symbol| symbol.kind == crate::ast::SymbolKind::Typedef)
+        .map(|symbol| symbol.name)
+        .collect();
+    let is_type_name = |token: &Token| match token {
+        Token::Ident(ident) => typedef_names.contains(&*ident.name),
+        _ => is_type_token(token),
+    };
+
+    // 空白とコメントも残し、各トークンのスパンが前後の空白を含まないようにする
+    let mut lexer = crate::lexer::Lexer::from_borrowed(source);
+    lexer.preserve_whitespace(true);
+    let tokens: Vec<Token> = std::iter::from_fn(|| lexer.next_token()).collect();
+    let is_space = |token: Option<&Token>| {
+        matches!(token, Some(Token::Whitespace(..) | Token::BlockComment(..) | Token::LineComment(..)))
+    };
+    let code: Vec<usize> = (0..tokens.len()).filter(|&i| !is_space(tokens.get(i))).collect();
+
+    let mut diagnostics = Vec::new();
+    for k in 1..code.len().saturating_sub(1) {
+        let token = &tokens[code[k]];
+        let Some(class) = operator_class(token) else {
+            continue;
+        };
+        if !classes.contains(&class) {
+            continue;
+        }
+        let (prev, next) = (&tokens[code[k - 1]], &tokens[code[k + 1]]);
+        if !ends_operand(prev) || !starts_operand(next) {
+            continue;
+        }
+        // `(型名)` の直後はキャストされた単項演算子
+        let after_cast = matches!(prev, Token::RightParen(..)) && k >= 2 && is_type_name(&tokens[code[k - 2]]);
+        let pointer_declarator = matches!(token, Token::Asterisk(..)) && is_type_name(prev);
+        if after_cast || pointer_declarator {
+            continue;
+        }
+
+        let space_before = is_space(tokens.get(code[k] - 1));
+        let space_after = is_space(tokens.get(code[k] + 1));
+        if space_before && space_after {
+            continue;
+        }
+        let span = token.span();
+        let operator = &source[span.byte_start_idx..span.byte_end_idx];
+        diagnostics.push(Diagnostic::new(
+            span.clone(),
+            DiagnosticSeverity::Warning,
+            format!("二項演算子 '{}' の前後に空白を入れてください（例: a {} b）", operator, operator),
+            DiagnosticCode::Custom("CGH047".to_string()),
+        ));
+    }
+    diagnostics
+}
+
+/// CGH048: インデントにスペースを使う設定で、ソース中のタブ文字をチェック
+/// 行頭のインデントだけを見る CGH009 と異なり、行中の位置揃えやコメント・文字列の中のタブも対象にする。
+/// 連続するタブは1つの診断にまとめ、その範囲を指す
+fn check_no_tabs(source: &str, indent_style: &crate::config::IndentStyle) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    if !matches!(indent_style, crate::config::IndentStyle::Spaces) {
+        return diagnostics;
+    }
+
+    let mut line_start = 0;
+    for (line_idx, line) in source.split('\n').enumerate() {
+        let mut column = 0;
+        while let Some(offset) = line[column..].find('\t') {
+            let start = column + offset;
+            let end = start + line[start..].len() - line[start..].trim_start_matches('\t').len();
+            diagnostics.push(Diagnostic::new(
+                Span {
+                    start_line: line_idx,
+                    start_column: start,
+                    end_line: line_idx,
+                    end_column: end,
+                    byte_start_idx: line_start + start,
+                    byte_end_idx: line_start + end,
+                },
+                DiagnosticSeverity::Warning,
+                "タブ文字が使われています。スペースを使用してください。".to_string(),
+                DiagnosticCode::Custom("CGH048".to_string()),
+            ));
+            column = end;
+        }
+        line_start += line.len() + 1;
+    }
+
+    diagnostics
+}
+
+/// CGH051: コメント・文字列リテラル・文字定数の外にある ASCII 以外の文字をチェック
+/// ファイル先頭の UTF-8 BOM は個別のメッセージで報告する。連続する文字は1つの診断にまとめる
+fn check_non_ascii(source: &str) -> Vec<Diagnostic> {
+    enum ScanState {
+        Code,
+        LineComment,
+        BlockComment,
+        Quoted(char),  // 文字列リテラルまたは文字定数（閉じる引用符）
+    }
+
+    // 列は行頭からの文字数で数える（バイト数で数えると ASCII 以外の文字の後ろの列がずれる）
+    fn non_ascii_diagnostic(source: &str, start: usize, end: usize, line: usize, line_start: usize) -> Diagnostic {
+        let start_column = source[line_start..start].chars().count();
+        Diagnostic::new(
+            Span {
+                start_line: line,
+                start_column,
+                end_line: line,
+                end_column: start_column + source[start..end].chars().count(),
+                byte_start_idx: start,
+                byte_end_idx: end,
+            },
+            DiagnosticSeverity::Warning,
+            format!("ASCII 以外の文字 '{}' がコード中に使われています", &source[start..end]),
+            DiagnosticCode::Custom("CGH051".to_string()),
+        )
+    }
+
+    let mut diagnostics = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    // 字句解析器と同じく、BOM の後ろから1行目の列を数える
+    let mut line_start = 0;
+    if source.starts_with('\u{FEFF}') {
+        chars.next();
+        line_start = '\u{FEFF}'.len_utf8();
+        diagnostics.push(Diagnostic::new(
+            Span {
+                start_line: 0,
+                start_column: 0,
+                end_line: 0,
+                end_column: 1,
+                byte_start_idx: 0,
+                byte_end_idx: '\u{FEFF}'.len_utf8(),
+            },
+            DiagnosticSeverity::Warning,
+            "ファイルの先頭に UTF-8 BOM があります。BOM なしで保存してください。".to_string(),
+            DiagnosticCode::Custom("CGH051".to_string()),
+        ));
+    }
+
+    let mut state = ScanState::Code;
+    let mut run_start: Option<usize> = None;  // コード中で続いている ASCII 以外の文字の開始位置
+    let mut line = 0;
+    while let Some((idx, ch)) = chars.next() {
+        let next = chars.peek().map(|&(_, c)| c);
+        match state {
+            ScanState::Code => {
+                if !ch.is_ascii() {
+                    run_start.get_or_insert(idx);
+                    continue;
+                }
+                if let Some(start) = run_start.take() {
+                    diagnostics.push(non_ascii_diagnostic(source, start, idx, line, line_start));
+                }
+                match (ch, next) {
+                    ('/', Some('/')) => {
+                        chars.next();
+                        state = ScanState::LineComment;
+                    }
+                    ('/', Some('*')) => {
+                        chars.next();
+                        state = ScanState::BlockComment;
+                    }
+                    ('"' | '\'', _) => state = ScanState::Quoted(ch),
+                    _ => {}
+                }
+            }
+            ScanState::LineComment => {
+                if ch == '\n' {
+                    state = ScanState::Code;
+                }
+            }
+            ScanState::BlockComment => {
+                if ch == '*' && next == Some('/') {
+                    chars.next();
+                    state = ScanState::Code;
+                }
+            }
+            ScanState::Quoted(quote) => {
+                if ch == '\\' {
+                    // エスケープされた文字は読み飛ばす（行継続の改行も行数に数える）
+                    if let Some((escaped, '\n')) = chars.next() {
+                        line += 1;
+                        line_start = escaped + 1;
+                    }
+                } else if ch == quote || ch == '\n' {
+                    state = ScanState::Code;
+                }
+            }
+        }
+        if ch == '\n' {
+            line += 1;
+            line_start = idx + 1;
+        }
+    }
+    if let Some(start) = run_start {
+        diagnostics.push(non_ascii_diagnostic(source, start, source.len(), line, line_start));
+    }
+
+    diagnostics
+}
+
 fn check_cert_c(_tu: &TranslationUnit, _source: &str) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
     diagnostics
@@ -0,0 +1,197 @@
+use crate::config::IndentStyle;
+use std::fs;
+use std::path::Path;
+
+/// .editorconfig から読み取ったインデント設定
+///
+/// 対象ファイルに一致するセクションの `indent_style` / `indent_size` のみを扱う。
+/// 値が指定されていない項目は None のままとし、呼び出し側の設定を優先する。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EditorConfigIndent {
+    pub indent_style: Option<IndentStyle>,
+    pub indent_width: Option<usize>,
+}
+
+impl EditorConfigIndent {
+    /// .editorconfig の内容を解析し、`relative_path`（.editorconfig のあるディレクトリからの相対パス）
+    /// に一致するセクションの設定を返す。後のセクションほど優先される
+    pub fn parse(content: &str, relative_path: &str) -> Self {
+        Self::parse_with_root(content, relative_path).0
+    }
+
+    /// 対象ファイルのディレクトリから親ディレクトリへ遡って .editorconfig を読み込む
+    /// 近い .editorconfig の設定ほど優先し、`root = true` の .editorconfig、`.git` を含むディレクトリ、
+    /// ファイルシステムのルートのいずれかで探索を打ち切る
+    pub fn find_for_file(file_path: &Path) -> Self {
+        let mut result = EditorConfigIndent::default();
+        let mut current = file_path.parent().map(Path::to_path_buf);
+
+        while let Some(dir) = current {
+            if let Ok(content) = fs::read_to_string(dir.join(".editorconfig")) {
+                let relative = file_path.strip_prefix(&dir).unwrap_or(file_path);
+                let relative = relative.to_string_lossy().replace('\\', "/");
+                let (found, is_root) = Self::parse_with_root(&content, &relative);
+                // 既に近いファイルで決まっている項目は上書きしない
+                result.indent_style = result.indent_style.or(found.indent_style);
+                result.indent_width = result.indent_width.or(found.indent_width);
+                if is_root {
+                    break;
+                }
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+            current = dir.parent().map(Path::to_path_buf);
+        }
+
+        result
+    }
+
+    /// 解析結果と `root = true` が指定されているかどうかを返す
+    fn parse_with_root(content: &str, relative_path: &str) -> (Self, bool) {
+        let mut result = EditorConfigIndent::default();
+        let mut is_root = false;
+        let mut in_matching_section = false;
+        let mut in_preamble = true;
+        let mut tab_width: Option<usize> = None;
+        let mut indent_size_is_tab = false;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_preamble = false;
+                in_matching_section = glob_matches(section, relative_path);
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim().to_lowercase();
+
+            if in_preamble {
+                if key == "root" {
+                    is_root = value == "true";
+                }
+                continue;
+            }
+            if !in_matching_section {
+                continue;
+            }
+
+            match key.as_str() {
+                "indent_style" => match value.as_str() {
+                    "tab" => result.indent_style = Some(IndentStyle::Tabs),
+                    "space" => result.indent_style = Some(IndentStyle::Spaces),
+                    _ => {}
+                },
+                "indent_size" => {
+                    if value == "tab" {
+                        indent_size_is_tab = true;
+                    } else if let Ok(size) = value.parse() {
+                        indent_size_is_tab = false;
+                        result.indent_width = Some(size);
+                    }
+                }
+                "tab_width" => {
+                    if let Ok(width) = value.parse() {
+                        tab_width = Some(width);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // indent_size = tab の場合は tab_width の値を使う
+        if indent_size_is_tab {
+            result.indent_width = tab_width.or(result.indent_width);
+        }
+
+        (result, is_root)
+    }
+}
+
+/// .editorconfig のセクション名（glob）がファイルパスに一致するかどうか
+/// `/` を含まないパターンは任意のディレクトリのファイル名に一致する
+///
+/// ファイルを診断するたびに呼ばれるため、正規表現に変換せずに glob を直接照合する。
+fn glob_matches(pattern: &str, relative_path: &str) -> bool {
+    let anchored = pattern.contains('/');
+    let pattern = pattern.trim_start_matches('/');
+    let path: Vec<char> = relative_path.chars().collect();
+
+    expand_braces(pattern).iter().any(|alternative| {
+        let alternative: Vec<char> = alternative.chars().collect();
+        if anchored {
+            return glob_match_chars(&alternative, &path);
+        }
+        // 先頭のディレクトリを読み飛ばした位置のいずれかから一致すればよい
+        std::iter::once(0)
+            .chain(path.iter().enumerate().filter(|&(_, &c)| c == '/').map(|(i, _)| i + 1))
+            .any(|start| glob_match_chars(&alternative, &path[start..]))
+    })
+}
+
+/// `{a,b}` を展開したパターンの一覧を返す（入れ子の `{}` も展開する）
+/// 対応する `}` がない場合は一致しないパターンとして扱う
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+
+    let mut depth = 0;
+    let mut alternatives = Vec::new();
+    let mut alternative_start = open + 1;
+    for (i, c) in pattern[open..].char_indices().map(|(i, c)| (open + i, c)) {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    alternatives.push(&pattern[alternative_start..i]);
+                    let (prefix, suffix) = (&pattern[..open], &pattern[i + 1..]);
+                    return alternatives
+                        .into_iter()
+                        .flat_map(|alternative| expand_braces(&format!("{}{}{}", prefix, alternative, suffix)))
+                        .collect();
+                }
+            }
+            ',' if depth == 1 => {
+                alternatives.push(&pattern[alternative_start..i]);
+                alternative_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    Vec::new()
+}
+
+/// `*`（`/` 以外の0文字以上）・`**`（任意の0文字以上）・`?`・`[abc]` / `[!abc]` を含むパターンがパス全体に一致するか
+fn glob_match_chars(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => (0..=path.len()).any(|i| glob_match_chars(rest, &path[i..])),
+        ['*', rest @ ..] => {
+            let segment_len = path.iter().position(|&c| c == '/').unwrap_or(path.len());
+            (0..=segment_len).any(|i| glob_match_chars(rest, &path[i..]))
+        }
+        ['?', rest @ ..] => matches!(path, [c, ..] if *c != '/') && glob_match_chars(rest, &path[1..]),
+        ['[', class @ ..] if class.contains(&']') => {
+            let (negated, class) = match class {
+                ['!', class @ ..] => (true, class),
+                _ => (false, class),
+            };
+            let close = class.iter().position(|&c| c == ']').unwrap_or(class.len());
+            let Some((c, path_rest)) = path.split_first() else {
+                return false;
+            };
+            class[..close].contains(c) != negated && glob_match_chars(&class[close + 1..], path_rest)
+        }
+        [c, rest @ ..] => path.first() == Some(c) && glob_match_chars(rest, &path[1..]),
+    }
+}
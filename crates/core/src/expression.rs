@@ -1,7 +1,8 @@
 use crate::span::Span;
 
 /// C言語の式を表すAST
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expression {
     /// 整数リテラル
     IntLiteral {
@@ -9,9 +10,21 @@ pub enum Expression {
         span: Span,
     },
     
-    /// 浮動小数点リテラル
+    /// 浮動小数点リテラル（接尾辞を含む元の表記。f64 で持つと Eq を実装できないため文字列で持つ）
     FloatLiteral {
-        value: f64,
+        value: String,
+        span: Span,
+    },
+    
+    /// 文字列リテラル（引用符を含む元の表記）
+    StringLiteral {
+        value: String,
+        span: Span,
+    },
+    
+    /// 文字定数（引用符を含む元の表記）
+    CharLiteral {
+        value: String,
         span: Span,
     },
     
@@ -88,7 +101,8 @@ pub enum Expression {
 }
 
 /// 二項演算子
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinaryOperator {
     // 算術演算子
     Add,        // +
@@ -115,11 +129,44 @@ pub enum BinaryOperator {
     // 論理演算子
     LogicalAnd,     // &&
     LogicalOr,      // ||
+
+    // 複合代入演算子
+    AddAssign,        // +=
+    SubtractAssign,   // -=
+    MultiplyAssign,   // *=
+    DivideAssign,     // /=
+    ModuloAssign,     // %=
+    BitwiseAndAssign, // &=
+    BitwiseOrAssign,  // |=
+    BitwiseXorAssign, // ^=
+    LeftShiftAssign,  // <<=
+    RightShiftAssign, // >>=
+}
+
+impl BinaryOperator {
+    /// 複合代入演算子（+= など）かどうか
+    pub fn is_assignment(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::AddAssign
+                | BinaryOperator::SubtractAssign
+                | BinaryOperator::MultiplyAssign
+                | BinaryOperator::DivideAssign
+                | BinaryOperator::ModuloAssign
+                | BinaryOperator::BitwiseAndAssign
+                | BinaryOperator::BitwiseOrAssign
+                | BinaryOperator::BitwiseXorAssign
+                | BinaryOperator::LeftShiftAssign
+                | BinaryOperator::RightShiftAssign
+        )
+    }
 }
 
 /// 単項演算子
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnaryOperator {
+    Plus,           // + (単項プラス)
     Negate,         // - (単項マイナス)
     LogicalNot,     // !
     BitwiseNot,     // ~
@@ -132,11 +179,20 @@ pub enum UnaryOperator {
 }
 
 impl Expression {
+    /// デバッグ表示用に、式をC言語のソースとして1行で出力する（例: `a + b * 2`）
+    ///
+    /// フォーマッターと同じく、優先順位に応じて必要な箇所だけ括弧を付ける。
+    pub fn pretty(&self) -> String {
+        crate::formatter::format_expression(self)
+    }
+
     /// 式のスパン情報を取得
     pub fn span(&self) -> &Span {
         match self {
             Expression::IntLiteral { span, .. } => span,
             Expression::FloatLiteral { span, .. } => span,
+            Expression::StringLiteral { span, .. } => span,
+            Expression::CharLiteral { span, .. } => span,
             Expression::Identifier { span, .. } => span,
             Expression::BinaryOp { span, .. } => span,
             Expression::UnaryOp { span, .. } => span,
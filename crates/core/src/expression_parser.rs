@@ -3,20 +3,22 @@ use crate::lexer::Lexer;
 use crate::parser::Parser;
 use crate::token::*;
 use crate::span::Span;
+use crate::token_stream::TokenStream;
 use crate::type_table::TypeTable;
 
 /// 式をパースするパーサー
-pub struct ExpressionParser<'a> {
-    lexer: &'a mut Lexer,
-    current_token: Option<Token>,
+pub struct ExpressionParser<'a, 'src> {
+    tokens: TokenStream<'a, 'src>,
+    current_token: Option<Token>,  // tokens の次のトークン（advance するまで読み進めない）
     type_table: Option<&'a TypeTable>,  // 型テーブルへの参照（オプション）
 }
 
-impl<'a> ExpressionParser<'a> {
-    pub fn new(lexer: &'a mut Lexer) -> Self {
-        let current_token = lexer.next_token();
+impl<'a, 'src> ExpressionParser<'a, 'src> {
+    pub fn new(lexer: &'a mut Lexer<'src>) -> Self {
+        let mut tokens = TokenStream::new(lexer);
+        let current_token = tokens.peek_token().cloned();
         ExpressionParser {
-            lexer,
+            tokens,
             current_token,
             type_table: None,
         }
@@ -28,11 +30,9 @@ impl<'a> ExpressionParser<'a> {
         self
     }
     
-    /// パース終了時にcurrent_tokenをLexerに戻す
+    /// パース終了時にcurrent_token以降の読み残したトークンをLexerに戻す
     pub fn finish(self) {
-        if let Some(token) = self.current_token {
-            self.lexer.unget_token(token);
-        }
+        self.tokens.finish();
     }
     
     /// 名前が型名かどうかをチェック
@@ -41,13 +41,27 @@ impl<'a> ExpressionParser<'a> {
         matches!(
             name,
             "void" | "char" | "short" | "int" | "long" | "float" | "double" 
-            | "signed" | "unsigned" | "_Bool" | "const" | "volatile" | "restrict" | "_Atomic"
+            | "signed" | "unsigned" | "_Bool" | "_Complex" | "_Imaginary" | "const" | "volatile" | "restrict" | "_Atomic"
         ) || self.type_table.map_or(false, |table| table.is_type_name(name))
     }
     
     /// 次のトークンを取得して current_token を更新
     fn advance(&mut self) {
-        self.current_token = self.lexer.next_token();
+        self.tokens.next_token();
+        self.current_token = self.tokens.peek_token().cloned();
+    }
+
+    /// 試しに解析し、`None` を返した場合は解析前のトークンの位置に戻す
+    fn speculate<T>(&mut self, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let mark = self.tokens.checkpoint();
+        let result = parse(self);
+        if result.is_some() {
+            self.tokens.commit(mark);
+        } else {
+            self.tokens.rewind(mark);
+            self.current_token = self.tokens.peek_token().cloned();
+        }
+        result
     }
     
     /// 現在のトークンを参照
@@ -57,7 +71,47 @@ impl<'a> ExpressionParser<'a> {
     
     /// 式全体をパース（エントリーポイント）
     pub fn parse_expression(&mut self) -> Option<Expression> {
-        self.parse_logical_or()
+        self.parse_assignment()
+    }
+    
+    /// 代入 (=)、右結合
+    fn parse_assignment(&mut self) -> Option<Expression> {
+        let left = self.parse_logical_or()?;
+        
+        if matches!(self.peek(), Some(Token::Equal(_))) {
+            self.advance(); // consume =
+            let right = self.parse_assignment()?;
+            let span = self.merge_spans(&left, &right);
+            return Some(Expression::Assignment {
+                left: Box::new(left),
+                right: Box::new(right),
+                span,
+            });
+        }
+
+        // 複合代入 (+=, <<= など) は演算子付きの二項演算として表す
+        let op = match self.peek() {
+            Some(Token::PlusEqual(_)) => BinaryOperator::AddAssign,
+            Some(Token::MinusEqual(_)) => BinaryOperator::SubtractAssign,
+            Some(Token::AsteriskEqual(_)) => BinaryOperator::MultiplyAssign,
+            Some(Token::SlashEqual(_)) => BinaryOperator::DivideAssign,
+            Some(Token::PercentEqual(_)) => BinaryOperator::ModuloAssign,
+            Some(Token::AmpersandEqual(_)) => BinaryOperator::BitwiseAndAssign,
+            Some(Token::PipeEqual(_)) => BinaryOperator::BitwiseOrAssign,
+            Some(Token::CaretEqual(_)) => BinaryOperator::BitwiseXorAssign,
+            Some(Token::LeftShiftEqual(_)) => BinaryOperator::LeftShiftAssign,
+            Some(Token::RightShiftEqual(_)) => BinaryOperator::RightShiftAssign,
+            _ => return Some(left),
+        };
+        self.advance(); // consume operator
+        let right = self.parse_assignment()?;
+        let span = self.merge_spans(&left, &right);
+        Some(Expression::BinaryOp {
+            left: Box::new(left),
+            op,
+            right: Box::new(right),
+            span,
+        })
     }
     
     /// 論理和 (||)
@@ -303,100 +357,108 @@ impl<'a> ExpressionParser<'a> {
         Some(left)
     }
     
-    /// 単項演算子 (-, !, ~, &, *, ++, --)
+    /// 単項演算子 (+, -, !, ~, &, *, ++, --)
     fn parse_unary(&mut self) -> Option<Expression> {
         // 前置単項演算子をチェック
-        let (op, op_span) = match self.peek() {
-            Some(Token::Minus(_)) => {
-                let span = self.get_current_span();
-                self.advance();
-                (Some(UnaryOperator::Negate), span)
-            },
-            Some(Token::Exclamation(_)) => {
-                let span = self.get_current_span();
-                self.advance();
-                (Some(UnaryOperator::LogicalNot), span)
-            },
-            Some(Token::Tilde(_)) => {
-                let span = self.get_current_span();
-                self.advance();
-                (Some(UnaryOperator::BitwiseNot), span)
-            },
-            Some(Token::Ampersand(_)) => {
-                let span = self.get_current_span();
-                self.advance();
-                (Some(UnaryOperator::AddressOf), span)
-            },
-            Some(Token::Asterisk(_)) => {
-                let span = self.get_current_span();
-                self.advance();
-                (Some(UnaryOperator::Dereference), span)
-            },
-            Some(Token::PlusPlus(_)) => {
-                let span = self.get_current_span();
-                self.advance();
-                (Some(UnaryOperator::PreIncrement), span)
-            },
-            Some(Token::MinusMinus(_)) => {
-                let span = self.get_current_span();
-                self.advance();
-                (Some(UnaryOperator::PreDecrement), span)
-            },
-            _ => (None, Span {
-                start_line: 0,
-                start_column: 0,
-                end_line: 0,
-                end_column: 0,
-                byte_start_idx: 0,
-                byte_end_idx: 0,
-            }),
-        };
-        
-        if let Some(operator) = op {
-            // 単項演算子がある場合、再帰的にparse_unary（複数の単項演算子に対応）
-            let operand = Box::new(self.parse_unary()?);
-            let operand_span = self.get_expression_span(&operand);
-            
-            let span = Span {
-                start_line: op_span.start_line,
-                start_column: op_span.start_column,
-                end_line: operand_span.end_line,
-                end_column: operand_span.end_column,
-                byte_start_idx: op_span.byte_start_idx,
-                byte_end_idx: operand_span.byte_end_idx,
-            };
-            
-            Some(Expression::UnaryOp {
-                op: operator,
-                operand,
-                span,
-            })
-        } else {
+        let op = match self.peek() {
+            Some(Token::Plus(_)) => UnaryOperator::Plus,
+            Some(Token::Minus(_)) => UnaryOperator::Negate,
+            Some(Token::Exclamation(_)) => UnaryOperator::LogicalNot,
+            Some(Token::Tilde(_)) => UnaryOperator::BitwiseNot,
+            Some(Token::Ampersand(_)) => UnaryOperator::AddressOf,
+            Some(Token::Asterisk(_)) => UnaryOperator::Dereference,
+            Some(Token::PlusPlus(_)) => UnaryOperator::PreIncrement,
+            Some(Token::MinusMinus(_)) => UnaryOperator::PreDecrement,
             // 単項演算子がない場合は postfix または primary
-            self.parse_postfix()
-        }
+            _ => return self.parse_postfix(),
+        };
+        let op_span = self.current_span()?;
+        self.advance();
+
+        // 単項演算子がある場合、再帰的にparse_unary（複数の単項演算子に対応）
+        let operand = Box::new(self.parse_unary()?);
+        let operand_span = self.get_expression_span(&operand);
+
+        let span = op_span.merge(&operand_span);
+
+        Some(Expression::UnaryOp {
+            op,
+            operand,
+            span,
+        })
     }
     
-    /// 後置演算子 (++, --)
+    /// 後置演算子 (++, --, [], ., ->)
     fn parse_postfix(&mut self) -> Option<Expression> {
         let mut expr = self.parse_primary()?;
         
-        // 後置インクリメント/デクリメント
+        // 後置インクリメント/デクリメント、関数呼び出し、配列アクセス、メンバーアクセス
         loop {
             match self.peek() {
-                Some(Token::PlusPlus(_)) => {
-                    let op_span = self.get_current_span();
+                Some(Token::LeftParen(_)) => {
+                    self.advance(); // consume (
+                    let mut arguments = Vec::new();
+                    if !matches!(self.peek(), Some(Token::RightParen(_))) {
+                        loop {
+                            arguments.push(self.parse_expression()?);
+                            match self.peek() {
+                                Some(Token::Comma(_)) => self.advance(),
+                                Some(Token::RightParen(_)) => break,
+                                _ => return None, // エラー: 閉じ括弧がない
+                            }
+                        }
+                    }
+                    let end_span = self.current_span()?;
+                    self.advance(); // consume )
+                    let span = self.get_expression_span(&expr).merge(&end_span);
+
+                    expr = Expression::FunctionCall {
+                        function: Box::new(expr),
+                        arguments,
+                        span,
+                    };
+                },
+                Some(Token::LeftBracket(_)) => {
+                    self.advance(); // consume [
+                    let index = self.parse_expression()?;
+                    let end_span = match self.peek() {
+                        Some(close @ Token::RightBracket(_)) => close.span().clone(),
+                        _ => return None, // エラー: 閉じ括弧がない
+                    };
+                    self.advance(); // consume ]
+                    let expr_span = self.get_expression_span(&expr);
+                    
+                    let span = expr_span.merge(&end_span);
+                    
+                    expr = Expression::ArrayAccess {
+                        array: Box::new(expr),
+                        index: Box::new(index),
+                        span,
+                    };
+                },
+                Some(Token::Dot(_)) | Some(Token::Arrow(_)) => {
+                    let is_arrow = matches!(self.peek(), Some(Token::Arrow(_)));
+                    self.advance(); // consume . または ->
+                    let Some(Token::Ident(IdentToken { name: member, span: member_span })) = self.current_token.clone() else {
+                        return None; // エラー: メンバー名がない
+                    };
                     self.advance();
                     let expr_span = self.get_expression_span(&expr);
                     
-                    let span = Span {
-                        start_line: expr_span.start_line,
-                        start_column: expr_span.start_column,
-                        end_line: op_span.end_line,
-                        end_column: op_span.end_column,
-                        byte_start_idx: expr_span.byte_start_idx,
-                        byte_end_idx: op_span.byte_end_idx,
+                    let span = expr_span.merge(&member_span);
+                    
+                    expr = if is_arrow {
+                        Expression::PointerMemberAccess { object: Box::new(expr), member: member.to_string(), span }
+                    } else {
+                        Expression::MemberAccess { object: Box::new(expr), member: member.to_string(), span }
                     };
+                },
+                Some(Token::PlusPlus(_)) => {
+                    let op_span = self.current_span()?;
+                    self.advance();
+                    let expr_span = self.get_expression_span(&expr);
+                    
+                    let span = expr_span.merge(&op_span);
                     
                     expr = Expression::UnaryOp {
                         op: UnaryOperator::PostIncrement,
@@ -405,18 +467,11 @@ impl<'a> ExpressionParser<'a> {
                     };
                 },
                 Some(Token::MinusMinus(_)) => {
-                    let op_span = self.get_current_span();
+                    let op_span = self.current_span()?;
                     self.advance();
                     let expr_span = self.get_expression_span(&expr);
                     
-                    let span = Span {
-                        start_line: expr_span.start_line,
-                        start_column: expr_span.start_column,
-                        end_line: op_span.end_line,
-                        end_column: op_span.end_column,
-                        byte_start_idx: expr_span.byte_start_idx,
-                        byte_end_idx: op_span.byte_end_idx,
-                    };
+                    let span = expr_span.merge(&op_span);
                     
                     expr = Expression::UnaryOp {
                         op: UnaryOperator::PostDecrement,
@@ -461,197 +516,129 @@ impl<'a> ExpressionParser<'a> {
             }
             Token::FloatLiteral(FloatLiteralToken { value, span }) => {
                 self.advance();
-                // Remove suffix before parsing
+                // 接尾辞を除いて数値として読めることだけ確認し、値は元の表記のまま持つ
                 let value_without_suffix = value.trim_end_matches(|c: char| {
                     c == 'f' || c == 'F' || c == 'l' || c == 'L'
                 });
-                
-                let float_value = value_without_suffix.parse::<f64>().ok()?;
+                value_without_suffix.parse::<f64>().ok()?;
                 Some(Expression::FloatLiteral { 
-                    value: float_value,
+                    value,
                     span,
                 })
             }
+            Token::StringLiteral(StringLiteralToken { value, span }) => {
+                self.advance();
+                Some(Expression::StringLiteral { value, span })
+            }
+            Token::CharLiteral(CharLiteralToken { value, span }) => {
+                self.advance();
+                Some(Expression::CharLiteral { value, span })
+            }
             Token::Ident(IdentToken { name, span }) => {
                 self.advance();
                 Some(Expression::Identifier { 
-                    name,
+                    name: name.to_string(),
                     span,
                 })
             }
             Token::LeftParen(_) => {
-                // ( で始まる場合、キャスト式か括弧式かを判定
-                let start_span = self.get_current_span();
+                // ( で始まる場合、キャスト式として読めなければ括弧式として読み直す
+                let start_span = self.current_span()?;
+                if let Some(cast) = self.speculate(|parser| parser.parse_cast(start_span.clone())) {
+                    return Some(cast);
+                }
                 self.advance(); // consume '('
+                // 括弧式 ( expression )
+                let inner_expr = self.parse_expression()?;
                 
-                // 次のトークンが型名キーワードかtypedef名かチェック
-                let is_type = match self.peek() {
-                    Some(Token::Void(_)) | Some(Token::Char(_)) | Some(Token::Short(_)) 
-                    | Some(Token::Int(_)) | Some(Token::Long(_)) | Some(Token::Float(_)) 
-                    | Some(Token::Double(_)) | Some(Token::Signed(_)) | Some(Token::Unsigned(_))
-                    | Some(Token::Struct(_)) | Some(Token::Union(_)) | Some(Token::Enum(_))
-                    | Some(Token::Const(_)) | Some(Token::Volatile(_)) => true,
-                    Some(Token::Ident(IdentToken { name, .. })) => {
-                        // typedef名かどうかをチェック
-                        self.is_type_name(name)
-                    },
-                    _ => false,
-                };
-                
-                if is_type {
-                    // キャスト式 (Type) expr
-                    let mut type_tokens = Vec::new();
-                    loop {
-                        match self.peek() {
-                            Some(Token::RightParen(_)) => break,
-                            None => return None,
-                            _ => {
-                                if let Some(token) = self.current_token.clone() {
-                                    type_tokens.push(token);
-                                }
-                                self.advance();
-                            }
-                        }
-                    }
-                    
-                    if !matches!(self.peek(), Some(Token::RightParen(_))) {
-                        return None;
-                    }
-                    self.advance(); // ')' をスキップ
-                    
-                    // 型名をパース
-                    let target_type = self.parse_type_from_tokens(&type_tokens);
-                    
-                    // キャスト対象の式をパース（unaryレベルから再開）
-                    let operand = self.parse_unary()?;
-                    let operand_span = self.get_expression_span(&operand);
-                    
-                    let span = Span {
-                        start_line: start_span.start_line,
-                        start_column: start_span.start_column,
-                        end_line: operand_span.end_line,
-                        end_column: operand_span.end_column,
-                        byte_start_idx: start_span.byte_start_idx,
-                        byte_end_idx: operand_span.byte_end_idx,
-                    };
-                    
-                    return Some(Expression::Cast {
-                        target_type,
-                        operand: Box::new(operand),
-                        span,
-                    });
-                } else {
-                    // 括弧式 ( expression )
-                    let inner_expr = self.parse_expression()?;
-                    
-                    // ')' を期待
-                    if !matches!(self.peek(), Some(Token::RightParen(_))) {
-                        return None; // エラー: 閉じ括弧がない
-                    }
-                    
-                    let end_span = self.get_current_span();
-                    self.advance(); // consume ')'
-                    
-                    // 括弧式のSpanは括弧全体を含む
-                    let span = Span {
-                        start_line: start_span.start_line,
-                        start_column: start_span.start_column,
-                        end_line: end_span.end_line,
-                        end_column: end_span.end_column,
-                        byte_start_idx: start_span.byte_start_idx,
-                        byte_end_idx: end_span.byte_end_idx,
-                    };
-                    
-                    // 括弧式は内部の式をそのまま返すが、Spanは更新
-                    // 注: 将来的にParenthesized式を追加してもよい
-                    Some(match inner_expr {
-                        Expression::IntLiteral { value, .. } => Expression::IntLiteral { value, span },
-                        Expression::FloatLiteral { value, .. } => Expression::FloatLiteral { value, span },
-                        Expression::Identifier { name, .. } => Expression::Identifier { name, span },
-                        Expression::BinaryOp { left, op, right, .. } => Expression::BinaryOp { left, op, right, span },
-                        Expression::UnaryOp { op, operand, .. } => Expression::UnaryOp { op, operand, span },
-                        Expression::Cast { target_type, operand, .. } => Expression::Cast { target_type, operand, span },
-                        other => other, // その他の式はそのまま
-                    })
+                // ')' を期待
+                if !matches!(self.peek(), Some(Token::RightParen(_))) {
+                    return None; // エラー: 閉じ括弧がない
                 }
+                
+                let end_span = self.current_span()?;
+                self.advance(); // consume ')'
+                
+                // 括弧式のSpanは括弧全体を含む
+                let span = start_span.merge(&end_span);
+                
+                // 括弧式は内部の式をそのまま返すが、Spanは更新
+                // 注: 将来的にParenthesized式を追加してもよい
+                Some(match inner_expr {
+                    Expression::IntLiteral { value, .. } => Expression::IntLiteral { value, span },
+                    Expression::FloatLiteral { value, .. } => Expression::FloatLiteral { value, span },
+                    Expression::StringLiteral { value, .. } => Expression::StringLiteral { value, span },
+                    Expression::CharLiteral { value, .. } => Expression::CharLiteral { value, span },
+                    Expression::Identifier { name, .. } => Expression::Identifier { name, span },
+                    Expression::BinaryOp { left, op, right, .. } => Expression::BinaryOp { left, op, right, span },
+                    Expression::UnaryOp { op, operand, .. } => Expression::UnaryOp { op, operand, span },
+                    Expression::Cast { target_type, operand, .. } => Expression::Cast { target_type, operand, span },
+                    other => other, // その他の式はそのまま
+                })
             }
             _ => None
         }
     }
     
-    /// 2つの式のSpanをマージして新しいSpanを作成
-    fn merge_spans(&self, left: &Expression, right: &Expression) -> Span {
-        let left_span = self.get_expression_span(left);
-        let right_span = self.get_expression_span(right);
-        
-        Span {
-            start_line: left_span.start_line,
-            start_column: left_span.start_column,
-            end_line: right_span.end_line,
-            end_column: right_span.end_column,
-            byte_start_idx: left_span.byte_start_idx,
-            byte_end_idx: right_span.byte_end_idx,
-        }
-    }
-    
-    /// 現在のトークンのSpanを取得
-    fn get_current_span(&self) -> Span {
-        match &self.current_token {
-            Some(Token::NumberLiteral(t)) => t.span.clone(),
-            Some(Token::FloatLiteral(t)) => t.span.clone(),
-            Some(Token::Ident(t)) => t.span.clone(),
-            Some(Token::Plus(t)) => t.span.clone(),
-            Some(Token::Minus(t)) => t.span.clone(),
-            Some(Token::Asterisk(t)) => t.span.clone(),
-            Some(Token::Slash(t)) => t.span.clone(),
-            Some(Token::Percent(t)) => t.span.clone(),
-            Some(Token::EqualEqual(t)) => t.span.clone(),
-            Some(Token::NotEqual(t)) => t.span.clone(),
-            Some(Token::LessThan(t)) => t.span.clone(),
-            Some(Token::LessThanOrEqual(t)) => t.span.clone(),
-            Some(Token::GreaterThan(t)) => t.span.clone(),
-            Some(Token::GreaterThanOrEqual(t)) => t.span.clone(),
-            Some(Token::Ampersand(t)) => t.span.clone(),
-            Some(Token::AmpersandAmpersand(t)) => t.span.clone(),
-            Some(Token::Pipe(t)) => t.span.clone(),
-            Some(Token::PipePipe(t)) => t.span.clone(),
-            Some(Token::Caret(t)) => t.span.clone(),
-            Some(Token::Tilde(t)) => t.span.clone(),
-            Some(Token::Exclamation(t)) => t.span.clone(),
-            Some(Token::LeftShift(t)) => t.span.clone(),
-            Some(Token::RightShift(t)) => t.span.clone(),
-            Some(Token::LeftParen(t)) => t.span.clone(),
-            Some(Token::RightParen(t)) => t.span.clone(),
-            Some(Token::PlusPlus(t)) => t.span.clone(),
-            Some(Token::MinusMinus(t)) => t.span.clone(),
-            _ => Span {
-                start_line: 0,
-                start_column: 0,
-                end_line: 0,
-                end_column: 0,
-                byte_start_idx: 0,
-                byte_end_idx: 0,
+    /// `( 型名 ) 単項式` のキャスト式を読む（型名として読めないトークンがあれば失敗）
+    fn parse_cast(&mut self, start_span: Span) -> Option<Expression> {
+        self.advance(); // consume '('
+
+        // 型名は型キーワードかtypedef名で始まる
+        let mut type_tokens = Vec::new();
+        loop {
+            let token = self.current_token.clone()?;
+            let is_type_token = match &token {
+                Token::RightParen(_) => break,
+                Token::Void(_) | Token::Char(_) | Token::Short(_) | Token::Int(_) | Token::Long(_)
+                | Token::Float(_) | Token::Double(_) | Token::Signed(_) | Token::Unsigned(_)
+                | Token::Bool(_) | Token::Complex(_) | Token::Imaginary(_)
+                | Token::Struct(_) | Token::Union(_) | Token::Enum(_)
+                | Token::Const(_) | Token::Volatile(_) | Token::Restrict(_) | Token::Atomic(_) => true,
+                // `*` は型名の途中（ポインタ）にだけ現れる
+                Token::Asterisk(_) => !type_tokens.is_empty(),
+                // typedef名か struct / union / enum のタグ名
+                Token::Ident(IdentToken { name, .. }) => {
+                    self.is_type_name(name)
+                        || matches!(type_tokens.last(), Some(Token::Struct(_) | Token::Union(_) | Token::Enum(_)))
+                },
+                _ => false,
+            };
+            if !is_type_token {
+                return None;
             }
+            type_tokens.push(token);
+            self.advance();
+        }
+        if type_tokens.is_empty() {
+            return None;
         }
+        self.advance(); // ')' をスキップ
+
+        // 型名をパース
+        let target_type = self.parse_type_from_tokens(&type_tokens);
+
+        // キャスト対象の式をパース（unaryレベルから再開）
+        let operand = self.parse_unary()?;
+        let operand_span = self.get_expression_span(&operand);
+
+        let span = start_span.merge(&operand_span);
+
+        Some(Expression::Cast {
+            target_type,
+            operand: Box::new(operand),
+            span,
+        })
+    }
+
+    /// 2つの式のSpanをマージして新しいSpanを作成
+    fn merge_spans(&self, left: &Expression, right: &Expression) -> Span {
+        self.get_expression_span(left).merge(&self.get_expression_span(right))
     }
     
-    /// トークンからSpanを取得するヘルパー
-    fn get_current_span_from_token(&self, token: &Token) -> Span {
-        match token {
-            Token::NumberLiteral(t) => t.span.clone(),
-            Token::FloatLiteral(t) => t.span.clone(),
-            Token::Ident(t) => t.span.clone(),
-            Token::LeftParen(t) => t.span.clone(),
-            Token::RightParen(t) => t.span.clone(),
-            _ => Span {
-                start_line: 0,
-                start_column: 0,
-                end_line: 0,
-                end_column: 0,
-                byte_start_idx: 0,
-                byte_end_idx: 0,
-            }
-        }
+    /// 現在のトークンのSpanを取得（入力の終わりなら None）
+    fn current_span(&self) -> Option<Span> {
+        self.current_token.as_ref().map(|token| token.span().clone())
     }
     
     /// 式からSpanを取得
@@ -659,6 +646,8 @@ impl<'a> ExpressionParser<'a> {
         match expr {
             Expression::IntLiteral { span, .. } => span.clone(),
             Expression::FloatLiteral { span, .. } => span.clone(),
+            Expression::StringLiteral { span, .. } => span.clone(),
+            Expression::CharLiteral { span, .. } => span.clone(),
             Expression::Identifier { span, .. } => span.clone(),
             Expression::BinaryOp { span, .. } => span.clone(),
             Expression::UnaryOp { span, .. } => span.clone(),
@@ -690,6 +679,9 @@ impl<'a> ExpressionParser<'a> {
                 Token::Double(_) => type_text.push_str("double "),
                 Token::Signed(_) => type_text.push_str("signed "),
                 Token::Unsigned(_) => type_text.push_str("unsigned "),
+                Token::Bool(_) => type_text.push_str("_Bool "),
+                Token::Complex(_) => type_text.push_str("_Complex "),
+                Token::Imaginary(_) => type_text.push_str("_Imaginary "),
                 Token::Const(_) => type_text.push_str("const "),
                 Token::Volatile(_) => type_text.push_str("volatile "),
                 Token::Restrict(_) => type_text.push_str("restrict "),
@@ -698,7 +690,7 @@ impl<'a> ExpressionParser<'a> {
                 Token::Ident(IdentToken { name, .. }) => {
                     // typedef名の可能性
                     has_ident = true;
-                    ident_name = name.clone();
+                    ident_name = name.to_string();
                 },
                 _ => {},
             }
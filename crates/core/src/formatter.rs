@@ -1,4 +1,12 @@
-use crate::ast::{TranslationUnit, Item};
+use crate::ast::{TranslationUnit, Item, Initializer, Statement};
+use crate::config::{FormattingConfig, PointerStyle};
+use crate::type_system::{PointerLayer, Type, TypeQualifier};
+use crate::span::Span;
+use regex::Regex;
+use std::sync::LazyLock;
+use crate::expression::{BinaryOperator, Expression, UnaryOperator};
+use crate::expression_parser::ExpressionParser;
+use crate::lexer::Lexer;
 
 const FILE_HEADER_TEMPLATE: &str = "/*****************************/
 /* Author:                   */
@@ -11,6 +19,12 @@ pub struct Formatter {
     pub add_header: bool,  // ヘッダーコメントを追加するかどうか
     pub use_type_info: bool,  // 型情報を使用して変数宣言をフォーマットするかどうか
     pub use_tabs: bool,  // 4スペースをタブに変換するかどうか
+    pub align_struct_members: bool,  // 構造体メンバーの名前の開始位置を揃えるかどうか
+    pub pointer_style: Option<PointerStyle>,  // ポインタの `*` の位置（None の場合は元のまま）
+    pub sort_includes: bool,  // 連続する #include をファイル名順に並べ替えるかどうか
+    pub group_includes: bool,  // 並べ替え時に <...> を "..." より前にまとめ、空行で区切るかどうか
+    pub space_binary_operators: bool,  // 関数本体の文と初期化子を式の構文木から書き直し、二項演算子の前後に空白を入れるかどうか
+    pub blank_lines_between_items: Option<usize>,  // トップレベルのアイテム間の空行の最大数（None の場合は元のまま）
 }
 
 impl Formatter {
@@ -19,6 +33,12 @@ impl Formatter {
             add_header: true,  // デフォルトはtrue
             use_type_info: false,  // デフォルトは既存のテキストを使用
             use_tabs: false,  // デフォルトはスペースのまま
+            align_struct_members: false,
+            pointer_style: None,
+            sort_includes: false,
+            group_includes: false,
+            space_binary_operators: false,
+            blank_lines_between_items: None,
         }
     }
     
@@ -27,6 +47,12 @@ impl Formatter {
             add_header: false,
             use_type_info: false,
             use_tabs: false,
+            align_struct_members: false,
+            pointer_style: None,
+            sort_includes: false,
+            group_includes: false,
+            space_binary_operators: false,
+            blank_lines_between_items: None,
         }
     }
     
@@ -35,6 +61,12 @@ impl Formatter {
             add_header: true,
             use_type_info: true,
             use_tabs: false,
+            align_struct_members: false,
+            pointer_style: None,
+            sort_includes: false,
+            group_includes: false,
+            space_binary_operators: false,
+            blank_lines_between_items: None,
         }
     }
     
@@ -43,6 +75,12 @@ impl Formatter {
             add_header,
             use_type_info,
             use_tabs: false,
+            align_struct_members: false,
+            pointer_style: None,
+            sort_includes: false,
+            group_includes: false,
+            space_binary_operators: false,
+            blank_lines_between_items: None,
         }
     }
     
@@ -51,6 +89,27 @@ impl Formatter {
             add_header,
             use_type_info,
             use_tabs,
+            align_struct_members: false,
+            pointer_style: None,
+            sort_includes: false,
+            group_includes: false,
+            space_binary_operators: false,
+            blank_lines_between_items: None,
+        }
+    }
+
+    /// 設定ファイルの [formatting] からフォーマッタを作成する
+    pub fn new_with_config(config: &FormattingConfig) -> Self {
+        Formatter {
+            add_header: config.add_file_header,
+            use_type_info: false,
+            use_tabs: config.use_tabs,
+            align_struct_members: config.align_struct_members,
+            pointer_style: config.pointer_style.clone(),
+            sort_includes: config.sort_includes,
+            group_includes: config.group_includes,
+            space_binary_operators: config.space_binary_operators,
+            blank_lines_between_items: config.blank_lines_between_items,
         }
     }
 
@@ -68,271 +127,362 @@ impl Formatter {
         // ファイル先頭のコメント（leading_trivia）を出力
         self.format_trivia(&tu.leading_trivia, &mut s);
         
-        for item in &tu.items {
-            // アイテム前のコメント（leading trivia）を出力
+        s.push_str(&self.format_items(&tu.items));
+
+        // ファイル先頭の空行を取り除く
+        if self.blank_lines_between_items.is_some() {
+            let content_start = s.find(|c: char| !c.is_whitespace()).unwrap_or(s.len());
+            let line_start = s[..content_start].rfind('\n').map_or(0, |newline| newline + 1);
+            s.drain(..line_start);
+        }
+        s
+    }
+
+    /// アイテムの並びをフォーマットする
+    /// sort_includes が有効な場合、連続する #include をまとめて並べ替える
+    fn format_items(&self, items: &[Item]) -> String {
+        let mut s = String::new();
+        let mut index = 0;
+        while index < items.len() {
+            if !self.sort_includes || !matches!(items[index], Item::Include { .. }) {
+                self.push_item_output(&mut s, items, index, &self.format_item(&items[index]));
+                index += 1;
+                continue;
+            }
+
+            // グループ分けしない場合は、空行で区切られた #include のまとまりごとに並べ替える
+            let run_end = items[index + 1..]
+                .iter()
+                .position(|item| match item {
+                    Item::Include { text, .. } => !self.group_includes && !split_leading_whitespace(text).0.is_empty(),
+                    _ => true,
+                })
+                .map_or(items.len(), |offset| index + 1 + offset);
+            self.push_item_output(&mut s, items, index, &self.format_include_run(&items[index..run_end]));
+            index = run_end;
+        }
+        s
+    }
+
+    /// items[index] から始まるアイテムの出力を追加する
+    ///
+    /// blank_lines_between_items が指定されている場合は、直前のアイテムとの間の空行を元の空行数（最大で指定値）にそろえる。
+    /// 関数定義の前後は元の空行数にかかわらず指定値の空行を入れる
+    fn push_item_output(&self, s: &mut String, items: &[Item], index: usize, output: &str) {
+        // 前の行の行末コメントから始まる出力は、同じ行の続きとしてそのまま追加する
+        let continues_line = !s.ends_with('\n') && !output.trim_start_matches([' ', '\t']).starts_with(['\r', '\n']);
+        let Some(max_blank_lines) = self.blank_lines_between_items.filter(|_| index > 0 && !continues_line) else {
+            s.push_str(output);
+            return;
+        };
+        let (previous, current) = (&items[index - 1], &items[index]);
+        let blank_lines = if is_function_definition(previous) || is_function_definition(current) {
+            max_blank_lines
+        } else {
+            // 前のアイテムの末尾と次のアイテムの先頭のテキストは同じ改行を含むことがあるため、位置で重複を除いて数える
+            let mut newlines = trailing_newline_positions(previous);
+            newlines.extend(self.leading_newline_positions(current));
+            newlines.sort_unstable();
+            newlines.dedup();
+            newlines.len().saturating_sub(1).min(max_blank_lines)
+        };
+
+        s.truncate(s.trim_end().len());
+        s.push('\n');
+        s.push_str(&"\n".repeat(blank_lines));
+        s.push_str(output.trim_start());
+    }
+
+    /// アイテムの前の空白（前置コメントの前を含む）にある改行のバイト位置
+    fn leading_newline_positions(&self, item: &Item) -> Vec<usize> {
+        use crate::trivia::Comment;
+
+        let (start, text) = match (self.get_item_trivia(item).and_then(|trivia| trivia.leading.first()), item_text(item)) {
+            (Some(Comment::Line { text, span } | Comment::Block { text, span }), _) => (span.byte_start_idx, text.as_str()),
+            (None, Some((span, text))) => (span.byte_start_idx, text),
+            // #if の span は前の空白を含むがテキストを持たないため、行番号の差を改行の数とみなす
+            (None, None) => {
+                let Item::ConditionalBlock { start_span, .. } = item else {
+                    return Vec::new();
+                };
+                let count = span_last_line(start_span).saturating_sub(start_span.start_line);
+                return (start_span.byte_start_idx..start_span.byte_start_idx + count).collect();
+            }
+        };
+        text.char_indices()
+            .take_while(|&(_, ch)| ch.is_whitespace())
+            .filter(|&(_, ch)| ch == '\n')
+            .map(|(i, _)| start + i)
+            .collect()
+    }
+
+    /// 連続する #include をファイル名順に並べ替えて出力する
+    /// 先頭の空行は並びの前に残し、各 #include の前のコメントは一緒に移動する
+    fn format_include_run(&self, run: &[Item]) -> String {
+        let mut includes: Vec<(bool, &str, String)> = run.iter().filter_map(|item| {
+            let Item::Include { text, filename, .. } = item else {
+                return None;
+            };
+            let mut entry = String::new();
             if let Some(trivia) = self.get_item_trivia(item) {
-                self.format_trivia(trivia, &mut s);
+                self.format_trivia(trivia, &mut entry);
             }
-            
-            match item {
-                Item::Include { text, ..} => {
-                    // 先頭の空白系文字列を見つける（スペース/タブ/CR/LF を含む）
-                    let first_non_ws = text
-                        .char_indices()
-                        .find(|&(_, ch)| !ch.is_whitespace())
-                        .map(|(i, _)| i)
-                        .unwrap_or(text.len());
-
-                    // 先頭の空白部分から改行だけ取り出して保持する
-                    let leading = &text[..first_non_ws];
-                    let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-
-                    // 改行を先頭に残し、それ以外の先頭空白は削除して残りを追加
-                    s.push_str(&kept_newlines);
-                    s.push_str(&text[first_non_ws..]);       
-                },
-                Item::Define { text, ..} => {
-                    // 先頭の空白系文字列を見つける（スペース/タブ/CR/LF を含む）
-                    let first_non_ws = text
-                        .char_indices()
-                        .find(|&(_, ch)| !ch.is_whitespace())
-                        .map(|(i, _)| i)
-                        .unwrap_or(text.len());
-
-                    // 先頭の空白部分から改行だけ取り出して保持する
-                    let leading = &text[..first_non_ws];
-                    let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-
-                    // 改行を先頭に残し、それ以外の先頭空白は削除して残りを追加
-                    s.push_str(&kept_newlines);
-                    s.push_str(&text[first_non_ws..]);      
-                },
-                // Stage 1: 条件コンパイルブロック
-                Item::ConditionalBlock { directive_type, condition, items, .. } => {
-                    // #ifdef CONDITION
-                    s.push('#');
-                    s.push_str(directive_type);
-                    if !condition.is_empty() {
-                        s.push(' ');
-                        s.push_str(condition);
-                    }
+            let directive = text.trim();
+            let is_system = directive.trim_start_matches('#').trim_start().trim_start_matches("include").trim_start().starts_with('<');
+            entry.push_str(directive);
+            Some((is_system, filename.as_str(), entry))
+        }).collect();
+
+        if self.group_includes {
+            includes.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        } else {
+            includes.sort_by(|a, b| a.1.cmp(b.1));
+        }
+
+        let mut s = String::new();
+        if let Some(Item::Include { text, .. }) = run.first() {
+            s.push_str(&split_leading_whitespace(text).0);
+        }
+        for (i, (is_system, _, entry)) in includes.iter().enumerate() {
+            if i > 0 {
+                s.push('\n');
+                // <...> から "..." に切り替わる位置に空行を入れる
+                if self.group_includes && includes[i - 1].0 && !is_system {
                     s.push('\n');
-                    
-                    // ブロック内のアイテムを再帰的にフォーマット
-                    for inner_item in items {
-                        s.push_str(&self.format_item(inner_item));
-                    }
-                    
-                    // #endif
-                    s.push_str("#endif\n");
-                },
-                Item::TypedefDecl { text, .. } => {
-                    // 先頭の空白系文字列を見つける（スペース/タブ/CR/LF を含む）
-                    let first_non_ws = text
-                        .char_indices()
-                        .find(|&(_, ch)| !ch.is_whitespace())
-                        .map(|(i, _)| i)
-                        .unwrap_or(text.len());
-
-                    // 先頭の空白部分から改行だけ取り出して保持する
-                    let leading = &text[..first_non_ws];
-                    let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-
-                    // 改行を先頭に残し、それ以外の先頭空白は削除して残りを追加
-                    s.push_str(&kept_newlines);
-                    s.push_str(&text[first_non_ws..]);
-                },
-                Item::VarDecl { text, var_name, var_type, has_initializer, .. } => {
-                    if self.use_type_info && var_type.is_some() {
-                        // 型情報を使用してフォーマット
-                        let ty = var_type.as_ref().unwrap();
-                        let type_str = ty.to_string();
-                        
-                        // 先頭の空白部分から改行だけ取り出して保持する
-                        let first_non_ws = text
-                            .char_indices()
-                            .find(|&(_, ch)| !ch.is_whitespace())
-                            .map(|(i, _)| i)
-                            .unwrap_or(text.len());
-                        let leading = &text[..first_non_ws];
-                        let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-                        
-                        s.push_str(&kept_newlines);
-                        s.push_str(&type_str);
-                        s.push_str(" ");
-                        s.push_str(var_name);
-                        
-                        // 初期化子がある場合は元のテキストから抽出
-                        if *has_initializer {
-                            // 元のテキストから'='を見つけて、それ以降を含める
-                            if let Some(eq_pos) = text.find('=') {
-                                s.push_str(" ");
-                                s.push_str(&text[eq_pos..].trim_end());
-                            } else {
-                                s.push_str(";");
-                            }
-                        } else {
-                            s.push_str(";");
-                        }
-                        
-                        // 改行が元のテキストの最後になければ追加
-                        if !text.ends_with('\n') && !text.ends_with("\r\n") {
-                            // 何もしない（改行なし）
-                        } else {
-                            s.push('\n');
-                        }
-                    } else {
-                        // 既存の実装: テキストをそのまま使用
-                        let first_non_ws = text
-                            .char_indices()
-                            .find(|&(_, ch)| !ch.is_whitespace())
-                            .map(|(i, _)| i)
-                            .unwrap_or(text.len());
-
-                        let leading = &text[..first_non_ws];
-                        let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-
-                        s.push_str(&kept_newlines);
-                        s.push_str(&text[first_non_ws..]);
-                    }
-                },
-                Item::StructDecl { text, .. } => {
-                    // 先頭の空白系文字列を見つける（スペース/タブ/CR/LF を含む）
-                    let first_non_ws = text
-                        .char_indices()
-                        .find(|&(_, ch)| !ch.is_whitespace())
-                        .map(|(i, _)| i)
-                        .unwrap_or(text.len());
-
-                    // 先頭の空白部分から改行だけ取り出して保持する
-                    let leading = &text[..first_non_ws];
-                    let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-
-                    // 改行を先頭に残し、それ以外の先頭空白は削除して残りを追加
-                    s.push_str(&kept_newlines);
-                    s.push_str(&text[first_non_ws..]);
-                },
-                Item::FunctionDecl { text, .. } => {
-                    // 先頭の空白系文字列を見つける（スペース/タブ/CR/LF を含む）
-                    let first_non_ws = text
-                        .char_indices()
-                        .find(|&(_, ch)| !ch.is_whitespace())
-                        .map(|(i, _)| i)
-                        .unwrap_or(text.len());
-
-                    // 先頭の空白部分から改行だけ取り出して保持する
-                    let leading = &text[..first_non_ws];
-                    let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-
-                    // インデント変換を適用して出力
-                    let content = self.format_item_text(&text[first_non_ws..]);
-                    s.push_str(&kept_newlines);
-                    s.push_str(&content);
-                },
-                Item::EnumDecl { text, .. } => {
-                    // 先頭の空白系文字列を見つける（スペース/タブ/CR/LF を含む）
-                    let first_non_ws = text
-                        .char_indices()
-                        .find(|&(_, ch)| !ch.is_whitespace())
-                        .map(|(i, _)| i)
-                        .unwrap_or(text.len());
-
-                    // 先頭の空白部分から改行だけ取り出して保持する
-                    let leading = &text[..first_non_ws];
-                    let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-
-                    // 改行を先頭に残し、それ以外の先頭空白は削除して残りを追加
-                    s.push_str(&kept_newlines);
-                    s.push_str(&text[first_non_ws..]);
-                },
-                Item::UnionDecl { text, .. } => {
-                    // 先頭の空白系文字列を見つける（スペース/タブ/CR/LF を含む）
-                    let first_non_ws = text
-                        .char_indices()
-                        .find(|&(_, ch)| !ch.is_whitespace())
-                        .map(|(i, _)| i)
-                        .unwrap_or(text.len());
-
-                    // 先頭の空白部分から改行だけ取り出して保持する
-                    let leading = &text[..first_non_ws];
-                    let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-
-                    // 改行を先頭に残し、それ以外の先頭空白は削除して残りを追加
-                    s.push_str(&kept_newlines);
-                    s.push_str(&text[first_non_ws..]);
                 }
             }
+            s.push_str(entry);
+        }
+        if matches!(run.last(), Some(Item::Include { text, .. }) if text.ends_with('\n')) {
+            s.push('\n');
         }
         s
     }
 
-    // 個別のアイテムをフォーマット（再帰用）
-    fn format_item(&self, item: &Item) -> String {
+    /// 1つのアイテム（宣言・ディレクティブ）をフォーマットする
+    /// アイテム前のコメント（leading trivia）も含めて出力する
+    pub fn format_item(&self, item: &Item) -> String {
         let mut s = String::new();
+        let respaced;
+        let respacing = Respacing { operators: self.space_binary_operators, pointer_style: self.pointer_style.as_ref() };
+        let item = match respace_item(item, respacing) {
+            Some(new_item) => {
+                respaced = new_item;
+                &respaced
+            }
+            None => item,
+        };
         
         // アイテム前のコメント（leading trivia）を出力
-        if let Some(trivia) = self.get_item_trivia(item) {
+        let trivia = self.get_item_trivia(item);
+        if let Some(trivia) = trivia {
             self.format_trivia(trivia, &mut s);
         }
+        let trivia_end = s.len();
         
         match item {
-            Item::Include { text, ..} => {
-                let first_non_ws = text
-                    .char_indices()
-                    .find(|&(_, ch)| !ch.is_whitespace())
-                    .map(|(i, _)| i)
-                    .unwrap_or(text.len());
-                let leading = &text[..first_non_ws];
-                let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
-                s.push_str(&kept_newlines);
-                s.push_str(&text[first_non_ws..]);       
+            // 条件コンパイルブロック
+            Item::ConditionalBlock { .. } => {
+                s.push_str(&self.format_conditional_block(item));
             },
-            Item::Define { text, .. } => {
-                let first_non_ws = text
-                    .char_indices()
-                    .find(|&(_, ch)| !ch.is_whitespace())
-                    .map(|(i, _)| i)
-                    .unwrap_or(text.len());
-                let leading = &text[..first_non_ws];
-                let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
+            // 型情報から組み立て直すと属性が失われるため、属性のある宣言は元のテキストのまま出力する
+            Item::VarDecl { text, var_name, var_type: Some(ty), has_initializer, attributes, .. } if self.use_type_info && attributes.is_empty() => {
+                // 型情報を使用してフォーマット
+                let (kept_newlines, _) = split_leading_whitespace(text);
                 s.push_str(&kept_newlines);
-                s.push_str(&text[first_non_ws..]);
-            },
-            Item::ConditionalBlock { directive_type, condition, items, .. } => {
-                s.push('#');
-                s.push_str(directive_type);
-                if !condition.is_empty() {
-                    s.push(' ');
-                    s.push_str(condition);
+                // 配列の次元は型ではなく変数名の後ろに書く（`int table[3]`）
+                let dimensions: String = ty.array_dimensions.iter().map(|d| d.to_string()).collect();
+                let ty = Type { array_dimensions: Vec::new(), ..ty.clone() };
+                match &self.pointer_style {
+                    Some(style) if !ty.pointer_layers.is_empty() => {
+                        let base = Type { pointer_layers: Vec::new(), ..ty.clone() }.to_string();
+                        s.push_str(&format_pointer_declarator(&base, &ty.pointer_layers, " ", var_name, style));
+                    }
+                    _ => {
+                        s.push_str(&ty.to_string());
+                        s.push(' ');
+                        s.push_str(var_name);
+                    }
                 }
-                s.push('\n');
-                for inner_item in items {
-                    s.push_str(&self.format_item(inner_item));
+                s.push_str(&dimensions);
+                
+                // 初期化子がある場合は元のテキストから抽出
+                match text.find('=').filter(|_| *has_initializer) {
+                    Some(eq_pos) => {
+                        s.push(' ');
+                        s.push_str(text[eq_pos..].trim_end());
+                    }
+                    None => s.push(';'),
+                }
+                
+                // 元のテキストが改行で終わっていれば改行を追加
+                if text.ends_with('\n') {
+                    s.push('\n');
                 }
-                s.push_str("#endif\n");
             },
-            Item::TypedefDecl { text, .. } | Item::VarDecl { text, .. } | 
-            Item::StructDecl { text, .. } | Item::FunctionDecl { text, .. } | 
-            Item::EnumDecl { text, .. } | Item::UnionDecl { text, .. } => {
-                let first_non_ws = text
-                    .char_indices()
-                    .find(|&(_, ch)| !ch.is_whitespace())
-                    .map(|(i, _)| i)
-                    .unwrap_or(text.len());
-                let leading = &text[..first_non_ws];
-                let kept_newlines: String = leading.chars().filter(|&c| c == '\n').collect();
+            Item::StructDecl { text, .. } if self.align_struct_members => {
+                let (kept_newlines, content) = split_leading_whitespace(text);
+                s.push_str(&kept_newlines);
+                s.push_str(&align_struct_members(&self.normalize_pointers(content), self.pointer_style.as_ref()));
+            },
+            Item::StructDecl { text, .. } | Item::UnionDecl { text, .. } if self.pointer_style.is_some() => {
+                // メンバー宣言の `*` の位置を揃える
+                let (kept_newlines, content) = split_leading_whitespace(text);
                 s.push_str(&kept_newlines);
-                s.push_str(&text[first_non_ws..]);
+                s.push_str(&self.normalize_pointers(content));
+            },
+            Item::VarDecl { text, .. } if self.pointer_style.is_some() => {
+                // 初期化子より前（宣言子の部分）だけを対象に `*` の位置を揃える
+                let (kept_newlines, content) = split_leading_whitespace(text);
+                let declarator_end = content.find('=').unwrap_or(content.len());
+                s.push_str(&kept_newlines);
+                s.push_str(&self.normalize_pointers(&content[..declarator_end]));
+                s.push_str(&content[declarator_end..]);
+            },
+            Item::FunctionDecl { text, .. } => {
+                // 戻り値の型と引数（本体の `{` より前）の `*` の位置を揃え、インデント変換を適用して出力
+                let (kept_newlines, content) = split_leading_whitespace(text);
+                let header_end = content.find('{').unwrap_or(content.len());
+                let content = format!("{}{}", self.normalize_pointers(&content[..header_end]), &content[header_end..]);
+                s.push_str(&kept_newlines);
+                s.push_str(&self.format_item_text(&content));
+            },
+            Item::Include { text, .. } | Item::Define { text, .. } | Item::LineDirective { text, .. } |
+            Item::ErrorDirective { text, .. } | Item::WarningDirective { text, .. } |
+            Item::TypedefDecl { text, .. } | Item::VarDecl { text, .. } |
+            Item::StructDecl { text, .. } | Item::EnumDecl { text, .. } | Item::UnionDecl { text, .. } => {
+                // 改行を先頭に残し、それ以外の先頭空白は削除して残りを追加
+                let (kept_newlines, content) = split_leading_whitespace(text);
+                s.push_str(&kept_newlines);
+                s.push_str(content);
             },
         }
-        
-        // インデント変換を適用
-        if self.use_tabs {
-            s = self.convert_indents_to_tabs(&s);
+
+        // 行コメントの後に補った改行と、テキスト先頭の同じ行末の改行を二重に出力しない
+        let ends_with_line_comment = trivia
+            .and_then(|trivia| trivia.leading.last())
+            .is_some_and(|comment| matches!(comment, crate::trivia::Comment::Line { .. }));
+        if ends_with_line_comment && s[trivia_end..].starts_with('\n') {
+            s.remove(trivia_end);
         }
         
         s
     }
+
+    /// 1つの文をフォーマットする（インデントなしの位置から出力し、末尾に改行を付ける）
+    pub fn format_statement(&self, stmt: &Statement) -> String {
+        let mut s = String::new();
+        self.write_statement(stmt, 0, &mut s);
+        s
+    }
+
+    /// 文をインデントの深さ `depth` で出力する
+    fn write_statement(&self, stmt: &Statement, depth: usize, out: &mut String) {
+        out.push_str(&self.indent_unit().repeat(depth));
+        self.write_statement_body(stmt, depth, out);
+    }
+
+    /// 行頭のインデントを除いた文の本体を出力する
+    fn write_statement_body(&self, stmt: &Statement, depth: usize, out: &mut String) {
+        match stmt {
+            Statement::VarDecl { var_type, var_name, initializer, .. } => {
+                if let Some(ty) = var_type {
+                    out.push_str(&ty.to_string());
+                    out.push(' ');
+                }
+                out.push_str(var_name);
+                if let Some(init) = initializer {
+                    out.push_str(" = ");
+                    out.push_str(&format_expression(init));
+                }
+                out.push_str(";\n");
+            },
+            Statement::Expression { expr, .. } => {
+                out.push_str(&format_expression(expr));
+                out.push_str(";\n");
+            },
+            Statement::Return { value, .. } => {
+                out.push_str("return");
+                if let Some(value) = value {
+                    out.push(' ');
+                    out.push_str(&format_expression(value));
+                }
+                out.push_str(";\n");
+            },
+            Statement::If { condition, then_block, else_block, .. } => {
+                out.push_str(&format!("if ({}) ", format_expression(condition)));
+                self.write_block(then_block, depth, out);
+                if let Some(else_block) = else_block {
+                    out.pop();
+                    out.push_str(" else ");
+                    // else if はブロックで囲まずに続ける
+                    if let [nested @ Statement::If { .. }] = else_block.as_slice() {
+                        self.write_statement_body(nested, depth, out);
+                    } else {
+                        self.write_block(else_block, depth, out);
+                    }
+                }
+            },
+            Statement::While { condition, body, .. } => {
+                out.push_str(&format!("while ({}) ", format_expression(condition)));
+                self.write_block(body, depth, out);
+            },
+            Statement::DoWhile { body, condition, .. } => {
+                out.push_str("do ");
+                self.write_block(body, depth, out);
+                out.pop();
+                out.push_str(&format!(" while ({});\n", format_expression(condition)));
+            },
+            Statement::For { init, condition, update, body, .. } => {
+                out.push_str("for (");
+                if let Some(init) = init {
+                    // 初期化部の文は末尾の ";\n" を取り除いて埋め込む
+                    out.push_str(self.format_statement(init).trim_end().trim_end_matches(';'));
+                }
+                out.push(';');
+                if let Some(condition) = condition {
+                    out.push(' ');
+                    out.push_str(&format_expression(condition));
+                }
+                out.push(';');
+                if let Some(update) = update {
+                    out.push(' ');
+                    out.push_str(&format_expression(update));
+                }
+                out.push_str(") ");
+                self.write_block(body, depth, out);
+            },
+            Statement::Block { statements, .. } => {
+                self.write_block(statements, depth, out);
+            },
+            Statement::Empty { .. } => {
+                out.push_str(";\n");
+            },
+        }
+    }
+
+    /// `{` から `}` までのブロックを出力する（`{` は現在の行に続けて書く）
+    fn write_block(&self, statements: &[Statement], depth: usize, out: &mut String) {
+        out.push_str("{\n");
+        for stmt in statements {
+            self.write_statement(stmt, depth + 1, out);
+        }
+        out.push_str(&self.indent_unit().repeat(depth));
+        out.push_str("}\n");
+    }
+
+    /// 宣言のテキスト中の `型 *名前` の並びを pointer_style に合わせて書き換える
+    fn normalize_pointers(&self, code: &str) -> String {
+        match &self.pointer_style {
+            Some(style) => normalize_pointer_declarators(code, style),
+            None => code.to_string(),
+        }
+    }
+
+    /// 1段分のインデント
+    fn indent_unit(&self) -> &'static str {
+        if self.use_tabs { "\t" } else { "    " }
+    }
     
     /// 4スペースをタブに変換し、ブロック内の字下げを処理
     fn convert_indents_to_tabs(&self, text: &str) -> String {
@@ -395,17 +545,20 @@ impl Formatter {
     // 個別のアイテムから元のコードを再構築
     fn original_item(&self, item: &Item) -> String {
         match item {
-            Item::ConditionalBlock { items, start_span: _, end_span: _, .. } => {
+            Item::ConditionalBlock { .. } => {
                 let mut s = String::new();
                 // start_spanから#ifdefディレクティブを取得
                 // TODO: lexer.inputへのアクセスが必要（現在は不可能）
-                // 暫定: 再構築は諦めて、内部のitemsだけ返す
-                for inner_item in items {
-                    s.push_str(&self.original_item(inner_item));
+                // 暫定: 再構築は諦めて、全ブランチの内部のitemsだけ返す
+                for branch in item.branches() {
+                    for inner_item in branch {
+                        s.push_str(&self.original_item(inner_item));
+                    }
                 }
                 s
             },
-            Item::Include { text, .. } | Item::Define { text, .. } | 
+            Item::Include { text, .. } | Item::Define { text, .. } | Item::LineDirective { text, .. } |
+            Item::ErrorDirective { text, .. } | Item::WarningDirective { text, .. } |
             Item::TypedefDecl { text, .. } | Item::VarDecl { text, .. } | 
             Item::StructDecl { text, .. } | Item::FunctionDecl { text, .. } | 
             Item::EnumDecl { text, .. } | Item::UnionDecl { text, .. } => {
@@ -457,11 +610,48 @@ impl Formatter {
         }
     }
 
+    /// 条件コンパイルブロックを #if / #elif / #else / #endif の順に出力
+    fn format_conditional_block(&self, item: &Item) -> String {
+        let mut s = String::new();
+        let Item::ConditionalBlock { directive_type, condition, items, elif_branches, else_branch, .. } = item else {
+            return s;
+        };
+
+        let mut push_branch = |directive: &str, condition: &str, items: &[Item]| {
+            // #ifdef CONDITION
+            s.push('#');
+            s.push_str(directive);
+            if !condition.is_empty() {
+                s.push(' ');
+                s.push_str(condition);
+            }
+            s.push('\n');
+
+            // ブロック内のアイテムを再帰的にフォーマット（#include の並べ替えは分岐ごとに行う）
+            s.push_str(&self.format_items(items));
+        };
+
+        push_branch(directive_type, condition, items);
+        for branch in elif_branches {
+            push_branch("elif", &branch.condition, &branch.items);
+        }
+        if let Some(else_items) = else_branch {
+            push_branch("else", "", else_items);
+        }
+
+        // #endif
+        s.push_str("#endif\n");
+        s
+    }
+
     /// Itemからtriviaを取得
     fn get_item_trivia<'a>(&self, item: &'a Item) -> Option<&'a crate::trivia::Trivia> {
         match item {
             Item::Include { trivia, .. } |
             Item::Define { trivia, .. } |
+            Item::LineDirective { trivia, .. } |
+            Item::ErrorDirective { trivia, .. } |
+            Item::WarningDirective { trivia, .. } |
             Item::ConditionalBlock { trivia, .. } |
             Item::TypedefDecl { trivia, .. } |
             Item::VarDecl { trivia, .. } |
@@ -473,4 +663,663 @@ impl Formatter {
     }
 }
 
+/// 関数定義（本体のある関数宣言）かどうか
+fn is_function_definition(item: &Item) -> bool {
+    matches!(item, Item::FunctionDecl { body: Some(_), .. })
+}
+
+/// アイテムの span とテキスト（テキストを持たない条件コンパイルブロックは None）
+fn item_text(item: &Item) -> Option<(&Span, &str)> {
+    match item {
+        Item::Include { span, text, .. }
+        | Item::Define { span, text, .. }
+        | Item::LineDirective { span, text, .. }
+        | Item::ErrorDirective { span, text, .. }
+        | Item::WarningDirective { span, text, .. }
+        | Item::TypedefDecl { span, text, .. }
+        | Item::VarDecl { span, text, .. }
+        | Item::StructDecl { span, text, .. }
+        | Item::EnumDecl { span, text, .. }
+        | Item::UnionDecl { span, text, .. }
+        | Item::FunctionDecl { span, text, .. } => Some((span, text)),
+        Item::ConditionalBlock { .. } => None,
+    }
+}
 
+/// アイテムの末尾の空白にある改行のバイト位置（条件コンパイルブロックは #endif の行末）
+fn trailing_newline_positions(item: &Item) -> Vec<usize> {
+    match (item, item_text(item)) {
+        (Item::ConditionalBlock { end_span, .. }, _) if end_span.end_column == 0 => vec![end_span.byte_end_idx - 1],
+        (_, Some((span, text))) => {
+            let content_end = text.trim_end().len();
+            text[content_end..].match_indices('\n').map(|(i, _)| span.byte_start_idx + content_end + i).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// span の最後の文字がある行（行末の改行で終わる span は、その改行の行）
+fn span_last_line(span: &Span) -> usize {
+    if span.end_column == 0 {
+        span.end_line.saturating_sub(1)
+    } else {
+        span.end_line
+    }
+}
+
+/// 先頭の空白系文字列（スペース/タブ/CR/LF）から改行だけを取り出し、残りのテキストと組で返す
+fn split_leading_whitespace(text: &str) -> (String, &str) {
+    let first_non_ws = text
+        .char_indices()
+        .find(|&(_, ch)| !ch.is_whitespace())
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let kept_newlines = text[..first_non_ws].chars().filter(|&c| c == '\n').collect();
+    (kept_newlines, &text[first_non_ws..])
+}
+
+/// 構造体本体の直下にあるメンバー宣言について、メンバー名の開始位置と行末コメントの開始位置を揃える
+///
+/// ポインタの `*` は名前の直前に置き、`*` を除いた名前の列を揃える（例: `int   x;` / `char *name;`）。
+/// pointer_style が RightOfType なら `*` を型の直後に（`char* name;`）、Spaced なら名前との間を空けて置く（`char * name;`）。
+/// 複数の宣言子（`int a, b;`）や関数ポインタ、入れ子の構造体など単純でない行はそのまま残す
+fn align_struct_members(text: &str, pointer_style: Option<&PointerStyle>) -> String {
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let mut members: Vec<(usize, StructMemberLine)> = Vec::new();
+    let mut depth = 0usize;
+    for (index, line) in lines.iter().enumerate() {
+        if depth == 1 {
+            if let Some(member) = StructMemberLine::parse(line) {
+                members.push((index, member));
+            }
+        }
+        let code = line.split("//").next().unwrap_or("");
+        depth += code.matches('{').count();
+        depth = depth.saturating_sub(code.matches('}').count());
+    }
+    if members.is_empty() {
+        return text.to_string();
+    }
+
+    // 型の側（型名と RightOfType の `*`）と名前の側（名前の直前の `*`）に分け、名前の側の先頭の列を揃える
+    let sides: Vec<(String, String)> = members.iter().map(|(_, m)| {
+        let stars = "*".repeat(m.stars);
+        match pointer_style {
+            Some(PointerStyle::RightOfType) => (format!("{}{}", m.type_name, stars), String::new()),
+            Some(PointerStyle::Spaced) if m.stars > 0 => (m.type_name.to_string(), format!("{} ", vec!["*"; m.stars].join(" "))),
+            _ => (m.type_name.to_string(), stars),
+        }
+    }).collect();
+    // 名前の開始列 = 型の側の幅 + 空白1つ + 名前の側の `*` の幅 の最大値
+    let name_column = sides.iter().map(|(type_side, name_side)| type_side.len() + 1 + name_side.len()).max().unwrap_or(0);
+    let codes: Vec<String> = members.iter().zip(&sides).map(|((_, m), (type_side, name_side))| {
+        let padding = name_column - type_side.len() - name_side.len();
+        format!("{}{}{}{}{}", m.indent, type_side, " ".repeat(padding), name_side, m.declarator)
+    }).collect();
+    let comment_column = codes.iter().map(|c| c.len()).max().unwrap_or(0) + 1;
+
+    let mut result: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    for ((index, member), code) in members.iter().zip(codes) {
+        let mut line = code;
+        if let Some(comment) = &member.comment {
+            line.push_str(&" ".repeat(comment_column - line.len()));
+            line.push_str(comment);
+        }
+        line.push_str(member.line_ending);
+        result[*index] = line;
+    }
+    result.concat()
+}
+
+/// 整列の対象となるメンバー宣言の1行（`<型> <*...><名前><配列・ビットフィールド>;` と行末コメント）
+struct StructMemberLine<'a> {
+    indent: &'a str,
+    type_name: &'a str,
+    stars: usize,
+    declarator: String,  // 名前から `;` まで（`name[4]` / `flag : 1` などを含む）
+    comment: Option<&'a str>,
+    line_ending: &'a str,
+}
+
+impl<'a> StructMemberLine<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let content = line.trim_end_matches(['\r', '\n']);
+        let line_ending = &line[content.len()..];
+        let indent = &content[..content.len() - content.trim_start().len()];
+        let body = content.trim();
+
+        let semicolon = body.find(';')?;
+        let code = &body[..semicolon];
+        let rest = body[semicolon + 1..].trim();
+        let comment = if rest.is_empty() {
+            None
+        } else if rest.starts_with("//") || rest.starts_with("/*") {
+            Some(rest)
+        } else {
+            return None;
+        };
+        if code.is_empty() || code.starts_with('#') || code.starts_with('/') || code.contains([',', '(', '{', '}', '=']) {
+            return None;
+        }
+
+        // ビットフィールド・配列の部分を除いた末尾の識別子を名前とする
+        let head_end = code.find([':', '[']).unwrap_or(code.len());
+        let head = code[..head_end].trim_end();
+        let name_start = head
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| c.is_alphanumeric() || c == '_')
+            .last()
+            .map(|(i, _)| i)?;
+        let before_name = head[..name_start].trim_end();
+        let type_part = before_name.trim_end_matches(|c: char| c == '*' || c.is_whitespace());
+        let stars = before_name[type_part.len()..].matches('*').count();
+        if type_part.is_empty() {
+            return None;
+        }
+
+        Some(StructMemberLine {
+            indent,
+            type_name: type_part,
+            stars,
+            declarator: format!("{};", code[name_start..].trim_end()),
+            comment,
+            line_ending,
+        })
+    }
+}
+
+/// `型 ポインタ層 名前` を pointer_style に従って出力する
+///
+/// `*` の後の修飾子は、RightOfType では `*` と空白で区切り（`char* const* p`）、
+/// LeftOfName では `*` に続けて書き（`char *const *p`）、Spaced ではすべて空白で区切る（`char * const * p`）。
+/// `name_separator` は最後のポインタ層と名前の間の空白で、改行を含む場合（関数名を別の行に書く場合）はそのまま残す
+fn format_pointer_declarator(base: &str, layers: &[PointerLayer], name_separator: &str, name: &str, style: &PointerStyle) -> String {
+    fn qualifiers(layer: &PointerLayer) -> Vec<&str> {
+        layer.qualifiers.iter().map(|q| q.to_string()).collect()
+    }
+    let ends_with_qualifier = layers.last().is_some_and(|layer| !layer.qualifiers.is_empty());
+
+    let (type_side, separator) = match style {
+        // `*` は直前の語（型名か修飾子）に付け、修飾子の前は空白で区切る
+        PointerStyle::RightOfType => {
+            let stars = layers.iter().fold(String::new(), |mut acc, layer| {
+                acc.push('*');
+                for qualifier in qualifiers(layer) {
+                    acc.push(' ');
+                    acc.push_str(qualifier);
+                }
+                acc
+            });
+            (format!("{}{}", base, stars), " ")
+        }
+        // `*` は直後の語（修飾子・次の `*`・名前）に付け、修飾子の後は空白で区切る
+        PointerStyle::LeftOfName => {
+            let stars = layers.iter().fold(String::new(), |mut acc, layer| {
+                if acc.ends_with(|c: char| c.is_alphanumeric()) {
+                    acc.push(' ');
+                }
+                acc.push('*');
+                acc.push_str(&qualifiers(layer).join(" "));
+                acc
+            });
+            (format!("{} {}", base, stars), if ends_with_qualifier { " " } else { "" })
+        }
+        PointerStyle::Spaced => {
+            let words: Vec<&str> = layers.iter()
+                .flat_map(|layer| std::iter::once("*").chain(qualifiers(layer)))
+                .collect();
+            (format!("{} {}", base, words.join(" ")), " ")
+        }
+    };
+
+    let separator = if name_separator.contains('\n') { name_separator } else { separator };
+    format!("{}{}{}", type_side, separator, name)
+}
+
+/// `型 ポインタ層 名前` の並び（1: 型名、2: `*` と修飾子の並び、3: 名前）
+/// 宣言を出力するたびに使うため、コンパイルは最初の1回だけ行う
+static POINTER_DECLARATOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"([A-Za-z_][A-Za-z0-9_]*)(\s*(?:\*\s*(?:(?:const|volatile|restrict|_Atomic)\b\s*)*)+)([A-Za-z_][A-Za-z0-9_]*)"
+    ).expect("ポインタ宣言子の正規表現は固定文字列で常に正しい")
+});
+
+/// テキスト中の `型 ポインタ層 名前` の並び（`int*p` / `char *const *name` など）をすべて書き換える
+/// 配列の添字（`[N * 2]`）やコメントの中は対象にしない
+fn normalize_pointer_declarators(code: &str, style: &PointerStyle) -> String {
+    let masked = mask_brackets_and_comments(code);
+
+    let mut result = String::with_capacity(code.len());
+    let mut last = 0;
+    for caps in POINTER_DECLARATOR.captures_iter(&masked) {
+        let (whole, base, run, name) = (caps.get(0).unwrap(), &caps[1], &caps[2], &caps[3]);
+        let layers = parse_pointer_layers(run);
+        let separator = &run[run.trim_end().len()..];
+        result.push_str(&code[last..whole.start()]);
+        result.push_str(&format_pointer_declarator(base, &layers, separator, name, style));
+        last = whole.end();
+    }
+    result.push_str(&code[last..]);
+    result
+}
+
+/// `*const *` のようなポインタ部分をポインタ層に分解する
+fn parse_pointer_layers(run: &str) -> Vec<PointerLayer> {
+    let span = Span::new(0, 0, 0, 0);
+    run.split('*').skip(1).map(|qualifiers| {
+        let qualifiers = qualifiers.split_whitespace().filter_map(|word| match word {
+            "const" => Some(TypeQualifier::Const),
+            "volatile" => Some(TypeQualifier::Volatile),
+            "restrict" => Some(TypeQualifier::Restrict),
+            "_Atomic" => Some(TypeQualifier::Atomic),
+            _ => None,
+        }).collect();
+        PointerLayer::with_qualifiers(qualifiers, span.clone())
+    }).collect()
+}
+
+/// `[...]` の中とコメントを同じバイト数の空白に置き換える（改行は残す）
+fn mask_brackets_and_comments(code: &str) -> String {
+    let mut result = String::with_capacity(code.len());
+    let mut bracket_depth = 0usize;
+    let mut chars = code.chars().peekable();
+    let blank = |result: &mut String, c: char| {
+        if c == '\n' {
+            result.push('\n');
+        } else {
+            result.push_str(&" ".repeat(c.len_utf8()));
+        }
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                blank(&mut result, c);
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    blank(&mut result, next);
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                blank(&mut result, c);
+                let mut prev = ' ';
+                for next in chars.by_ref() {
+                    blank(&mut result, next);
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            '[' => {
+                bracket_depth += 1;
+                result.push(c);
+            }
+            ']' => {
+                bracket_depth = bracket_depth.saturating_sub(1);
+                result.push(c);
+            }
+            _ if bracket_depth > 0 => blank(&mut result, c),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// 式をC言語のソースとして出力する（優先順位に応じて必要な箇所だけ括弧を付ける）
+pub(crate) fn format_expression(expr: &Expression) -> String {
+    format_operand(expr, 0, None)
+}
+
+/// 式のスパンから元の表記を取り出すためのソースの一部（`offset` は `text` の先頭のバイト位置）
+struct SourceText<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl SourceText<'_> {
+    /// 数値リテラルの元の表記を返す（括弧を含むなど、リテラルだけの範囲でなければ None）
+    fn literal(&self, span: &Span) -> Option<String> {
+        let start = span.byte_start_idx.checked_sub(self.offset)?;
+        let end = span.byte_end_idx.checked_sub(self.offset)?;
+        let literal = self.text.get(start..end)?.trim();
+        let is_number = literal.starts_with(|c: char| c.is_ascii_digit())
+            && literal.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '+' || c == '-');
+        is_number.then(|| literal.to_string())
+    }
+
+    /// span の範囲を前後の空白を除いたテキスト内の位置で返す
+    fn range(&self, span: &Span) -> Option<(usize, usize)> {
+        let start = span.byte_start_idx.checked_sub(self.offset)?;
+        let end = span.byte_end_idx.checked_sub(self.offset)?;
+        let slice = self.text.get(start..end)?;
+        let start = start + (slice.len() - slice.trim_start().len());
+        let end = end - (slice.len() - slice.trim_end().len());
+        (start < end).then_some((start, end))
+    }
+
+    /// 元のテキストとの違いが空白と冗長な括弧だけなら置き換えとして登録する（複数行にわたる範囲は対象外）
+    fn push_respacing(&self, start: usize, end: usize, rendered: String, replacements: &mut Vec<(usize, usize, String)>) {
+        let original = &self.text[start..end];
+        if original != rendered && !original.contains('\n') && differs_only_in_spacing(original, &rendered) {
+            replacements.push((start, end, rendered));
+        }
+    }
+}
+
+/// 関数本体の文と変数の初期化子の空白の書き直し方
+#[derive(Clone, Copy)]
+struct Respacing<'a> {
+    operators: bool,  // 二項演算子の前後に空白を入れる
+    pointer_style: Option<&'a PointerStyle>,  // 関数本体の変数宣言の `*` の位置を揃える
+}
+
+/// 関数本体の文と変数の初期化子を `respacing` に従って書き直したアイテムを返す
+///
+/// 二項演算子は1行に収まる式文・return 文・初期化付きの変数宣言と if / while の条件、トップレベルの変数の初期化子が対象。
+/// `*` の位置は関数本体の変数宣言の宣言子が対象（トップレベルの宣言は format_item で揃える）。
+/// 元のテキストとの違いが空白と冗長な括弧だけの場合に限って置き換えるため、コメントを含む文や
+/// 構文木で表せない書き方（関数呼び出しなど）の文は元のまま残る。書き換える箇所がなければ None
+fn respace_item(item: &Item, respacing: Respacing) -> Option<Item> {
+    let (Item::FunctionDecl { span, text, .. } | Item::VarDecl { span, text, .. }) = item else {
+        return None;
+    };
+    let source = SourceText { text, offset: span.byte_start_idx };
+    let mut replacements = Vec::new();
+    match item {
+        Item::FunctionDecl { body: Some(body), .. } => respace_statements(body, &source, respacing, &mut replacements),
+        Item::VarDecl { .. } if !respacing.operators => {}
+        Item::VarDecl { initializer: Some(Initializer::Value { text: value, span: value_span }), .. } => {
+            // `=` の前後も空白1つにそろえる
+            if let (Some(rendered), Some((value_start, value_end))) = (format_initializer(value), source.range(value_span)) {
+                let equal = text[..value_start].rfind('=');
+                if let Some(declarator_end) = equal.map(|equal| text[..equal].trim_end().len()) {
+                    source.push_respacing(declarator_end, value_end, format!(" = {}", rendered), &mut replacements);
+                }
+            }
+        }
+        Item::VarDecl { initializer: Some(initializer), .. } => respace_initializer(initializer, &source, &mut replacements),
+        _ => {}
+    }
+    if replacements.is_empty() {
+        return None;
+    }
+
+    let mut new_text = text.clone();
+    replacements.sort_by_key(|&(start, _, _)| std::cmp::Reverse(start));
+    for (start, end, replacement) in replacements {
+        new_text.replace_range(start..end, &replacement);
+    }
+    let mut item = item.clone();
+    if let Item::FunctionDecl { text, .. } | Item::VarDecl { text, .. } = &mut item {
+        *text = new_text;
+    }
+    Some(item)
+}
+
+/// 文を書き直す箇所を集める（ブロックの中の文も含む）
+fn respace_statements(statements: &[Statement], source: &SourceText, respacing: Respacing, replacements: &mut Vec<(usize, usize, String)>) {
+    for stmt in statements {
+        let Some((start, end)) = source.range(stmt.span()) else {
+            continue;
+        };
+        let original = &source.text[start..end];
+        let rendered = match stmt {
+            Statement::Expression { expr, .. } if respacing.operators => Some(format!("{};", format_operand(expr, 0, Some(source)))),
+            Statement::Return { value: Some(value), .. } if respacing.operators => {
+                Some(format!("return {};", format_operand(value, 0, Some(source))))
+            }
+            Statement::VarDecl { initializer, .. } => {
+                // 宣言子（`int *p` など）は pointer_style の指定がなければ元の表記のまま残す
+                let declarator_end = original.find('=').unwrap_or_else(|| original.trim_end_matches(';').len());
+                let declarator = original[..declarator_end].trim_end();
+                let rest = &original[declarator.len()..];
+                let declarator = match respacing.pointer_style {
+                    Some(style) => normalize_pointer_declarators(declarator, style),
+                    None => declarator.to_string(),
+                };
+                match initializer {
+                    Some(init) if respacing.operators => Some(format!("{} = {};", declarator, format_operand(init, 0, Some(source)))),
+                    _ => Some(format!("{}{}", declarator, rest)),
+                }
+            }
+            _ => None,
+        };
+        if let Some(rendered) = rendered {
+            source.push_respacing(start, end, rendered, replacements);
+        }
+
+        match stmt {
+            Statement::If { condition, then_block, else_block, .. } => {
+                if respacing.operators {
+                    respace_condition(start, "if", condition, source, replacements);
+                }
+                respace_statements(then_block, source, respacing, replacements);
+                if let Some(else_block) = else_block {
+                    respace_statements(else_block, source, respacing, replacements);
+                }
+            }
+            Statement::While { condition, body, .. } => {
+                if respacing.operators {
+                    respace_condition(start, "while", condition, source, replacements);
+                }
+                respace_statements(body, source, respacing, replacements);
+            }
+            Statement::DoWhile { body, .. } | Statement::For { body, .. } => respace_statements(body, source, respacing, replacements),
+            Statement::Block { statements, .. } => respace_statements(statements, source, respacing, replacements),
+            _ => {}
+        }
+    }
+}
+
+/// `if (...)` / `while (...)` の括弧の中の条件を書き直す（文の span はキーワードだけを指すため、続きは元のテキストから探す）
+fn respace_condition(
+    start: usize,
+    keyword: &str,
+    condition: &Expression,
+    source: &SourceText,
+    replacements: &mut Vec<(usize, usize, String)>,
+) {
+    let original = &source.text[start..];
+    let Some(after_keyword) = original.strip_prefix(keyword) else {
+        return;
+    };
+    let Some(open) = after_keyword.find('(').filter(|&open| after_keyword[..open].trim().is_empty()) else {
+        return;
+    };
+    let open = keyword.len() + open;
+    let mut depth = 0usize;
+    for (i, ch) in original[open..].char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    let rendered = format_operand(condition, 0, Some(source));
+                    source.push_respacing(start + open + 1, start + open + i, rendered, replacements);
+                    return;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 初期化子リストの各要素を書き直す
+fn respace_initializer(initializer: &Initializer, source: &SourceText, replacements: &mut Vec<(usize, usize, String)>) {
+    match initializer {
+        Initializer::Value { text, span } => {
+            if let (Some(rendered), Some((start, end))) = (format_initializer(text), source.range(span)) {
+                source.push_respacing(start, end, rendered, replacements);
+            }
+        }
+        Initializer::List { elements, .. } => {
+            for element in elements {
+                respace_initializer(element, source, replacements);
+            }
+        }
+    }
+}
+
+/// 初期化子のテキストを式として解析して出力する（式として解析できなければ None）
+fn format_initializer(text: &str) -> Option<String> {
+    let mut lexer = Lexer::from_borrowed(text);
+    let expr = ExpressionParser::new(&mut lexer).parse_expression()?;
+    Some(format_operand(&expr, 0, Some(&SourceText { text, offset: 0 })))
+}
+
+/// 書き直した式が元のテキストから空白と括弧を取り除いただけのものかどうか
+/// （書き直しで括弧が増えていたり、トークンが失われていたりする場合は false）
+fn differs_only_in_spacing(original: &str, rendered: &str) -> bool {
+    let mut original = original.chars().filter(|c| !c.is_whitespace());
+    let rendered = rendered.chars().filter(|c| !c.is_whitespace());
+    for expected in rendered {
+        loop {
+            match original.next() {
+                Some(c) if c == expected => break,
+                Some('(' | ')') => continue,
+                _ => return false,
+            }
+        }
+    }
+    original.all(|c| matches!(c, '(' | ')'))
+}
+
+/// 式の優先順位（大きいほど強く結合する）
+fn expression_precedence(expr: &Expression) -> u8 {
+    match expr {
+        Expression::Assignment { .. } => 1,
+        Expression::BinaryOp { op, .. } if op.is_assignment() => 1,
+        Expression::Conditional { .. } => 2,
+        Expression::BinaryOp { op, .. } => binary_precedence(*op),
+        Expression::UnaryOp { op: UnaryOperator::PostIncrement | UnaryOperator::PostDecrement, .. } => 14,
+        Expression::UnaryOp { .. } | Expression::Cast { .. } => 13,
+        Expression::FunctionCall { .. } | Expression::ArrayAccess { .. } |
+        Expression::MemberAccess { .. } | Expression::PointerMemberAccess { .. } => 14,
+        Expression::IntLiteral { .. } | Expression::FloatLiteral { .. } | Expression::StringLiteral { .. } |
+        Expression::CharLiteral { .. } | Expression::Identifier { .. } => 15,
+    }
+}
+
+fn binary_precedence(op: BinaryOperator) -> u8 {
+    match op {
+        BinaryOperator::LogicalOr => 3,
+        BinaryOperator::LogicalAnd => 4,
+        BinaryOperator::BitwiseOr => 5,
+        BinaryOperator::BitwiseXor => 6,
+        BinaryOperator::BitwiseAnd => 7,
+        BinaryOperator::Equal | BinaryOperator::NotEqual => 8,
+        BinaryOperator::LessThan | BinaryOperator::LessThanOrEq |
+        BinaryOperator::GreaterThan | BinaryOperator::GreaterThanOrEq => 9,
+        BinaryOperator::LeftShift | BinaryOperator::RightShift => 10,
+        BinaryOperator::Add | BinaryOperator::Subtract => 11,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 12,
+        _ => 1,  // 複合代入演算子
+    }
+}
+
+fn binary_operator_str(op: BinaryOperator) -> &'static str {
+    match op {
+        BinaryOperator::Add => "+",
+        BinaryOperator::Subtract => "-",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Modulo => "%",
+        BinaryOperator::BitwiseAnd => "&",
+        BinaryOperator::BitwiseOr => "|",
+        BinaryOperator::BitwiseXor => "^",
+        BinaryOperator::LeftShift => "<<",
+        BinaryOperator::RightShift => ">>",
+        BinaryOperator::Equal => "==",
+        BinaryOperator::NotEqual => "!=",
+        BinaryOperator::LessThan => "<",
+        BinaryOperator::LessThanOrEq => "<=",
+        BinaryOperator::GreaterThan => ">",
+        BinaryOperator::GreaterThanOrEq => ">=",
+        BinaryOperator::LogicalAnd => "&&",
+        BinaryOperator::LogicalOr => "||",
+        BinaryOperator::AddAssign => "+=",
+        BinaryOperator::SubtractAssign => "-=",
+        BinaryOperator::MultiplyAssign => "*=",
+        BinaryOperator::DivideAssign => "/=",
+        BinaryOperator::ModuloAssign => "%=",
+        BinaryOperator::BitwiseAndAssign => "&=",
+        BinaryOperator::BitwiseOrAssign => "|=",
+        BinaryOperator::BitwiseXorAssign => "^=",
+        BinaryOperator::LeftShiftAssign => "<<=",
+        BinaryOperator::RightShiftAssign => ">>=",
+    }
+}
+
+/// 優先順位が `min_precedence` より低い式は括弧で囲んで出力する
+fn format_operand(expr: &Expression, min_precedence: u8, source: Option<&SourceText>) -> String {
+    let precedence = expression_precedence(expr);
+    let text = match expr {
+        // 元のソースがあれば 0xFF や 1.5f などの表記をそのまま使う
+        Expression::IntLiteral { value, span } => source.and_then(|source| source.literal(span)).unwrap_or_else(|| value.to_string()),
+        Expression::FloatLiteral { value, span } => source.and_then(|source| source.literal(span)).unwrap_or_else(|| value.clone()),
+        Expression::StringLiteral { value, .. } | Expression::CharLiteral { value, .. } => value.clone(),
+        Expression::Identifier { name, .. } => name.clone(),
+        Expression::BinaryOp { op, left, right, .. } if op.is_assignment() => {
+            // 代入は右結合
+            format!("{} {} {}", format_operand(left, precedence + 1, source), binary_operator_str(*op), format_operand(right, precedence, source))
+        }
+        Expression::BinaryOp { op, left, right, .. } => {
+            format!("{} {} {}", format_operand(left, precedence, source), binary_operator_str(*op), format_operand(right, precedence + 1, source))
+        }
+        Expression::Assignment { left, right, .. } => {
+            format!("{} = {}", format_operand(left, precedence + 1, source), format_operand(right, precedence, source))
+        }
+        Expression::UnaryOp { op, operand, .. } => {
+            let operand = format_operand(operand, precedence, source);
+            match op {
+                // `- -x` が `--x` にならないよう、同じ記号が続く場合は空白を挟む
+                UnaryOperator::Plus if operand.starts_with('+') => format!("+ {}", operand),
+                UnaryOperator::Negate if operand.starts_with('-') => format!("- {}", operand),
+                UnaryOperator::Plus => format!("+{}", operand),
+                UnaryOperator::Negate => format!("-{}", operand),
+                UnaryOperator::LogicalNot => format!("!{}", operand),
+                UnaryOperator::BitwiseNot => format!("~{}", operand),
+                UnaryOperator::AddressOf => format!("&{}", operand),
+                UnaryOperator::Dereference => format!("*{}", operand),
+                UnaryOperator::PreIncrement => format!("++{}", operand),
+                UnaryOperator::PreDecrement => format!("--{}", operand),
+                UnaryOperator::PostIncrement => format!("{}++", operand),
+                UnaryOperator::PostDecrement => format!("{}--", operand),
+            }
+        }
+        Expression::Cast { target_type, operand, .. } => {
+            format!("({}){}", target_type, format_operand(operand, precedence, source))
+        }
+        Expression::FunctionCall { function, arguments, .. } => {
+            let arguments: Vec<String> = arguments.iter().map(|arg| format_operand(arg, 1, source)).collect();
+            format!("{}({})", format_operand(function, precedence, source), arguments.join(", "))
+        }
+        Expression::ArrayAccess { array, index, .. } => {
+            format!("{}[{}]", format_operand(array, precedence, source), format_operand(index, 0, source))
+        }
+        Expression::MemberAccess { object, member, .. } => {
+            format!("{}.{}", format_operand(object, precedence, source), member)
+        }
+        Expression::PointerMemberAccess { object, member, .. } => {
+            format!("{}->{}", format_operand(object, precedence, source), member)
+        }
+        Expression::Conditional { condition, then_expr, else_expr, .. } => {
+            format!(
+                "{} ? {} : {}",
+                format_operand(condition, precedence + 1, source),
+                format_operand(then_expr, 0, source),
+                format_operand(else_expr, precedence, source)
+            )
+        }
+    };
+
+    if precedence < min_precedence {
+        format!("({})", text)
+    } else {
+        text
+    }
+}
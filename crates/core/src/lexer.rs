@@ -1,47 +1,150 @@
-use std::str::CharIndices;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::Arc;
 use crate::token::*;
 use crate::span::Span;
+use crate::config::CStandard;
 
+// ファイル先頭の UTF-8 BOM
+const BOM: char = '\u{FEFF}';
+
+/// 字句解析器
+///
+/// 入力の先頭に UTF-8 BOM がある場合は読み飛ばす。トークンの Span のバイトオフセットは BOM を含む
+/// 入力での位置（`input` をそのままスライスできる）で、行・列は BOM がない場合と同じになる。
+/// 空白保持モードでは BOM を最初の `Token::Whitespace` として出力する。
 #[derive(Debug)]
-pub struct Lexer {
-    pub input: String,
-    char_offsets: CharIndices<'static>,
+pub struct Lexer<'a> {
+    pub input: Cow<'a, str>,
+    next_byte: usize,  // 次に読み込む文字のバイトオフセット
     cur: usize,
     pub column: usize,
     pub line: usize,
     now: Option<(usize, char)>,
     peeked: Option<(usize, char)>,
-    next_token_buffer: Option<Token>,
+    next_token_buffer: Vec<Token>,  // 戻されたトークン（末尾から順に読む）
+    preserve_whitespace: bool,  // 空白を Whitespace トークンとして出力するかどうか
+    identifiers: HashSet<Arc<str>>,  // 識別子名のインターン表（同名の識別子ごとに文字列を確保しないため）
+    disabled_keywords: HashSet<String>,  // キーワードではなく識別子として扱う語（古い規格の方言向け）
+    standard: Option<CStandard>,  // 解析対象の C 規格（None ならすべての構文を受け付ける）
+    dialect_errors: Vec<ErrorToken>,  // 規格で使えない構文（二進数リテラルなど）の位置とメッセージ
+    bom_pending: bool,  // 読み飛ばした先頭の BOM をまだトークンとして出力していないかどうか
 }
 
 // Lexer の実装
 // 文字列をトークンに分ける
-impl Lexer {
+impl Lexer<'static> {
+    /// 入力をコピーして所有する Lexer を作成する
     pub fn new(input: &str) -> Self {
-        let input_string = input.to_string();
-        // SAFETY: Stringを所有しているため'staticライフタイムに変換
-        // Lexerが生きている間は有効
-        let static_str: &'static str = unsafe {
-            std::mem::transmute(input_string.as_str())
-        };
-        
+        Lexer::from_cow(Cow::Owned(input.to_string()))
+    }
+}
+
+impl<'a> Lexer<'a> {
+    /// 呼び出し側のバッファを借用する Lexer を作成する（入力をコピーしない）
+    ///
+    /// トークンの Span のバイトオフセットはそのまま `input` の位置として使える。
+    pub fn from_borrowed(input: &'a str) -> Self {
+        Lexer::from_cow(Cow::Borrowed(input))
+    }
+
+    fn from_cow(input: Cow<'a, str>) -> Self {
         let mut lx = Lexer {
-            input: input_string,
-            char_offsets: static_str.char_indices(),
+            input,
+            next_byte: 0,
             cur: 0,
             column: 0,
             line: 0,
             now: None,
             peeked: None,
-            next_token_buffer: None,
+            next_token_buffer: Vec::new(),
+            preserve_whitespace: false,
+            identifiers: HashSet::new(),
+            disabled_keywords: HashSet::new(),
+            standard: None,
+            dialect_errors: Vec::new(),
+            bom_pending: false,
         };
+        // 先頭の UTF-8 BOM は読み飛ばす（バイトオフセットは BOM を含む入力の位置のまま）
+        if lx.input.starts_with(BOM) {
+            lx.next_byte = BOM.len_utf8();
+            lx.bom_pending = true;
+        }
         // peeked に最初の文字を入れる
-        lx.peeked = lx.char_offsets.next();
+        lx.peeked = lx.read_char();
         lx
     }
 
+    /// 入力の途中（`offset` バイト目）から字句解析を再開する Lexer を作成する
+    ///
+    /// `offset` はトークンの読み取りを開始した位置（トークンの `byte_start_idx`）であること。
+    /// 内部の行・列は先頭から読み進めて `offset` の文字に到達した場合と同じ値になる。
+    pub(crate) fn resume_at(input: &'a str, offset: usize) -> Self {
+        let mut lx = Lexer::from_borrowed(input);
+        if offset == 0 {
+            return lx;
+        }
+        let (line, column) = Self::line_column_at(input, offset);
+        lx.line = line;
+        lx.column = column;
+        lx.bom_pending = false;
+        lx.next_byte = offset;
+        lx.now = lx.read_char();
+        lx.peeked = lx.read_char();
+        lx.cur = input[..offset].chars().count() + usize::from(lx.now.is_some());
+        lx
+    }
+
+    /// `offset` バイト目の文字を now に読み込んだ時点の内部の行・列（next_char と同じ数え方）
+    ///
+    /// 改行文字は次の行の 0 列目として数え、CRLF は \r の時点で改行として数える。
+    /// 先頭の文字は常に 0 行 0 列になる。
+    pub(crate) fn line_column_at(input: &str, offset: usize) -> (usize, usize) {
+        // 先頭の BOM は読み飛ばすので、BOM を除いた入力で数える
+        if let Some(rest) = input.strip_prefix(BOM) {
+            return Self::line_column_at(rest, offset.saturating_sub(BOM.len_utf8()));
+        }
+        if offset == 0 {
+            return (0, 0);
+        }
+        let through = &input[..offset];
+        let rest = &input[offset..];
+        // offset の文字までに現れた改行（先頭の文字は数えない。先頭の文字は複数バイトのこともある）
+        let first_len = input.chars().next().map_or(0, char::len_utf8);
+        let mut line = input[first_len..offset].matches('\n').count() + usize::from(rest.starts_with('\n'));
+        // 先頭の \r\n は \r が先頭の文字として数えられないため改行にならない
+        if input.starts_with("\r\n") && offset >= 1 {
+            line -= 1;
+        }
+        // CRLF の \r は \n を先読みした時点で改行として数える
+        if rest.starts_with("\r\n") {
+            return (line + 1, 0);
+        }
+        if rest.starts_with('\n') {
+            return (line, 0);
+        }
+        let column = match through.rfind('\n') {
+            Some(pos) => through[pos..].chars().count(),
+            None => through.chars().count(),
+        };
+        (line, column)
+    }
+
+    // 入力から次の文字を読み込む（バイトオフセットと文字の組）
+    fn read_char(&mut self) -> Option<(usize, char)> {
+        let byte_idx = self.next_byte;
+        let ch = self.input[byte_idx..].chars().next()?;
+        self.next_byte += ch.len_utf8();
+        Some((byte_idx, ch))
+    }
+
     // 記号ではないキーワードはここで処理する
-    fn keyword_to_token(&self, byte_idx_start: usize, byte_idx_end: usize, span: Span) -> Option<Token> {
+    fn keyword_to_token(&mut self, byte_idx_start: usize, byte_idx_end: usize, span: Span) -> Option<Token> {
+        // 無効化されたキーワードは通常の識別子として扱う
+        if self.disabled_keywords.contains(&self.input[byte_idx_start..byte_idx_end]) {
+            let name = self.intern(byte_idx_start, byte_idx_end);
+            return Some(Token::Ident(IdentToken { span, name }));
+        }
         match &self.input[byte_idx_start..byte_idx_end] {
             "auto" => Some(Token::Auto(AutoToken { span })),
             "register" => Some(Token::Register(RegisterToken { span })),
@@ -61,6 +164,12 @@ impl Lexer {
             "short" => Some(Token::Short(ShortToken { span })),
             "signed" => Some(Token::Signed(SignedToken { span })),
             "unsigned" => Some(Token::Unsigned(UnsignedToken { span })),
+            "_Bool" => Some(Token::Bool(BoolToken { span })),
+            "_Complex" => Some(Token::Complex(ComplexToken { span })),
+            "_Imaginary" => Some(Token::Imaginary(ImaginaryToken { span })),
+            "inline" => Some(Token::Inline(InlineToken { span })),
+            "_Noreturn" => Some(Token::Noreturn(NoreturnToken { span })),
+            "_Thread_local" => Some(Token::ThreadLocal(ThreadLocalToken { span })),
             "struct" => Some(Token::Struct(StructToken { span })),
             "enum" => Some(Token::Enum(EnumToken { span })),
             "union" => Some(Token::Union(UnionToken { span })),
@@ -69,22 +178,41 @@ impl Lexer {
             "else" => Some(Token::ElseKeyword(ElseKeywordToken { span })),
             "while" => Some(Token::While(WhileToken { span })),
             "for" => Some(Token::For(ForToken { span })),
-            _ => Some(Token::Ident(IdentToken {
-                span,
-                name: self.input[byte_idx_start..byte_idx_end].to_string(),
-            })),
+            "do" => Some(Token::Do(DoToken { span })),
+            _ => {
+                let name = self.intern(byte_idx_start, byte_idx_end);
+                Some(Token::Ident(IdentToken { span, name }))
+            }
+        }
+    }
+
+    // 識別子名をインターンする（既出の名前なら同じ文字列を共有して返す）
+    fn intern(&mut self, byte_idx_start: usize, byte_idx_end: usize) -> Arc<str> {
+        let name = &self.input[byte_idx_start..byte_idx_end];
+        if let Some(interned) = self.identifiers.get(name) {
+            return Arc::clone(interned);
         }
+        let interned: Arc<str> = Arc::from(name);
+        self.identifiers.insert(Arc::clone(&interned));
+        interned
     }
 
     // 先に進めて文字を返す（存在しなければ None）
     pub fn next_char(&mut self) -> Option<(usize, char)> {
 
+        let prev = self.now;
         self.now = self.peeked;
-        self.peeked = self.char_offsets.next();
+        self.peeked = self.read_char();
 
         if let Some((_, ch)) = self.now {
             self.cur += 1;
-            if ch == '\n' {
+            // CRLF は \r の時点で改行として数え、続く \n では位置を進めない
+            // （LF のファイルと同じ行・列になるようにする）
+            let crlf_head = ch == '\r' && matches!(self.peeked, Some((_, '\n')));
+            let crlf_tail = ch == '\n' && matches!(prev, Some((_, '\r')));
+            if crlf_tail {
+                // \r で改行済み
+            } else if ch == '\n' || crlf_head {
                 self.line += 1;
                 self.column = 0;
             } else {
@@ -106,27 +234,89 @@ impl Lexer {
 
     /// 次のトークンを先読みする（消費しない）
     pub fn peek_token(&mut self) -> Option<Token> {
-        if self.next_token_buffer.is_none() {
-            self.next_token_buffer = self.next_token_internal();
+        if self.next_token_buffer.is_empty() {
+            let token = self.next_token_internal()?;
+            self.next_token_buffer.push(token);
         }
-        self.next_token_buffer.clone()
+        self.next_token_buffer.last().cloned()
     }
 
     /// トークンをバッファに戻す（ExpressionParser等で使用）
+    ///
+    /// 複数戻した場合は最後に戻したトークンから読む
     pub fn unget_token(&mut self, token: Token) {
-        self.next_token_buffer = Some(token);
+        self.next_token_buffer.push(token);
     }
 
     /// トークンを一つ読み取る（消費する）
     pub fn next_token(&mut self) -> Option<Token> {
-        if let Some(token) = self.next_token_buffer.take() {
+        if let Some(token) = self.next_token_buffer.pop() {
             return Some(token);
         }
         self.next_token_internal()
     }
 
+    /// 空白保持モードを切り替える
+    ///
+    /// 有効にすると空白・改行の連続を `Token::Whitespace` として出力し、各トークンのスパンは
+    /// 前後の空白を含まなくなる。先頭の BOM も `Token::Whitespace` として出力するため、
+    /// 全トークンのテキストを連結すると元のソースと一致する。
+    pub fn preserve_whitespace(&mut self, preserve: bool) {
+        self.preserve_whitespace = preserve;
+    }
+
+    /// 指定したキーワードを無効化し、以降は識別子として字句解析する
+    ///
+    /// `restrict` や `_Atomic` などが予約語でない古い規格向けのコードを解析するために使う。
+    pub fn disable_keyword(&mut self, keyword: &str) {
+        self.disabled_keywords.insert(keyword.to_string());
+    }
+
+    /// 解析対象の C 規格を設定する
+    ///
+    /// その規格にないキーワードは識別子として字句解析し、規格で使えないリテラルは
+    /// `take_dialect_errors` で取り出せるエラーとして記録する。
+    pub fn set_standard(&mut self, standard: CStandard) {
+        self.standard = Some(standard);
+        for keyword in standard.disabled_keywords() {
+            self.disable_keyword(keyword);
+        }
+    }
+
+    /// 設定されている C 規格を返す
+    pub fn standard(&self) -> Option<CStandard> {
+        self.standard
+    }
+
+    /// これまでに記録した、規格で使えない構文のエラーを取り出す
+    pub fn take_dialect_errors(&mut self) -> Vec<ErrorToken> {
+        std::mem::take(&mut self.dialect_errors)
+    }
+
     // 内部トークン読み取りメソッド（バッファを使わない生の読み取り）
     fn next_token_internal(&mut self) -> Option<Token> {
+        if std::mem::take(&mut self.bom_pending) && self.preserve_whitespace {
+            return Some(Token::Whitespace(WhitespaceToken {
+                span: Span {
+                    start_line: 0,
+                    start_column: 0,
+                    end_line: 0,
+                    end_column: 0,
+                    byte_start_idx: 0,
+                    byte_end_idx: BOM.len_utf8(),
+                },
+                text: BOM.to_string(),
+            }));
+        }
+        let mut token = self.read_token()?;
+        if self.preserve_whitespace {
+            // トークン直後の文字を含めないよう、終端を未消費の文字の位置に揃える
+            token.span_mut().byte_end_idx = self.now.map_or(self.input.len(), |(b, _)| b);
+        }
+        Some(token)
+    }
+
+    fn read_token(&mut self) -> Option<Token> {
         // 初回呼び出し時に now を初期化
         if self.now.is_none() && self.peeked.is_some() {
             self.next_char();
@@ -144,7 +334,25 @@ impl Lexer {
 
         loop {
             match self.now {
-                Some((byte_idx, ' ')) | Some((byte_idx, '\t')) | Some((byte_idx, '\n')) | Some((byte_idx, '\r')) => {
+                Some((byte_idx, ' ' | '\t' | '\n' | '\r' | '\x0b' | '\x0c')) => {
+                    // 空白保持モードでは空白の連続を1つのトークンとして返す
+                    if self.preserve_whitespace && start_byte_flag.is_none() {
+                        while matches!(self.now, Some((_, ' ' | '\t' | '\n' | '\r' | '\x0b' | '\x0c'))) {
+                            self.next_char();
+                        }
+                        let end_byte = self.now.map_or(self.input.len(), |(b, _)| b);
+                        return Some(Token::Whitespace(WhitespaceToken {
+                            span: Span {
+                                start_line,
+                                start_column,
+                                end_line: self.line,
+                                end_column: self.column,
+                                byte_start_idx: byte_idx,
+                                byte_end_idx: end_byte,
+                            },
+                            text: self.input[byte_idx..end_byte].to_string(),
+                        }));
+                    }
                     // 空白文字はスキップ
                     if start_byte_flag.is_none() {
                         start_byte_flag = Some(byte_idx);
@@ -234,6 +442,26 @@ impl Lexer {
                     }
                     self.next_char();
 
+                    // Check for *=
+                    if let Some((_, '=')) = self.now {
+                        self.next_char();
+                        let end_byte = if let Some((b, _)) = self.peeked {
+                            b
+                        } else {
+                            self.input.len()
+                        };
+                        return Some(Token::AsteriskEqual(AsteriskEqualToken {
+                            span: Span {
+                                start_line,
+                                start_column,
+                                end_line: self.line,
+                                end_column: self.column,
+                                byte_start_idx: start_byte_flag.unwrap(),
+                                byte_end_idx: end_byte,
+                            }
+                        }));
+                    }
+
                     let end_byte = if let Some((b, _)) = self.peeked {
                         b
                     } else {
@@ -280,6 +508,26 @@ impl Lexer {
                         }));
                     }
 
+                    // Check for +=
+                    if let Some((_, '=')) = self.now {
+                        self.next_char();
+                        let end_byte = if let Some((b, _)) = self.peeked {
+                            b
+                        } else {
+                            self.input.len()
+                        };
+                        return Some(Token::PlusEqual(PlusEqualToken {
+                            span: Span {
+                                start_line,
+                                start_column,
+                                end_line: self.line,
+                                end_column: self.column,
+                                byte_start_idx: start_byte_flag.unwrap(),
+                                byte_end_idx: end_byte,
+                            }
+                        }));
+                    }
+
                     let end_byte = if let Some((b, _)) = self.peeked {
                         b
                     } else {
@@ -341,6 +589,26 @@ impl Lexer {
                             }));
                         },
                         _ => {
+                            // Check for -=
+                            if let Some((_, '=')) = self.now {
+                                self.next_char();
+                                let end_byte = if let Some((b, _)) = self.peeked {
+                                    b
+                                } else {
+                                    self.input.len()
+                                };
+                                return Some(Token::MinusEqual(MinusEqualToken {
+                                    span: Span {
+                                        start_line,
+                                        start_column,
+                                        end_line: self.line,
+                                        end_column: self.column,
+                                        byte_start_idx: start_byte_flag.unwrap(),
+                                        byte_end_idx: end_byte,
+                                    }
+                                }));
+                            }
+
                             let end_byte = if let Some((b, _)) = self.peeked {
                                 b
                             } else {
@@ -365,6 +633,26 @@ impl Lexer {
                     }
                     self.next_char();
 
+                    // Check for %=
+                    if let Some((_, '=')) = self.now {
+                        self.next_char();
+                        let end_byte = if let Some((b, _)) = self.peeked {
+                            b
+                        } else {
+                            self.input.len()
+                        };
+                        return Some(Token::PercentEqual(PercentEqualToken {
+                            span: Span {
+                                start_line,
+                                start_column,
+                                end_line: self.line,
+                                end_column: self.column,
+                                byte_start_idx: start_byte_flag.unwrap(),
+                                byte_end_idx: end_byte,
+                            }
+                        }));
+                    }
+
                     let end_byte = if let Some((b, _)) = self.peeked {
                         b
                     } else {
@@ -451,6 +739,26 @@ impl Lexer {
                         },
                         Some((_, '<')) => {
                             self.next_char();
+                            // Check for <<=
+                            if let Some((_, '=')) = self.now {
+                                self.next_char();
+                                let end_byte = if let Some((b, _)) = self.peeked {
+                                    b
+                                } else {
+                                    self.input.len()
+                                };
+                                return Some(Token::LeftShiftEqual(LeftShiftEqualToken {
+                                    span: Span {
+                                        start_line,
+                                        start_column,
+                                        end_line: self.line,
+                                        end_column: self.column,
+                                        byte_start_idx: start_byte_flag.unwrap(),
+                                        byte_end_idx: end_byte,
+                                    }
+                                }));
+                            }
+
                             let end_byte = if let Some((b, _)) = self.peeked {
                                 b
                             } else {
@@ -514,6 +822,26 @@ impl Lexer {
                         },
                         Some((_, '>')) => {
                             self.next_char();
+                            // Check for >>=
+                            if let Some((_, '=')) = self.now {
+                                self.next_char();
+                                let end_byte = if let Some((b, _)) = self.peeked {
+                                    b
+                                } else {
+                                    self.input.len()
+                                };
+                                return Some(Token::RightShiftEqual(RightShiftEqualToken {
+                                    span: Span {
+                                        start_line,
+                                        start_column,
+                                        end_line: self.line,
+                                        end_column: self.column,
+                                        byte_start_idx: start_byte_flag.unwrap(),
+                                        byte_end_idx: end_byte,
+                                    }
+                                }));
+                            }
+
                             let end_byte = if let Some((b, _)) = self.peeked {
                                 b
                             } else {
@@ -575,6 +903,26 @@ impl Lexer {
                         }));
                     }
 
+                    // Check for &=
+                    if let Some((_, '=')) = self.now {
+                        self.next_char();
+                        let end_byte = if let Some((b, _)) = self.peeked {
+                            b
+                        } else {
+                            self.input.len()
+                        };
+                        return Some(Token::AmpersandEqual(AmpersandEqualToken {
+                            span: Span {
+                                start_line,
+                                start_column,
+                                end_line: self.line,
+                                end_column: self.column,
+                                byte_start_idx: start_byte_flag.unwrap(),
+                                byte_end_idx: end_byte,
+                            }
+                        }));
+                    }
+
                     let end_byte = if let Some((b, _)) = self.peeked {
                         b
                     } else {
@@ -617,6 +965,26 @@ impl Lexer {
                         }));
                     }
 
+                    // Check for |=
+                    if let Some((_, '=')) = self.now {
+                        self.next_char();
+                        let end_byte = if let Some((b, _)) = self.peeked {
+                            b
+                        } else {
+                            self.input.len()
+                        };
+                        return Some(Token::PipeEqual(PipeEqualToken {
+                            span: Span {
+                                start_line,
+                                start_column,
+                                end_line: self.line,
+                                end_column: self.column,
+                                byte_start_idx: start_byte_flag.unwrap(),
+                                byte_end_idx: end_byte,
+                            }
+                        }));
+                    }
+
                     let end_byte = if let Some((b, _)) = self.peeked {
                         b
                     } else {
@@ -639,6 +1007,26 @@ impl Lexer {
                     }
                     self.next_char();
 
+                    // Check for ^=
+                    if let Some((_, '=')) = self.now {
+                        self.next_char();
+                        let end_byte = if let Some((b, _)) = self.peeked {
+                            b
+                        } else {
+                            self.input.len()
+                        };
+                        return Some(Token::CaretEqual(CaretEqualToken {
+                            span: Span {
+                                start_line,
+                                start_column,
+                                end_line: self.line,
+                                end_column: self.column,
+                                byte_start_idx: start_byte_flag.unwrap(),
+                                byte_end_idx: end_byte,
+                            }
+                        }));
+                    }
+
                     let end_byte = if let Some((b, _)) = self.peeked {
                         b
                     } else {
@@ -769,6 +1157,13 @@ impl Lexer {
                     if start_byte_flag.is_none() {
                         start_byte_flag = Some(byte_idx);
                     }
+
+                    // C23 の属性 [[...]] は1つのトークンにする
+                    if matches!(self.peeked, Some((_, '['))) {
+                        if let Some(token) = self.attribute_token(byte_idx, '[', ']', start_byte_flag.unwrap(), start_line, start_column) {
+                            return Some(token);
+                        }
+                    }
                     self.next_char();
 
                     let end_byte = if let Some((b, _)) = self.peeked {
@@ -996,6 +1391,26 @@ impl Lexer {
                         }
                     } else {
                         // '/' 単独のトークン
+                        // Check for /=
+                        if let Some((_, '=')) = self.now {
+                            self.next_char();
+                            let end_byte = if let Some((b, _)) = self.peeked {
+                                b
+                            } else {
+                                self.input.len()
+                            };
+                            return Some(Token::SlashEqual(SlashEqualToken {
+                                span: Span {
+                                    start_line,
+                                    start_column,
+                                    end_line: self.line,
+                                    end_column: self.column,
+                                    byte_start_idx: start_byte_flag.unwrap(),
+                                    byte_end_idx: end_byte,
+                                }
+                            }));
+                        }
+
                         let end_byte = if let Some((b, _)) = self.peeked {
                             b
                         } else {
@@ -1013,6 +1428,61 @@ impl Lexer {
                         }));
                     }
                 },
+                Some((byte_idx, quote @ ('"' | '\''))) => {
+                    // 文字列リテラル・文字定数（中の /* や // はコメントとして扱わない）
+                    if start_byte_flag.is_none() {
+                        start_byte_flag = Some(byte_idx);
+                    }
+                    self.next_char(); // 開きの引用符を消費
+
+                    let mut terminated = false;
+                    loop {
+                        match self.now {
+                            Some((_, ch)) if ch == quote => {
+                                self.next_char();
+                                terminated = true;
+                                break;
+                            },
+                            Some((_, '\\')) => {
+                                // エスケープシーケンス（\" や \' や \\）は次の文字ごと読み飛ばす
+                                self.next_char();
+                                if !matches!(self.now, Some((_, '\n')) | None) {
+                                    self.next_char();
+                                }
+                            },
+                            Some((_, '\n')) | None => break,
+                            Some(_) => {
+                                self.next_char();
+                            },
+                        }
+                    }
+
+                    let end_byte = self.now.map_or(self.input.len(), |(b, _)| b);
+                    let span = Span {
+                        start_line,
+                        start_column,
+                        end_line: self.line,
+                        end_column: self.column,
+                        byte_start_idx: start_byte_flag.unwrap(),
+                        byte_end_idx: end_byte,
+                    };
+                    if !terminated {
+                        let message = if quote == '"' {
+                            "Unterminated string literal"
+                        } else {
+                            "Unterminated character literal"
+                        };
+                        return Some(Token::Error(ErrorToken {
+                            span,
+                            message: message.to_string(),
+                        }));
+                    }
+                    let value = self.input[byte_idx..end_byte].to_string();
+                    if quote == '"' {
+                        return Some(Token::StringLiteral(StringLiteralToken { span, value }));
+                    }
+                    return Some(Token::CharLiteral(CharLiteralToken { span, value }));
+                },
                 Some((byte_idx, '\\')) => {
                     // バックスラッシュ単体はエラー（行継続は非サポート）
                     if start_byte_flag.is_none() {
@@ -1045,21 +1515,12 @@ impl Lexer {
                     
                     // プリプロセッサディレクティブは行頭からの位置を記録
                     // 行頭（直前の\nまたはファイル先頭）から#までの文字数を計算
-                    let directive_start_column = {
-                        let mut col = 0;
-                        let mut pos = byte_idx;
-                        // 行頭まで遡る
-                        while pos > 0 {
-                            let prev_byte = pos - 1;
-                            let ch = self.input[prev_byte..pos].chars().next().unwrap_or('\0');
-                            if ch == '\n' || ch == '\r' {
-                                break;
-                            }
-                            col += 1;
-                            pos = prev_byte;
-                        }
-                        col
-                    };
+                    // 行頭まで文字単位で遡る（マルチバイト文字を含む行でも文字数で数える）
+                    let directive_start_column = self.input[..byte_idx]
+                        .chars()
+                        .rev()
+                        .take_while(|&ch| ch != '\n' && ch != '\r')
+                        .count();
                     
                     self.next_char();
 
@@ -1157,12 +1618,7 @@ impl Lexer {
 
                     // #define の処理：先頭の空白は token の offset/length に含まれる（start_byte がそれを指す）
                     if let Some(rest) = content.strip_prefix("define") {
-                        let rest = rest.trim();
-                        let mut parts = rest.splitn(2, ' ');
-                        if let Some(name) = parts.next() {
-                            let macro_name = name.to_string();
-                            let macro_value = parts.next().unwrap_or("").to_string();
-
+                        if let Some((macro_name, params, macro_value)) = Self::parse_define(rest) {
                             return Some(Token::Define(DefineToken {
                                 span: Span {
                                     start_line,
@@ -1173,6 +1629,7 @@ impl Lexer {
                                     byte_end_idx: end_byte_idx,
                                 },
                                 macro_name,
+                                params,
                                 macro_value,
                             }));
                         }
@@ -1262,6 +1719,50 @@ impl Lexer {
                         }));
                     }
 
+                    // #error / #warning の処理
+                    if let Some(message) = Self::directive_message(&content, "error") {
+                        return Some(Token::ErrorDirective(ErrorDirectiveToken {
+                            span: Span {
+                                start_line,
+                                start_column: directive_start_column,
+                                end_line,
+                                end_column,
+                                byte_start_idx: start_byte_flag.unwrap(),
+                                byte_end_idx: end_byte_idx,
+                            },
+                            message,
+                        }));
+                    }
+                    if let Some(message) = Self::directive_message(&content, "warning") {
+                        return Some(Token::WarningDirective(WarningDirectiveToken {
+                            span: Span {
+                                start_line,
+                                start_column: directive_start_column,
+                                end_line,
+                                end_column,
+                                byte_start_idx: start_byte_flag.unwrap(),
+                                byte_end_idx: end_byte_idx,
+                            },
+                            message,
+                        }));
+                    }
+
+                    // #line の処理: #line 行番号 ["ファイル名"]
+                    if let Some((line_number, file)) = Self::parse_line_directive(&content) {
+                        return Some(Token::Line(LineToken {
+                            span: Span {
+                                start_line,
+                                start_column: directive_start_column,
+                                end_line,
+                                end_column,
+                                byte_start_idx: start_byte_flag.unwrap(),
+                                byte_end_idx: end_byte_idx,
+                            },
+                            line_number,
+                            file,
+                        }));
+                    }
+
                     // それ以外の # 系ディレクティブはとりあえず Include 風に生テキストを残す（既存互換）
                     return Some(Token::Include(IncludeToken {
                         span: Span {
@@ -1285,6 +1786,7 @@ impl Lexer {
                         let actual_byte_idx_start = byte_idx;
                         self.next_char();
                         let mut is_float = false;
+                        let mut is_binary = false;
 
                         // 16進数 (0x または 0X)
                         if actual_byte_idx_start + 1 < self.input.len() 
@@ -1301,6 +1803,16 @@ impl Lexer {
                                     _ => break,
                                 }
                             }
+                        } else if &self.input[actual_byte_idx_start..actual_byte_idx_start+1] == "0"
+                            && matches!(self.now, Some((_, 'b')) | Some((_, 'B')))
+                            && matches!(self.peeked, Some((_, '0')) | Some((_, '1'))) {
+                            // 2進数 (0b または 0B、C23 以降)
+                            self.next_char(); // 'b' または 'B' を消費
+                            is_binary = true;
+
+                            while matches!(self.now, Some((_, '0')) | Some((_, '1'))) {
+                                self.next_char();
+                            }
                         } else {
                             // 8進数または10進数
                             loop {
@@ -1392,6 +1904,14 @@ impl Lexer {
                             byte_end_idx,
                         };
                         
+                        // 二進数リテラルを使えない規格ならエラーとして記録する（トークンはそのまま返す）
+                        if let Some(standard) = self.standard.filter(|standard| is_binary && !standard.allows_binary_literals()) {
+                            self.dialect_errors.push(ErrorToken {
+                                span: span.clone(),
+                                message: format!("二進数リテラル '{}' は C23 以降でのみ使用できます（対象の規格: {}）", value, standard),
+                            });
+                        }
+
                         if is_float {
                             return Some(Token::FloatLiteral(FloatLiteralToken {
                                 span,
@@ -1429,6 +1949,16 @@ impl Lexer {
 
                         let byte_start_idx = start_byte_flag.unwrap();
                         let byte_end_idx = actual_byte_idx_end;
+
+                        // GCC の属性 __attribute__((...)) は括弧まで含めて1つのトークンにする
+                        if matches!(&self.input[actual_byte_idx_start..actual_byte_idx_end], "__attribute__" | "__attribute") {
+                            let rest = &self.input[actual_byte_idx_end..];
+                            let open = actual_byte_idx_end + (rest.len() - rest.trim_start().len());
+                            if let Some(token) = self.attribute_token(open, '(', ')', byte_start_idx, start_line, start_column) {
+                                return Some(token);
+                            }
+                        }
+
                         let span = Span {
                             start_line,
                             start_column,
@@ -1449,7 +1979,135 @@ impl Lexer {
         }
     }
 
+    /// `open` の位置の括弧と対応する閉じ括弧までを属性トークンとして読み進める
+    /// （`__attribute__` の後に括弧がない場合や、括弧が閉じていない場合は何も読まずに None）
+    fn attribute_token(&mut self, open: usize, open_ch: char, close_ch: char, byte_start_idx: usize, start_line: usize, start_column: usize) -> Option<Token> {
+        let group = &self.input[open..];
+        if !group.starts_with(open_ch) {
+            return None;
+        }
+        let mut depth = 0usize;
+        let close = group.char_indices().find_map(|(i, ch)| {
+            if ch == open_ch {
+                depth += 1;
+            } else if ch == close_ch {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            None
+        })?;
+        let byte_end_idx = open + close + close_ch.len_utf8();
+
+        // 外側の括弧を外す（`__attribute__((...))` と `[[...]]` はどちらも二重）
+        let mut inner = &self.input[open..byte_end_idx];
+        for _ in 0..2 {
+            inner = inner.trim().strip_prefix(open_ch).and_then(|t| t.strip_suffix(close_ch)).unwrap_or(inner);
+        }
+        let attributes = Self::split_attribute_list(inner);
+
+        while self.now.is_some_and(|(b, _)| b < byte_end_idx) {
+            self.next_char();
+        }
+        Some(Token::Attribute(AttributeToken {
+            span: Span {
+                start_line,
+                start_column,
+                end_line: self.line,
+                end_column: self.column,
+                byte_start_idx,
+                byte_end_idx,
+            },
+            attributes,
+        }))
+    }
+
+    /// 属性の並びを括弧の外のカンマで分ける（`packed, aligned(4)` → ["packed", "aligned(4)"]）
+    fn split_attribute_list(inner: &str) -> Vec<String> {
+        let mut attributes = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 0;
+        for (i, ch) in inner.char_indices() {
+            match ch {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth -= 1,
+                ',' if depth == 0 => {
+                    attributes.push(inner[start..i].trim().to_string());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        attributes.push(inner[start..].trim().to_string());
+        attributes.retain(|attribute| !attribute.is_empty());
+        attributes
+    }
+
     fn is_identifier_start(&self, ch: char) -> bool {
         ch.is_ascii_alphabetic() || ch == '_'
     }
+
+    /// `#error` / `#warning` ディレクティブの内容（# を除いたもの）からメッセージを取り出す
+    /// メッセージ全体が "..." で囲まれている場合は引用符を外す
+    fn directive_message(content: &str, directive: &str) -> Option<String> {
+        let rest = content.strip_prefix(directive)?;
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let message = rest.trim();
+        let message = message
+            .strip_prefix('"')
+            .and_then(|m| m.strip_suffix('"'))
+            .unwrap_or(message);
+        Some(message.to_string())
+    }
+
+    /// `#line` ディレクティブの内容（# を除いたもの）から行番号とファイル名を取り出す
+    /// 行番号が数値でない場合（マクロで指定している場合など）は None
+    fn parse_line_directive(content: &str) -> Option<(usize, Option<String>)> {
+        let rest = content.strip_prefix("line")?;
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let mut parts = rest.trim().splitn(2, char::is_whitespace);
+        let line_number = parts.next()?.parse().ok()?;
+        let file = parts
+            .next()
+            .map(str::trim)
+            .and_then(|f| f.strip_prefix('"'))
+            .and_then(|f| f.split('"').next())
+            .map(str::to_string);
+        Some((line_number, file))
+    }
+
+    /// `#define` 以降の内容からマクロ名・引数・置換値を取り出す
+    /// マクロ名の直後（空白なし）に `(` が続く場合のみ関数形式マクロとして引数リストを解析する
+    fn parse_define(rest: &str) -> Option<(String, Option<Vec<String>>, String)> {
+        let rest = rest.trim();
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '(')
+            .unwrap_or(rest.len());
+        let macro_name = &rest[..name_end];
+        if macro_name.is_empty() {
+            return None;
+        }
+
+        let after_name = &rest[name_end..];
+        let (params, body) = match after_name.strip_prefix('(') {
+            Some(param_list) => {
+                let close = param_list.find(')')?;
+                let params = param_list[..close]
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                (Some(params), &param_list[close + 1..])
+            }
+            None => (None, after_name),
+        };
+
+        Some((macro_name.to_string(), params, body.trim().to_string()))
+    }
 }
@@ -1,5 +1,7 @@
 pub mod span;
 pub mod token;
+pub mod token_index;
+pub mod token_stream;
 pub mod lexer;
 pub mod parser;
 pub mod ast;
@@ -12,20 +14,27 @@ pub mod trivia;
 pub mod type_system;
 pub mod type_table;
 pub mod config;
+pub mod editorconfig;
+pub mod const_eval;
+pub mod analysis;
 
 // 主要な型を re-export して使いやすくする
 pub use span::Span;
-pub use token::Token;
+pub use token::{Token, TokenKind};
+pub use token_index::TokenIndex;
+pub use token_stream::{Mark, TokenStream};
 pub use lexer::Lexer;
 pub use parser::Parser;
-pub use ast::{TranslationUnit, Item};
+pub use ast::{TranslationUnit, Item, ElifBranch, Initializer, Symbol, SymbolKind};
 pub use expression::{Expression, BinaryOperator, UnaryOperator};
 pub use statement::{Statement, SwitchCase};
 pub use formatter::Formatter;
-pub use diagnostics::{Diagnostic, DiagnosticSeverity, DiagnosticConfig, diagnose};
+pub use diagnostics::{Diagnostic, DiagnosticSeverity, DiagnosticConfig, DiagnosticConfigBuilder, diagnose};
 pub use trivia::{Comment, Trivia};
 pub use type_table::TypeTable;
-pub use config::{ProjectConfig, LoadedProjectConfig, PreprocessorConfig};
+pub use config::{ProjectConfig, LoadedProjectConfig, PreprocessorConfig, CStandard, NamingPattern, NamingStyle, RuleLevel};
+pub use editorconfig::EditorConfigIndent;
+pub use analysis::{AnalysisResult, analyze, analyze_with_preprocessor, parse_with_types};
 
 #[cfg(test)]
 mod tests;
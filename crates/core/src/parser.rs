@@ -1,11 +1,12 @@
 use crate::lexer::Lexer;
 use crate::token::*;
-use crate::ast::{TranslationUnit, Item, StructMember, UnionMember, EnumVariant};
+use crate::ast::{TranslationUnit, Item, ElifBranch, Initializer, Statement, StructMember, UnionMember, EnumVariant};
 use crate::span::Span;
 use crate::trivia::{Trivia, Comment};
 use crate::type_system::{BaseType, Type, TypeQualifier};
 use crate::type_table::TypeTable;
 use crate::config::PreprocessorConfig;
+use crate::diagnostics::{Diagnostic, DiagnosticCode, DiagnosticSeverity};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
@@ -28,6 +29,9 @@ enum StopReason {
     Eof,
 }
 
+// Item の specifiers に記録する関数指定子・スレッド記憶域指定子
+const DECLARATION_SPECIFIERS: [&str; 3] = ["inline", "_Noreturn", "_Thread_local"];
+
 // ifdef/ifndef のコンテキスト
 #[derive(Debug, Clone)]
 struct IfdefContext {
@@ -37,23 +41,28 @@ struct IfdefContext {
 }
 
 #[derive(Debug)]
-pub struct Parser {
-    pub lexer: Lexer,
+pub struct Parser<'a> {
+    pub lexer: Lexer<'a>,
     pending_comments: Vec<Comment>,  // 次のItemに付与する予定のコメント
     type_table: TypeTable,           // typedef名を管理
     defined_macros: HashMap<String, String>,  // #define で定義されたマクロ
     preprocessor_config: PreprocessorConfig,  // プリプロセッサ設定
     ifdef_stack: Vec<IfdefContext>,  // ifdef/ifndef のネストを追跡
     parsed_headers: HashSet<PathBuf>, // 解析済みヘッダー（循環include防止）
+    header_functions: HashSet<String>, // 解析済みヘッダーで宣言された関数名
     current_file_dir: PathBuf,       // 現在解析中のファイルのディレクトリ
+    diagnostics: Vec<Diagnostic>,    // 解析中に検出したエラー（字句エラーなど）
 }
 
-impl Parser {
-    pub fn new(lexer: Lexer) -> Self {
+impl<'a> Parser<'a> {
+    pub fn new(lexer: Lexer<'a>) -> Self {
         Self::new_with_config(lexer, PreprocessorConfig::default())
     }
     
-    pub fn new_with_config(lexer: Lexer, preprocessor_config: PreprocessorConfig) -> Self {
+    pub fn new_with_config(mut lexer: Lexer<'a>, preprocessor_config: PreprocessorConfig) -> Self {
+        if let Some(standard) = preprocessor_config.standard {
+            lexer.set_standard(standard);
+        }
         let mut type_table = TypeTable::new();
         
         // 組み込み型名を事前登録（文字列のみ）
@@ -84,7 +93,9 @@ impl Parser {
             preprocessor_config,
             ifdef_stack: Vec::new(),
             parsed_headers: HashSet::new(),
+            header_functions: HashSet::new(),
             current_file_dir: PathBuf::from("."),
+            diagnostics: Vec::new(),
         }
     }
 
@@ -93,6 +104,30 @@ impl Parser {
         self.current_file_dir = dir.into();
     }
     
+    /// 解析中に収集した診断を取り出す
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// 解析中に #define で定義されたマクロ（マクロ名 → 値）を取得する
+    ///
+    /// #include で読み込んだヘッダー内の定義も含む。値を持たないマクロ（`#define DEBUG`）の値は空文字列になる。
+    /// 設定ファイルの defines で与えたマクロは含まない。
+    pub fn defined_macros(&self) -> &HashMap<String, String> {
+        &self.defined_macros
+    }
+
+    /// #include で解析したヘッダー（入れ子のヘッダーを含む）で宣言・定義された関数名を取得する
+    ///
+    /// ヘッダーを1つも解析していない場合は None を返す。
+    pub fn header_functions(&self) -> Option<&HashSet<String>> {
+        if self.parsed_headers.is_empty() {
+            None
+        } else {
+            Some(&self.header_functions)
+        }
+    }
+
     /// 現在のブランチがアクティブか判定（型登録に使用）
     fn is_current_branch_active(&self) -> bool {
         // ifdef_stackが空ならトップレベル（常にアクティブ）
@@ -102,10 +137,31 @@ impl Parser {
 
     pub fn parse(&mut self) -> TranslationUnit {
         let (items, _) = self.parse_items(ParseContext::TopLevel, false);
-        TranslationUnit { 
+        // 対象の規格で使えない構文を診断にする
+        for ErrorToken { span, message } in self.lexer.take_dialect_errors() {
+            self.diagnostics.push(Diagnostic::new(
+                span,
+                DiagnosticSeverity::Error,
+                message,
+                DiagnosticCode::Custom("CGH050".to_string()),
+            ));
+        }
+        let mut tu = TranslationUnit { 
             items,
             leading_trivia: Trivia::empty(),  // TODO: 後で実装
+        };
+        if self.preprocessor_config.prune_inactive_branches {
+            tu.prune_inactive_branches();
         }
+        tu
+    }
+
+    /// 解析して、AST・型テーブル（typedef 名）・#define で定義されたマクロをまとめて返す
+    ///
+    /// `parse` と違い、解析後の型テーブルとマクロをパーサーから取り出して所有できる。
+    pub fn into_parts(mut self) -> (TranslationUnit, TypeTable, HashMap<String, String>) {
+        let tu = self.parse();
+        (tu, self.type_table, self.defined_macros)
     }
 
     // stop_at_endif: true の場合、#elif/#else/#endif で停止
@@ -113,8 +169,13 @@ impl Parser {
     // 戻り値: (items, stop_reason)
     fn parse_items(&mut self, context: ParseContext, stop_at_endif: bool) -> (Vec<Item>, StopReason) {
         let mut items = Vec::new();
+        let mut leading_attributes: Vec<AttributeToken> = Vec::new();  // 次の宣言の前に書かれた属性
+        // 型キーワードで始まらない宣言の先頭（`uint32_t` や `size_t *` など、未知の型名と `*` の並び）
+        let mut pending_return_type: Option<Span> = None;
 
         while let Some(token) = self.lexer.next_token() {
+            let item_count = items.len();
+            let return_type_start = pending_return_type.take();
             // struct/union/enum内部でRightBraceを検出したら終了
             if matches!(context, ParseContext::InStruct | ParseContext::InUnion | ParseContext::InEnum) {
                 if matches!(token, Token::RightBrace(..)) {
@@ -126,11 +187,18 @@ impl Parser {
                 Token::BlockComment(BlockCommentToken { span }) => {
                     let text = self.lexer.input[span.byte_start_idx..span.byte_end_idx].to_string();
                     self.pending_comments.push(Comment::Block { text, span });
+                    pending_return_type = return_type_start;
                     continue;
                 },
                 Token::LineComment(LineCommentToken { span }) => {
                     let text = self.lexer.input[span.byte_start_idx..span.byte_end_idx].to_string();
                     self.pending_comments.push(Comment::Line { text, span });
+                    pending_return_type = return_type_start;
+                    continue;
+                },
+                Token::Attribute(attribute) => {
+                    leading_attributes.push(attribute);
+                    pending_return_type = return_type_start;
                     continue;
                 },
                 Token::Include(IncludeToken { span, filename }) => {
@@ -138,18 +206,51 @@ impl Parser {
                     let trivia = self.take_trivia();
                     
                     // ヘッダーファイルを解析（アクティブなブランチのみ）
-                    if self.is_current_branch_active() {
-                        self.parse_header_file(&filename);
+                    if self.is_current_branch_active() && self.preprocessor_config.resolve_includes {
+                        let is_quoted = Self::is_quoted_include(&text);
+                        self.parse_header_file(&filename, is_quoted, &span);
                     }
                     
                     items.push(Item::Include { span, text, filename, trivia });
                 },
-                Token::Define(DefineToken { span, macro_name, macro_value }) => {
+                Token::Define(DefineToken { span, macro_name, params, macro_value }) => {
                     let text = self.lexer.input[span.byte_start_idx..span.byte_end_idx].to_string();
                     let trivia = self.take_trivia();
                     // マクロを登録
                     self.defined_macros.insert(macro_name.clone(), macro_value.clone());
-                    items.push(Item::Define { span, text, macro_name, macro_value, trivia });
+                    items.push(Item::Define { span, text, macro_name, params, macro_value, trivia });
+                },
+                Token::ErrorDirective(ErrorDirectiveToken { span, message }) => {
+                    let text = self.lexer.input[span.byte_start_idx..span.byte_end_idx].to_string();
+                    let trivia = self.take_trivia();
+                    // 条件コンパイルで有効なブランチにある場合のみ診断として報告する
+                    if self.is_current_branch_active() {
+                        self.diagnostics.push(Diagnostic::new(
+                            span.clone(),
+                            DiagnosticSeverity::Error,
+                            format!("#error: {}", message),
+                            DiagnosticCode::Custom("CGH029".to_string()),
+                        ));
+                    }
+                    items.push(Item::ErrorDirective { span, text, message, trivia });
+                },
+                Token::WarningDirective(WarningDirectiveToken { span, message }) => {
+                    let text = self.lexer.input[span.byte_start_idx..span.byte_end_idx].to_string();
+                    let trivia = self.take_trivia();
+                    if self.is_current_branch_active() {
+                        self.diagnostics.push(Diagnostic::new(
+                            span.clone(),
+                            DiagnosticSeverity::Warning,
+                            format!("#warning: {}", message),
+                            DiagnosticCode::Custom("CGH029".to_string()),
+                        ));
+                    }
+                    items.push(Item::WarningDirective { span, text, message, trivia });
+                },
+                Token::Line(LineToken { span, line_number, file }) => {
+                    let text = self.lexer.input[span.byte_start_idx..span.byte_end_idx].to_string();
+                    let trivia = self.take_trivia();
+                    items.push(Item::LineDirective { span, text, line_number, file, trivia });
                 },
                 // Stage 1: 条件コンパイルブロック
                 Token::Ifdef(IfdefToken { span }) => {
@@ -199,20 +300,31 @@ impl Parser {
                 Token::Restrict(RestrictToken { span }) | Token::Atomic(AtomicToken { span }) |
                 Token::Int(IntToken { span }) | Token::Char(CharToken { span }) | Token::Float(FloatToken { span }) | 
                 Token::Double(DoubleToken { span }) | Token::Void(VoidToken { span }) | Token::Long(LongToken { span }) | 
-                Token::Short(ShortToken { span }) | Token::Signed(SignedToken { span }) | Token::Unsigned(UnsignedToken { span }) => {
+                Token::Short(ShortToken { span }) | Token::Signed(SignedToken { span }) | Token::Unsigned(UnsignedToken { span }) |
+                Token::Bool(BoolToken { span }) | Token::Complex(ComplexToken { span }) | Token::Imaginary(ImaginaryToken { span }) |
+                Token::Inline(InlineToken { span }) | Token::Noreturn(NoreturnToken { span }) | Token::ThreadLocal(ThreadLocalToken { span }) => {
                     let start_byte = span.byte_start_idx;
                     let mut end_byte = span.byte_end_idx;
                     let mut var_name = String::new();
                     let mut has_initializer = false;
+                    let mut initializer: Option<Initializer> = None;
+                    let mut array_dimensions = Vec::new();
                     let mut is_function = false;
                     let mut has_function_body = false;
+                    let mut unterminated_body = false;
                     let mut function_name = String::new();
                     let mut function_name_start = 0;
                     let mut params_start_byte = 0;
                     let mut params_end_byte = 0;
+                    let mut knr_names: Option<Vec<String>> = None;  // K&R 形式の識別子リスト
+                    let mut has_knr_declarations = false;
                     
                     loop {
                         match self.lexer.next_token() {
+                            // 引数リストの後の識別子は K&R 形式の引数宣言
+                            Some(Token::Ident(..)) if is_function => {
+                                has_knr_declarations = knr_names.is_some();
+                            },
                             Some(Token::Ident(IdentToken { span: id_span, name })) => {
                                 var_name = name.to_string();
                                 function_name = name.to_string();
@@ -241,32 +353,35 @@ impl Parser {
                                         None => break,
                                     }
                                 }
+                                knr_names = Self::knr_identifier_list(&self.lexer.input[params_start_byte..params_end_byte]);
                             },
-                            Some(Token::LeftBrace(..)) if is_function => {
+                            Some(Token::LeftBrace(LeftBraceToken { span: lbrace_span })) if is_function => {
                                 // 関数本体の開始
                                 has_function_body = true;
                                 // LeftBraceはすでにnext_token()で消費済み
-                                // 関数本体全体をスキップ
-                                let mut brace_depth = 1;
-                                loop {
-                                    match self.lexer.next_token() {
-                                        Some(Token::LeftBrace(..)) => brace_depth += 1,
-                                        Some(Token::RightBrace(RightBraceToken { span: rbrace_span })) => {
-                                            brace_depth -= 1;
-                                            end_byte = rbrace_span.byte_end_idx;
-                                            if brace_depth == 0 {
-                                                break;
-                                            }
-                                        },
-                                        Some(_) => continue,
-                                        None => break,
-                                    }
+                                // 関数本体全体をスキップ（閉じ括弧がなければ入力の終わりまでを本体とみなす）
+                                match self.skip_function_body(&lbrace_span) {
+                                    Some(rbrace_end) => end_byte = rbrace_end,
+                                    None => {
+                                        end_byte = self.lexer.input.trim_end().len();
+                                        unterminated_body = true;
+                                    },
                                 }
                                 break;
                             },
                             Some(Token::Equal(EqualToken { span: eq_span })) => {
                                 has_initializer = true;
                                 end_byte = eq_span.byte_end_idx;
+                                initializer = self.parse_initializer();
+                            },
+                            // 変数名の後の配列の次元
+                            Some(token @ Token::LeftBracket(..)) if !is_function && !var_name.is_empty() => {
+                                self.lexer.unget_token(token);
+                                array_dimensions = self.parse_array_dimensions();
+                            },
+                            // K&R 形式の引数宣言（int f(a) int a; { ... }）は本体まで読み進める
+                            Some(Token::Semicolon(..)) if has_knr_declarations => {
+                                continue;
                             },
                             Some(Token::Semicolon(SemicolonToken { span: semi_span })) => {
                                 end_byte = semi_span.byte_end_idx;
@@ -281,7 +396,9 @@ impl Parser {
                             Some(Token::Float(..)) | Some(Token::Double(..)) | 
                             Some(Token::Void(..)) | Some(Token::Long(..)) | 
                             Some(Token::Short(..)) | Some(Token::Signed(..)) | 
-                            Some(Token::Unsigned(..)) => {
+                            Some(Token::Unsigned(..)) | Some(Token::Bool(..)) |
+                            Some(Token::Complex(..)) | Some(Token::Imaginary(..)) |
+                            Some(Token::Inline(..)) | Some(Token::Noreturn(..)) | Some(Token::ThreadLocal(..)) => {
                                 continue;
                             },
                             Some(_) => {
@@ -303,9 +420,17 @@ impl Parser {
                         byte_end_idx: end_byte,
                     };
                     
+                    let specifiers = Self::declaration_specifiers(&text);
+                    
                     if is_function {
                         // 関数定義
-                        let full_prefix = self.lexer.input[start_byte..function_name_start].trim();
+                        // 関数指定子（inline など）は specifiers に分け、戻り値の型には含めない
+                        let full_prefix = self.lexer.input[start_byte..function_name_start]
+                            .split_whitespace()
+                            .filter(|word| !DECLARATION_SPECIFIERS.contains(word))
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        let full_prefix = full_prefix.as_str();
                         
                         // storage class を抽出
                         let storage_class = if full_prefix.starts_with("static ") {
@@ -323,45 +448,35 @@ impl Parser {
                             full_prefix.to_string()
                         };
                         
-                        let parameters = self.lexer.input[params_start_byte..params_end_byte].to_string();
+                        let mut parameters = self.lexer.input[params_start_byte..params_end_byte].to_string();
+                        if let (Some(names), true) = (&knr_names, has_function_body) {
+                            // K&R 形式は引数宣言から型を補って ANSI 形式の引数リストに正規化する
+                            let full_text = &self.lexer.input[start_byte..end_byte];
+                            let params_close = params_start_byte + parameters.rfind(')').unwrap_or(0) + 1;
+                            let body_open = start_byte + full_text.find('{').unwrap_or(full_text.len());
+                            let declarations = self.lexer.input.get(params_close..body_open).unwrap_or("");
+                            parameters = Self::normalize_knr_parameters(names, declarations);
+                        }
                         
                         // 関数本体があるかチェック
                         let body = if has_function_body {
                             // textから関数本体部分を抽出して再解析
                             // text全体から{ }を見つけて、その中を解析
                             let full_text = &self.lexer.input[start_byte..end_byte];
-                            if let Some(brace_start) = full_text.find('{') {
-                                if let Some(brace_end) = full_text.rfind('}') {
-                                    let body_text = &full_text[brace_start+1..brace_end];
-                                    // 新しいlexerとparserでbody_textを解析
-                                    let body_lexer = Lexer::new(body_text);
-                                    let mut body_parser = Parser::new(body_lexer);
-                                    // body_textは{}の中身なので、直接ステートメントを解析
-                                    let mut statements = Vec::new();
-                                    body_parser.type_table.push_scope();
-                                    loop {
-                                        if body_parser.lexer.peek_token().is_none() {
-                                            break;
-                                        }
-                                        if let Some(stmt) = body_parser.parse_statement() {
-                                            statements.push(stmt);
-                                        } else {
-                                            // 解析できない場合はスキップ
-                                            body_parser.lexer.next_token();
-                                        }
-                                    }
-                                    body_parser.type_table.pop_scope();
-                                    Some(statements)
-                                } else {
-                                    None
+                            match (full_text.find('{'), full_text.rfind('}')) {
+                                (Some(brace_start), _) if unterminated_body => {
+                                    Some(self.parse_body_statements(start_byte + brace_start + 1, end_byte))
+                                }
+                                (Some(brace_start), Some(brace_end)) => {
+                                    Some(self.parse_body_statements(start_byte + brace_start + 1, start_byte + brace_end))
                                 }
-                            } else {
-                                None
+                                _ => None,
                             }
                         } else {
                             None
                         };
                         
+                        let parameter_list = self.parse_parameter_declarations(&parameters);
                         let trivia = self.take_trivia();
                         items.push(Item::FunctionDecl {
                             span: final_span,
@@ -369,8 +484,11 @@ impl Parser {
                             return_type,
                             function_name,
                             parameters,
+                            parameter_list,
                             storage_class,
+                            specifiers,
                             body,
+                            attributes: Vec::new(),
                             trivia,
                         });
                     } else {
@@ -383,6 +501,7 @@ impl Parser {
                             let mut type_parser = Parser::new(type_lexer);
                             type_parser.parse_type()
                         };
+                        let var_type = var_type.map(|ty| Type { array_dimensions, ..ty });
                         
                         let trivia = self.take_trivia();
                         items.push(Item::VarDecl { 
@@ -390,13 +509,18 @@ impl Parser {
                             text,
                             var_name,
                             has_initializer,
+                            initializer,
                             var_type,
+                            specifiers,
+                            attributes: Vec::new(),
                             trivia,
                         });
                     }
                 },
                 Token::Struct(StructToken { span }) => {
                     // struct 宣言または構造体変数宣言
+                    // 前置コメントはメンバーの解析で取られないよう先に取り出す
+                    let trivia = self.take_trivia();
                     
                     let start_byte = span.byte_start_idx;
                     let mut end_byte = span.byte_end_idx;
@@ -406,14 +530,14 @@ impl Parser {
                     let mut parsed_successfully = false;
                     
                     // 次のトークンをチェック
-                    let next_token = self.lexer.next_token();
+                    let next_token = self.next_token_skipping_attributes();
                     
                     match next_token {
                         Some(Token::Ident(IdentToken { name, .. })) => {
                             struct_name = Some(name.to_string());
                             
                             // 構造体名の後をチェック
-                            match self.lexer.next_token() {
+                            match self.next_token_skipping_attributes() {
                                 Some(Token::LeftBrace(..)) => {
                                     // struct Name { ... }
                                     let (inner_items, _) = self.parse_items(ParseContext::InStruct, false);
@@ -517,7 +641,6 @@ impl Parser {
                         byte_start_idx: start_byte,
                         byte_end_idx: end_byte,
                     };
-                    let trivia = self.take_trivia();
                     items.push(Item::StructDecl { 
                         span: final_span, 
                         text,
@@ -525,6 +648,7 @@ impl Parser {
                         has_typedef,
                         variable_names: Vec::new(),  // TODO: 後で実装
                         members,
+                        attributes: Vec::new(),
                         trivia,
                     });
                 },
@@ -536,72 +660,59 @@ impl Parser {
                     let mut enum_name: Option<String> = None;
                     let has_typedef = false;
                     let mut variable_names = Vec::new();
-                    let mut variants = Vec::new();
+                    let mut variants: Vec<EnumVariant> = Vec::new();
                     let mut parsed_successfully = false;
                     
                     // 次のトークンをチェック
-                    let next_token = self.lexer.next_token();
+                    let next_token = self.next_token_skipping_attributes();
                     
                     match next_token {
                         Some(Token::Ident(IdentToken { name, .. })) => {
                             enum_name = Some(name.to_string());
                             
                             // enum名の後をチェック
-                            match self.lexer.next_token() {
+                            match self.next_token_skipping_attributes() {
                                 Some(Token::LeftBrace(..)) => {
                                     // enum Name { ... }
                                     // enum内部は列挙子（カンマ区切り）
                                     let mut current_name: Option<String> = None;
                                     let mut current_value: Option<i64> = None;
+                                    let mut current_explicit = false;
                                     let mut variant_start_line = self.lexer.line;
                                     let mut variant_start_col = self.lexer.column;
-                                    let mut expect_value = false;  // = の直後かどうか
                                     
                                     loop {
                                         match self.lexer.next_token() {
-                                            Some(Token::NumberLiteral(NumberLiteralToken { value, .. })) => {
-                                                if expect_value {
-                                                    // = の後の数値リテラル
-                                                    // suffixを除去 (u, U, l, L, ll, LL等)
-                                                    let value_without_suffix = value.trim_end_matches(|c: char| {
-                                                        c == 'u' || c == 'U' || c == 'l' || c == 'L'
-                                                    });
-                                                    
-                                                    // 16進数 (0x), 8進数 (0), 10進数に対応
-                                                    let parsed_value = if value_without_suffix.starts_with("0x") || value_without_suffix.starts_with("0X") {
-                                                        i64::from_str_radix(&value_without_suffix[2..], 16).ok()
-                                                    } else if value_without_suffix.starts_with("0") && value_without_suffix.len() > 1 {
-                                                        i64::from_str_radix(&value_without_suffix[1..], 8).ok()
-                                                    } else {
-                                                        value_without_suffix.parse::<i64>().ok()
-                                                    };
-                                                    current_value = parsed_value;
-                                                    expect_value = false;
-                                                }
-                                            },
                                             Some(Token::Equal(..)) => {
-                                                // = の後に数値が来る
-                                                expect_value = true;
+                                                // = の後の定数式を評価（前の列挙子やマクロも参照可能）
+                                                let mut expr_parser = crate::expression_parser::ExpressionParser::new(&mut self.lexer);
+                                                let expr = expr_parser.parse_expression();
+                                                expr_parser.finish();  // current_tokenをLexerに戻す
+                                                
+                                                let mut known_values = self.macro_values();
+                                                for variant in &variants {
+                                                    if let Some(value) = variant.value {
+                                                        known_values.insert(variant.name.clone(), value.to_string());
+                                                    }
+                                                }
+                                                current_value = expr.and_then(|expr| crate::const_eval::eval_const_expr(&expr, &known_values));
+                                                current_explicit = true;
                                             },
                                             Some(Token::Ident(IdentToken { name, span: id_span })) => {
-                                                if expect_value {
-                                                    // 後方互換性: Identとして数値が来る場合（本来はNumberLiteralが来るべき）
-                                                    if let Ok(val) = name.parse::<i64>() {
-                                                        current_value = Some(val);
-                                                    }
-                                                    expect_value = false;
-                                                } else if name == "," {
+                                                if &*name == "," {
                                                     // 前の列挙子を保存してリセット
                                                     if let Some(prev_name) = current_name.take() {
                                                         variants.push(EnumVariant {
                                                             name: prev_name,
                                                             value: current_value.take(),
+                                                            has_explicit_value: std::mem::take(&mut current_explicit),
                                                             span: Span::new(variant_start_line, variant_start_col, id_span.start_line, id_span.start_column),
                                                         });
                                                     }
                                                     variant_start_line = self.lexer.line;
                                                     variant_start_col = self.lexer.column;
                                                     current_value = None;
+                                                    current_explicit = false;
                                                 } else {
                                                     // 列挙子名
                                                     if let Some(prev_name) = current_name.take() {
@@ -609,6 +720,7 @@ impl Parser {
                                                         variants.push(EnumVariant {
                                                             name: prev_name,
                                                             value: current_value.take(),
+                                                            has_explicit_value: std::mem::take(&mut current_explicit),
                                                             span: Span::new(variant_start_line, variant_start_col, id_span.start_line, id_span.start_column),
                                                         });
                                                     }
@@ -623,6 +735,7 @@ impl Parser {
                                                     variants.push(EnumVariant {
                                                         name,
                                                         value: current_value.take(),
+                                                        has_explicit_value: std::mem::take(&mut current_explicit),
                                                         span: Span::new(variant_start_line, variant_start_col, self.lexer.line, self.lexer.column),
                                                     });
                                                 }
@@ -741,11 +854,14 @@ impl Parser {
                         has_typedef,
                         variable_names,
                         variants,
+                        attributes: Vec::new(),
                         trivia,
                     });
                 },
                 Token::Union(UnionToken { span }) => {
                     // union 宣言
+                    // 前置コメントはメンバーの解析で取られないよう先に取り出す
+                    let trivia = self.take_trivia();
                     
                     let start_byte = span.byte_start_idx;
                     let mut end_byte = span.byte_end_idx;
@@ -756,14 +872,14 @@ impl Parser {
                     let mut parsed_successfully = false;
                     
                     // 次のトークンをチェック
-                    let next_token = self.lexer.next_token();
+                    let next_token = self.next_token_skipping_attributes();
                     
                     match next_token {
                         Some(Token::Ident(IdentToken { name, .. })) => {
                             union_name = Some(name.to_string());
                             
                             // union名の後をチェック
-                            match self.lexer.next_token() {
+                            match self.next_token_skipping_attributes() {
                                 Some(Token::LeftBrace(..)) => {
                                     // union Name { ... }
                                     let (inner_items, _) = self.parse_items(ParseContext::InUnion, false);
@@ -874,7 +990,6 @@ impl Parser {
                         byte_start_idx: start_byte,
                         byte_end_idx: end_byte,
                     };
-                    let trivia = self.take_trivia();
                     items.push(Item::UnionDecl { 
                         span: final_span,
                         text,
@@ -882,6 +997,7 @@ impl Parser {
                         has_typedef,
                         variable_names,
                         members,
+                        attributes: Vec::new(),
                         trivia,
                     });
                 },
@@ -944,6 +1060,7 @@ impl Parser {
                                 has_typedef: true,
                                 variable_names: Vec::new(),  // TODO: 後で実装
                                 members: Vec::new(),  // TODO: 後で実装
+                                attributes: Vec::new(),
                                 trivia,
                             });
                             // 型テーブルに登録（アクティブなブランチのみ）
@@ -1008,6 +1125,7 @@ impl Parser {
                                 has_typedef: true,
                                 variable_names: variable_names.clone(),
                                 variants: Vec::new(),  // TODO: 後で実装
+                                attributes: Vec::new(),
                                 trivia,
                             });
                             // 型テーブルに登録（アクティブなブランチのみ）
@@ -1069,6 +1187,7 @@ impl Parser {
                                 has_typedef: true,
                                 variable_names: variable_names.clone(),
                                 members: Vec::new(),  // TODO: 後で実装
+                                attributes: Vec::new(),
                                 trivia,
                             });
                             // 型テーブルに登録（アクティブなブランチのみ）
@@ -1099,7 +1218,7 @@ impl Parser {
                                 byte_end_idx: end_byte,
                             };
                             let trivia = self.take_trivia();
-                            items.push(Item::TypedefDecl { span: final_span, text: text.clone(), trivia });
+                            items.push(Item::TypedefDecl { span: final_span, text: text.clone(), attributes: Vec::new(), trivia });
                             // 型テーブルに登録（アクティブなブランチのみ）
                             if self.is_current_branch_active() {
                                 self.register_typedef_name(&text);
@@ -1115,17 +1234,20 @@ impl Parser {
                         let mut end_byte = span.byte_end_idx;
                         let mut var_name = String::new();
                         let mut has_initializer = false;
+                        let mut initializer: Option<Initializer> = None;
                         
                         // 次の識別子が変数名
-                        if let Some(Token::Ident(IdentToken { span: var_span, name: vname })) = self.lexer.next_token() {
-                            var_name = vname;
+                        if let Some(Token::Ident(IdentToken { span: var_span, name: vname })) = self.next_token_skipping_attributes() {
+                            var_name = vname.to_string();
                             end_byte = var_span.byte_end_idx;
+                            let array_dimensions = self.parse_array_dimensions();
                             
                             // セミコロンまたは初期化子を探す
                             loop {
                                 match self.lexer.next_token() {
                                     Some(Token::Equal(..)) => {
                                         has_initializer = true;
+                                        initializer = self.parse_initializer();
                                     },
                                     Some(Token::Semicolon(SemicolonToken { span: semi_span })) => {
                                         end_byte = semi_span.byte_end_idx;
@@ -1152,10 +1274,13 @@ impl Parser {
                             };
                             
                             // 型情報を作成（簡易版 - Intをプレースホルダーとして使用）
-                            let var_type = Some(crate::type_system::Type::new(
+                            let mut var_type = crate::type_system::Type::new(
                                 crate::type_system::BaseType::Int,
                                 span.clone()
-                            ));
+                            );
+                            var_type.alias = Some(name.to_string());
+                            var_type.array_dimensions = array_dimensions;
+                            let var_type = Some(var_type);
                             
                             let trivia = self.take_trivia();
                             items.push(Item::VarDecl {
@@ -1163,26 +1288,110 @@ impl Parser {
                                 text,
                                 var_name,
                                 has_initializer,
+                                initializer,
                                 var_type,
+                                specifiers: Vec::new(),
+                                attributes: Vec::new(),
                                 trivia,
                             });
+                        } else {
+                            // `Node *make(...)` のように名前の前に `*` が続く場合は、関数の戻り値の型として残す
+                            pending_return_type = Some(span);
+                        }
+                    } else if matches!(context, ParseContext::TopLevel)
+                        && matches!(self.lexer.peek_token(), Some(Token::LeftParen(..)))
+                    {
+                        // 型キーワードで始まらない関数定義: foo(void) { ... }（暗黙の int）や uint32_t foo(void) { ... }
+                        if let Some(item) = self.parse_function_without_type_keyword(return_type_start, span, name.to_string()) {
+                            items.push(item);
                         }
                     } else {
-                        // 型名ではない識別子は無視
+                        // 型名ではない識別子は、続く関数定義の戻り値の型（未知の typedef 名など）になりうるので覚えておく
+                        pending_return_type = Some(return_type_start.unwrap_or(span));
                         continue;
                     }
                 },
+                Token::Asterisk(..) if return_type_start.is_some() => {
+                    pending_return_type = return_type_start;
+                    continue;
+                },
+                Token::Error(ErrorToken { span, message }) => {
+                    self.diagnostics.push(Diagnostic::new(
+                        span,
+                        DiagnosticSeverity::Error,
+                        message,
+                        DiagnosticCode::Custom("CGH013".to_string()),
+                    ));
+                    continue;
+                },
                 _ => {
                     continue;
                 }
             }
+
+            if items.len() > item_count {
+                if let Some(item) = items.last_mut() {
+                    self.attach_attributes(item, &leading_attributes);
+                }
+                leading_attributes.clear();
+            }
         }
 
         (items, StopReason::Eof)
     }
 
+    /// 属性トークンを読み飛ばして次のトークンを返す（`struct __attribute__((packed)) S` など）
+    fn next_token_skipping_attributes(&mut self) -> Option<Token> {
+        loop {
+            match self.lexer.next_token() {
+                Some(Token::Attribute(..)) => continue,
+                token => return token,
+            }
+        }
+    }
+
+    /// 宣言の前に書かれた属性を宣言の範囲とテキストに含め、宣言の属性を attributes に設定する
+    fn attach_attributes(&self, item: &mut Item, leading_attributes: &[AttributeToken]) {
+        let (Item::TypedefDecl { span, text, attributes, .. }
+        | Item::VarDecl { span, text, attributes, .. }
+        | Item::StructDecl { span, text, attributes, .. }
+        | Item::EnumDecl { span, text, attributes, .. }
+        | Item::UnionDecl { span, text, attributes, .. }
+        | Item::FunctionDecl { span, text, attributes, .. }) = item
+        else {
+            return;
+        };
+        if let Some(first) = leading_attributes.first().filter(|first| first.span.byte_start_idx < span.byte_start_idx) {
+            span.start_line = first.span.start_line;
+            span.start_column = first.span.start_column;
+            span.byte_start_idx = first.span.byte_start_idx;
+            *text = self.lexer.input[span.byte_start_idx..span.byte_end_idx].to_string();
+        }
+        *attributes = Self::declaration_attributes(text);
+    }
+
+    /// 宣言のテキストから属性を取り出す
+    /// 本体の `{ }` や引数の `( )` の中の属性はメンバーや引数のものなので含めない
+    fn declaration_attributes(text: &str) -> Vec<String> {
+        if !text.contains("__attribute") && !text.contains("[[") {
+            return Vec::new();
+        }
+        let mut lexer = Lexer::from_borrowed(text);
+        let mut depth = 0i32;
+        let mut attributes = Vec::new();
+        while let Some(token) = lexer.next_token() {
+            match token {
+                Token::LeftBrace(..) | Token::LeftParen(..) => depth += 1,
+                Token::RightBrace(..) | Token::RightParen(..) => depth -= 1,
+                Token::Attribute(AttributeToken { attributes: found, .. }) if depth == 0 => attributes.extend(found),
+                _ => {}
+            }
+        }
+        attributes
+    }
+
     // Stage 2: 条件コンパイルブロックを解析（#ifdef から #endif まで）
-    // #elif/#else も子ブロックとして扱う
+    // #elif / #else のブランチはブロック自身のフィールドとして保持する
     fn parse_conditional_block(&mut self, context: ParseContext, start_span: Span, directive_type: &str) -> Item {
         let condition = self.extract_condition(&start_span);
         
@@ -1190,148 +1399,73 @@ impl Parser {
         let condition_result = self.evaluate_condition(directive_type, &condition);
         
         // ifdef_stackに追加（型登録用）
-        let is_active = condition_result && self.is_current_branch_active();
+        let parent_active = self.is_current_branch_active();
         self.ifdef_stack.push(IfdefContext {
             condition: condition.clone(),
-            is_active,
+            is_active: condition_result && parent_active,
             seen_else: false,
         });
         
         // このブロック（#ifdef/#ifndef/#if）内のアイテムを解析
-        let (mut block_items, stop_reason) = self.parse_items(context, true);
-        
-        // parse_items が終了した理由を確認（#elif, #else, #endif のいずれか、またはEOF）
-        match stop_reason {
-            StopReason::Elif(span) => {
-                // スタックを更新（elifは新しい条件）
-                if let Some(ctx) = self.ifdef_stack.last_mut() {
-                    ctx.is_active = false;  // 現在のブランチは終了
-                }
-                
-                // #elif ブロックを子アイテムとして追加し、再帰的に処理
-                let elif_block = self.parse_conditional_block(context, span, "elif");
-                
-                // end_span は #elif ブロックの end_span を使う
-                let end_span = if let Item::ConditionalBlock { end_span, .. } = &elif_block {
-                    end_span.clone()
-                } else {
-                    start_span.clone()
-                };
-                
-                block_items.push(elif_block);
-                
-                // スタックから削除（elifブランチで追加されているので）
-                self.ifdef_stack.pop();
-                
-                return Item::ConditionalBlock {
-                    directive_type: directive_type.to_string(),
-                    condition: condition.clone(),
-                    condition_result,
-                    items: block_items,
-                    start_span,
-                    end_span,
-                    trivia: Trivia::empty(),
-                };
-            },
-            StopReason::Else(span) => {
-                // elseブランチに入る：条件を反転
-                // 親スコープがアクティブかを先に計算
-                let parent_active = if self.ifdef_stack.len() > 1 {
-                    self.ifdef_stack[..self.ifdef_stack.len()-1].iter().all(|ctx| ctx.is_active)
-                } else {
-                    true
-                };
-                
-                if let Some(ctx) = self.ifdef_stack.last_mut() {
-                    ctx.is_active = !ctx.is_active && parent_active;
-                    ctx.seen_else = true;
-                }
-                
-                // #else ブロックを子アイテムとして追加
-                let (else_items, end_reason) = self.parse_items(context, true);
-                
-                // #else の後は必ず #endif が来るはず
-                let end_span = if let StopReason::Endif(span) = end_reason {
-                    span
-                } else {
-                    span.clone()
-                };
-                
-                let else_block = Item::ConditionalBlock {
-                    directive_type: "else".to_string(),
-                    condition: String::new(),
-                    condition_result: !condition_result,  // elseはifの逆
-                    items: else_items,
-                    start_span: span.clone(),
-                    end_span: end_span.clone(),
-                    trivia: Trivia::empty(),
-                };
-                block_items.push(else_block);
-                
-                // #endif を子アイテムとして追加
-                block_items.push(Item::ConditionalBlock {
-                    directive_type: "endif".to_string(),
-                    condition: String::new(),
-                    condition_result: true,  // endifは常にtrue
-                    items: Vec::new(),
-                    start_span: end_span.clone(),
-                    end_span: end_span.clone(),
-                    trivia: Trivia::empty(),
-                });
-                
-                // スタックから削除
-                self.ifdef_stack.pop();
-                
-                return Item::ConditionalBlock {
-                    directive_type: directive_type.to_string(),
-                    condition: condition.clone(),
-                    condition_result,
-                    items: block_items,
-                    start_span,
-                    end_span,
-                    trivia: Trivia::empty(),
-                };
-            },
-            StopReason::Endif(span) => {
-                // #endif で終了
-                let end_span = span;
-                
-                // #endif を子アイテムとして追加
-                block_items.push(Item::ConditionalBlock {
-                    directive_type: "endif".to_string(),
-                    condition: String::new(),
-                    condition_result: true,  // endifは常にtrue
-                    items: Vec::new(),
-                    start_span: end_span.clone(),
-                    end_span: end_span.clone(),
-                    trivia: Trivia::empty(),
-                });
-                
-                // スタックから削除
-                self.ifdef_stack.pop();
-                
-                return Item::ConditionalBlock {
-                    directive_type: directive_type.to_string(),
-                    condition: condition.clone(),
-                    condition_result,
-                    items: block_items,
-                    start_span,
-                    end_span,
-                    trivia: Trivia::empty(),
-                };
-            },
-            StopReason::Eof => {
+        let (items, mut stop_reason) = self.parse_items(context, true);
+        
+        // いずれかのブランチが既に選ばれたか（#elif / #else は前のブランチが選ばれていない場合のみ有効）
+        let mut branch_taken = condition_result;
+        let mut elif_branches = Vec::new();
+        let mut else_branch = None;
+        let mut else_span = None;
+        
+        // parse_items が終了した理由（#elif, #else, #endif のいずれか、またはEOF）に応じて続きのブランチを解析
+        let end_span = loop {
+            match stop_reason {
+                StopReason::Elif(span) => {
+                    let elif_condition = self.extract_condition(&span);
+                    let elif_result = self.evaluate_condition("elif", &elif_condition);
+                    if let Some(ctx) = self.ifdef_stack.last_mut() {
+                        ctx.is_active = !branch_taken && elif_result && parent_active;
+                    }
+                    branch_taken |= elif_result;
+                    
+                    let (branch_items, next_reason) = self.parse_items(context, true);
+                    elif_branches.push(ElifBranch {
+                        condition: elif_condition,
+                        condition_result: elif_result,
+                        span,
+                        items: branch_items,
+                    });
+                    stop_reason = next_reason;
+                },
+                StopReason::Else(span) => {
+                    if let Some(ctx) = self.ifdef_stack.last_mut() {
+                        ctx.is_active = !branch_taken && parent_active;
+                        ctx.seen_else = true;
+                    }
+                    
+                    let (branch_items, next_reason) = self.parse_items(context, true);
+                    else_branch = Some(branch_items);
+                    else_span = Some(span);
+                    stop_reason = next_reason;
+                },
+                StopReason::Endif(span) => break span,
                 // EOF など、#endif がない場合
-                return Item::ConditionalBlock {
-                    directive_type: directive_type.to_string(),
-                    condition: condition.clone(),
-                    condition_result,
-                    items: block_items,
-                    start_span: start_span.clone(),
-                    end_span: start_span,
-                    trivia: Trivia::empty(),
-                };
+                StopReason::Eof => break start_span.clone(),
             }
+        };
+        
+        // スタックから削除
+        self.ifdef_stack.pop();
+        
+        Item::ConditionalBlock {
+            directive_type: directive_type.to_string(),
+            condition,
+            condition_result,
+            items,
+            elif_branches,
+            else_branch,
+            else_span,
+            start_span,
+            end_span,
+            trivia: Trivia::empty(),
         }
     }
 
@@ -1376,102 +1510,21 @@ impl Parser {
         }
     }
 
-    // #if の式を評価（簡易実装）
+    // #if の式を評価（定数式評価器を使用、評価できない場合はfalse）
     fn evaluate_if_expression(&self, expr: &str) -> bool {
-        let expr = expr.trim();
-        
-        // 論理演算子 && と || のサポート（最優先で処理）
-        if expr.contains("&&") {
-            let parts: Vec<&str> = expr.split("&&").collect();
-            return parts.iter().all(|p| self.evaluate_if_expression(p.trim()));
-        }
-        
-        if expr.contains("||") {
-            let parts: Vec<&str> = expr.split("||").collect();
-            return parts.iter().any(|p| self.evaluate_if_expression(p.trim()));
-        }
-        
-        // defined(MACRO) のパターンをチェック
-        if let Some(rest) = expr.strip_prefix("defined(") {
-            if let Some(macro_name) = rest.strip_suffix(')') {
-                let macro_name = macro_name.trim();
-                return self.defined_macros.contains_key(macro_name) || 
-                       self.preprocessor_config.is_macro_defined(macro_name);
-            }
-        }
-        
-        // defined MACRO のパターンをチェック
-        if let Some(macro_name) = expr.strip_prefix("defined ") {
-            let macro_name = macro_name.trim();
-            return self.defined_macros.contains_key(macro_name) || 
-                   self.preprocessor_config.is_macro_defined(macro_name);
-        }
-        
-        // !defined(MACRO) のパターン
-        if let Some(rest) = expr.strip_prefix("!defined(") {
-            if let Some(macro_name) = rest.strip_suffix(')') {
-                let macro_name = macro_name.trim();
-                return !self.defined_macros.contains_key(macro_name) && 
-                       !self.preprocessor_config.is_macro_defined(macro_name);
-            }
-        }
-        
-        // 数値リテラルの評価
-        if let Ok(num) = expr.parse::<i64>() {
-            return num != 0;
-        }
-        
-        // マクロ名の展開と評価
-        if let Some(value) = self.defined_macros.get(expr) {
-            if let Ok(num) = value.parse::<i64>() {
-                return num != 0;
-            }
-            return !value.is_empty();
-        }
-        
-        // 比較演算子のサポート（簡易版）
-        for op in &["==", "!=", ">=", "<=", ">", "<"] {
-            if expr.contains(op) {
-                let parts: Vec<&str> = expr.split(op).collect();
-                if parts.len() == 2 {
-                    let left = self.evaluate_macro_value(parts[0].trim());
-                    let right = self.evaluate_macro_value(parts[1].trim());
-                    
-                    return match *op {
-                        "==" => left == right,
-                        "!=" => left != right,
-                        ">" => left > right,
-                        "<" => left < right,
-                        ">=" => left >= right,
-                        "<=" => left <= right,
-                        _ => false,
-                    };
-                }
-            }
-        }
-        
-        // デフォルトはfalse（未定義のマクロ）
-        false
+        crate::const_eval::eval_preprocessor_condition(expr, &self.macro_values())
+            .is_some_and(|value| value != 0)
     }
 
-    // マクロ値を数値として評価
-    fn evaluate_macro_value(&self, expr: &str) -> i64 {
-        let expr = expr.trim();
-        
-        // 数値リテラル
-        if let Ok(num) = expr.parse::<i64>() {
-            return num;
-        }
-        
-        // マクロ名の展開
-        if let Some(value) = self.defined_macros.get(expr) {
-            if let Ok(num) = value.parse::<i64>() {
-                return num;
-            }
+    // 定数式評価用のマクロ表（#define と設定ファイルの defines を統合）
+    fn macro_values(&self) -> HashMap<String, String> {
+        let mut macros = self.defined_macros.clone();
+        for def in &self.preprocessor_config.defines {
+            // "MACRO" または "MACRO=value" の形式に対応
+            let (name, value) = def.split_once('=').unwrap_or((def.as_str(), "1"));
+            macros.entry(name.to_string()).or_insert_with(|| value.to_string());
         }
-        
-        // 評価できない場合は0
-        0
+        macros
     }
 
     /// pending_commentsを取り出してTriviaを作成
@@ -1510,87 +1563,6 @@ impl Parser {
         }
     }
 
-    /// Get the span from a token
-    fn get_token_span(token: &Token) -> Span {
-        match token {
-            Token::BlockComment(t) => t.span.clone(),
-            Token::Include(t) => t.span.clone(),
-            Token::Define(t) => t.span.clone(),
-            Token::Semicolon(t) => t.span.clone(),
-            Token::Equal(t) => t.span.clone(),
-            Token::Asterisk(t) => t.span.clone(),
-            Token::NumberLiteral(t) => t.span.clone(),
-            Token::FloatLiteral(t) => t.span.clone(),
-            Token::Ident(t) => t.span.clone(),
-            Token::Auto(t) => t.span.clone(),
-            Token::Register(t) => t.span.clone(),
-            Token::Static(t) => t.span.clone(),
-            Token::Extern(t) => t.span.clone(),
-            Token::Typedef(t) => t.span.clone(),
-            Token::Const(t) => t.span.clone(),
-            Token::Volatile(t) => t.span.clone(),
-            Token::Restrict(t) => t.span.clone(),
-            Token::Atomic(t) => t.span.clone(),
-            Token::Int(t) => t.span.clone(),
-            Token::Char(t) => t.span.clone(),
-            Token::Float(t) => t.span.clone(),
-            Token::Double(t) => t.span.clone(),
-            Token::Void(t) => t.span.clone(),
-            Token::Long(t) => t.span.clone(),
-            Token::Short(t) => t.span.clone(),
-            Token::Signed(t) => t.span.clone(),
-            Token::Unsigned(t) => t.span.clone(),
-            Token::Struct(t) => t.span.clone(),
-            Token::Enum(t) => t.span.clone(),
-            Token::Union(t) => t.span.clone(),
-            Token::LeftBrace(t) => t.span.clone(),
-            Token::RightBrace(t) => t.span.clone(),
-            Token::LeftParen(t) => t.span.clone(),
-            Token::RightParen(t) => t.span.clone(),
-            Token::Ifdef(t) => t.span.clone(),
-            Token::Ifndef(t) => t.span.clone(),
-            Token::If(t) => t.span.clone(),
-            Token::Elif(t) => t.span.clone(),
-            Token::Else(t) => t.span.clone(),
-            Token::Endif(t) => t.span.clone(),
-            Token::LineComment(t) => t.span.clone(),
-            Token::Plus(t) => t.span.clone(),
-            Token::Minus(t) => t.span.clone(),
-            Token::Slash(t) => t.span.clone(),
-            Token::Percent(t) => t.span.clone(),
-            Token::EqualEqual(t) => t.span.clone(),
-            Token::NotEqual(t) => t.span.clone(),
-            Token::LessThan(t) => t.span.clone(),
-            Token::LessThanOrEqual(t) => t.span.clone(),
-            Token::GreaterThan(t) => t.span.clone(),
-            Token::GreaterThanOrEqual(t) => t.span.clone(),
-            Token::Ampersand(t) => t.span.clone(),
-            Token::AmpersandAmpersand(t) => t.span.clone(),
-            Token::Pipe(t) => t.span.clone(),
-            Token::PipePipe(t) => t.span.clone(),
-            Token::Caret(t) => t.span.clone(),
-            Token::Tilde(t) => t.span.clone(),
-            Token::Exclamation(t) => t.span.clone(),
-            Token::LeftShift(t) => t.span.clone(),
-            Token::RightShift(t) => t.span.clone(),
-            Token::LeftBracket(t) => t.span.clone(),
-            Token::RightBracket(t) => t.span.clone(),
-            Token::Question(t) => t.span.clone(),
-            Token::Colon(t) => t.span.clone(),
-            Token::Comma(t) => t.span.clone(),
-            Token::Dot(t) => t.span.clone(),
-            Token::Arrow(t) => t.span.clone(),
-            Token::PlusPlus(t) => t.span.clone(),
-            Token::MinusMinus(t) => t.span.clone(),
-            Token::Return(t) => t.span.clone(),
-            Token::IfKeyword(t) => t.span.clone(),
-            Token::ElseKeyword(t) => t.span.clone(),
-            Token::While(t) => t.span.clone(),
-            Token::For(t) => t.span.clone(),
-            Token::Error(t) => t.span.clone(),
-        }
-    }
-
     /// Parse a C type (base type with optional qualifiers and pointer layers)
     /// Returns None if the current token doesn't start a type
     pub fn parse_type(&mut self) -> Option<Type> {
@@ -1603,7 +1575,7 @@ impl Parser {
         // Phase 1: Parse base qualifiers and base type
         loop {
             let token = self.lexer.next_token()?;
-            let token_span = Self::get_token_span(&token);
+            let token_span = token.span().clone();
             
             last_span = Some(token_span.clone());
 
@@ -1621,47 +1593,41 @@ impl Parser {
                 Token::Atomic(_) => {
                     base_qualifiers.push(TypeQualifier::Atomic);
                 }
+                // 関数指定子・スレッド記憶域指定子は型に影響しないため読み飛ばす
+                Token::Inline(_) | Token::Noreturn(_) | Token::ThreadLocal(_) => {}
                 // Base types
                 Token::Void(_) => {
                     base_type = Some(BaseType::Void);
                     break;
                 }
-                Token::Char(_) => {
-                    base_type = Some(BaseType::Char);
-                    break;
-                }
-                Token::Short(_) => {
-                    base_type = Some(BaseType::Short);
-                    break;
-                }
-                Token::Int(_) => {
-                    base_type = Some(BaseType::Int);
-                    break;
-                }
-                Token::Long(_) => {
-                    base_type = Some(BaseType::Long);
+                Token::Char(_) | Token::Short(_) | Token::Int(_) | Token::Long(_)
+                | Token::Double(_) | Token::Signed(_) | Token::Unsigned(_) => {
+                    // unsigned long long int のように複数トークンで1つの型になる
+                    let (arithmetic_type, end) = self.parse_arithmetic_specifiers(&token, &mut base_qualifiers);
+                    base_type = Some(arithmetic_type);
+                    last_span = Some(end);
                     break;
                 }
                 Token::Float(_) => {
                     base_type = Some(BaseType::Float);
                     break;
                 }
-                Token::Double(_) => {
-                    base_type = Some(BaseType::Double);
+                Token::Bool(_) => {
+                    base_type = Some(BaseType::Bool);
                     break;
                 }
-                Token::Signed(_) => {
-                    base_type = Some(BaseType::Signed);
+                Token::Complex(_) => {
+                    base_type = Some(BaseType::Complex);
                     break;
                 }
-                Token::Unsigned(_) => {
-                    base_type = Some(BaseType::Unsigned);
+                Token::Imaginary(_) => {
+                    base_type = Some(BaseType::Imaginary);
                     break;
                 }
                 Token::Struct(_) => {
                     // struct [name] を解析
                     let struct_name = if let Some(Token::Ident(IdentToken { name, .. })) = self.lexer.next_token() {
-                        Some(name)
+                        Some(name.to_string())
                     } else {
                         None
                     };
@@ -1671,7 +1637,7 @@ impl Parser {
                 Token::Union(_) => {
                     // union [name] を解析
                     let union_name = if let Some(Token::Ident(IdentToken { name, .. })) = self.lexer.next_token() {
-                        Some(name)
+                        Some(name.to_string())
                     } else {
                         None
                     };
@@ -1681,7 +1647,7 @@ impl Parser {
                 Token::Enum(_) => {
                     // enum [name] を解析
                     let enum_name = if let Some(Token::Ident(IdentToken { name, .. })) = self.lexer.next_token() {
-                        Some(name)
+                        Some(name.to_string())
                     } else {
                         None
                     };
@@ -1691,7 +1657,7 @@ impl Parser {
                 Token::Ident(IdentToken { name, .. }) => {
                     // typedef済みの型名を許容
                     if self.type_table.is_type_name(&name) {
-                        alias_name = Some(name.clone());
+                        alias_name = Some(name.to_string());
                         if let Some(type_info) = self.type_table.get_type_info(&name) {
                             base_qualifiers.extend(type_info.base_qualifiers.clone());
                             inherited_pointer_layers = type_info.pointer_layers.clone();
@@ -1750,19 +1716,19 @@ impl Parser {
                         match next_token {
                             Token::Const(_) => {
                                 qualifiers.push(TypeQualifier::Const);
-                                end_span = Self::get_token_span(&next_token);
+                                end_span = next_token.span().clone();
                             }
                             Token::Volatile(_) => {
                                 qualifiers.push(TypeQualifier::Volatile);
-                                end_span = Self::get_token_span(&next_token);
+                                end_span = next_token.span().clone();
                             }
                             Token::Restrict(_) => {
                                 qualifiers.push(TypeQualifier::Restrict);
-                                end_span = Self::get_token_span(&next_token);
+                                end_span = next_token.span().clone();
                             }
                             Token::Atomic(_) => {
                                 qualifiers.push(TypeQualifier::Atomic);
-                                end_span = Self::get_token_span(&next_token);
+                                end_span = next_token.span().clone();
                             }
                             Token::Asterisk(next_ast) => {
                                 // Another asterisk - save current layer and continue with outer loop
@@ -1792,19 +1758,19 @@ impl Parser {
                                     match qual_token {
                                         Token::Const(_) => {
                                             new_qualifiers.push(TypeQualifier::Const);
-                                            end_span = Self::get_token_span(&qual_token);
+                                            end_span = qual_token.span().clone();
                                         }
                                         Token::Volatile(_) => {
                                             new_qualifiers.push(TypeQualifier::Volatile);
-                                            end_span = Self::get_token_span(&qual_token);
+                                            end_span = qual_token.span().clone();
                                         }
                                         Token::Restrict(_) => {
                                             new_qualifiers.push(TypeQualifier::Restrict);
-                                            end_span = Self::get_token_span(&qual_token);
+                                            end_span = qual_token.span().clone();
                                         }
                                         Token::Atomic(_) => {
                                             new_qualifiers.push(TypeQualifier::Atomic);
-                                            end_span = Self::get_token_span(&qual_token);
+                                            end_span = qual_token.span().clone();
                                         }
                                         Token::Asterisk(third_ast) => {
                                             // Third asterisk - save current and continue outer loop
@@ -1830,6 +1796,8 @@ impl Parser {
                                                 new_qualifiers,
                                                 new_asterisk_span,
                                             ));
+                                            // 宣言子の名前などは呼び出し側で読むため戻す
+                                            self.lexer.unget_token(qual_token);
                                             break 'pointer_loop;
                                         }
                                     }
@@ -1841,6 +1809,8 @@ impl Parser {
                                     qualifiers,
                                     asterisk_span,
                                 ));
+                                // 宣言子の名前などは呼び出し側で読むため戻す
+                                self.lexer.unget_token(next_token);
                                 break 'pointer_loop;
                             }
                         }
@@ -1866,6 +1836,31 @@ impl Parser {
         Some(ty)
     }
     
+    /// 最初の型指定子に続く組み合わせ可能な型指定子を読み進め、正規化した基本型と末尾のSpanを返す
+    ///
+    /// 指定子の間や後ろの型修飾子（`unsigned const int` / `int const` など）は base_qualifiers に加える。
+    fn parse_arithmetic_specifiers(&mut self, first: &Token, base_qualifiers: &mut Vec<TypeQualifier>) -> (BaseType, Span) {
+        let mut specifiers: Vec<&str> = arithmetic_specifier_keyword(first).into_iter().collect();
+        let mut end_span = first.span().clone();
+
+        while let Some(token) = self.lexer.peek_token() {
+            match token {
+                Token::Const(_) => base_qualifiers.push(TypeQualifier::Const),
+                Token::Volatile(_) => base_qualifiers.push(TypeQualifier::Volatile),
+                Token::Restrict(_) => base_qualifiers.push(TypeQualifier::Restrict),
+                Token::Atomic(_) => base_qualifiers.push(TypeQualifier::Atomic),
+                _ => match arithmetic_specifier_keyword(&token) {
+                    Some(keyword) => specifiers.push(keyword),
+                    None => break,
+                },
+            }
+            self.lexer.next_token();
+            end_span = token.span().clone();
+        }
+
+        (BaseType::from_specifiers(&specifiers).unwrap_or(BaseType::Int), end_span)
+    }
+
     /// typedef宣言用：型と型名（declarator）を両方パース
     /// parse_type()と違い、型の後の識別子も返す
     pub fn parse_type_and_declarator(&mut self) -> Option<(Type, String)> {
@@ -1878,7 +1873,7 @@ impl Parser {
         // Phase 1: Parse base qualifiers and base type
         loop {
             let token = self.lexer.next_token()?;
-            let token_span = Self::get_token_span(&token);
+            let token_span = token.span().clone();
             
             last_span = Some(token_span.clone());
 
@@ -1896,41 +1891,35 @@ impl Parser {
                 Token::Atomic(_) => {
                     base_qualifiers.push(TypeQualifier::Atomic);
                 }
+                // 関数指定子・スレッド記憶域指定子は型に影響しないため読み飛ばす
+                Token::Inline(_) | Token::Noreturn(_) | Token::ThreadLocal(_) => {}
                 // Base types
                 Token::Void(_) => {
                     base_type = Some(BaseType::Void);
                     break;
                 }
-                Token::Char(_) => {
-                    base_type = Some(BaseType::Char);
-                    break;
-                }
-                Token::Short(_) => {
-                    base_type = Some(BaseType::Short);
-                    break;
-                }
-                Token::Int(_) => {
-                    base_type = Some(BaseType::Int);
-                    break;
-                }
-                Token::Long(_) => {
-                    base_type = Some(BaseType::Long);
+                Token::Char(_) | Token::Short(_) | Token::Int(_) | Token::Long(_)
+                | Token::Double(_) | Token::Signed(_) | Token::Unsigned(_) => {
+                    // unsigned long long int のように複数トークンで1つの型になる
+                    let (arithmetic_type, end) = self.parse_arithmetic_specifiers(&token, &mut base_qualifiers);
+                    base_type = Some(arithmetic_type);
+                    last_span = Some(end);
                     break;
                 }
                 Token::Float(_) => {
                     base_type = Some(BaseType::Float);
                     break;
                 }
-                Token::Double(_) => {
-                    base_type = Some(BaseType::Double);
+                Token::Bool(_) => {
+                    base_type = Some(BaseType::Bool);
                     break;
                 }
-                Token::Signed(_) => {
-                    base_type = Some(BaseType::Signed);
+                Token::Complex(_) => {
+                    base_type = Some(BaseType::Complex);
                     break;
                 }
-                Token::Unsigned(_) => {
-                    base_type = Some(BaseType::Unsigned);
+                Token::Imaginary(_) => {
+                    base_type = Some(BaseType::Imaginary);
                     break;
                 }
                 Token::Struct(_) => {
@@ -1938,7 +1927,7 @@ impl Parser {
                     // 次のトークンを確認（波括弧の場合は匿名struct）
                     match self.lexer.next_token() {
                         Some(Token::Ident(IdentToken { name, .. })) => {
-                            base_type = Some(BaseType::Struct(Some(name)));
+                            base_type = Some(BaseType::Struct(Some(name.to_string())));
                             break;
                         }
                         Some(Token::LeftBrace(_)) => {
@@ -1956,7 +1945,7 @@ impl Parser {
                     // 次のトークンを確認（波括弧の場合は匿名union）
                     match self.lexer.next_token() {
                         Some(Token::Ident(IdentToken { name, .. })) => {
-                            base_type = Some(BaseType::Union(Some(name)));
+                            base_type = Some(BaseType::Union(Some(name.to_string())));
                             break;
                         }
                         Some(Token::LeftBrace(_)) => {
@@ -1974,7 +1963,7 @@ impl Parser {
                     // 次のトークンを確認（波括弧の場合は匿名enum）
                     match self.lexer.next_token() {
                         Some(Token::Ident(IdentToken { name, .. })) => {
-                            base_type = Some(BaseType::Enum(Some(name)));
+                            base_type = Some(BaseType::Enum(Some(name.to_string())));
                             break;
                         }
                         Some(Token::LeftBrace(_)) => {
@@ -1989,7 +1978,7 @@ impl Parser {
                 }
                 Token::Ident(IdentToken { name, .. }) => {
                     if self.type_table.is_type_name(&name) {
-                        alias_name = Some(name.clone());
+                        alias_name = Some(name.to_string());
                         if let Some(type_info) = self.type_table.get_type_info(&name) {
                             base_qualifiers.extend(type_info.base_qualifiers.clone());
                             inherited_pointer_layers = type_info.pointer_layers.clone();
@@ -2042,25 +2031,27 @@ impl Parser {
                         match next_token {
                             Token::Const(_) => {
                                 qualifiers.push(TypeQualifier::Const);
-                                end_span = Self::get_token_span(&next_token);
+                                end_span = next_token.span().clone();
                             }
                             Token::Volatile(_) => {
                                 qualifiers.push(TypeQualifier::Volatile);
-                                end_span = Self::get_token_span(&next_token);
+                                end_span = next_token.span().clone();
                             }
                             Token::Restrict(_) => {
                                 qualifiers.push(TypeQualifier::Restrict);
-                                end_span = Self::get_token_span(&next_token);
+                                end_span = next_token.span().clone();
                             }
                             Token::Atomic(_) => {
                                 qualifiers.push(TypeQualifier::Atomic);
-                                end_span = Self::get_token_span(&next_token);
+                                end_span = next_token.span().clone();
                             }
                             Token::Asterisk(_) => {
                                 pointer_layers.push(crate::type_system::PointerLayer::with_qualifiers(
                                     qualifiers,
                                     asterisk_span,
                                 ));
+                                // 次の層の '*' として読み直す
+                                self.lexer.unget_token(next_token);
                                 continue 'pointer_loop;
                             }
                             Token::Ident(IdentToken { name, .. }) => {
@@ -2068,7 +2059,7 @@ impl Parser {
                                     qualifiers,
                                     asterisk_span,
                                 ));
-                                declarator_name = Some(name);
+                                declarator_name = Some(name.to_string());
                                 break 'pointer_loop;
                             }
                             _ => {
@@ -2082,7 +2073,7 @@ impl Parser {
                     }
                 }
                 Token::Ident(IdentToken { name, .. }) => {
-                    declarator_name = Some(name);
+                    declarator_name = Some(name.to_string());
                     break;
                 }
                 _ => {
@@ -2098,9 +2089,169 @@ impl Parser {
             end_span,
         );
         type_info.alias = alias_name;
+        if declarator_name.is_some() {
+            type_info.array_dimensions = self.parse_array_dimensions();
+        }
         
         declarator_name.map(|name| (type_info, name))
     }
+
+    /// 宣言子の名前に続く配列の次元 `[...]` を解析する
+    /// 括弧内の先頭にある `static` と修飾子（C99: `int a[const static 4]`）を記録し、残りを要素数の式とする
+    fn parse_array_dimensions(&mut self) -> Vec<crate::type_system::ArrayDimension> {
+        let mut dimensions = Vec::new();
+
+        while let Some(Token::LeftBracket(open)) = self.lexer.peek_token() {
+            self.lexer.next_token();
+            let mut qualifiers = Vec::new();
+            let mut is_static = false;
+            let mut size_start: Option<usize> = None;
+            let mut depth = 0usize;
+            let mut close_span = None;
+
+            while let Some(token) = self.lexer.next_token() {
+                match &token {
+                    Token::RightBracket(_) if depth == 0 => {
+                        close_span = Some(token.span().clone());
+                        break;
+                    }
+                    Token::Const(_) if size_start.is_none() => qualifiers.push(TypeQualifier::Const),
+                    Token::Volatile(_) if size_start.is_none() => qualifiers.push(TypeQualifier::Volatile),
+                    Token::Restrict(_) if size_start.is_none() => qualifiers.push(TypeQualifier::Restrict),
+                    Token::Atomic(_) if size_start.is_none() => qualifiers.push(TypeQualifier::Atomic),
+                    Token::Static(_) if size_start.is_none() => is_static = true,
+                    _ => {
+                        match &token {
+                            Token::LeftBracket(_) => depth += 1,
+                            Token::RightBracket(_) => depth -= 1,
+                            _ => {}
+                        }
+                        size_start.get_or_insert(token.span().byte_start_idx);
+                    }
+                }
+            }
+
+            // 閉じ括弧がなければ不完全な宣言子として打ち切る
+            let Some(close_span) = close_span else {
+                break;
+            };
+            // 記号トークンの終端は後続の文字を含むことがあるため、`]` の開始位置までを要素数の式とする
+            let size = size_start
+                .and_then(|start| self.lexer.input.get(start..close_span.byte_start_idx))
+                .map(|text| text.trim().to_string());
+            dimensions.push(crate::type_system::ArrayDimension {
+                size,
+                qualifiers,
+                is_static,
+                span: open.span.merge(&close_span),
+            });
+        }
+
+        dimensions
+    }
+
+    /// `=` の後の初期化子を解析する（宣言を区切る `,` と `;` は消費しない）
+    /// 閉じ括弧のない初期化子リストなど、解析できない場合は None を返す
+    fn parse_initializer(&mut self) -> Option<Initializer> {
+        match self.lexer.peek_token()? {
+            Token::LeftBrace(open) => {
+                self.lexer.next_token();
+                let mut elements = Vec::new();
+                loop {
+                    match self.lexer.peek_token()? {
+                        Token::RightBrace(close) => {
+                            self.lexer.next_token();
+                            return Some(Initializer::List { elements, span: open.span.merge(&close.span) });
+                        }
+                        // 要素の区切り（末尾のカンマを含む）
+                        Token::Comma(_) => {
+                            self.lexer.next_token();
+                        }
+                        Token::Semicolon(_) => return None,
+                        _ => elements.push(self.parse_initializer()?),
+                    }
+                }
+            }
+            _ => self.parse_initializer_value(),
+        }
+    }
+
+    /// 初期化子の1つの値を、括弧の外にある `,` `;` `}` の直前までのテキストとして読み取る
+    fn parse_initializer_value(&mut self) -> Option<Initializer> {
+        let mut depth = 0usize;
+        let mut first: Option<Span> = None;
+        let mut last: Option<Span> = None;
+        let mut terminator: Option<Span> = None;
+
+        while let Some(token) = self.lexer.peek_token() {
+            match token {
+                Token::Comma(_) | Token::Semicolon(_) | Token::RightBrace(_) if depth == 0 => {
+                    terminator = Some(token.into_span());
+                    break;
+                }
+                Token::LeftParen(_) | Token::LeftBracket(_) | Token::LeftBrace(_) => depth += 1,
+                Token::RightParen(_) | Token::RightBracket(_) | Token::RightBrace(_) => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            let span = token.into_span();
+            first.get_or_insert_with(|| span.clone());
+            last = Some(span);
+            self.lexer.next_token();
+        }
+
+        // 記号トークンの終端は後続の文字を含むことがあるため、区切りのトークンの開始位置までをテキストとする
+        let (first, last) = (first?, last?);
+        let end = terminator.as_ref().map_or(last.byte_end_idx, |t| t.byte_start_idx);
+        let raw = &self.lexer.input[first.byte_start_idx..end.max(first.byte_start_idx)];
+        let text = raw.trim().to_string();
+        let start_byte = first.byte_start_idx + (raw.len() - raw.trim_start().len());
+        let span = Span {
+            start_line: first.start_line,
+            start_column: first.start_column,
+            end_line: last.end_line,
+            end_column: last.end_column,
+            byte_start_idx: start_byte,
+            byte_end_idx: start_byte + text.len(),
+        };
+        Some(Initializer::Value { text, span })
+    }
+
+    /// 関数の引数リスト（`FunctionDecl` の `parameters`、括弧を含む）を引数ごとの型と名前に分解する
+    ///
+    /// 解析時点の型テーブルを使うため、typedef 名の引数も解決できる。
+    /// 名前のない引数（`(void)` や `int[]`）や解析できない引数は結果に含めない。
+    /// 返す Span は `parameters` の先頭を基準とする。
+    pub fn parse_parameter_declarations(&self, parameters: &str) -> Vec<(Type, String)> {
+        let offset = parameters.find('(').map_or(0, |pos| pos + 1);
+        let end = parameters.rfind(')').filter(|&pos| pos >= offset).unwrap_or(parameters.len());
+        let inner = &parameters[offset..end];
+
+        // 関数ポインタ引数 `void (*cb)(int, int)` の内側のカンマでは分割しない
+        let mut declarations = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (i, c) in inner.char_indices() {
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    declarations.push((start, i));
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        declarations.push((start, inner.len()));
+
+        declarations
+            .into_iter()
+            .filter_map(|(start, end)| {
+                let mut parser = Parser::new(Lexer::resume_at(&parameters[..offset + end], offset + start));
+                parser.type_table = self.type_table.clone();
+                parser.parse_type_and_declarator()
+            })
+            .collect()
+    }
     
     /// typedef宣言から型名と型情報を抽出して型テーブルに登録
     fn register_typedef_name(&mut self, typedef_text: &str) {
@@ -2158,7 +2309,7 @@ impl Parser {
                     // 波括弧の外の識別子のみを型名候補とする
                     // （丸括弧・角括弧内は型名の可能性あり：関数ポインタや配列）
                     if brace_depth == 0 {
-                        last_ident = Some(name);
+                        last_ident = Some(name.to_string());
                     }
                 }
                 Some(Token::Semicolon(_)) => {
@@ -2192,13 +2343,219 @@ impl Parser {
         }
     }
     
+    /// 関数本体（入力の `body_start..body_end` バイト、`{` と `}` の中身）を文のリストとして解析する
+    ///
+    /// 文の Span がファイル内の位置になるよう、入力の途中から字句解析を再開する。
+    /// 本体の中でも typedef 名を型として扱えるよう、それまでに登録された型テーブルを引き継ぐ
+    fn parse_body_statements(&self, body_start: usize, body_end: usize) -> Vec<Statement> {
+        let mut body_lexer = Lexer::resume_at(&self.lexer.input[..body_end], body_start);
+        if let Some(standard) = self.lexer.standard() {
+            body_lexer.set_standard(standard);
+        }
+        let mut body_parser = Parser::new(body_lexer);
+        body_parser.type_table = self.type_table.clone();
+        // 本体は{}の中身なので、直接ステートメントを解析
+        let mut statements = Vec::new();
+        body_parser.type_table.push_scope();
+        loop {
+            if body_parser.lexer.peek_token().is_none() {
+                break;
+            }
+            if let Some(stmt) = body_parser.parse_statement() {
+                statements.push(stmt);
+            } else {
+                // 解析できない場合はスキップ
+                body_parser.lexer.next_token();
+            }
+        }
+        body_parser.type_table.pop_scope();
+        statements
+    }
+
+    /// 関数本体の `{` の直後から対応する `}` までを読み飛ばし、`}` の終了位置を返す
+    ///
+    /// 閉じ括弧がないまま入力の終わりに達した場合は、開き括弧の位置にパース時の診断を追加して `None` を返す。
+    fn skip_function_body(&mut self, lbrace_span: &Span) -> Option<usize> {
+        let mut brace_depth = 1;
+        while let Some(token) = self.lexer.next_token() {
+            match token {
+                Token::LeftBrace(..) => brace_depth += 1,
+                Token::RightBrace(RightBraceToken { span: rbrace_span }) => {
+                    brace_depth -= 1;
+                    if brace_depth == 0 {
+                        return Some(rbrace_span.byte_end_idx);
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        self.diagnostics.push(Diagnostic::new(
+            lbrace_span.clone(),
+            DiagnosticSeverity::Error,
+            "関数本体の '{' に対応する '}' がありません".to_string(),
+            DiagnosticCode::Custom("CGH013".to_string()),
+        ));
+        None
+    }
+
+    /// 型キーワードで始まらない関数定義を解析する
+    ///
+    /// `return_type_start` は関数名より前に書かれた型（未知の typedef 名と `*` の並び）の先頭。
+    /// `None` なら戻り値の型を省略した関数定義 `foo(void) { ... }`（暗黙の int）として扱う。
+    /// 関数名の識別子は消費済みで、次のトークンが `(` であること。
+    /// 本体が続かない場合（マクロ呼び出しなど）は `None` を返す。
+    fn parse_function_without_type_keyword(&mut self, return_type_start: Option<Span>, name_span: Span, function_name: String) -> Option<Item> {
+        let span = return_type_start.unwrap_or_else(|| name_span.clone());
+        let start_byte = span.byte_start_idx;
+        let return_type = self.lexer.input[start_byte..name_span.byte_start_idx]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let params_start_byte = self.lexer.next_token()?.span().byte_start_idx;
+        let mut params_end_byte = params_start_byte;
+
+        // 引数リストを読み飛ばす
+        let mut paren_depth = 1;
+        while paren_depth > 0 {
+            match self.lexer.next_token()? {
+                Token::LeftParen(..) => paren_depth += 1,
+                Token::RightParen(RightParenToken { span: rparen_span }) => {
+                    paren_depth -= 1;
+                    params_end_byte = rparen_span.byte_end_idx;
+                },
+                _ => {}
+            }
+        }
+
+        if !matches!(self.lexer.peek_token(), Some(Token::LeftBrace(..))) {
+            return None;
+        }
+        let lbrace_span = self.lexer.next_token()?.span().clone();
+
+        // 関数本体を読み飛ばす（閉じ括弧がなければ入力の終わりまでを本体とみなす）
+        let closing = self.skip_function_body(&lbrace_span);
+        let end_byte = closing.unwrap_or_else(|| self.lexer.input.trim_end().len());
+
+        let text = self.lexer.input[start_byte..end_byte].to_string();
+        let body = match (text.find('{'), text.rfind('}')) {
+            (Some(brace_start), _) if closing.is_none() => {
+                Some(self.parse_body_statements(start_byte + brace_start + 1, end_byte))
+            },
+            (Some(brace_start), Some(brace_end)) if brace_start < brace_end => {
+                Some(self.parse_body_statements(start_byte + brace_start + 1, start_byte + brace_end))
+            },
+            _ => None,
+        };
+
+        let parameters = self.lexer.input[params_start_byte..params_end_byte].to_string();
+        let parameter_list = self.parse_parameter_declarations(&parameters);
+        Some(Item::FunctionDecl {
+            span: Span {
+                start_line: span.start_line,
+                start_column: span.start_column,
+                end_line: self.lexer.line,
+                end_column: self.lexer.column,
+                byte_start_idx: start_byte,
+                byte_end_idx: end_byte,
+            },
+            text,
+            return_type,
+            function_name,
+            parameters,
+            parameter_list,
+            storage_class: None,
+            specifiers: Vec::new(),
+            body,
+            attributes: Vec::new(),
+            trivia: self.take_trivia(),
+        })
+    }
+
+    /// 宣言の指定子部分（宣言子・初期化子・本体より前）に含まれる関数指定子・スレッド記憶域指定子
+    fn declaration_specifiers(text: &str) -> Vec<String> {
+        let specifiers_end = text.find(['(', '=', ';', '{', '[']).unwrap_or(text.len());
+        text[..specifiers_end]
+            .split(|c: char| c.is_whitespace() || c == '*')
+            .filter(|word| DECLARATION_SPECIFIERS.contains(word))
+            .map(|word| word.to_string())
+            .collect()
+    }
+
+    /// K&R 形式の識別子のみの引数リスト `(a, b)` なら識別子を返す
+    fn knr_identifier_list(parameters: &str) -> Option<Vec<String>> {
+        let inner = parameters.trim();
+        let inner = inner.strip_prefix('(')?;
+        let inner = &inner[..inner.rfind(')')?];
+        let names: Vec<String> = inner.split(',').map(|name| name.trim().to_string()).collect();
+        let is_identifier = |name: &String| {
+            name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        if names.iter().all(is_identifier) && names.iter().all(|name| !Self::is_type_keyword(name)) {
+            Some(names)
+        } else {
+            None
+        }
+    }
+
+    /// 型キーワードかどうか（`(void)` や `(int)` を K&R 形式と誤認しないため）
+    fn is_type_keyword(word: &str) -> bool {
+        matches!(
+            word,
+            "void" | "int" | "char" | "float" | "double" | "long" | "short" | "signed" | "unsigned" | "_Bool"
+                | "const" | "volatile" | "struct" | "union" | "enum"
+        )
+    }
+
+    /// K&R 形式の引数宣言（`int a; char *b, c;`）から ANSI 形式の引数リストを組み立てる
+    ///
+    /// 宣言のない引数は K&R の規則どおり `int` とみなす。
+    fn normalize_knr_parameters(names: &[String], declarations: &str) -> String {
+        let mut declared: HashMap<String, String> = HashMap::new();
+        for declaration in declarations.split(';') {
+            let mut declarators = declaration.split(',');
+            let Some(first) = declarators.next() else { continue };
+            // 最初の宣言子の名前より前が基本型（ポインタの * は宣言子側に含める）
+            let first = first.trim();
+            let name_start = first
+                .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .map_or(0, |pos| pos + 1);
+            let prefix = &first[..name_start];
+            let base_type = prefix.trim_end_matches(|c: char| c == '*' || c.is_whitespace());
+            if base_type.is_empty() {
+                continue;
+            }
+            let first_declarator = &first[base_type.len()..];
+            for declarator in std::iter::once(first_declarator).chain(declarators) {
+                let declarator = declarator.trim();
+                let name = declarator.trim_start_matches(|c: char| c == '*' || c.is_whitespace());
+                declared.insert(name.to_string(), format!("{} {}", base_type, declarator));
+            }
+        }
+
+        let parameters: Vec<String> = names
+            .iter()
+            .map(|name| declared.remove(name).unwrap_or_else(|| format!("int {}", name)))
+            .collect();
+        format!("({})", parameters.join(", "))
+    }
+
+    /// `#include "file.h"` 形式かどうか（トークンのfilenameには引用符が含まれないためテキストから判定）
+    fn is_quoted_include(text: &str) -> bool {
+        text.trim_start()
+            .trim_start_matches('#')
+            .trim_start()
+            .strip_prefix("include")
+            .is_some_and(|rest| rest.trim_start().starts_with('"'))
+    }
+
     /// ヘッダーファイルのパス解決
-    fn resolve_include_path(&self, filename: &str) -> Option<PathBuf> {
+    fn resolve_include_path(&self, filename: &str, is_quoted: bool) -> Option<PathBuf> {
         // #include "file.h" の場合：カレントディレクトリから検索
-        if filename.starts_with('"') && filename.ends_with('"') {
-            let clean_name = filename.trim_matches('"');
-            let current_dir_path = self.current_file_dir.join(clean_name);
-            if current_dir_path.exists() {
+        if is_quoted {
+            let current_dir_path = self.current_file_dir.join(filename);
+            if current_dir_path.is_file() {
                 return Some(current_dir_path);
             }
         }
@@ -2209,7 +2566,7 @@ impl Parser {
         
         for include_path in &self.preprocessor_config.include_paths {
             let full_path = include_path.join(clean_name);
-            if full_path.exists() {
+            if full_path.is_file() {
                 return Some(full_path);
             }
         }
@@ -2218,10 +2575,18 @@ impl Parser {
     }
     
     /// ヘッダーファイルを解析して型情報を取得
-    fn parse_header_file(&mut self, filename: &str) {
+    fn parse_header_file(&mut self, filename: &str, is_quoted: bool, span: &Span) {
         // パスを解決
-        let Some(header_path) = self.resolve_include_path(filename) else {
-            // ファイルが見つからない場合は無視（警告を出すことも可能）
+        let Some(header_path) = self.resolve_include_path(filename, is_quoted) else {
+            // "..." 形式のみ警告する（<...> はシステムヘッダーの可能性が高いため無視）
+            if is_quoted {
+                self.diagnostics.push(Diagnostic::new(
+                    span.clone(),
+                    DiagnosticSeverity::Warning,
+                    format!("インクルードファイルが見つかりません: {}", filename),
+                    DiagnosticCode::Custom("CGH014".to_string()),
+                ));
+            }
             return;
         };
         
@@ -2250,7 +2615,10 @@ impl Parser {
         }
         
         // ヘッダーを解析（型情報のみ取得、ASTは破棄）
-        let header_lexer = Lexer::new(&content);
+        let mut header_lexer = Lexer::new(&content);
+        if let Some(standard) = self.preprocessor_config.standard {
+            header_lexer.set_standard(standard);
+        }
         let mut header_parser = Parser {
             lexer: header_lexer,
             pending_comments: Vec::new(),
@@ -2259,16 +2627,37 @@ impl Parser {
             preprocessor_config: self.preprocessor_config.clone(),
             ifdef_stack: self.ifdef_stack.clone(), // スタック状態を引き継ぐ
             parsed_headers: self.parsed_headers.clone(),
+            header_functions: HashSet::new(),
             current_file_dir: self.current_file_dir.clone(),
+            diagnostics: Vec::new(),
         };
         
-        // 解析実行（ASTは破棄）
-        let _ = header_parser.parse();
+        // 解析実行（ASTは関数名の収集後に破棄）
+        let header_tu = header_parser.parse();
+        for item in header_tu.all_items() {
+            if let Item::FunctionDecl { function_name, .. } = item {
+                self.header_functions.insert(function_name.clone());
+            }
+        }
         
-        // 型テーブルとマクロ定義を反映
+        // 型テーブル・マクロ定義・ヘッダー内の関数宣言を反映
         self.type_table = header_parser.type_table;
         self.defined_macros = header_parser.defined_macros;
         self.parsed_headers = header_parser.parsed_headers;
+        self.header_functions.extend(header_parser.header_functions);
+
+        // ヘッダー内の診断は #include 行の位置で報告する（ヘッダー内の位置はメッセージに含める）
+        for diagnostic in header_parser.diagnostics {
+            let Diagnostic { span: header_span, severity, message, code, notes, .. } = diagnostic;
+            let mut reported = Diagnostic::new(
+                span.clone(),
+                severity,
+                format!("{}:{}:{}: {}", filename, header_span.start_line + 1, header_span.start_column + 1, message),
+                code,
+            );
+            reported.notes = notes;
+            self.diagnostics.push(reported);
+        }
         
         // ディレクトリを復元
         self.current_file_dir = old_dir;
@@ -2340,10 +2729,14 @@ impl Parser {
             Token::For(_) => {
                 self.parse_for_statement()
             }
+            Token::Do(_) => {
+                self.parse_do_while_statement()
+            }
             // 型指定子で始まる場合は変数宣言として扱う
             Token::Int(_) | Token::Float(_) | Token::Double(_) | Token::Char(_) | 
             Token::Void(_) | Token::Long(_) | Token::Short(_) | Token::Signed(_) | 
-            Token::Unsigned(_) | Token::Struct(_) | Token::Union(_) | Token::Enum(_) |
+            Token::Unsigned(_) | Token::Bool(_) | Token::Complex(_) | Token::Imaginary(_) |
+            Token::Struct(_) | Token::Union(_) | Token::Enum(_) |
             Token::Const(_) | Token::Volatile(_) => {
                 self.parse_var_decl_statement()
             }
@@ -2374,11 +2767,12 @@ impl Parser {
         expr_parser.finish();  // current_tokenをLexerに戻す
         let expr = expr?;
         
-        let span = expr.span().clone();
+        let mut span = expr.span().clone();
         
         // セミコロンを消費（オプション）
-        if let Some(Token::Semicolon(_)) = self.lexer.peek_token() {
+        if let Some(Token::Semicolon(semicolon)) = self.lexer.peek_token() {
             self.lexer.next_token();
+            span = extend_to_semicolon(&span, &semicolon.span);
         }
         
         Some(Statement::Expression { expr, span })
@@ -2409,14 +2803,13 @@ impl Parser {
         };
         
         // セミコロンを消費（オプション）
-        if let Some(Token::Semicolon(_)) = self.lexer.peek_token() {
+        let mut span = start_span;
+        if let Some(Token::Semicolon(semicolon)) = self.lexer.peek_token() {
             self.lexer.next_token();
+            span = extend_to_semicolon(&span, &semicolon.span);
         }
         
-        Some(Statement::Return {
-            value,
-            span: start_span,
-        })
+        Some(Statement::Return { value, span })
     }
 
     /// 変数宣言文を解析（型 変数名; または 型 変数名 = 式;）
@@ -2424,14 +2817,14 @@ impl Parser {
         use crate::ast::Statement;
         use crate::expression_parser::ExpressionParser;
         
-        let start_span = self.lexer.peek_token()?.span();
+        let start_span = self.lexer.peek_token()?.into_span();
         
         // 型を解析
         let var_type = self.parse_type()?;
         
         // 変数名を取得
         let var_name = if let Some(Token::Ident(ident_token)) = self.lexer.next_token() {
-            ident_token.name
+            ident_token.name.to_string()
         } else {
             return None;
         };
@@ -2448,15 +2841,17 @@ impl Parser {
         };
         
         // セミコロンを消費（オプション）
-        if let Some(Token::Semicolon(_)) = self.lexer.peek_token() {
+        let mut span = start_span;
+        if let Some(Token::Semicolon(semicolon)) = self.lexer.peek_token() {
             self.lexer.next_token();
+            span = extend_to_semicolon(&span, &semicolon.span);
         }
         
         Some(Statement::VarDecl {
             var_type: Some(var_type),
             var_name,
             initializer,
-            span: start_span,
+            span,
         })
     }
 
@@ -2576,6 +2971,62 @@ impl Parser {
         })
     }
 
+    /// do-while文を解析（do { statements } while (condition);）
+    fn parse_do_while_statement(&mut self) -> Option<crate::ast::Statement> {
+        use crate::ast::Statement;
+        use crate::expression_parser::ExpressionParser;
+        
+        // do トークンを消費
+        let start_span = if let Some(Token::Do(token)) = self.lexer.next_token() {
+            token.span
+        } else {
+            return None;
+        };
+        
+        // ボディブロックを解析
+        let body = if matches!(self.lexer.peek_token(), Some(Token::LeftBrace(_))) {
+            self.lexer.next_token(); // { を消費
+            self.parse_block()
+        } else {
+            // ブロックではない場合、単一のステートメントを解析
+            if let Some(stmt) = self.parse_statement() {
+                vec![stmt]
+            } else {
+                Vec::new()
+            }
+        };
+        
+        // while ( を期待
+        if !matches!(self.lexer.next_token(), Some(Token::While(_))) {
+            return None;
+        }
+        if !matches!(self.lexer.next_token(), Some(Token::LeftParen(_))) {
+            return None;
+        }
+        
+        // 条件式を解析
+        let mut expr_parser = ExpressionParser::new(&mut self.lexer);
+        let condition = expr_parser.parse_expression();
+        expr_parser.finish();  // current_tokenをLexerに戻す
+        let condition = condition?;
+        
+        // ) を期待
+        if !matches!(self.lexer.next_token(), Some(Token::RightParen(_))) {
+            return None;
+        }
+        
+        // セミコロンを消費（オプション）
+        if let Some(Token::Semicolon(_)) = self.lexer.peek_token() {
+            self.lexer.next_token();
+        }
+        
+        Some(Statement::DoWhile {
+            body,
+            condition,
+            span: start_span,
+        })
+    }
+
     /// for文を解析（for (init; condition; update) { statements }）
     fn parse_for_statement(&mut self) -> Option<crate::ast::Statement> {
         use crate::ast::Statement;
@@ -2660,6 +3111,32 @@ impl Parser {
     }
 }
 
+/// 文の範囲を終端のセミコロンまで広げる（複数行にわたる文の継続行を診断で扱えるようにする）
+///
+/// セミコロンのトークンは終了位置が次の行にかかることがあるため、開始位置を基準に終端を決める。
+fn extend_to_semicolon(start: &Span, semicolon: &Span) -> Span {
+    Span {
+        end_line: semicolon.start_line,
+        end_column: semicolon.start_column + 1,
+        byte_end_idx: semicolon.byte_start_idx + 1,
+        ..start.clone()
+    }
+}
+
+/// 組み合わせ可能な型指定子トークンのキーワード（それ以外は None）
+fn arithmetic_specifier_keyword(token: &Token) -> Option<&'static str> {
+    match token {
+        Token::Signed(_) => Some("signed"),
+        Token::Unsigned(_) => Some("unsigned"),
+        Token::Short(_) => Some("short"),
+        Token::Long(_) => Some("long"),
+        Token::Int(_) => Some("int"),
+        Token::Char(_) => Some("char"),
+        Token::Double(_) => Some("double"),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod statement_tests {
     use super::*;
@@ -3071,6 +3548,23 @@ mod statement_tests {
         }
     }
 
+    #[test]
+    fn test_parse_do_while_statement() {
+        let input = "do { x++; } while (x < 10);";
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        
+        let stmt = parser.parse_statement();
+        assert!(stmt.is_some());
+        
+        if let Some(crate::ast::Statement::DoWhile { body, .. }) = stmt {
+            assert_eq!(body.len(), 1);
+        } else {
+            panic!("Expected DoWhile statement");
+        }
+        assert!(parser.lexer.next_token().is_none());
+    }
+
     #[test]
     fn test_parse_for_statement_full() {
         let input = "for (int i = 0; i; i) { return i; }";
@@ -3265,5 +3759,79 @@ mod statement_tests {
             panic!("Expected ConditionalBlock");
         }
     }
-}
 
+    #[test]
+    fn test_if_arithmetic_evaluation() {
+        let input = "#define VERSION 3\n#if (VERSION * 2 + 1) > 6 && !defined(LEGACY)\nint x;\n#endif";
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        
+        let tu = parser.parse();
+        
+        if let crate::ast::Item::ConditionalBlock { condition_result, .. } = &tu.items[1] {
+            assert!(*condition_result);
+        } else {
+            panic!("Expected ConditionalBlock");
+        }
+    }
+
+    #[test]
+    fn test_defined_macros_after_parse() {
+        let input = "#define VERSION 3\n#define DEBUG\nint x;\n";
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        
+        let _ = parser.parse();
+        let macros = parser.defined_macros();
+        assert_eq!(macros.len(), 2);
+        assert_eq!(macros.get("VERSION").map(String::as_str), Some("3"));
+        assert_eq!(macros.get("DEBUG").map(String::as_str), Some(""));
+    }
+
+    #[test]
+    fn test_parameter_array_qualifiers() {
+        let input = "void f(int a[const 4]);\n";
+        let mut parser = Parser::new(Lexer::new(input));
+        let tu = parser.parse();
+
+        let crate::ast::Item::FunctionDecl { parameters, .. } = &tu.items[0] else {
+            panic!("Expected FunctionDecl");
+        };
+        let params = parser.parse_parameter_declarations(parameters);
+        assert_eq!(params.len(), 1);
+        let (ty, name) = &params[0];
+        assert_eq!(name, "a");
+        assert!(ty.is_array());
+        assert_eq!(ty.array_dimensions[0].qualifiers, vec![TypeQualifier::Const]);
+        assert_eq!(ty.array_dimensions[0].size.as_deref(), Some("4"));
+        assert!(!ty.array_dimensions[0].is_static);
+        assert_eq!(ty.to_string(), "int [const 4]");
+    }
+
+    #[test]
+    fn test_parameter_array_restrict_static_and_typedef() {
+        let input = "typedef unsigned char u8;\nvoid g(char s[restrict], u8 *buf[static N + 1], void (*cb)(int, int));\n";
+        let mut parser = Parser::new(Lexer::new(input));
+        let tu = parser.parse();
+
+        let Some(crate::ast::Item::FunctionDecl { parameters, .. }) = tu.items.get(1) else {
+            panic!("Expected FunctionDecl");
+        };
+        let params = parser.parse_parameter_declarations(parameters);
+        // 関数ポインタ引数は名前を取り出せないため含まれない
+        assert_eq!(params.len(), 2);
+
+        let (s_ty, s_name) = &params[0];
+        assert_eq!(s_name, "s");
+        assert!(s_ty.array_dimensions[0].has_qualifier(TypeQualifier::Restrict));
+        assert_eq!(s_ty.array_dimensions[0].size, None);
+
+        let (buf_ty, buf_name) = &params[1];
+        assert_eq!(buf_name, "buf");
+        assert_eq!(buf_ty.alias.as_deref(), Some("u8"));
+        assert_eq!(buf_ty.pointer_level(), 1);
+        assert!(buf_ty.array_dimensions[0].is_static);
+        assert_eq!(buf_ty.array_dimensions[0].size.as_deref(), Some("N + 1"));
+        assert_eq!(buf_ty.to_string(), "u8 *[static N + 1]");
+    }
+}
@@ -1,5 +1,6 @@
 // ルートとノードを定義。所有する Span を持たせる（ライフタイム回避のため String/span を所有）
-#[derive(Debug, Clone, PartialEq, Eq)]
+// 順序は開始位置（行・列）→ 終了位置（行・列）の順に比較する
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Span {
     pub start_line: usize,
     pub start_column: usize,
@@ -26,4 +27,100 @@ impl Span {
             byte_end_idx: 0,
         }
     }
+
+    /// 指定した位置（行・列）が範囲内にあるか（開始位置を含み、終了位置は含まない）
+    pub fn contains(&self, line: usize, column: usize) -> bool {
+        let pos = (line, column);
+        (self.start_line, self.start_column) <= pos && pos < (self.end_line, self.end_column)
+    }
+
+    /// 2つの範囲を両方とも含む最小の範囲を返す
+    ///
+    /// 開始はバイトオフセットが小さい方、終了はバイトオフセットが大きい方の行・列を使う
+    pub fn merge(&self, other: &Span) -> Span {
+        let start = if (other.byte_start_idx, other.start_line, other.start_column)
+            < (self.byte_start_idx, self.start_line, self.start_column)
+        {
+            other
+        } else {
+            self
+        };
+        let end = if (other.byte_end_idx, other.end_line, other.end_column)
+            > (self.byte_end_idx, self.end_line, self.end_column)
+        {
+            other
+        } else {
+            self
+        };
+
+        Span {
+            start_line: start.start_line,
+            start_column: start.start_column,
+            end_line: end.end_line,
+            end_column: end.end_column,
+            byte_start_idx: start.byte_start_idx,
+            byte_end_idx: end.byte_end_idx,
+        }
+    }
+
+    /// 範囲のバイト長
+    pub fn len_bytes(&self) -> usize {
+        self.byte_end_idx.saturating_sub(self.byte_start_idx)
+    }
+
+    /// LSP の Range と同じ 0 始まりの (開始行, 開始列, 終了行, 終了列) を返す
+    ///
+    /// Span の行・列はもともと 0 始まりのため、値はそのまま変換する。
+    /// LSP クレートなどはこのタプルから Range を組み立て、変換をここに集約する。
+    pub fn to_lsp_range(&self) -> (u32, u32, u32, u32) {
+        (
+            self.start_line as u32,
+            self.start_column as u32,
+            self.end_line as u32,
+            self.end_column as u32,
+        )
+    }
+
+    /// `to_lsp_range` の逆変換（バイトオフセットは持たないため 0 になる）
+    pub fn from_lsp_range((start_line, start_column, end_line, end_column): (u32, u32, u32, u32)) -> Span {
+        Span::new(start_line as usize, start_column as usize, end_line as usize, end_column as usize)
+    }
+
+    /// ソースコードから範囲の文字列を取り出す（範囲外・文字境界でない場合は空文字列）
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        source.get(self.byte_start_idx..self.byte_end_idx).unwrap_or("")
+    }
+}
+
+// Span は AST 中に大量に現れるため、フィールド名を省いた配列
+// [start_line, start_column, end_line, end_column, byte_start_idx, byte_end_idx] としてシリアライズする
+#[cfg(feature = "serde")]
+impl serde::Serialize for Span {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (
+            self.start_line,
+            self.start_column,
+            self.end_line,
+            self.end_column,
+            self.byte_start_idx,
+            self.byte_end_idx,
+        )
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Span {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (start_line, start_column, end_line, end_column, byte_start_idx, byte_end_idx) =
+            <(usize, usize, usize, usize, usize, usize)>::deserialize(deserializer)?;
+        Ok(Span {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+            byte_start_idx,
+            byte_end_idx,
+        })
+    }
 }
@@ -0,0 +1,78 @@
+use crate::analysis::{analyze, parse_with_types};
+use crate::ast::Item;
+use crate::diagnostics::{diagnose_with_source, DiagnosticConfig, DiagnosticCode, DiagnosticSeverity};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+#[test]
+fn test_analyze_matches_manual_pipeline() {
+    let source = r#"
+#define VALUE 1 + 2
+int counter;
+void foo() {
+    int x = 1;
+}
+"#;
+
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        ..Default::default()
+    };
+
+    // 従来の手動パイプライン
+    let lexer = Lexer::new(source);
+    let mut parser = Parser::new(lexer);
+    let tu = parser.parse();
+    let expected = diagnose_with_source(&tu, &config, source);
+
+    let result = analyze(source, &config);
+
+    assert!(!expected.is_empty());
+    assert_eq!(result.diagnostics, expected);
+    assert_eq!(result.translation_unit.items.len(), tu.items.len());
+    assert!(result.parse_diagnostics.is_empty());
+}
+
+#[test]
+fn test_analyze_returns_translation_unit() {
+    let source = "int x;\nvoid foo() {}\n";
+    let result = analyze(source, &DiagnosticConfig::default());
+
+    assert_eq!(result.translation_unit.items.len(), 2);
+    assert!(matches!(result.translation_unit.items[0], Item::VarDecl { .. }));
+    assert!(matches!(result.translation_unit.items[1], Item::FunctionDecl { .. }));
+}
+
+#[test]
+fn test_analyze_reports_lexer_errors_as_parse_diagnostics() {
+    let source = "int x;\n\\\nint y;\n";
+    let result = analyze(source, &DiagnosticConfig::default());
+
+    assert_eq!(result.parse_diagnostics.len(), 1);
+    let diag = &result.parse_diagnostics[0];
+    assert_eq!(diag.severity, DiagnosticSeverity::Error);
+    assert!(matches!(diag.code, DiagnosticCode::Custom(ref code) if code == "CGH013"));
+}
+
+#[test]
+fn test_parse_with_types_returns_type_table_and_macros() {
+    let source = "#define BUFFER_SIZE 64\ntypedef unsigned int uint32;\nuint32 counter;\n";
+
+    let (tu, type_table, macros) = parse_with_types(source);
+
+    assert_eq!(tu.items.len(), 3);
+    assert!(type_table.is_type_name("uint32"));
+    assert!(!type_table.is_type_name("counter"));
+    assert_eq!(macros.get("BUFFER_SIZE").map(String::as_str), Some("64"));
+}
+
+#[test]
+fn test_parser_into_parts_matches_parse() {
+    let source = "typedef struct { int x; } Point;\nPoint origin;\n";
+
+    let expected = Parser::new(Lexer::new(source)).parse();
+    let (tu, type_table, _) = Parser::new(Lexer::new(source)).into_parts();
+
+    assert_eq!(tu.items.len(), expected.items.len());
+    assert!(type_table.is_type_name("Point"));
+}
@@ -0,0 +1,96 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::ast::Item;
+use crate::token::*;
+
+fn tokens(s: &str) -> Vec<Token> {
+    let mut lx = Lexer::new(s);
+    std::iter::from_fn(|| lx.next_token()).collect()
+}
+
+#[test]
+fn test_lexer_gnu_attribute_is_one_token() {
+    let s = "__attribute__((packed, aligned(4))) x";
+    let tokens = tokens(s);
+
+    assert_eq!(tokens.len(), 2);
+    match &tokens[0] {
+        Token::Attribute(AttributeToken { span, attributes }) => {
+            assert_eq!(attributes, &vec!["packed".to_string(), "aligned(4)".to_string()]);
+            assert_eq!(&s[span.byte_start_idx..span.byte_end_idx], "__attribute__((packed, aligned(4)))");
+        },
+        other => panic!("Expected Attribute, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_lexer_c23_attribute_is_one_token() {
+    let tokens = tokens("[[gnu::unused, nodiscard]] int");
+    assert_eq!(tokens.len(), 2);
+    assert!(matches!(&tokens[0], Token::Attribute(AttributeToken { attributes, .. }) if attributes == &vec!["gnu::unused".to_string(), "nodiscard".to_string()]));
+}
+
+#[test]
+fn test_lexer_attribute_identifier_without_parens_is_ident() {
+    let tokens = tokens("__attribute__;");
+    assert!(matches!(&tokens[0], Token::Ident(IdentToken { name, .. }) if &**name == "__attribute__"));
+}
+
+#[test]
+fn test_parse_packed_struct() {
+    let s = "struct __attribute__((packed)) Header {\n    char tag;\n    int length;\n};\nint next;\n";
+    let tu = Parser::new(Lexer::new(s)).parse();
+
+    assert_eq!(tu.items.len(), 2);
+    match &tu.items[0] {
+        Item::StructDecl { struct_name, members, attributes, .. } => {
+            assert_eq!(struct_name.as_deref(), Some("Header"));
+            assert_eq!(members.len(), 2);
+            assert_eq!(attributes, &vec!["packed".to_string()]);
+        },
+        other => panic!("Expected StructDecl, got {:?}", other),
+    }
+    assert!(matches!(&tu.items[1], Item::VarDecl { var_name, .. } if var_name == "next"));
+}
+
+#[test]
+fn test_parse_attribute_after_struct_body() {
+    let s = "struct Header { char tag; } __attribute__((packed));\n";
+    let tu = Parser::new(Lexer::new(s)).parse();
+    assert!(matches!(&tu.items[0], Item::StructDecl { attributes, .. } if attributes == &vec!["packed".to_string()]));
+}
+
+#[test]
+fn test_parse_noreturn_function() {
+    let s = "[[noreturn]] void fatal(void) {\n    for (;;) {}\n}\n";
+    let tu = Parser::new(Lexer::new(s)).parse();
+
+    assert_eq!(tu.items.len(), 1);
+    match &tu.items[0] {
+        Item::FunctionDecl { function_name, return_type, attributes, text, span, .. } => {
+            assert_eq!(function_name, "fatal");
+            assert_eq!(return_type, "void");
+            assert_eq!(attributes, &vec!["noreturn".to_string()]);
+            // 宣言の前の属性も宣言の範囲に含める
+            assert!(text.starts_with("[[noreturn]]"));
+            assert_eq!(span.byte_start_idx, 0);
+        },
+        other => panic!("Expected FunctionDecl, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_variable_attribute_after_declarator() {
+    let s = "int buffer __attribute__((aligned(16)));\n";
+    let tu = Parser::new(Lexer::new(s)).parse();
+    assert!(matches!(&tu.items[0], Item::VarDecl { var_name, attributes, .. }
+        if var_name == "buffer" && attributes == &vec!["aligned(16)".to_string()]));
+}
+
+#[test]
+fn test_parameter_attributes_are_not_function_attributes() {
+    let s = "void handler(int signo __attribute__((unused)));\n";
+    let tu = Parser::new(Lexer::new(s)).parse();
+    assert!(matches!(&tu.items[0], Item::FunctionDecl { function_name, attributes, .. }
+        if function_name == "handler" && attributes.is_empty()));
+}
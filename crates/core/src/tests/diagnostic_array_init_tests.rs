@@ -0,0 +1,48 @@
+use crate::diagnostics::{Diagnostic, DiagnosticConfig, DiagnosticSeverity};
+use super::diagnostics_with_config;
+
+fn array_init_diagnostics(source: &str, warn_partial: bool) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        warn_partial_array_init: warn_partial,
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH036")
+}
+
+#[test]
+fn test_too_many_initializers_is_error() {
+    let diagnostics = array_init_diagnostics("int a[2] = {1, 2, 3};\n", false);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+    assert!(diagnostics[0].message.contains("'a'"));
+}
+
+#[test]
+fn test_exact_count_passes() {
+    assert!(array_init_diagnostics("int a[3] = {1, 2, 3};\n", true).is_empty());
+}
+
+#[test]
+fn test_macro_size_is_evaluated() {
+    let diagnostics = array_init_diagnostics("#define SIZE 2\nint a[SIZE] = {1, 2, 3};\n", false);
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_unsized_and_designated_initializers_are_skipped() {
+    assert!(array_init_diagnostics("int a[] = {1, 2, 3};\n", true).is_empty());
+    assert!(array_init_diagnostics("int a[2] = {[0] = 1, [1] = 2, [1] = 3};\n", true).is_empty());
+}
+
+#[test]
+fn test_partial_initialization_warns_only_when_enabled() {
+    assert!(array_init_diagnostics("int a[4] = {1, 2};\n", false).is_empty());
+
+    let diagnostics = array_init_diagnostics("int a[4] = {1, 2};\n", true);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+
+    // {0} によるゼロ初期化は警告しない
+    assert!(array_init_diagnostics("int a[4] = {0};\n", true).is_empty());
+}
@@ -0,0 +1,36 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_minority_function_brace_style_is_reported() {
+    let source = "void first(void)\n{\n}\n\nvoid second(void) {\n}\n\nvoid third(void)\n{\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH046");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("second"));
+    assert_eq!(warnings[0].span.start_line, 4);
+    assert_eq!(&source[warnings[0].span.byte_start_idx..warnings[0].span.byte_end_idx], "{");
+}
+
+#[test]
+fn test_minority_block_brace_style_is_reported() {
+    let source = "void f(int x)\n{\n    if (x) {\n        x = 1;\n    }\n    while (x)\n    {\n        x = 0;\n    }\n    for (;;) {\n    }\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH046");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].span.start_line, 6);
+}
+
+#[test]
+fn test_consistent_styles_pass() {
+    // 関数本体は単独の行、制御文は行末という組み合わせはそれぞれ統一されていれば対象外
+    let source = "void f(int x)\n{\n    int values[2] = {1, 2};\n    if (x) {\n        x = values[0];\n    } else {\n        x = 0;\n    }\n}\n\nvoid g(void)\n{\n    do {\n    } while (0);\n}\n";
+    assert!(diagnostics_with_code(source, "CGH046").is_empty());
+}
+
+#[test]
+fn test_tie_follows_first_style() {
+    let source = "void first(void) {\n}\n\nvoid second(void)\n{\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH046");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("second"));
+}
@@ -0,0 +1,66 @@
+use crate::diagnostics::{Diagnostic, DiagnosticConfig, DiagnosticSeverity};
+use super::diagnostics_with_config;
+
+fn complexity_warnings(source: &str, max_cyclomatic_complexity: Option<usize>) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        max_cyclomatic_complexity,
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH019")
+}
+
+// 複雑度: 1 + if + if + && = 4
+const SOURCE: &str = r#"int
+check(int a, int b)
+{
+    if (a > 0 && b > 0) {
+        return 1;
+    }
+    if (a < 0) {
+        return -1;
+    }
+    return 0;
+}
+"#;
+
+#[test]
+fn test_complexity_counts_branches_and_logical_operators() {
+    let warnings = complexity_warnings(SOURCE, Some(3));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("'check'"));
+    assert!(warnings[0].message.contains("循環的複雑度は 4 で"));
+}
+
+#[test]
+fn test_complexity_within_limit_not_flagged() {
+    assert!(complexity_warnings(SOURCE, Some(4)).is_empty());
+}
+
+#[test]
+fn test_complexity_counts_loops() {
+    // 1 + while + for + do + || = 5
+    let source = r#"void
+loops(int n)
+{
+    while (n > 0) {
+        n--;
+    }
+    for (; n < 10; n++) {
+    }
+    do {
+        n--;
+    } while (n > 5 || n < -5);
+}
+"#;
+
+    let warnings = complexity_warnings(source, Some(1));
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("循環的複雑度は 5 で"));
+}
+
+#[test]
+fn test_complexity_disabled_by_default() {
+    assert!(complexity_warnings(SOURCE, None).is_empty());
+}
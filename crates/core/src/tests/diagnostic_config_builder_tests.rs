@@ -0,0 +1,105 @@
+use crate::diagnostics::{DiagnosticConfig, DiagnosticConfigBuilder, CONFIGURABLE_CODES};
+use crate::config::ProjectConfig;
+
+#[test]
+fn test_builder_with_only_two_checks_enabled() {
+    let config = DiagnosticConfigBuilder::new()
+        .disable_all()
+        .enable("CGH003")
+        .and_then(|builder| builder.enable("CGH017"))
+        .unwrap()
+        .build();
+
+    assert!(config.check_type_safety);
+    assert!(config.check_empty_loop_body);
+
+    let others = [
+        config.check_file_header,
+        config.check_storage_class_order,
+        config.check_function_format,
+        config.check_macro_parentheses,
+        config.check_duplicate_macros,
+        config.check_include_guard,
+        config.check_global_var_naming,
+        config.check_global_var_type_prefix,
+        config.check_local_var_type_prefix,
+        config.check_const_pointer_params,
+        config.check_function_naming,
+        config.check_type_naming,
+        config.check_enum_monotonic,
+        config.check_mixed_indentation,
+        config.check_implicit_int,
+        config.check_empty_aggregate,
+        config.check_short_identifiers,
+        config.check_preprocessor_indent,
+        config.check_indent_style,
+        config.check_include_dir,
+        config.check_src_dir,
+        config.check_cert_c,
+        config.check_cwe_c,
+        config.check_misra_c,
+    ];
+    assert!(others.iter().all(|enabled| !enabled));
+    assert_eq!(config.max_function_statements, None);
+    assert_eq!(config.max_cyclomatic_complexity, None);
+    assert_eq!(config.max_nesting_depth, None);
+}
+
+#[test]
+fn test_builder_disable_clears_limit() {
+    let config = DiagnosticConfigBuilder::new()
+        .max_nesting_depth(Some(3))
+        .max_function_statements(Some(10))
+        .disable("CGH020")
+        .unwrap()
+        .build();
+
+    assert_eq!(config.max_nesting_depth, None);
+    assert_eq!(config.max_function_statements, Some(10));
+    assert!(config.check_file_header);
+}
+
+#[test]
+fn test_builder_rejects_unknown_code() {
+    let error = DiagnosticConfigBuilder::new().disable("CGH999").unwrap_err();
+    assert!(error.contains("CGH999"));
+    assert!(DiagnosticConfigBuilder::new().enable("CGH013").is_err());
+}
+
+#[test]
+fn test_builder_rejects_enabling_limit_checks() {
+    for (code, setter) in [
+        ("CGH018", "max_function_statements"),
+        ("CGH019", "max_cyclomatic_complexity"),
+        ("CGH020", "max_nesting_depth"),
+    ] {
+        let error = DiagnosticConfigBuilder::new().enable(code).unwrap_err();
+        assert!(error.contains(code) && error.contains(setter), "{}", error);
+    }
+}
+
+#[test]
+fn test_every_configurable_code_can_be_disabled() {
+    for code in CONFIGURABLE_CODES {
+        assert!(DiagnosticConfigBuilder::new().disable(code).is_ok(), "{}", code);
+    }
+    assert!(CONFIGURABLE_CODES.contains(&"CGH051"));
+    assert!(CONFIGURABLE_CODES.contains(&"CGH020"));
+}
+
+#[test]
+fn test_project_config_conversion_matches_default() {
+    let converted = ProjectConfig::default().to_diagnostic_config();
+    let default = DiagnosticConfig::default();
+
+    assert_eq!(converted.check_file_header, default.check_file_header);
+    assert_eq!(converted.check_enum_monotonic, default.check_enum_monotonic);
+    assert_eq!(converted.check_short_identifiers, default.check_short_identifiers);
+    assert_eq!(converted.check_function_naming, default.check_function_naming);
+    assert_eq!(converted.check_type_naming, default.check_type_naming);
+    assert!(!default.check_function_naming);
+    assert!(!default.check_type_naming);
+    assert_eq!(converted.min_identifier_length, default.min_identifier_length);
+    assert_eq!(converted.indent_width, default.indent_width);
+    assert!(converted.check_cert_c);
+}
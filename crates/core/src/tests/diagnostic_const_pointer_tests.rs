@@ -0,0 +1,128 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_read_only_pointer_param_suggests_const() {
+    let source = r#"int
+sum(int *values, int n)
+{
+    int total = *values + n;
+    return total;
+}
+"#;
+
+    let infos = diagnostics_with_code(source, "CGH021");
+    assert_eq!(infos.len(), 1);
+    assert_eq!(infos[0].severity, DiagnosticSeverity::Information);
+    assert!(infos[0].message.contains("'values'"));
+}
+
+#[test]
+fn test_written_pointer_param_is_silent() {
+    let source = r#"void
+reset(int *value, struct point *pt, int *counter)
+{
+    *value = 0;
+    pt->x = 0;
+    (*counter)++;
+}
+"#;
+
+    assert!(diagnostics_with_code(source, "CGH021").is_empty());
+}
+
+#[test]
+fn test_const_pointer_param_is_silent() {
+    let source = r#"int
+first(const int *values)
+{
+    return *values;
+}
+"#;
+
+    assert!(diagnostics_with_code(source, "CGH021").is_empty());
+}
+
+#[test]
+fn test_prototype_is_silent() {
+    assert!(diagnostics_with_code("int sum(int *values, int n);\n", "CGH021").is_empty());
+}
+
+#[test]
+fn test_compound_assigned_pointer_param_is_silent() {
+    let source = r#"void
+accumulate(int *total, int n)
+{
+    *total += n;
+}
+"#;
+
+    assert!(diagnostics_with_code(source, "CGH021").is_empty());
+}
+
+#[test]
+fn test_pointer_passed_to_call_is_silent() {
+    let source = r#"void
+clear(struct point *pt, int *values)
+{
+    memset(pt, 0, sizeof *pt);
+    fill(&values[1], 4);
+}
+"#;
+
+    assert!(diagnostics_with_code(source, "CGH021").is_empty());
+}
+
+#[test]
+fn test_pointer_dereferenced_in_call_still_suggests_const() {
+    let source = r#"void
+show(int *value)
+{
+    printf("%d", *value);
+}
+"#;
+
+    let infos = diagnostics_with_code(source, "CGH021");
+    assert_eq!(infos.len(), 1);
+    assert!(infos[0].message.contains("'value'"));
+}
+
+#[test]
+fn test_aliased_pointer_is_silent() {
+    let source = r#"void
+copy(char *dst, char *src)
+{
+    char *cursor = dst;
+    char *other;
+    other = src;
+    *cursor = *other;
+}
+"#;
+
+    assert!(diagnostics_with_code(source, "CGH021").is_empty());
+}
+
+#[test]
+fn test_returned_pointer_is_silent() {
+    let source = r#"char *
+skip(char *text, int n)
+{
+    return text + n;
+}
+"#;
+
+    assert!(diagnostics_with_code(source, "CGH021").is_empty());
+}
+
+#[test]
+fn test_multi_level_and_typedef_pointers_are_silent() {
+    let source = r#"typedef char *Str;
+int
+length(int **table, Str name)
+{
+    return **table + *name;
+}
+"#;
+
+    assert!(diagnostics_with_code(source, "CGH021").is_empty());
+}
@@ -0,0 +1,45 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_misindented_continuation_is_reported() {
+    let source = "void f(void) {\n    total = first +\n      second;\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH039");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert_eq!(warnings[0].span.start_line, 2);
+}
+
+#[test]
+fn test_continuation_at_same_level_is_reported() {
+    let source = "void f(void) {\n    int total = first +\n    second;\n}\n";
+    assert_eq!(diagnostics_with_code(source, "CGH039").len(), 1);
+}
+
+#[test]
+fn test_continuation_by_indent_multiple_passes() {
+    let source = "void f(void) {\n    total = first +\n        second +\n            third;\n    return total +\n        1;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH039").is_empty());
+}
+
+#[test]
+fn test_tab_counts_as_indent_width() {
+    let source = "void f(void) {\n\ttotal = first +\n\t\tsecond;\n\tcount = first +\n\t  second;\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH039");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].span.start_line, 4);
+}
+
+#[test]
+fn test_single_line_subscript_after_directives_is_silent() {
+    let source = "#include <stdio.h>\n#define N 3\n\nint tbl[N];\n\nvoid f(void) {\n    int w;\n    w = tbl[2];\n    w = tbl[w] + tbl[1];\n}\n";
+    assert!(diagnostics_with_code(source, "CGH039").is_empty());
+}
+
+#[test]
+fn test_misindented_subscript_continuation_after_directives_is_reported() {
+    let source = "#define N 3\n\nvoid f(void) {\n    w = tbl[1] +\n      tbl[2];\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH039");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].span.start_line, 4);
+}
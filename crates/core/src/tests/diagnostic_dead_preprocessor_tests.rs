@@ -0,0 +1,48 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose, DiagnosticConfig, DiagnosticCode, DiagnosticSeverity};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_if_zero_block_is_reported() {
+    let diags = diagnostics_with_code("#if 0\nint unused;\n#endif\n", "CGH030");
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].severity, DiagnosticSeverity::Hint);
+    // ブロック全体（#if から #endif まで）を範囲とする
+    assert_eq!(diags[0].span.start_line, 0);
+    assert!(diags[0].span.end_line >= 2);
+}
+
+#[test]
+fn test_defined_macro_branch_is_not_reported() {
+    assert!(diagnostics_with_code("#define DEBUG\n#ifdef DEBUG\nint debug_mode;\n#endif\n", "CGH030").is_empty());
+}
+
+#[test]
+fn test_if_zero_with_else_is_not_reported() {
+    assert!(diagnostics_with_code("#if 0\nint a;\n#else\nint b;\n#endif\n", "CGH030").is_empty());
+}
+
+#[test]
+fn test_if_one_is_not_reported() {
+    assert!(diagnostics_with_code("#if 1\nint a;\n#endif\n", "CGH030").is_empty());
+}
+
+#[test]
+fn test_nested_block_inside_dead_block_is_reported_once() {
+    let diags = diagnostics_with_code("#if 0\n#if 0\nint a;\n#endif\n#endif\n", "CGH030");
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].span.start_line, 0);
+}
+
+#[test]
+fn test_dead_preprocessor_check_can_be_disabled() {
+    let tu = Parser::new(Lexer::new("#if 0\nint a;\n#endif\n")).parse();
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_dead_preprocessor: false,
+        ..Default::default()
+    };
+    let diags = diagnose(&tu, &config);
+    assert!(!diags.iter().any(|d| matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH030")));
+}
@@ -0,0 +1,27 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_declaration_after_statement_is_reported() {
+    let source = "void f(void) {\n    int a = 0;\n    a = a + 1;\n    int b = a;\n}\n";
+    let diagnostics = diagnostics_with_code(source, "CGH038");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Information);
+    assert!(diagnostics[0].message.contains("'b'"));
+    assert_eq!(diagnostics[0].span.start_line, 3);
+}
+
+#[test]
+fn test_declarations_first_passes() {
+    let source = "void f(void) {\n    int a = 0;\n    int b;\n    b = a;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH038").is_empty());
+}
+
+#[test]
+fn test_each_nested_block_is_checked_separately() {
+    // 内側のブロックは先頭に宣言があれば良い
+    let source = "void f(int x) {\n    x = 1;\n    if (x) {\n        int y = x;\n        y++;\n        int z = y;\n    }\n}\n";
+    let diagnostics = diagnostics_with_code(source, "CGH038");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("'z'"));
+}
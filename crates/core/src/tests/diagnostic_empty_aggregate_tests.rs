@@ -0,0 +1,45 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose, DiagnosticConfig, DiagnosticCode};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_empty_struct_warns() {
+    let diags = diagnostics_with_code("struct S {};\n", "CGH027");
+    assert_eq!(diags.len(), 1);
+    assert!(diags[0].message.contains("'S'"));
+}
+
+#[test]
+fn test_struct_with_member_does_not_warn() {
+    assert!(diagnostics_with_code("struct S { int x; };\n", "CGH027").is_empty());
+}
+
+#[test]
+fn test_forward_declaration_does_not_warn() {
+    assert!(diagnostics_with_code("struct S;\nunion U;\n", "CGH027").is_empty());
+}
+
+#[test]
+fn test_empty_union_warns() {
+    let diags = diagnostics_with_code("union U {};\n", "CGH027");
+    assert_eq!(diags.len(), 1);
+    assert!(diags[0].message.contains("共用体"));
+}
+
+#[test]
+fn test_typedef_struct_with_member_does_not_warn() {
+    assert!(diagnostics_with_code("typedef struct { int x; } Point;\n", "CGH027").is_empty());
+}
+
+#[test]
+fn test_empty_aggregate_check_can_be_disabled() {
+    let tu = Parser::new(Lexer::new("struct S {};\n")).parse();
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_empty_aggregate: false,
+        ..Default::default()
+    };
+    let diags = diagnose(&tu, &config);
+    assert!(!diags.iter().any(|d| matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH027")));
+}
@@ -0,0 +1,67 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_while_with_empty_statement_body() {
+    let source = r#"
+void foo(int x) {
+    while (x);
+}
+"#;
+
+    let warnings = diagnostics_with_code(source, "CGH017");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("while"));
+}
+
+#[test]
+fn test_while_with_empty_block_body() {
+    let source = r#"
+void foo(int x) {
+    while (x) {}
+}
+"#;
+
+    assert!(diagnostics_with_code(source, "CGH017").is_empty());
+}
+
+#[test]
+fn test_for_with_empty_statement_body() {
+    let source = r#"
+void foo(int i) {
+    for (; i < 10; i++);
+}
+"#;
+
+    let warnings = diagnostics_with_code(source, "CGH017");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("for"));
+}
+
+#[test]
+fn test_do_while_with_empty_statement_body() {
+    let source = r#"
+void foo(int x) {
+    do ; while (x);
+}
+"#;
+
+    let warnings = diagnostics_with_code(source, "CGH017");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("do-while"));
+}
+
+#[test]
+fn test_nested_empty_loop_in_if() {
+    let source = r#"
+void foo(int x) {
+    if (x) {
+        while (x);
+    }
+    while (x) { x--; }
+}
+"#;
+
+    assert_eq!(diagnostics_with_code(source, "CGH017").len(), 1);
+}
@@ -0,0 +1,41 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose, DiagnosticConfig, DiagnosticSeverity, DiagnosticCode};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_enum_value_out_of_order_fires() {
+    let warnings = diagnostics_with_code("enum Level { A = 1, B = 3, C = 2 };\n", "CGH024");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("'C'"));
+    assert!(warnings[0].message.contains("'B'"));
+}
+
+#[test]
+fn test_monotonic_enum_passes() {
+    assert!(diagnostics_with_code("enum Flag { NONE, READ = 1, WRITE = 2, EXEC = 4, ALL };\n", "CGH024").is_empty());
+}
+
+#[test]
+fn test_explicit_value_equal_to_implicit_previous_fires() {
+    // B の暗黙値は 1 なので、C = 1 は単調増加ではない
+    let warnings = diagnostics_with_code("enum Level { A, B, C = 1 };\n", "CGH024");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("'C'"));
+}
+
+#[test]
+fn test_unknown_value_is_not_compared() {
+    assert!(diagnostics_with_code("enum Level { A = 10, B = EXTERNAL_VALUE, C = 5 };\n", "CGH024").is_empty());
+}
+
+#[test]
+fn test_enum_monotonic_disabled_by_default() {
+    let lexer = Lexer::new("enum Level { A = 1, B = 3, C = 2 };\n");
+    let mut parser = Parser::new(lexer);
+    let tu = parser.parse();
+
+    let diagnostics = diagnose(&tu, &DiagnosticConfig::default());
+    assert!(!diagnostics.iter().any(|d| matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH024")));
+}
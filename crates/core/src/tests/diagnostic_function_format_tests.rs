@@ -0,0 +1,64 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_well_formatted_function_passes() {
+    let source = "static int\nadd(int a, int b)\n{\n    return a + b;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH002").is_empty());
+}
+
+#[test]
+fn test_parameter_type_matching_return_type_does_not_fire() {
+    // 引数の型が戻り値の型と同じでも、関数名の行に戻り値の型があるとはみなさない
+    let source = "int\nlength(int length_max)\n{\n    return length_max;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH002").is_empty());
+}
+
+#[test]
+fn test_function_name_in_comment_does_not_fire() {
+    let source = "int /* scale(x) は x を2倍にする */\nscale(int x)\n{\n    return x * 2;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH002").is_empty());
+}
+
+#[test]
+fn test_return_type_on_name_line_fires() {
+    let source = "int add(int a, int b)\n{\n    return a + b;\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH002");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("戻り値の型とは別の行"));
+    assert_eq!(warnings[0].span.start_column, 4);
+}
+
+#[test]
+fn test_brace_on_parameter_line_fires() {
+    let source = "int\nadd(int a,\n    int b) {\n    return a + b;\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH002");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("opening brace"));
+}
+
+#[test]
+fn test_brace_inside_comment_after_parameters_is_ignored() {
+    // 引数リストの後のコメント内の `{` は本体の開き括弧とみなさない
+    let source = "int\nadd(int a, int b) /* { */\n{\n    return a + b;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH002").is_empty());
+}
+
+#[test]
+fn test_parenthesized_parameter_declarator_brace_fires() {
+    // 関数ポインタ引数の括弧を含む引数リストでも、閉じ括弧の後の `{` を判定する
+    let source = "int\napply(int (*op)(int), int x) {\n    return op(x);\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH002");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("opening brace"));
+}
+
+#[test]
+fn test_return_type_on_name_line_after_comment_line_fires() {
+    let source = "/* scale(x) */ static int scale(int x)\n{\n    return x * 2;\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH002");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("戻り値の型とは別の行"));
+    assert_eq!(warnings[0].span.start_column, 26);
+}
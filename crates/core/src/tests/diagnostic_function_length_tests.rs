@@ -0,0 +1,53 @@
+use crate::diagnostics::{Diagnostic, DiagnosticConfig, DiagnosticSeverity};
+use super::diagnostics_with_config;
+
+fn function_length_warnings(source: &str, max_function_statements: Option<usize>) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        max_function_statements,
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH018")
+}
+
+// ステートメント数: int a, int b, if, a++, while, b++, return = 7
+const SOURCE: &str = r#"int
+counter(int x)
+{
+    int a = 0;
+    int b = 0;
+    if (x) {
+        a++;
+    }
+    while (x) {
+        b++;
+    }
+    return a;
+}
+"#;
+
+#[test]
+fn test_function_at_limit_not_flagged() {
+    assert!(function_length_warnings(SOURCE, Some(7)).is_empty());
+}
+
+#[test]
+fn test_function_over_limit_flagged() {
+    let warnings = function_length_warnings(SOURCE, Some(6));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("counter"));
+    assert!(warnings[0].message.contains("7"));
+
+    // 関数名の位置を指す
+    let span = &warnings[0].span;
+    assert_eq!(span.start_line, 1);
+    assert_eq!(span.start_column, 0);
+    assert_eq!(span.end_column, "counter".len());
+    assert_eq!(&SOURCE[span.byte_start_idx..span.byte_end_idx], "counter");
+}
+
+#[test]
+fn test_function_length_disabled_by_default() {
+    assert!(function_length_warnings(SOURCE, None).is_empty());
+}
@@ -291,3 +291,23 @@ int MAX_VALUE;
     let var_warnings: Vec<_> = diagnostics.iter().filter(|d| matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH006")).collect();
     assert_eq!(var_warnings.len(), 3);
 }
+
+#[test]
+fn test_global_var_inside_conditional_block() {
+    let input = "#ifndef CONFIG_H\n#define CONFIG_H\nint counter;\n#endif\n";
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer);
+    let tu = parser.parse();
+
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        ..Default::default()
+    };
+
+    let diagnostics = diagnose(&tu, &config);
+
+    assert!(diagnostics.iter().any(|d|
+        matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH006")
+        && d.message.contains("counter")
+    ));
+}
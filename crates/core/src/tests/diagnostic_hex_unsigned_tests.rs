@@ -0,0 +1,46 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose_with_source, DiagnosticConfig, DiagnosticCode, DiagnosticSeverity};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_hex_mask_without_suffix_is_reported() {
+    let source = "void f(int val) {\n    int x;\n    x = val & 0xFF;\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH044");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert_eq!(warnings[0].span.start_line, 2);
+    assert!(warnings[0].message.contains("0xFF"));
+}
+
+#[test]
+fn test_hex_mask_with_suffix_passes() {
+    let source = "void f(unsigned val) {\n    unsigned x;\n    x = val & 0xFFu;\n    x |= 0x10U;\n    x = 0x1UL << 4;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH044").is_empty());
+}
+
+#[test]
+fn test_hex_in_shift_and_complement_is_reported() {
+    let source = "void f(unsigned val) {\n    unsigned x;\n    x = 0x1 << val;\n    x = ~0x0F;\n    x ^= 0x80;\n}\n";
+    assert_eq!(diagnostics_with_code(source, "CGH044").len(), 3);
+}
+
+#[test]
+fn test_hex_outside_bitwise_context_passes() {
+    // ビット演算以外の16進定数と10進定数は対象外
+    let source = "int limit = 0x7F;\nvoid f(int val) {\n    int x;\n    x = val + 0x10;\n    x = val & 255;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH044").is_empty());
+}
+
+#[test]
+fn test_hex_unsigned_suffix_is_off_by_default() {
+    let source = "void f(int val) {\n    int x;\n    x = val & 0xFF;\n}\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        ..Default::default()
+    };
+    assert!(diagnose_with_source(&tu, &config, source)
+        .iter()
+        .all(|d| !matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH044")));
+}
@@ -0,0 +1,105 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::ast::Item;
+use crate::diagnostics::{diagnose, DiagnosticConfig, DiagnosticSeverity, DiagnosticCode};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_implicit_int_function_is_parsed_with_empty_return_type() {
+    let source = "foo(void)\n{\n    return 1;\n}\nint next;\n";
+    let mut parser = Parser::new(Lexer::new(source));
+    let tu = parser.parse();
+
+    assert_eq!(tu.items.len(), 2);
+    match &tu.items[0] {
+        Item::FunctionDecl { return_type, function_name, parameters, body, .. } => {
+            assert_eq!(return_type, "");
+            assert_eq!(function_name, "foo");
+            assert!(parameters.starts_with("(void)"));
+            assert_eq!(body.as_ref().map(|b| b.len()), Some(1));
+        }
+        other => panic!("Expected FunctionDecl, got {:?}", other),
+    }
+    assert!(matches!(&tu.items[1], Item::VarDecl { var_name, .. } if var_name == "next"));
+}
+
+#[test]
+fn test_implicit_int_warns() {
+    let source = "foo(void)\n{\n    return 1;\n}\n";
+
+    let warnings = diagnostics_with_code(source, "CGH026");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("'foo'"));
+    assert_eq!(warnings[0].span.start_line, 0);
+}
+
+#[test]
+fn test_explicit_return_type_is_silent() {
+    let source = "int\nfoo(void)\n{\n    return 1;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH026").is_empty());
+}
+
+#[test]
+fn test_macro_call_without_body_is_not_a_function() {
+    let source = "DECLARE_MODULE(foo);\nint next;\n";
+    let mut parser = Parser::new(Lexer::new(source));
+    let tu = parser.parse();
+
+    assert!(!tu.items.iter().any(|item| matches!(item, Item::FunctionDecl { .. })));
+    assert!(diagnostics_with_code(source, "CGH026").is_empty());
+}
+
+#[test]
+fn test_implicit_int_can_be_disabled() {
+    let source = "foo(void)\n{\n    return 1;\n}\n";
+    let mut parser = Parser::new(Lexer::new(source));
+    let tu = parser.parse();
+
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_implicit_int: false,
+        ..Default::default()
+    };
+    let diagnostics = diagnose(&tu, &config);
+    assert!(!diagnostics.iter().any(|d| matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH026")));
+}
+
+/// トップレベルの関数定義の戻り値の型を返す
+fn function_return_types(source: &str) -> Vec<(String, String)> {
+    let mut parser = Parser::new(Lexer::new(source));
+    let tu = parser.parse();
+    tu.items.iter().filter_map(|item| match item {
+        Item::FunctionDecl { return_type, function_name, .. } => Some((return_type.clone(), function_name.clone())),
+        _ => None,
+    }).collect()
+}
+
+#[test]
+fn test_typedef_return_type_is_silent() {
+    let source = "typedef struct node Node;\n\nNode *make(Node *n)\n{\n    return n;\n}\n";
+
+    assert_eq!(function_return_types(source), vec![("Node *".to_string(), "make".to_string())]);
+    assert!(diagnostics_with_code(source, "CGH026").is_empty());
+}
+
+#[test]
+fn test_stdint_return_type_is_silent() {
+    let source = "#include <stdint.h>\n\nuint32_t get_value(void)\n{\n    return 1;\n}\n";
+
+    assert_eq!(function_return_types(source), vec![("uint32_t".to_string(), "get_value".to_string())]);
+    assert!(diagnostics_with_code(source, "CGH026").is_empty());
+}
+
+#[test]
+fn test_unknown_pointer_return_type_is_silent() {
+    let source = "size_t *buf(void)\n{\n    return 0;\n}\n\nfoo(void)\n{\n    return 1;\n}\n";
+
+    assert_eq!(function_return_types(source), vec![
+        ("size_t *".to_string(), "buf".to_string()),
+        ("".to_string(), "foo".to_string()),
+    ]);
+    let warnings = diagnostics_with_code(source, "CGH026");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("'foo'"));
+}
@@ -0,0 +1,75 @@
+use crate::diagnostics::{Diagnostic, DiagnosticConfig, DiagnosticSeverity};
+use std::path::PathBuf;
+use super::diagnostics_with_config;
+
+fn include_guard_warnings(source: &str, filename: &str) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        source_path: Some(PathBuf::from(filename)),
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH016")
+}
+
+#[test]
+fn test_include_guard_well_formed() {
+    let source = r#"/* types.h */
+#ifndef TYPES_H
+#define TYPES_H
+
+typedef int MyInt;
+int global_value;
+
+#endif /* TYPES_H */
+"#;
+
+    assert!(include_guard_warnings(source, "types.h").is_empty());
+}
+
+#[test]
+fn test_include_guard_missing() {
+    let source = r#"
+typedef int MyInt;
+int global_value;
+"#;
+
+    let warnings = include_guard_warnings(source, "types.h");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+}
+
+#[test]
+fn test_include_guard_define_mismatch() {
+    let source = r#"
+#ifndef TYPES_H
+#define TYPE_H
+typedef int MyInt;
+#endif
+"#;
+
+    let warnings = include_guard_warnings(source, "types.h");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("TYPES_H"));
+}
+
+#[test]
+fn test_include_guard_declaration_outside() {
+    let source = r#"
+#ifndef TYPES_H
+#define TYPES_H
+typedef int MyInt;
+#endif
+int outside;
+"#;
+
+    assert_eq!(include_guard_warnings(source, "types.h").len(), 1);
+}
+
+#[test]
+fn test_include_guard_not_checked_for_source_file() {
+    let source = r#"
+int global_value;
+"#;
+
+    assert!(include_guard_warnings(source, "main.c").is_empty());
+}
@@ -0,0 +1,48 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose, DiagnosticConfig, DiagnosticSeverity};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_unsorted_includes_are_reported() {
+    let diags = diagnostics_with_code("#include <stdlib.h>\n#include <assert.h>\n", "CGH032");
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].severity, DiagnosticSeverity::Hint);
+    assert_eq!(diags[0].span.start_line, 1);
+    assert!(diags[0].message.contains("assert.h"));
+}
+
+#[test]
+fn test_system_include_after_local_is_reported() {
+    let diags = diagnostics_with_code("#include \"app.h\"\n\n#include <stdio.h>\n", "CGH032");
+    assert_eq!(diags.len(), 1);
+    assert!(diags[0].message.contains("<stdio.h>"));
+}
+
+#[test]
+fn test_sorted_and_grouped_includes_pass() {
+    let source = "#include <assert.h>\n#include <stdlib.h>\n\n#include \"app.h\"\n#include \"util.h\"\nint x;\n";
+    assert!(diagnostics_with_code(source, "CGH032").is_empty());
+}
+
+#[test]
+fn test_blank_line_starts_new_sorted_block() {
+    // 空行で区切られたまとまりごとにファイル名順であればよい
+    let source = "#include <stdlib.h>\n#include <string.h>\n\n#include <zlib.h>\n#include <curl/curl.h>\n";
+    let diags = diagnostics_with_code(source, "CGH032");
+    assert_eq!(diags.len(), 1);
+    assert!(diags[0].message.contains("curl/curl.h"));
+}
+
+#[test]
+fn test_includes_are_not_compared_across_branches_or_items() {
+    let source = "#include <b.h>\n#ifdef DEBUG\n#include <a.h>\n#else\n#include <z.h>\n#endif\n#define X 1\n#include <a.h>\n";
+    assert!(diagnostics_with_code(source, "CGH032").is_empty());
+}
+
+#[test]
+fn test_include_order_disabled_by_default() {
+    let tu = Parser::new(Lexer::new("#include <b.h>\n#include <a.h>\n")).parse();
+    let diags = diagnose(&tu, &DiagnosticConfig::default());
+    assert!(!diags.iter().any(|d| d.code.to_string() == "CGH032"));
+}
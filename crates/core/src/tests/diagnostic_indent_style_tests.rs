@@ -2,6 +2,7 @@ use crate::config::{IndentStyle, ProjectConfig};
 use crate::diagnostics::{diagnose_with_source, DiagnosticConfig, DiagnosticCode};
 use crate::lexer::Lexer;
 use crate::parser::Parser;
+use super::diagnostics_with_code;
 
 #[test]
 fn test_indent_style_tabs_expected() {
@@ -121,6 +122,7 @@ fn test_indent_style_mixed_tabs_and_spaces() {
     
     let mut config = DiagnosticConfig::default();
     config.check_indent_style = true;
+    config.check_mixed_indentation = false;  // 混在チェックを無効にした場合は CGH009 で報告される
     config.indent_style = IndentStyle::Spaces;
     config.indent_width = 4;
     
@@ -154,3 +156,47 @@ void foo() {
     // チェックが無効なので警告なし
     assert_eq!(cgh009_diagnostics.len(), 0);
 }
+
+#[test]
+fn test_mixed_indentation_fires() {
+    let source = "void
+foo(void)
+{
+\t    int x = 1;
+}
+";
+    let warnings = diagnostics_with_code(source, "CGH025");
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].span.start_line, 3);
+    assert_eq!(warnings[0].span.end_column, 5);
+    assert_eq!(&source[warnings[0].span.byte_start_idx..warnings[0].span.byte_end_idx], "\t    ");
+}
+
+#[test]
+fn test_consistent_spaces_do_not_fire() {
+    let source = "void
+foo(void)
+{
+    int x = 1;
+        \n}
+";
+    assert!(diagnostics_with_code(source, "CGH025").is_empty());
+}
+
+#[test]
+fn test_mixed_indentation_not_duplicated_by_indent_style() {
+    let source = "void
+foo(void)
+{
+\t    int x = 1;
+}
+";
+    let lexer = Lexer::new(source);
+    let mut parser = Parser::new(lexer);
+    let tu = parser.parse();
+
+    let diagnostics = diagnose_with_source(&tu, &DiagnosticConfig::default(), source);
+    assert!(!diagnostics.iter().any(|d| d.message.contains("タブとスペースが混在")
+        && matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH009")));
+}
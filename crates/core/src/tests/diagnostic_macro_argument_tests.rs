@@ -0,0 +1,56 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose, DiagnosticConfig, DiagnosticCode, DiagnosticSeverity};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_unparenthesized_argument_warns() {
+    let diags = diagnostics_with_code("#define SQ(x) x*x\n", "CGH031");
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].severity, DiagnosticSeverity::Warning);
+    assert_eq!(diags[0].span.start_line, 0);
+    assert!(diags[0].message.contains("'SQ'"));
+    assert!(diags[0].message.contains("'x'"));
+}
+
+#[test]
+fn test_parenthesized_argument_does_not_warn() {
+    assert!(diagnostics_with_code("#define SQ(x) ((x)*(x))\n", "CGH031").is_empty());
+}
+
+#[test]
+fn test_wrapped_body_with_bare_argument_warns() {
+    // 置換値全体が括弧で囲まれていても、引数自体が囲まれていなければ警告する
+    let diags = diagnostics_with_code("#define DOUBLE(x) (x * 2)\n", "CGH031");
+    assert_eq!(diags.len(), 1);
+}
+
+#[test]
+fn test_multiple_unparenthesized_arguments_reported_together() {
+    let diags = diagnostics_with_code("#define ADD(a, b) a + b\n", "CGH031");
+    assert_eq!(diags.len(), 1);
+    assert!(diags[0].message.contains("'a', 'b'"));
+}
+
+#[test]
+fn test_non_expression_uses_do_not_warn() {
+    let source = "#define STR(x) #x\n#define CONCAT(a, b) a ## b\n#define CALL(f, a) (f((a), 1) + 1)\n#define SWAP(a, b) do { int t = a; a = b; b = t; } while (0)\n#define ID(x) x\n";
+    assert!(diagnostics_with_code(source, "CGH031").is_empty());
+}
+
+#[test]
+fn test_object_like_macro_is_not_checked() {
+    assert!(diagnostics_with_code("#define VALUE x * 2\n", "CGH031").is_empty());
+}
+
+#[test]
+fn test_macro_argument_check_can_be_disabled() {
+    let tu = Parser::new(Lexer::new("#define SQ(x) x*x\n")).parse();
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_macro_argument_parentheses: false,
+        ..Default::default()
+    };
+    let diags = diagnose(&tu, &config);
+    assert!(!diags.iter().any(|d| matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH031")));
+}
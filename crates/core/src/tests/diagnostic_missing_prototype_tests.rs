@@ -0,0 +1,87 @@
+use crate::analysis::analyze;
+use crate::diagnostics::{DiagnosticConfig, DiagnosticSeverity};
+use std::fs;
+use tempfile::tempdir;
+
+fn missing_prototype_config(source_path: std::path::PathBuf) -> DiagnosticConfig {
+    DiagnosticConfig {
+        check_missing_prototypes: true,
+        source_path: Some(source_path),
+        ..Default::default()
+    }
+}
+
+fn cgh035_messages(source: &str, config: &DiagnosticConfig) -> Vec<String> {
+    analyze(source, config)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.code.to_string() == "CGH035")
+        .map(|d| d.message)
+        .collect()
+}
+
+#[test]
+fn test_missing_prototype_reported_for_undeclared_function() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("util.h"), "int util_add(int a, int b);\n").unwrap();
+    let source = "#include \"util.h\"\n\nint util_add(int a, int b) {\n    return a + b;\n}\n\nint util_sub(int a, int b) {\n    return a - b;\n}\n";
+    let source_path = temp.path().join("util.c");
+    fs::write(&source_path, source).unwrap();
+
+    let config = missing_prototype_config(source_path);
+    let result = analyze(source, &config);
+    let diagnostics: Vec<_> = result.diagnostics.iter().filter(|d| d.code.to_string() == "CGH035").collect();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("util_sub"));
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Information);
+    assert_eq!(diagnostics[0].span.start_line, 6);
+}
+
+#[test]
+fn test_missing_prototype_uses_nested_headers() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("inner.h"), "#ifdef FEATURE\n#endif\nvoid inner(void);\n").unwrap();
+    fs::write(temp.path().join("outer.h"), "#include \"inner.h\"\nvoid outer(void);\n").unwrap();
+    let source = "#include \"outer.h\"\nvoid inner(void) {}\nvoid outer(void) {}\n";
+    let source_path = temp.path().join("main.c");
+    fs::write(&source_path, source).unwrap();
+
+    assert!(cgh035_messages(source, &missing_prototype_config(source_path)).is_empty());
+}
+
+#[test]
+fn test_missing_prototype_skips_static_and_main() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("app.h"), "void app_run(void);\n").unwrap();
+    let source = "#include \"app.h\"\nstatic void helper(void) {}\nvoid app_run(void) { helper(); }\nint main(void) { app_run(); return 0; }\n";
+    let source_path = temp.path().join("app.c");
+    fs::write(&source_path, source).unwrap();
+
+    assert!(cgh035_messages(source, &missing_prototype_config(source_path)).is_empty());
+}
+
+#[test]
+fn test_missing_prototype_silent_without_analyzed_header() {
+    let temp = tempdir().unwrap();
+    let source = "void orphan(void) {}\n";
+    let source_path = temp.path().join("orphan.c");
+    fs::write(&source_path, source).unwrap();
+
+    assert!(cgh035_messages(source, &missing_prototype_config(source_path)).is_empty());
+}
+
+#[test]
+fn test_missing_prototype_disabled_by_default() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("util.h"), "int util_add(int a, int b);\n").unwrap();
+    let source = "#include \"util.h\"\nint util_sub(int a, int b) { return a - b; }\n";
+    let source_path = temp.path().join("util.c");
+    fs::write(&source_path, source).unwrap();
+
+    let config = DiagnosticConfig {
+        source_path: Some(source_path),
+        ..Default::default()
+    };
+    assert!(cgh035_messages(source, &config).is_empty());
+}
@@ -0,0 +1,125 @@
+use crate::config::{NamingPattern, NamingStyle, ProjectConfig};
+use crate::diagnostics::{Diagnostic, DiagnosticConfig, DiagnosticSeverity};
+use super::diagnostics_with_config;
+
+fn function_naming_warnings(source: &str, style: NamingStyle) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_function_naming: true,
+        function_naming_style: style,
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH022")
+}
+
+#[test]
+fn test_pascal_case_function_fires_under_snake_case() {
+    let warnings = function_naming_warnings("void MyFunc(void) {}\n", NamingStyle::SnakeCase);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("'MyFunc'"));
+    assert!(warnings[0].message.contains("'my_func'"));
+}
+
+#[test]
+fn test_snake_case_function_passes() {
+    assert!(function_naming_warnings("void my_func(void) {}\n", NamingStyle::SnakeCase).is_empty());
+}
+
+#[test]
+fn test_main_is_exempt() {
+    assert!(function_naming_warnings("int main(void) {}\n", NamingStyle::PascalCase).is_empty());
+}
+
+#[test]
+fn test_camel_case_suggestion() {
+    let warnings = function_naming_warnings("void read_HTTP_header(void);\n", NamingStyle::CamelCase);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("'readHttpHeader'"));
+}
+
+#[test]
+fn test_regex_function_naming() {
+    let style = NamingStyle::Regex(NamingPattern::new("mod_[a-z_]+").unwrap());
+    assert!(function_naming_warnings("void mod_init(void) {}\n", style.clone()).is_empty());
+
+    let warnings = function_naming_warnings("void init(void) {}\n", style);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("正規表現"));
+}
+
+#[test]
+fn test_naming_style_from_toml() {
+    let config: ProjectConfig = toml::from_str(
+        "[diagnostics]\nfunction_naming_style = { regex = \"^[a-z]+$\" }\n",
+    )
+    .unwrap();
+    assert_eq!(config.diagnostics.function_naming_style, NamingStyle::Regex(NamingPattern::new("^[a-z]+$").unwrap()));
+
+    let config: ProjectConfig = toml::from_str("[diagnostics]\nfunction_naming_style = \"camel_case\"\n").unwrap();
+    assert_eq!(config.diagnostics.function_naming_style, NamingStyle::CamelCase);
+}
+
+#[test]
+fn test_invalid_naming_regex_is_config_error() {
+    for key in ["function_naming_style", "type_naming_style"] {
+        let toml_str = format!("[diagnostics]\n{} = {{ regex = \"[a-z\" }}\n", key);
+        let error = toml::from_str::<ProjectConfig>(&toml_str).unwrap_err();
+        assert!(error.to_string().contains("命名規則の正規表現 '[a-z' が不正です"), "{}", error);
+    }
+}
+
+#[test]
+fn test_invalid_naming_regex_in_config_file_is_load_error() {
+    let dir = std::env::temp_dir().join("cgh_invalid_naming_regex");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("coding-guide.toml");
+    std::fs::write(&path, "[diagnostics]\nfunction_naming_style = { regex = \"(\" }\n").unwrap();
+
+    assert!(ProjectConfig::load_from_file(&path).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_naming_checks_disabled_by_default() {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        ..Default::default()
+    };
+    assert!(diagnostics_with_config("void MyFunc(void) {}\n", &config, "CGH022").is_empty());
+    assert!(diagnostics_with_config("struct point_2d { int x; };\n", &config, "CGH023").is_empty());
+}
+
+fn type_naming_warnings(source: &str) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_type_naming: true,
+        type_naming_style: NamingStyle::PascalCase,
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH023")
+}
+
+#[test]
+fn test_lowercase_struct_tag_fires() {
+    let warnings = type_naming_warnings("struct point_2d { int x; };\n");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("構造体タグ 'point_2d'"));
+    assert!(warnings[0].message.contains("'Point2d'"));
+}
+
+#[test]
+fn test_pascal_case_struct_tag_passes() {
+    assert!(type_naming_warnings("struct Point { int x; };\n").is_empty());
+}
+
+#[test]
+fn test_typedef_names_checked() {
+    let source = "typedef enum { RED, GREEN } color_t;\ntypedef unsigned int Uint32;\ntypedef void (*event_handler)(int);\n";
+    let warnings = type_naming_warnings(source);
+
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings.iter().any(|d| d.message.contains("typedef 名 'color_t'")));
+    assert!(warnings.iter().any(|d| d.message.contains("typedef 名 'event_handler'")));
+}
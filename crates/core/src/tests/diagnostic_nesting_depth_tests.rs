@@ -0,0 +1,114 @@
+use crate::diagnostics::{Diagnostic, DiagnosticConfig, DiagnosticSeverity};
+use super::diagnostics_with_config;
+
+fn nesting_warnings(source: &str, max_nesting_depth: Option<usize>) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        max_nesting_depth,
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH020")
+}
+
+#[test]
+fn test_three_nested_ifs_flagged() {
+    let source = r#"void
+foo(int a, int b, int c)
+{
+    if (a) {
+        if (b) {
+            if (c) {
+                a++;
+            }
+        }
+    }
+}
+"#;
+
+    let warnings = nesting_warnings(source, Some(2));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("ネストの深さ（3）"));
+}
+
+#[test]
+fn test_two_nested_ifs_not_flagged() {
+    let source = r#"void
+foo(int a, int b)
+{
+    if (a) {
+        if (b) {
+            a++;
+        }
+    }
+}
+"#;
+
+    assert!(nesting_warnings(source, Some(2)).is_empty());
+}
+
+#[test]
+fn test_nesting_counts_loops_and_blocks() {
+    let source = r#"void
+foo(int n)
+{
+    while (n) {
+        {
+            for (; n < 10; n++) {
+                n--;
+            }
+        }
+    }
+}
+"#;
+
+    let warnings = nesting_warnings(source, Some(2));
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("ネストの深さ（3）"));
+}
+
+#[test]
+fn test_flat_else_if_chain_not_flagged() {
+    let source = r#"int
+classify(int n)
+{
+    int r;
+    if (n == 0) {
+        r = 0;
+    } else if (n == 1) {
+        r = 1;
+    } else if (n == 2) {
+        r = 2;
+    } else if (n == 3) {
+        r = 3;
+    } else if (n == 4) {
+        r = 4;
+    } else {
+        r = 5;
+    }
+    return r;
+}
+"#;
+
+    assert!(nesting_warnings(source, Some(1)).is_empty());
+}
+
+#[test]
+fn test_nesting_inside_else_if_branch_counted_from_chain_depth() {
+    let source = r#"void
+foo(int a, int b)
+{
+    if (a == 0) {
+        a++;
+    } else if (a == 1) {
+        if (b) {
+            a--;
+        }
+    }
+}
+"#;
+
+    let warnings = nesting_warnings(source, Some(1));
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("ネストの深さ（2）"));
+}
@@ -0,0 +1,49 @@
+use crate::config::IndentStyle;
+use crate::diagnostics::{Diagnostic, DiagnosticConfig};
+use super::diagnostics_with_config;
+
+fn tab_warnings(source: &str, indent_style: IndentStyle) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_no_tabs: true,
+        indent_style,
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH048")
+}
+
+#[test]
+fn test_mid_line_tab_is_reported() {
+    let source = "int x;\nint value =\t1;\n";
+    let warnings = tab_warnings(source, IndentStyle::Spaces);
+    assert_eq!(warnings.len(), 1);
+    let span = &warnings[0].span;
+    assert_eq!((span.start_line, span.start_column, span.end_column), (1, 11, 12));
+    assert_eq!(&source[span.byte_start_idx..span.byte_end_idx], "\t");
+}
+
+#[test]
+fn test_consecutive_tabs_are_one_diagnostic() {
+    let source = "void f(void) {\n\t\tint a;\t// コメント\n}\n";
+    let warnings = tab_warnings(source, IndentStyle::Spaces);
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(&source[warnings[0].span.byte_start_idx..warnings[0].span.byte_end_idx], "\t\t");
+    assert_eq!((warnings[1].span.start_line, warnings[1].span.start_column), (1, 8));
+}
+
+#[test]
+fn test_space_only_file_passes() {
+    let source = "void f(void) {\n    int a = 1;  // コメント\n}\n";
+    assert!(tab_warnings(source, IndentStyle::Spaces).is_empty());
+}
+
+#[test]
+fn test_tabs_allowed_when_indent_style_is_tabs() {
+    let source = "void f(void) {\n\tint a =\t1;\n}\n";
+    assert!(tab_warnings(source, IndentStyle::Tabs).is_empty());
+}
+
+#[test]
+fn test_no_tabs_disabled_by_default() {
+    assert!(!DiagnosticConfig::default().check_no_tabs);
+}
@@ -0,0 +1,74 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose_with_source, DiagnosticConfig};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_non_ascii_identifier_is_reported() {
+    let source = "int x;\nint 変数 = 1;\n";
+    let warnings = diagnostics_with_code(source, "CGH051");
+    assert_eq!(warnings.len(), 1);
+    let span = &warnings[0].span;
+    assert_eq!((span.start_line, span.start_column), (1, 4));
+    assert_eq!(&source[span.byte_start_idx..span.byte_end_idx], "変数");
+}
+
+#[test]
+fn test_non_ascii_comment_is_not_reported() {
+    let source = "// 日本語のコメント\nint x; /* 説明 */\n";
+    assert!(diagnostics_with_code(source, "CGH051").is_empty());
+}
+
+#[test]
+fn test_non_ascii_string_and_char_literals_are_not_reported() {
+    let source = "const char *s = \"日本 \\\" // é\";\nint c = 'é';\n";
+    assert!(diagnostics_with_code(source, "CGH051").is_empty());
+}
+
+#[test]
+fn test_non_ascii_after_comment_is_reported() {
+    let source = "/* é */ int x = 1；\n";
+    let warnings = diagnostics_with_code(source, "CGH051");
+    assert_eq!(warnings.len(), 1);
+    let span = &warnings[0].span;
+    assert_eq!(&source[span.byte_start_idx..span.byte_end_idx], "；");
+}
+
+#[test]
+fn test_utf8_bom_is_reported() {
+    let source = "\u{FEFF}int x;\n";
+    let warnings = diagnostics_with_code(source, "CGH051");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("BOM"));
+    assert_eq!((warnings[0].span.byte_start_idx, warnings[0].span.byte_end_idx), (0, 3));
+    assert_eq!((warnings[0].span.start_column, warnings[0].span.end_column), (0, 1));
+}
+
+#[test]
+fn test_non_ascii_columns_count_characters() {
+    let source = "int été;\n";
+    let warnings = diagnostics_with_code(source, "CGH051");
+    let columns: Vec<_> = warnings.iter().map(|w| (w.span.start_column, w.span.end_column)).collect();
+    assert_eq!(columns, vec![(4, 5), (6, 7)]);
+}
+
+#[test]
+fn test_non_ascii_columns_after_utf8_bom() {
+    let source = "\u{FEFF}int 変数;\n";
+    let warnings = diagnostics_with_code(source, "CGH051");
+    assert_eq!(warnings.len(), 2);
+    assert_eq!((warnings[1].span.start_column, warnings[1].span.end_column), (4, 6));
+}
+
+#[test]
+fn test_non_ascii_disabled_by_default() {
+    let source = "int 変数 = 1;\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        ..Default::default()
+    };
+    assert!(diagnose_with_source(&tu, &config, source)
+        .iter()
+        .all(|d| d.code.to_string() != "CGH051"));
+}
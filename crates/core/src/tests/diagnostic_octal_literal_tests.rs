@@ -0,0 +1,42 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose_with_source, DiagnosticConfig, DiagnosticCode, DiagnosticSeverity};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_octal_literal_is_reported() {
+    let source = "int x = 010;\n";
+    let warnings = diagnostics_with_code(source, "CGH045");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert_eq!(warnings[0].span.start_line, 0);
+    assert!(warnings[0].message.contains("010"));
+}
+
+#[test]
+fn test_octal_literal_in_function_body_is_reported() {
+    let source = "void f(void) {\n    int mode;\n    mode = 0755u;\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH045");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].span.start_line, 2);
+}
+
+#[test]
+fn test_zero_hex_and_decimal_pass() {
+    let source = "int a = 0;\nint b = 0x10;\nint c = 10;\nint d = 0u;\ndouble e = 0.5;\n/* 010 */\n";
+    assert!(diagnostics_with_code(source, "CGH045").is_empty());
+}
+
+#[test]
+fn test_octal_literal_check_can_be_disabled() {
+    let source = "int x = 010;\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_octal_literals: false,
+        ..Default::default()
+    };
+    assert!(diagnose_with_source(&tu, &config, source)
+        .iter()
+        .all(|d| !matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH045")));
+}
@@ -0,0 +1,68 @@
+use crate::config::OperatorClass;
+use crate::diagnostics::{Diagnostic, DiagnosticConfig, DiagnosticSeverity};
+use super::diagnostics_with_config;
+
+fn spacing_warnings_for(source: &str, classes: Vec<OperatorClass>) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_operator_spacing: true,
+        operator_spacing_classes: classes,
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH047")
+}
+
+fn spacing_warnings(source: &str) -> Vec<Diagnostic> {
+    spacing_warnings_for(source, OperatorClass::ALL.to_vec())
+}
+
+#[test]
+fn test_missing_space_around_binary_operator_is_reported() {
+    let source = "void f(int a, int b)\n{\n    int c;\n    c = a+b;\n}\n";
+    let warnings = spacing_warnings(source);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert_eq!(warnings[0].span.start_line, 3);
+    assert_eq!(&source[warnings[0].span.byte_start_idx..warnings[0].span.byte_end_idx], "+");
+}
+
+#[test]
+fn test_space_on_one_side_is_reported() {
+    let source = "void f(int a, int b)\n{\n    int c;\n    c= a *b;\n}\n";
+    assert_eq!(spacing_warnings(source).len(), 2);
+}
+
+#[test]
+fn test_spaced_binary_operators_pass() {
+    let source = "void f(int a, int b)\n{\n    int c;\n    c = a + b;\n    c += (a << 2) & b;\n    if (a == b && c != 0) {\n        c = a % b;\n    }\n}\n";
+    assert!(spacing_warnings(source).is_empty());
+}
+
+#[test]
+fn test_unary_and_pointer_operators_pass() {
+    let source = "typedef int Count;\nvoid f(int *p, int a)\n{\n    int *q;\n    Count*r;\n    char* s;\n    q = &a;\n    a = *p;\n    a = -1;\n    a = (int)-a;\n    a = !a;\n    a++;\n}\n";
+    assert!(spacing_warnings(source).is_empty());
+}
+
+#[test]
+fn test_operator_classes_are_configurable() {
+    let source = "void f(int a, int b)\n{\n    int c;\n    c=a+b;\n}\n";
+    let warnings = spacing_warnings_for(source, vec![OperatorClass::Assignment]);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("'='"));
+
+    let warnings = spacing_warnings_for(source, vec![OperatorClass::Arithmetic]);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("'+'"));
+}
+
+#[test]
+fn test_operator_spacing_classes_from_toml() {
+    let config: crate::config::ProjectConfig = toml::from_str(
+        "[diagnostics]\ncheck_operator_spacing = true\noperator_spacing_classes = [\"comparison\", \"logical\"]\n",
+    )
+    .unwrap();
+    let config = config.to_diagnostic_config();
+    assert!(config.check_operator_spacing);
+    assert_eq!(config.operator_spacing_classes, vec![OperatorClass::Comparison, OperatorClass::Logical]);
+}
@@ -0,0 +1,142 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::span::Span;
+use crate::diagnostics::{dedup_diagnostics, diagnose, sort_by_position, Diagnostic, DiagnosticConfig, DiagnosticCode, DiagnosticSeverity};
+
+#[test]
+fn test_diagnostics_from_multiple_checks_are_sorted_by_position() {
+    // 後で実行されるチェック（マクロの括弧）の診断がソースの先頭にある
+    let source = "#define SUM 1 + 2\nint lower_case = 1;\nstruct Empty {};\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let diags = diagnose(&tu, &DiagnosticConfig::default());
+
+    let codes: Vec<String> = diags.iter().map(|d| d.code.to_string()).collect();
+    assert!(codes.contains(&"CGH001".to_string()));
+    assert!(codes.contains(&"CGH005".to_string()));
+    assert!(codes.contains(&"CGH006".to_string()));
+    assert!(codes.contains(&"CGH027".to_string()));
+
+    let positions: Vec<(usize, usize)> = diags.iter().map(|d| (d.span.start_line, d.span.start_column)).collect();
+    let mut sorted = positions.clone();
+    sorted.sort();
+    assert_eq!(positions, sorted);
+}
+
+#[test]
+fn test_same_position_is_ordered_by_code_and_stable() {
+    let span = |line, column| Span::new(line, column, line, column + 1);
+    let diag = |line, column, code: &str, message: &str| {
+        Diagnostic::new(span(line, column), DiagnosticSeverity::Warning, message.to_string(), DiagnosticCode::Custom(code.to_string()))
+    };
+    let mut diags = vec![
+        diag(2, 0, "CGH006", "b"),
+        diag(0, 4, "CGH005", "first"),
+        diag(0, 4, "CGH003", "c"),
+        diag(0, 4, "CGH005", "second"),
+        diag(0, 0, "CGH001", "a"),
+    ];
+    sort_by_position(&mut diags);
+
+    let order: Vec<&str> = diags.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(order, vec!["a", "c", "first", "second", "b"]);
+}
+
+#[test]
+fn test_identical_diagnostics_collapse_to_one() {
+    let diag = |line, column, code: &str, message: &str| {
+        Diagnostic::new(Span::new(line, column, line, column + 3), DiagnosticSeverity::Warning, message.to_string(), DiagnosticCode::Custom(code.to_string()))
+    };
+    // 条件ブロックへの再帰などで同じ診断が2回出力された状況
+    let mut diags = vec![
+        diag(1, 4, "CGH002", "same"),
+        diag(1, 4, "CGH002", "same"),
+        diag(1, 4, "CGH002", "other message"),
+        diag(1, 4, "CGH003", "same"),
+        diag(2, 4, "CGH002", "same"),
+        diag(1, 4, "CGH002", "same"),
+    ];
+    dedup_diagnostics(&mut diags);
+
+    assert_eq!(diags.len(), 4);
+    let keys: Vec<(usize, String, &str)> = diags.iter().map(|d| (d.span.start_line, d.code.to_string(), d.message.as_str())).collect();
+    assert_eq!(keys, vec![
+        (1, "CGH002".to_string(), "same"),
+        (1, "CGH002".to_string(), "other message"),
+        (1, "CGH003".to_string(), "same"),
+        (2, "CGH002".to_string(), "same"),
+    ]);
+}
+
+#[test]
+fn test_diagnose_output_has_no_duplicates() {
+    let source = "#ifdef A\nint bad_Func(void) {\n    return 0;\n}\n#else\nint bad_Func(void) {\n    return 1;\n}\n#endif\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let diags = diagnose(&tu, &DiagnosticConfig::default());
+
+    for (i, a) in diags.iter().enumerate() {
+        for b in &diags[i + 1..] {
+            assert!(!(a.code == b.code && a.span == b.span && a.message == b.message), "duplicate diagnostic: {:?}", a);
+        }
+    }
+}
+
+#[test]
+fn test_shuffled_diagnostics_sort_into_total_order() {
+    let diag = |line, column, end_column, code: DiagnosticCode, severity: DiagnosticSeverity, message: &str| {
+        Diagnostic::new(Span::new(line, column, line, end_column), severity, message.to_string(), code)
+    };
+    let custom = |code: &str| DiagnosticCode::Custom(code.to_string());
+    let expected = vec![
+        diag(0, 0, 3, custom("CGH001"), DiagnosticSeverity::Warning, "a"),
+        diag(0, 4, 6, custom("CGH003"), DiagnosticSeverity::Warning, "b"),
+        diag(0, 4, 6, custom("CGH005"), DiagnosticSeverity::Error, "c"),
+        diag(0, 4, 6, custom("CGH005"), DiagnosticSeverity::Warning, "c"),
+        diag(0, 4, 6, custom("CGH005"), DiagnosticSeverity::Warning, "d"),
+        diag(0, 4, 6, DiagnosticCode::CweC(20), DiagnosticSeverity::Warning, "e"),
+        diag(0, 4, 6, DiagnosticCode::CweC(119), DiagnosticSeverity::Warning, "f"),
+        diag(0, 4, 9, custom("CGH001"), DiagnosticSeverity::Hint, "g"),
+        diag(3, 0, 1, custom("CGH002"), DiagnosticSeverity::Information, "h"),
+    ];
+
+    let mut shuffled = expected.clone();
+    shuffled.reverse();
+    shuffled.swap(1, 6);
+    shuffled.swap(0, 4);
+    shuffled.sort();
+    assert_eq!(shuffled, expected);
+}
+
+#[test]
+fn test_severity_parses_from_str() {
+    assert_eq!("error".parse::<DiagnosticSeverity>(), Ok(DiagnosticSeverity::Error));
+    assert_eq!("Warning".parse::<DiagnosticSeverity>(), Ok(DiagnosticSeverity::Warning));
+    assert_eq!("info".parse::<DiagnosticSeverity>(), Ok(DiagnosticSeverity::Information));
+    assert_eq!("information".parse::<DiagnosticSeverity>(), Ok(DiagnosticSeverity::Information));
+    assert_eq!("hint".parse::<DiagnosticSeverity>(), Ok(DiagnosticSeverity::Hint));
+    assert!("fatal".parse::<DiagnosticSeverity>().is_err());
+
+    for severity in [DiagnosticSeverity::Error, DiagnosticSeverity::Warning, DiagnosticSeverity::Information, DiagnosticSeverity::Hint] {
+        assert_eq!(severity.as_str().parse::<DiagnosticSeverity>(), Ok(severity));
+    }
+}
+
+#[test]
+fn test_severity_ordering() {
+    assert!(DiagnosticSeverity::Error > DiagnosticSeverity::Warning);
+    assert!(DiagnosticSeverity::Warning > DiagnosticSeverity::Information);
+    assert!(DiagnosticSeverity::Information > DiagnosticSeverity::Hint);
+
+    let severities = [DiagnosticSeverity::Hint, DiagnosticSeverity::Error, DiagnosticSeverity::Warning];
+    assert_eq!(severities.iter().max(), Some(&DiagnosticSeverity::Error));
+}
+
+#[test]
+fn test_same_position_and_code_sorts_higher_severity_first() {
+    let span = Span::new(0, 0, 0, 1);
+    let code = DiagnosticCode::Custom("CGH001".to_string());
+    let hint = Diagnostic::new(span.clone(), DiagnosticSeverity::Hint, "m".to_string(), code.clone());
+    let error = Diagnostic::new(span, DiagnosticSeverity::Error, "m".to_string(), code);
+    let mut diags = vec![hint.clone(), error.clone()];
+    diags.sort();
+    assert_eq!(diags, vec![error, hint]);
+}
@@ -0,0 +1,38 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose, DiagnosticConfig};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_uncommented_packed_struct_is_reported() {
+    let source = "struct __attribute__((packed)) Header {\n    char tag;\n    int length;\n};\n";
+    let warnings = diagnostics_with_code(source, "CGH049");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("Header"));
+}
+
+#[test]
+fn test_commented_packed_struct_passes() {
+    let source = "/* 通信フレームのレイアウトに合わせるためパックする */\nstruct __attribute__((packed)) Header {\n    char tag;\n    int length;\n};\n";
+    assert!(diagnostics_with_code(source, "CGH049").is_empty());
+}
+
+#[test]
+fn test_packed_attribute_after_body_is_reported() {
+    let source = "struct Header {\n    char tag;\n} __attribute__((packed));\n";
+    assert_eq!(diagnostics_with_code(source, "CGH049").len(), 1);
+}
+
+#[test]
+fn test_unpacked_struct_passes() {
+    let source = "struct __attribute__((aligned(8))) Header {\n    char tag;\n};\n";
+    assert!(diagnostics_with_code(source, "CGH049").is_empty());
+}
+
+#[test]
+fn test_packed_struct_comment_disabled_by_default() {
+    let source = "struct __attribute__((packed)) Header {\n    char tag;\n};\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let config = DiagnosticConfig { check_file_header: false, ..Default::default() };
+    assert!(!diagnose(&tu, &config).iter().any(|d| d.code.to_string() == "CGH049"));
+}
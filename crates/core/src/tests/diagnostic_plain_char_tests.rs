@@ -0,0 +1,40 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::diagnostics::{diagnose, DiagnosticConfig, DiagnosticSeverity};
+use super::diagnostics_with_code;
+
+#[test]
+fn test_char_initialized_with_arithmetic_is_reported() {
+    let warnings = diagnostics_with_code("void f(int x) {\n    char c = x + 1;\n}\n", "CGH037");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("'c'"));
+    assert_eq!(warnings[0].span.start_line, 1);
+}
+
+#[test]
+fn test_char_used_as_operand_is_reported() {
+    let warnings = diagnostics_with_code("char total;\nvoid f(void) {\n    char c;\n    c++;\n    total += 2;\n}\n", "CGH037");
+    assert_eq!(warnings.len(), 2);
+}
+
+#[test]
+fn test_explicit_signedness_passes() {
+    let source = "void f(int x) {\n    unsigned char c = x + 1;\n    signed char d;\n    d = d * 2;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH037").is_empty());
+}
+
+#[test]
+fn test_char_used_only_as_character_passes() {
+    assert!(diagnostics_with_code("void f(void) {\n    char c = 'a';\n    put(c);\n}\n", "CGH037").is_empty());
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let tu = Parser::new(Lexer::new("void f(int x) {\n    char c = x + 1;\n}\n")).parse();
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        ..Default::default()
+    };
+    assert!(diagnose(&tu, &config).iter().all(|d| d.code.to_string() != "CGH037"));
+}
@@ -151,3 +151,54 @@ fn test_preprocessor_indent_config_disabled() {
         .collect();
     assert_eq!(preprocessor_warnings.len(), 0);
 }
+
+const COLUMN_ZERO_LAYOUT: &str = "#ifdef A\n#ifdef B\n#define X 1\n#endif\n#endif\n";
+const HASH_COLUMN_ZERO_LAYOUT: &str = "#ifdef A\n#  ifdef B\n#    define X 1\n#  endif\n#endif\n";
+const INDENTED_LAYOUT: &str = "#ifdef A\n  #ifdef B\n    #define X 1\n  #endif\n#endif\n";
+
+fn cgh008_count(source: &str, style: crate::config::PreprocessorIndentStyle) -> usize {
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let config = DiagnosticConfig {
+        preprocessor_indent_style: style,
+        indent_width: 2,
+        ..Default::default()
+    };
+    crate::diagnostics::diagnose_with_source(&tu, &config, source)
+        .iter()
+        .filter(|d| matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH008"))
+        .count()
+}
+
+#[test]
+fn test_preprocessor_indent_style_column_zero() {
+    use crate::config::PreprocessorIndentStyle::ColumnZero;
+    assert_eq!(cgh008_count(COLUMN_ZERO_LAYOUT, ColumnZero), 0);
+    assert_eq!(cgh008_count(HASH_COLUMN_ZERO_LAYOUT, ColumnZero), 3);
+    assert_eq!(cgh008_count(INDENTED_LAYOUT, ColumnZero), 3);
+}
+
+#[test]
+fn test_preprocessor_indent_style_hash_column_zero() {
+    use crate::config::PreprocessorIndentStyle::HashColumnZero;
+    assert_eq!(cgh008_count(HASH_COLUMN_ZERO_LAYOUT, HashColumnZero), 0);
+    assert_eq!(cgh008_count(COLUMN_ZERO_LAYOUT, HashColumnZero), 3);
+    assert_eq!(cgh008_count(INDENTED_LAYOUT, HashColumnZero), 3);
+}
+
+#[test]
+fn test_preprocessor_indent_style_indented() {
+    use crate::config::PreprocessorIndentStyle::Indented;
+    assert_eq!(cgh008_count(INDENTED_LAYOUT, Indented), 0);
+    assert_eq!(cgh008_count(COLUMN_ZERO_LAYOUT, Indented), 3);
+    assert_eq!(cgh008_count(HASH_COLUMN_ZERO_LAYOUT, Indented), 3);
+}
+
+#[test]
+fn test_preprocessor_indent_style_from_toml() {
+    let config: crate::config::ProjectConfig =
+        toml::from_str("[diagnostics]\npreprocessor_indent_style = \"hash_column_zero\"\n").unwrap();
+    assert_eq!(
+        config.diagnostics.preprocessor_indent_style,
+        crate::config::PreprocessorIndentStyle::HashColumnZero
+    );
+}
@@ -0,0 +1,43 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_duplicate_const_is_reported() {
+    let warnings = diagnostics_with_code("const const int x;\n", "CGH034");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("'x'"));
+    assert!(warnings[0].message.contains("'const'"));
+}
+
+#[test]
+fn test_single_const_passes() {
+    assert!(diagnostics_with_code("const int x;\n", "CGH034").is_empty());
+}
+
+#[test]
+fn test_duplicate_qualifier_on_pointer_layer() {
+    let warnings = diagnostics_with_code("int *volatile volatile p;\n", "CGH034");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("'volatile'"));
+}
+
+#[test]
+fn test_same_qualifier_on_different_levels_passes() {
+    assert!(diagnostics_with_code("const int *const p;\n", "CGH034").is_empty());
+}
+
+#[test]
+fn test_duplicate_qualifier_in_locals_and_members() {
+    let source = "struct S { const const int m; };\nvoid f(){ volatile volatile int y; }\n";
+    let warnings = diagnostics_with_code(source, "CGH034");
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings.iter().any(|w| w.message.contains("'m'")));
+    assert!(warnings.iter().any(|w| w.message.contains("'y'") && w.span.start_line == 1));
+}
+
+#[test]
+fn test_qualifier_added_to_typedef_passes() {
+    // typedef 側の const に重ねて const を付けても重複とはみなさない
+    assert!(diagnostics_with_code("typedef const int CInt;\nconst CInt x;\n", "CGH034").is_empty());
+}
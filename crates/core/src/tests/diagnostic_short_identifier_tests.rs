@@ -0,0 +1,50 @@
+use crate::diagnostics::{Diagnostic, DiagnosticConfig};
+use super::diagnostics_with_config;
+
+fn short_identifier_warnings(source: &str, config: DiagnosticConfig) -> Vec<Diagnostic> {
+    diagnostics_with_config(source, &config, "CGH028")
+}
+
+fn enabled_config() -> DiagnosticConfig {
+    DiagnosticConfig {
+        check_file_header: false,
+        check_short_identifiers: true,
+        min_identifier_length: 3,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_short_global_name_warns() {
+    let diags = short_identifier_warnings("int x;\n", enabled_config());
+    assert_eq!(diags.len(), 1);
+    assert!(diags[0].message.contains("'x'"));
+}
+
+#[test]
+fn test_long_global_name_does_not_warn() {
+    assert!(short_identifier_warnings("int counter;\n", enabled_config()).is_empty());
+}
+
+#[test]
+fn test_loop_index_names_are_exempt_by_default() {
+    assert!(short_identifier_warnings("int i;\nint j;\nint k;\n", enabled_config()).is_empty());
+}
+
+#[test]
+fn test_loop_index_names_warn_when_exemption_disabled() {
+    let config = DiagnosticConfig {
+        allow_loop_index_names: false,
+        ..enabled_config()
+    };
+    assert_eq!(short_identifier_warnings("int i;\n", config).len(), 1);
+}
+
+#[test]
+fn test_short_identifier_check_is_disabled_by_default() {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        ..Default::default()
+    };
+    assert!(short_identifier_warnings("int x;\n", config).is_empty());
+}
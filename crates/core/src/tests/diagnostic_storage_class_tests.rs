@@ -0,0 +1,31 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_storage_class_after_qualifier_fires() {
+    let warnings = diagnostics_with_code("const static int X;\n", "CGH004");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("'static'"));
+    assert!(warnings[0].message.contains("'static const int X'"));
+}
+
+#[test]
+fn test_storage_class_first_passes() {
+    assert!(diagnostics_with_code("static const int X;\nextern unsigned long Y;\n", "CGH004").is_empty());
+}
+
+#[test]
+fn test_storage_class_after_type_fires() {
+    let warnings = diagnostics_with_code("int static counter = 0;\n", "CGH004");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("'int'"));
+}
+
+#[test]
+fn test_function_storage_class_order() {
+    let source = "const static int\nget_value(void)\n{\n    return 1;\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH004");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("'static const int get_value'"));
+}
@@ -100,3 +100,28 @@ fn test_type_safety_disabled() {
     // Should NOT have type safety diagnostics when disabled
     assert!(!diagnostics.iter().any(|d| matches!(d.code, DiagnosticCode::Custom(ref code) if code.starts_with("CGH1"))));
 }
+
+#[test]
+fn test_void_variable_inside_ifdef_error() {
+    let input = "#ifdef FEATURE\nvoid x;\n#else\nvoid y;\n#endif\n";
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer);
+    let tu = parser.parse();
+
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        check_function_format: false,
+        ..Default::default()
+    };
+
+    let diagnostics = diagnose(&tu, &config);
+
+    // 条件ブロックの両方の分岐で検出される
+    let void_errors: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| matches!(d.code, DiagnosticCode::Custom(ref code) if code == "CGH101"))
+        .collect();
+    assert_eq!(void_errors.len(), 2);
+    assert!(void_errors.iter().any(|d| d.message.contains("'x'")));
+    assert!(void_errors.iter().any(|d| d.message.contains("'y'")));
+}
@@ -0,0 +1,47 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::ast::{Item, Statement};
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_local_variable_shadowing_typedef_is_reported() {
+    let warnings = diagnostics_with_code("typedef int Foo;\nvoid f(){ int Foo; }\n", "CGH033");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("Foo"));
+    // ローカル変数の宣言位置を指し、typedef の定義を関連情報として持つ
+    assert_eq!(warnings[0].span.start_line, 1);
+    assert_eq!(warnings[0].related.len(), 1);
+    assert_eq!(warnings[0].related[0].0.start_line, 0);
+}
+
+#[test]
+fn test_shadowing_in_nested_block_and_struct_typedef() {
+    let source = "typedef struct { int x; } Point;\nvoid\nf(void)\n{\n    if (1) {\n        int Point = 0;\n    }\n}\n";
+    let warnings = diagnostics_with_code(source, "CGH033");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].span.start_line, 5);
+}
+
+#[test]
+fn test_non_shadowing_local_variable_passes() {
+    let source = "typedef int Foo;\nvoid f(){ int bar; Foo baz; }\n";
+    assert!(diagnostics_with_code(source, "CGH033").is_empty());
+}
+
+#[test]
+fn test_typedef_after_function_is_not_in_scope() {
+    let source = "void f(){ int Foo; }\ntypedef int Foo;\n";
+    assert!(diagnostics_with_code(source, "CGH033").is_empty());
+}
+
+#[test]
+fn test_function_body_uses_typedefs_declared_before_it() {
+    // 本体の解析でも型テーブルを参照するため、typedef 名で始まる文は変数宣言になる
+    let tu = Parser::new(Lexer::new("typedef int Foo;\nvoid f(){ Foo baz; }\n")).parse();
+    let Some(Item::FunctionDecl { body: Some(body), .. }) = tu.items.last() else {
+        panic!("Expected function definition with body");
+    };
+    assert!(matches!(&body[..], [Statement::VarDecl { var_name, .. }] if var_name == "baz"));
+}
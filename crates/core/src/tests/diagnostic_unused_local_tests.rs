@@ -0,0 +1,32 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_unused_local_is_reported() {
+    // 初期化だけでは使用とみなさない
+    let warnings = diagnostics_with_code("void f(void) {\n    int count = 10;\n    int used = 1;\n    g(used);\n}\n", "CGH042");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity, DiagnosticSeverity::Warning);
+    assert!(warnings[0].message.contains("'count'"));
+    assert_eq!(warnings[0].span.start_line, 1);
+}
+
+#[test]
+fn test_local_used_in_later_statement_passes() {
+    let source = "int f(int x) {\n    int total = 0;\n    if (x) {\n        total = total + x;\n    }\n    return total;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH042").is_empty());
+}
+
+#[test]
+fn test_address_of_counts_as_use() {
+    assert!(diagnostics_with_code("void f(void) {\n    int value;\n    read(&value);\n}\n", "CGH042").is_empty());
+}
+
+#[test]
+fn test_for_loop_variable() {
+    assert!(diagnostics_with_code("void f(void) {\n    for (int i = 0; i < 3; i++) {\n    }\n}\n", "CGH042").is_empty());
+
+    let warnings = diagnostics_with_code("void f(void) {\n    int n = 0;\n    for (int i = 0; n < 3; n++) {\n    }\n}\n", "CGH042");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("'i'"));
+}
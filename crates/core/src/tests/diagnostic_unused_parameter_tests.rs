@@ -0,0 +1,34 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_unused_parameter_is_reported() {
+    let hints = diagnostics_with_code("int add(int a, int b) {\n    return a + 1;\n}\n", "CGH041");
+    assert_eq!(hints.len(), 1);
+    assert_eq!(hints[0].severity, DiagnosticSeverity::Hint);
+    assert!(hints[0].message.contains("'b'"));
+    assert!(hints[0].message.contains("(void)b;"));
+}
+
+#[test]
+fn test_used_parameters_pass() {
+    let source = "typedef unsigned int Size;\nint sum(const int *values, Size count) {\n    int total = 0;\n    for (int i = 0; i < count; i++) {\n        total += values[i];\n    }\n    return total;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH041").is_empty());
+}
+
+#[test]
+fn test_void_cast_and_underscore_prefix_pass() {
+    let source = "void on_event(int code, void *_context) {\n    (void)code;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH041").is_empty());
+}
+
+#[test]
+fn test_prototype_without_body_is_skipped() {
+    assert!(diagnostics_with_code("int add(int a, int b);\n", "CGH041").is_empty());
+}
+
+#[test]
+fn test_parameter_compared_with_double_quote_char_passes() {
+    let hints = diagnostics_with_code("int is_quote(char c) {\n    return c == '\"';\n}\n", "CGH041");
+    assert!(hints.is_empty(), "{:?}", hints);
+}
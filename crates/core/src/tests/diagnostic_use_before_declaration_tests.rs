@@ -0,0 +1,31 @@
+use crate::diagnostics::DiagnosticSeverity;
+use super::diagnostics_with_code;
+
+#[test]
+fn test_use_before_declaration_is_error() {
+    let errors = diagnostics_with_code("void f(void) {\n    {\n        x = 1;\n        int x;\n    }\n}\n", "CGH043");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].severity, DiagnosticSeverity::Error);
+    assert!(errors[0].message.contains("'x'"));
+    assert_eq!(errors[0].span.start_line, 2);
+    assert_eq!(errors[0].related[0].0.start_line, 3);
+}
+
+#[test]
+fn test_declaration_first_passes() {
+    let source = "void f(void) {\n    int x;\n    x = 1;\n    if (x) {\n        x = 2;\n    }\n}\n";
+    assert!(diagnostics_with_code(source, "CGH043").is_empty());
+}
+
+#[test]
+fn test_outer_declaration_is_not_reported() {
+    // 内側の宣言より前の x は引数・グローバル変数を指す
+    let source = "int g;\nvoid f(int x) {\n    x = 1;\n    g = 2;\n    {\n        int x;\n        int g;\n    }\n}\n";
+    assert!(diagnostics_with_code(source, "CGH043").is_empty());
+}
+
+#[test]
+fn test_nested_redeclaration_does_not_count_as_use() {
+    let source = "void f(void) {\n    {\n        int y;\n        y = 1;\n    }\n    int y = 0;\n    y++;\n}\n";
+    assert!(diagnostics_with_code(source, "CGH043").is_empty());
+}
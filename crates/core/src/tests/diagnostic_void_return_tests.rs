@@ -0,0 +1,37 @@
+use crate::diagnostics::{Diagnostic, DiagnosticConfig, DiagnosticSeverity};
+use super::diagnostics_with_config;
+
+fn return_diagnostics(source: &str, warn_empty: bool) -> Vec<Diagnostic> {
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        warn_empty_return: warn_empty,
+        ..Default::default()
+    };
+    diagnostics_with_config(source, &config, "CGH040")
+}
+
+#[test]
+fn test_value_returned_from_void_function_is_error() {
+    let source = "void reset(int x) {\n    if (x) {\n        return x;\n    }\n}\n";
+    let diagnostics = return_diagnostics(source, false);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+    assert!(diagnostics[0].message.contains("'reset'"));
+    assert_eq!(diagnostics[0].span.start_line, 2);
+}
+
+#[test]
+fn test_empty_return_in_non_void_function_warns_when_enabled() {
+    let source = "int get(int x) {\n    while (x) {\n        return;\n    }\n    return 0;\n}\n";
+    assert!(return_diagnostics(source, false).is_empty());
+
+    let diagnostics = return_diagnostics(source, true);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+}
+
+#[test]
+fn test_matching_returns_pass() {
+    let source = "void reset(void) {\n    return;\n}\nint get(void) {\n    return 1;\n}\nvoid *ptr(void) {\n    return 0;\n}\n";
+    assert!(return_diagnostics(source, true).is_empty());
+}
@@ -0,0 +1,80 @@
+use crate::config::{IndentStyle, ProjectConfig};
+use crate::editorconfig::EditorConfigIndent;
+
+const MINIMAL_EDITORCONFIG: &str = "root = true
+
+[*]
+indent_style = space
+indent_size = 4
+
+[*.{c,h}]
+indent_style = tab
+indent_size = 8
+
+[*.py]
+indent_size = 2
+";
+
+#[test]
+fn test_parse_minimal_editorconfig_for_c_source() {
+    let indent = EditorConfigIndent::parse(MINIMAL_EDITORCONFIG, "src/main.c");
+    assert_eq!(indent.indent_style, Some(IndentStyle::Tabs));
+    assert_eq!(indent.indent_width, Some(8));
+}
+
+#[test]
+fn test_parse_editorconfig_for_header() {
+    let indent = EditorConfigIndent::parse(MINIMAL_EDITORCONFIG, "include/main.h");
+    assert_eq!(indent.indent_style, Some(IndentStyle::Tabs));
+}
+
+#[test]
+fn test_parse_editorconfig_ignores_unmatched_sections() {
+    let content = "[*.py]\nindent_style = tab\nindent_size = 2\n";
+    assert_eq!(EditorConfigIndent::parse(content, "main.c"), EditorConfigIndent::default());
+}
+
+#[test]
+fn test_parse_editorconfig_section_globs() {
+    let matches = |section: &str, path: &str| {
+        let content = format!("[{}]\nindent_size = 3\n", section);
+        EditorConfigIndent::parse(&content, path).indent_width == Some(3)
+    };
+
+    assert!(matches("src/*.c", "src/main.c"));
+    assert!(!matches("src/*.c", "src/sub/main.c"));
+    assert!(!matches("src/*.c", "lib/src/main.c"));
+    assert!(matches("/src/**.c", "src/sub/main.c"));
+    assert!(matches("main.?", "lib/main.h"));
+    assert!(matches("*.[ch]", "main.h"));
+    assert!(!matches("*.[!ch]", "main.c"));
+    assert!(matches("*.[!ch]", "main.s"));
+    assert!(matches("{src,lib/{a,b}}/*.c", "lib/b/x.c"));
+    assert!(!matches("*.{c,h", "main.c"));
+}
+
+#[test]
+fn test_parse_editorconfig_indent_size_tab_uses_tab_width() {
+    let content = "[*.c]\nindent_style = tab\nindent_size = tab\ntab_width = 2\n";
+    let indent = EditorConfigIndent::parse(content, "main.c");
+    assert_eq!(indent.indent_width, Some(2));
+}
+
+#[test]
+fn test_editorconfig_applies_unless_project_config_is_explicit() {
+    let temp_dir = std::env::temp_dir().join("coding_guide_test_editorconfig");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    let src_dir = temp_dir.join("src");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(temp_dir.join(".editorconfig"), MINIMAL_EDITORCONFIG).unwrap();
+    std::fs::write(temp_dir.join("coding-guide.toml"), "[formatting]\nindent_width = 2\n").unwrap();
+
+    let loaded = ProjectConfig::find_and_load_with_root(&src_dir);
+    let config = loaded.to_diagnostic_config_with_path(Some(src_dir.join("main.c")));
+
+    // indent_style は .editorconfig から、indent_width は設定ファイルの明示的な値を使う
+    assert_eq!(config.indent_style, IndentStyle::Tabs);
+    assert_eq!(config.indent_width, 2);
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
@@ -144,3 +144,24 @@ fn test_parser_enum_mixed_values() {
         _ => panic!("Expected EnumDecl"),
     }
 }
+
+#[test]
+fn test_parser_enum_constant_expression_values() {
+    let code = "#define BASE 0x10\nenum Flags { FLAG_A = 1 << 2, FLAG_B = FLAG_A + 1, FLAG_C = BASE | 1, FLAG_D };";
+    let lx = Lexer::new(code);
+    let mut parser = Parser::new(lx);
+    let tu = parser.parse();
+
+    match &tu.items[1] {
+        Item::EnumDecl { variants, .. } => {
+            let values: Vec<_> = variants.iter().map(|v| (v.name.as_str(), v.value)).collect();
+            assert_eq!(values, vec![
+                ("FLAG_A", Some(4)),
+                ("FLAG_B", Some(5)),
+                ("FLAG_C", Some(17)),
+                ("FLAG_D", None),
+            ]);
+        },
+        _ => panic!("Expected EnumDecl"),
+    }
+}
@@ -0,0 +1,80 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::ast::Item;
+use crate::token::*;
+use crate::diagnostics::{Diagnostic, DiagnosticSeverity};
+
+fn directive_diagnostics(source: &str) -> Vec<Diagnostic> {
+    let mut parser = Parser::new(Lexer::new(source));
+    parser.parse();
+    parser
+        .take_diagnostics()
+        .into_iter()
+        .filter(|d| d.code.to_string() == "CGH029")
+        .collect()
+}
+
+#[test]
+fn test_lex_error_directive_is_not_lexer_error() {
+    let mut lexer = Lexer::new("#error \"unsupported platform\"\n");
+    match lexer.next_token() {
+        Some(Token::ErrorDirective(ErrorDirectiveToken { message, .. })) => {
+            assert_eq!(message, "unsupported platform");
+        }
+        other => panic!("Expected ErrorDirective token, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_lex_warning_directive() {
+    let mut lexer = Lexer::new("#warning deprecated header\n");
+    match lexer.next_token() {
+        Some(Token::WarningDirective(WarningDirectiveToken { message, .. })) => {
+            assert_eq!(message, "deprecated header");
+        }
+        other => panic!("Expected WarningDirective token, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_error_directive_in_taken_ifdef_fires() {
+    let source = "#define FOO\n#ifdef FOO\n#error \"FOO is not supported\"\n#endif\n";
+    let diags = directive_diagnostics(source);
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].severity, DiagnosticSeverity::Error);
+    assert!(diags[0].message.contains("FOO is not supported"));
+}
+
+#[test]
+fn test_error_directive_in_not_taken_ifdef_is_silent() {
+    let source = "#ifdef FOO\n#error \"FOO is not supported\"\n#endif\n";
+    assert!(directive_diagnostics(source).is_empty());
+}
+
+#[test]
+fn test_error_directive_in_taken_else_fires() {
+    let source = "#ifdef FOO\nint foo_only;\n#else\n#error \"FOO is required\"\n#endif\n";
+    assert_eq!(directive_diagnostics(source).len(), 1);
+}
+
+#[test]
+fn test_warning_directive_reports_warning_severity() {
+    let diags = directive_diagnostics("#warning \"check this\"\n");
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].severity, DiagnosticSeverity::Warning);
+}
+
+#[test]
+fn test_error_directive_item_is_kept_in_conditional_block() {
+    let mut parser = Parser::new(Lexer::new("#ifdef FOO\n#error \"no\"\n#endif\n"));
+    let tu = parser.parse();
+    assert!(tu.all_items().iter().any(|item| matches!(item, Item::ErrorDirective { message, .. } if message == "no")));
+}
+
+#[test]
+fn test_error_directive_in_taken_elif_fires() {
+    let source = "#if 0\nint a;\n#elif 1\n#error \"elif taken\"\n#else\n#error \"else taken\"\n#endif\n";
+    let diags = directive_diagnostics(source);
+    assert_eq!(diags.len(), 1);
+    assert!(diags[0].message.contains("elif taken"));
+}
@@ -19,7 +19,7 @@ fn test_cast_int() {
             // オペランドが浮動小数点リテラルであることを確認
             match *operand {
                 Expression::FloatLiteral { value, .. } => {
-                    assert!((value - 3.14).abs() < 0.0001);
+                    assert_eq!(value, "3.14");
                 },
                 _ => panic!("Expected FloatLiteral, got {:?}", operand),
             }
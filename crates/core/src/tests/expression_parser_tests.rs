@@ -1,6 +1,6 @@
 use crate::lexer::Lexer;
 use crate::expression_parser::ExpressionParser;
-use crate::expression::Expression;
+use crate::expression::{BinaryOperator, Expression, UnaryOperator};
 
 #[test]
 fn test_parse_integer_literal() {
@@ -38,12 +38,24 @@ fn test_parse_float_literal() {
     
     match parser.parse_expression() {
         Some(Expression::FloatLiteral { value, .. }) => {
-            assert!((value - 3.14).abs() < 0.0001);
+            assert_eq!(value, "3.14");
         }
         other => panic!("Expected FloatLiteral, got {:?}", other),
     }
 }
 
+#[test]
+fn test_parse_float_literal_keeps_suffix() {
+    let code = "1.5f";
+    let mut lexer = Lexer::new(code);
+    let mut parser = ExpressionParser::new(&mut lexer);
+
+    match parser.parse_expression() {
+        Some(Expression::FloatLiteral { value, .. }) => assert_eq!(value, "1.5f"),
+        other => panic!("Expected FloatLiteral, got {:?}", other),
+    }
+}
+
 #[test]
 fn test_parse_identifier() {
     let code = "foo";
@@ -57,3 +69,162 @@ fn test_parse_identifier() {
         other => panic!("Expected Identifier, got {:?}", other),
     }
 }
+
+#[test]
+fn test_parse_assignment_is_right_associative() {
+    let mut lexer = Lexer::new("a = b = 1");
+    let mut parser = ExpressionParser::new(&mut lexer);
+    let expr = parser.parse_expression().unwrap();
+
+    match expr {
+        Expression::Assignment { left, right, .. } => {
+            assert!(matches!(*left, Expression::Identifier { ref name, .. } if name == "a"));
+            assert!(matches!(*right, Expression::Assignment { .. }));
+        }
+        _ => panic!("Expected Assignment, got {:?}", expr),
+    }
+}
+
+#[test]
+fn test_parse_member_and_array_access() {
+    let mut lexer = Lexer::new("p->items[i].value");
+    let mut parser = ExpressionParser::new(&mut lexer);
+    let expr = parser.parse_expression().unwrap();
+
+    match expr {
+        Expression::MemberAccess { object, member, .. } => {
+            assert_eq!(member, "value");
+            match *object {
+                Expression::ArrayAccess { array, .. } => {
+                    assert!(matches!(*array, Expression::PointerMemberAccess { ref member, .. } if member == "items"));
+                }
+                other => panic!("Expected ArrayAccess, got {:?}", other),
+            }
+        }
+        _ => panic!("Expected MemberAccess, got {:?}", expr),
+    }
+}
+
+#[test]
+fn test_array_access_span_covers_closing_bracket() {
+    let code = "w = a[i]";
+    let mut lexer = Lexer::new(code);
+    let mut parser = ExpressionParser::new(&mut lexer);
+
+    match parser.parse_expression() {
+        Some(Expression::Assignment { right, span, .. }) => {
+            assert_eq!(span.byte_end_idx, code.len());
+            match *right {
+                Expression::ArrayAccess { array, span, .. } => {
+                    // 配列の式から閉じ括弧 ']' までを覆う（0 行 0 列の空のスパンと結合しない）
+                    let Expression::Identifier { span: array_span, .. } = *array else {
+                        panic!("Expected Identifier, got {:?}", array);
+                    };
+                    assert_eq!((span.start_line, span.start_column), (array_span.start_line, array_span.start_column));
+                    assert_eq!(span.byte_start_idx, array_span.byte_start_idx);
+                    assert_eq!(span.byte_end_idx, code.len());
+                }
+                other => panic!("Expected ArrayAccess, got {:?}", other),
+            }
+        }
+        other => panic!("Expected Assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_unary_span_starts_at_operator_token() {
+    let code = "y + -tbl[0]";
+    let mut lexer = Lexer::new(code);
+    let mut parser = ExpressionParser::new(&mut lexer);
+
+    match parser.parse_expression() {
+        Some(Expression::BinaryOp { right, .. }) => match *right {
+            Expression::UnaryOp { op: UnaryOperator::Negate, span, .. } => {
+                assert!(span.byte_start_idx > 0);
+                assert!(span.start_column > 0);
+                assert_eq!(span.byte_end_idx, code.len());
+            }
+            other => panic!("Expected UnaryOp, got {:?}", other),
+        },
+        other => panic!("Expected BinaryOp, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_compound_assignment() {
+    let mut lexer = Lexer::new("x += 1");
+    let mut parser = ExpressionParser::new(&mut lexer);
+    let expr = parser.parse_expression().unwrap();
+
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::AddAssign, left, right, .. } => {
+            assert!(matches!(*left, Expression::Identifier { ref name, .. } if name == "x"));
+            assert!(matches!(*right, Expression::IntLiteral { value: 1, .. }));
+        }
+        _ => panic!("Expected AddAssign, got {:?}", expr),
+    }
+}
+
+#[test]
+fn test_parse_shift_compound_assignment() {
+    // 右辺全体が代入の右オペランドになる
+    let mut lexer = Lexer::new("flags <<= n + 1");
+    let mut parser = ExpressionParser::new(&mut lexer);
+    let expr = parser.parse_expression().unwrap();
+
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::LeftShiftAssign, left, right, .. } => {
+            assert!(matches!(*left, Expression::Identifier { ref name, .. } if name == "flags"));
+            assert!(matches!(*right, Expression::BinaryOp { op: BinaryOperator::Add, .. }));
+        }
+        _ => panic!("Expected LeftShiftAssign, got {:?}", expr),
+    }
+}
+
+#[test]
+fn test_parse_string_literal() {
+    let code = "url = \"see http://example.com /* not a comment */\"";
+    let mut lexer = Lexer::new(code);
+    let mut parser = ExpressionParser::new(&mut lexer);
+    
+    match parser.parse_expression() {
+        Some(Expression::Assignment { right, .. }) => match *right {
+            Expression::StringLiteral { value, .. } => {
+                assert_eq!(value, "\"see http://example.com /* not a comment */\"");
+            }
+            other => panic!("Expected StringLiteral, got {:?}", other),
+        },
+        other => panic!("Expected Assignment, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_function_call_arguments() {
+    let code = "memset(p, 0, n * 2)";
+    let mut lexer = Lexer::new(code);
+    let mut parser = ExpressionParser::new(&mut lexer);
+
+    match parser.parse_expression() {
+        Some(Expression::FunctionCall { function, arguments, span }) => {
+            assert!(matches!(*function, Expression::Identifier { ref name, .. } if name == "memset"));
+            assert_eq!(arguments.len(), 3);
+            assert!(matches!(arguments[2], Expression::BinaryOp { op: BinaryOperator::Multiply, .. }));
+            assert_eq!(span.byte_end_idx, code.len());
+        }
+        other => panic!("Expected FunctionCall, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_function_call_without_arguments() {
+    let code = "f() + 1";
+    let mut lexer = Lexer::new(code);
+    let mut parser = ExpressionParser::new(&mut lexer);
+
+    match parser.parse_expression() {
+        Some(Expression::BinaryOp { left, .. }) => {
+            assert!(matches!(*left, Expression::FunctionCall { ref arguments, .. } if arguments.is_empty()));
+        }
+        other => panic!("Expected BinaryOp, got {:?}", other),
+    }
+}
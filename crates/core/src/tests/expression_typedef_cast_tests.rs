@@ -153,3 +153,42 @@ fn test_no_typedef_fallback() {
         _ => {},
     }
 }
+
+#[test]
+fn test_parenthesized_expression_starting_with_typedef_name() {
+    let typedef_code = "typedef int count;";
+    let typedef_lexer = Lexer::new(typedef_code);
+    let mut parser = Parser::new(typedef_lexer);
+    let _ast = parser.parse();
+
+    // (count * x) は型名として読めないので、括弧式として読み直す
+    let input = "(count * x) + 1";
+    let mut lexer = Lexer::new(input);
+    let mut expr_parser = ExpressionParser::new(&mut lexer).with_type_table(parser.get_type_table());
+    let expr = expr_parser.parse_expression().unwrap();
+
+    match expr {
+        Expression::BinaryOp { left, op: BinaryOperator::Add, .. } => {
+            assert!(matches!(*left, Expression::BinaryOp { op: BinaryOperator::Multiply, .. }));
+        },
+        _ => panic!("Expected BinaryOp, got {:?}", expr),
+    }
+}
+
+#[test]
+fn test_finish_returns_tokens_read_ahead_for_cast() {
+    let typedef_code = "typedef int count;";
+    let typedef_lexer = Lexer::new(typedef_code);
+    let mut parser = Parser::new(typedef_lexer);
+    let _ast = parser.parse();
+
+    // キャストとして試しに読んだトークンも、式の後に続くトークンとして字句解析器に戻る
+    let input = "(count) ;";
+    let mut lexer = Lexer::new(input);
+    let mut expr_parser = ExpressionParser::new(&mut lexer).with_type_table(parser.get_type_table());
+    let expr = expr_parser.parse_expression().unwrap();
+    expr_parser.finish();
+
+    assert!(matches!(expr, Expression::Identifier { ref name, .. } if name == "count"));
+    assert!(matches!(lexer.next_token(), Some(crate::token::Token::Semicolon(_))));
+}
@@ -1,6 +1,6 @@
 use crate::lexer::Lexer;
 use crate::expression_parser::ExpressionParser;
-use crate::expression::{Expression, UnaryOperator};
+use crate::expression::{BinaryOperator, Expression, UnaryOperator};
 
 #[test]
 fn test_parse_unary_minus() {
@@ -247,3 +247,73 @@ fn test_parse_address_dereference() {
         _ => panic!("Expected Dereference at top level"),
     }
 }
+
+#[test]
+fn test_parse_dereference_binds_tighter_than_add() {
+    // *p + 1 は (*p) + 1
+    let mut lexer = Lexer::new("*p + 1");
+    let mut parser = ExpressionParser::new(&mut lexer);
+
+    let expr = parser.parse_expression().expect("Failed to parse");
+
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Add, left, right, .. } => {
+            assert!(matches!(*left, Expression::UnaryOp { op: UnaryOperator::Dereference, .. }));
+            assert!(matches!(*right, Expression::IntLiteral { value: 1, .. }));
+        },
+        _ => panic!("Expected Add at top level, got {:?}", expr),
+    }
+}
+
+#[test]
+fn test_parse_negate_binds_tighter_than_multiply() {
+    // -a * b は (-a) * b
+    let mut lexer = Lexer::new("-a * b");
+    let mut parser = ExpressionParser::new(&mut lexer);
+
+    let expr = parser.parse_expression().expect("Failed to parse");
+
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right, .. } => {
+            assert!(matches!(*left, Expression::UnaryOp { op: UnaryOperator::Negate, .. }));
+            assert!(matches!(*right, Expression::Identifier { ref name, .. } if name == "b"));
+        },
+        _ => panic!("Expected Multiply at top level, got {:?}", expr),
+    }
+}
+
+#[test]
+fn test_parse_binary_and_unary_share_tokens() {
+    // a & *p は a & (*p)、a * &x は a * (&x)
+    let mut lexer = Lexer::new("a & *p");
+    let mut parser = ExpressionParser::new(&mut lexer);
+    match parser.parse_expression().expect("Failed to parse") {
+        Expression::BinaryOp { op: BinaryOperator::BitwiseAnd, right, .. } => {
+            assert!(matches!(*right, Expression::UnaryOp { op: UnaryOperator::Dereference, .. }));
+        },
+        other => panic!("Expected BitwiseAnd at top level, got {:?}", other),
+    }
+
+    let mut lexer = Lexer::new("a * &x");
+    let mut parser = ExpressionParser::new(&mut lexer);
+    match parser.parse_expression().expect("Failed to parse") {
+        Expression::BinaryOp { op: BinaryOperator::Multiply, right, .. } => {
+            assert!(matches!(*right, Expression::UnaryOp { op: UnaryOperator::AddressOf, .. }));
+        },
+        other => panic!("Expected Multiply at top level, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_post_decrement_on_dereference() {
+    // *p-- は *(p--)
+    let mut lexer = Lexer::new("*p--");
+    let mut parser = ExpressionParser::new(&mut lexer);
+
+    match parser.parse_expression().expect("Failed to parse") {
+        Expression::UnaryOp { op: UnaryOperator::Dereference, operand, .. } => {
+            assert!(matches!(*operand, Expression::UnaryOp { op: UnaryOperator::PostDecrement, .. }));
+        },
+        other => panic!("Expected Dereference at top level, got {:?}", other),
+    }
+}
@@ -0,0 +1,64 @@
+use crate::config::ProjectConfig;
+use crate::formatter::Formatter;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn format_with_blank_lines(source: &str, blank_lines: usize) -> String {
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let mut formatter = Formatter::new_no_header();
+    formatter.blank_lines_between_items = Some(blank_lines);
+    formatter.format_tu(&tu)
+}
+
+#[test]
+fn test_blank_lines_collapse_to_maximum() {
+    let source = "int a;\n\n\n\nint b;\n";
+    assert_eq!(format_with_blank_lines(source, 1), "int a;\n\nint b;\n");
+}
+
+#[test]
+fn test_blank_lines_inserted_between_functions() {
+    let source = "void f(void) {\n}\nvoid g(void) {\n}\n";
+    assert_eq!(format_with_blank_lines(source, 1), "void f(void) {\n}\n\nvoid g(void) {\n}\n");
+}
+
+#[test]
+fn test_blank_lines_keep_grouping_below_maximum() {
+    let source = "int a;\nint b;\n\nint c;\n\n\n\nint d;\n";
+    assert_eq!(format_with_blank_lines(source, 2), "int a;\nint b;\n\nint c;\n\n\nint d;\n");
+}
+
+#[test]
+fn test_blank_lines_removed_at_start_of_file() {
+    let source = "\n\n\nint a;\n";
+    assert_eq!(format_with_blank_lines(source, 1), "int a;\n");
+}
+
+#[test]
+fn test_blank_lines_count_leading_comment() {
+    let source = "int a;\n\n\n// b の説明\nint b;\n";
+    assert_eq!(format_with_blank_lines(source, 1), "int a;\n\n// b の説明\nint b;\n");
+}
+
+#[test]
+fn test_blank_lines_keep_trailing_comment_on_same_line() {
+    let source = "int a;  // a の説明\nint b;\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let formatted = format_with_blank_lines(source, 1);
+    assert_eq!(formatted, Formatter::new_no_header().format_tu(&tu));
+    assert!(formatted.starts_with("int a;  "));
+}
+
+#[test]
+fn test_blank_lines_around_conditional_block() {
+    let source = "int a;\n\n\n\n#ifdef X\nint b;\n#endif\nint c;\n";
+    assert_eq!(format_with_blank_lines(source, 1), "int a;\n\n#ifdef X\nint b;\n#endif\nint c;\n");
+}
+
+#[test]
+fn test_blank_lines_from_toml() {
+    let config: ProjectConfig = toml::from_str("[formatting]\nblank_lines_between_items = 1\n").unwrap();
+    assert_eq!(config.formatting.blank_lines_between_items, Some(1));
+    assert_eq!(Formatter::new_with_config(&config.formatting).blank_lines_between_items, Some(1));
+    assert_eq!(ProjectConfig::default().formatting.blank_lines_between_items, None);
+}
@@ -0,0 +1,58 @@
+use crate::formatter::Formatter;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn format_sorted(source: &str, group_includes: bool) -> String {
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let mut formatter = Formatter::new_no_header();
+    formatter.sort_includes = true;
+    formatter.group_includes = group_includes;
+    formatter.format_tu(&tu)
+}
+
+#[test]
+fn test_sort_includes_shuffled_block() {
+    let source = "#include <stdlib.h>\n#include \"util.h\"\n#include <assert.h>\n#include \"app.h\"\n";
+    assert_eq!(
+        format_sorted(source, false),
+        "#include \"app.h\"\n#include <assert.h>\n#include <stdlib.h>\n#include \"util.h\"\n"
+    );
+}
+
+#[test]
+fn test_sort_includes_groups_system_before_local() {
+    let source = "#include \"util.h\"\n#include <stdlib.h>\n\n#include \"app.h\"\n#include <assert.h>\n";
+    assert_eq!(
+        format_sorted(source, true),
+        "#include <assert.h>\n#include <stdlib.h>\n\n#include \"app.h\"\n#include \"util.h\"\n"
+    );
+}
+
+#[test]
+fn test_sort_includes_keeps_blank_line_blocks_without_grouping() {
+    let source = "#include <stdlib.h>\n#include <assert.h>\n\n#include \"util.h\"\n#include \"app.h\"\n";
+    assert_eq!(
+        format_sorted(source, false),
+        "#include <assert.h>\n#include <stdlib.h>\n\n#include \"app.h\"\n#include \"util.h\"\n"
+    );
+}
+
+#[test]
+fn test_sort_includes_does_not_cross_other_items() {
+    let source = "#include <b.h>\n#define X 1\n#include <a.h>\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let unsorted = Formatter::new_no_header().format_tu(&tu);
+    assert_eq!(format_sorted(source, true), unsorted);
+}
+
+#[test]
+fn test_sort_includes_does_not_cross_ifdef_boundary() {
+    let source = "#include <d.h>\n#include <c.h>\n#ifdef DEBUG\n#include <b.h>\n#include <a.h>\n#endif\n";
+    let formatted = format_sorted(source, false);
+
+    let order: Vec<&str> = formatted.lines().filter(|line| line.starts_with("#include")).collect();
+    assert_eq!(order, vec!["#include <c.h>", "#include <d.h>", "#include <a.h>", "#include <b.h>"]);
+    let ifdef = formatted.find("#ifdef").unwrap();
+    assert!(formatted.find("#include <d.h>").unwrap() < ifdef);
+    assert!(formatted.find("#include <a.h>").unwrap() > ifdef);
+}
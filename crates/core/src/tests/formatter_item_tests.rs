@@ -0,0 +1,94 @@
+use crate::ast::Item;
+use crate::formatter::Formatter;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+#[test]
+fn test_format_item_standalone_function() {
+    let source = "int\nadd(int a, int b)\n{\n    return a + b;\n}\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    assert_eq!(tu.items.len(), 1);
+
+    let formatter = Formatter::new_with_all_options(false, false, true);
+    let formatted = formatter.format_item(&tu.items[0]);
+
+    assert_eq!(formatted, "int\nadd(int a, int b)\n{\n\treturn a + b;\n}\n");
+}
+
+#[test]
+fn test_format_item_standalone_struct() {
+    let source = "int origin;\n  struct Point {\n    int x;\n    int y;\n};\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let item = tu.items.iter().find(|item| matches!(item, Item::StructDecl { .. })).expect("StructDecl");
+
+    // use_tabs によるインデント変換は関数のみが対象で、構造体のメンバーはそのまま
+    let formatted = Formatter::new_with_all_options(false, false, true).format_item(item);
+
+    // 先頭の空白は改行だけが残る
+    assert_eq!(formatted, "\nstruct Point {\n    int x;\n    int y;\n};\n");
+}
+
+#[test]
+fn test_format_tu_is_concatenation_of_items() {
+    let source = "#include <stdio.h>\nstruct S {\n    int a;\n};\nint\nmain(void)\n{\n    return 0;\n}\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let formatter = Formatter::new_no_header();
+
+    let joined: String = tu.items.iter().map(|item| formatter.format_item(item)).collect();
+    assert_eq!(formatter.format_tu(&tu), joined);
+}
+
+#[test]
+fn test_format_statement_from_function_body() {
+    let source = "int\nf(int x)\n{\n    int y = (x + 1) * 2;\n    if (x > 0 && y != 3) {\n        y += x;\n    } else if (x < 0) {\n        y = -x;\n    } else {\n        return 0;\n    }\n    for (int i = 0; i < 10; i++) {\n        y = y - i;\n    }\n    return y;\n}\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let Some(Item::FunctionDecl { body: Some(body), .. }) = tu.items.first() else {
+        panic!("Expected function definition with body");
+    };
+
+    let formatter = Formatter::new_no_header();
+    let formatted: Vec<String> = body.iter().map(|stmt| formatter.format_statement(stmt)).collect();
+
+    assert_eq!(formatted, vec![
+        "int y = (x + 1) * 2;\n".to_string(),
+        "if (x > 0 && y != 3) {\n    y += x;\n} else if (x < 0) {\n    y = -x;\n} else {\n    return 0;\n}\n".to_string(),
+        "for (int i = 0; i < 10; i++) {\n    y = y - i;\n}\n".to_string(),
+        "return y;\n".to_string(),
+    ]);
+}
+
+#[test]
+fn test_format_item_aligns_struct_members() {
+    let source = "struct Node {\n    int id;  // 識別子\n    char *name;  // 名前\n    unsigned int flag : 1;\n};\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let mut formatter = Formatter::new_no_header();
+    formatter.align_struct_members = true;
+
+    let formatted = formatter.format_item(&tu.items[0]);
+    assert_eq!(
+        formatted,
+        "struct Node {\n    int          id;       // 識別子\n    char        *name;     // 名前\n    unsigned int flag : 1;\n};\n"
+    );
+
+    // `*` を除いたメンバー名の開始列がすべて一致する
+    let columns: Vec<usize> = ["id", "name", "flag"]
+        .iter()
+        .map(|name| formatted.lines().find(|line| line.contains(name)).and_then(|line| line.find(name)).unwrap())
+        .collect();
+    assert_eq!(columns, vec![17, 17, 17]);
+}
+
+#[test]
+fn test_format_item_keeps_struct_members_by_default() {
+    let source = "struct Node {\n    int id;\n    char *name;\n};\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+
+    assert_eq!(Formatter::new_no_header().format_item(&tu.items[0]), source);
+}
+
+#[test]
+fn test_format_line_comment_before_item_keeps_single_newline() {
+    let source = "// 説明\nint a;\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    assert_eq!(Formatter::new_no_header().format_tu(&tu), source);
+}
@@ -0,0 +1,64 @@
+use crate::config::ProjectConfig;
+use crate::formatter::Formatter;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn format_spaced(source: &str) -> String {
+    let tu = Parser::new(Lexer::new(source)).parse();
+    let mut formatter = Formatter::new_no_header();
+    formatter.space_binary_operators = true;
+    formatter.format_tu(&tu)
+}
+
+#[test]
+fn test_space_binary_operators_in_expression_statement() {
+    let source = "void f(void) {\n    x=a+b*c;\n}\n";
+    assert_eq!(format_spaced(source), "void f(void) {\n    x = a + b * c;\n}\n");
+}
+
+#[test]
+fn test_space_binary_operators_keeps_required_parentheses() {
+    let source = "int f(int a, int b, int c) {\n    return (a+b)*c;\n}\n";
+    assert_eq!(format_spaced(source), "int f(int a, int b, int c) {\n    return (a + b) * c;\n}\n");
+}
+
+#[test]
+fn test_space_binary_operators_removes_redundant_parentheses() {
+    let source = "int f(int a, int b, int c) {\n    return a+(b*c);\n}\n";
+    assert_eq!(format_spaced(source), "int f(int a, int b, int c) {\n    return a + b * c;\n}\n");
+}
+
+#[test]
+fn test_space_binary_operators_in_local_declaration_and_condition() {
+    let source = "void f(int n) {\n    int *p = &n;\n    int m=n<<2|1;\n    if (m>0&&n!=0) {\n        m-=0x10;\n    }\n}\n";
+    assert_eq!(
+        format_spaced(source),
+        "void f(int n) {\n    int *p = &n;\n    int m = n << 2 | 1;\n    if (m > 0 && n != 0) {\n        m -= 0x10;\n    }\n}\n"
+    );
+}
+
+#[test]
+fn test_space_binary_operators_in_global_initializer() {
+    let source = "int g=1+2*3;\n";
+    assert_eq!(format_spaced(source), "int g = 1 + 2 * 3;\n");
+}
+
+#[test]
+fn test_space_binary_operators_keeps_statement_with_comment() {
+    let source = "void f(void) {\n    x=a+/* note */b;\n}\n";
+    assert_eq!(format_spaced(source), source);
+}
+
+#[test]
+fn test_space_binary_operators_disabled_by_default() {
+    let source = "void f(void) {\n    x=a+b*c;\n}\n";
+    let tu = Parser::new(Lexer::new(source)).parse();
+    assert_eq!(Formatter::new_no_header().format_tu(&tu), source);
+}
+
+#[test]
+fn test_space_binary_operators_from_toml() {
+    let config: ProjectConfig = toml::from_str("[formatting]\nspace_binary_operators = true\n").unwrap();
+    assert!(config.formatting.space_binary_operators);
+    assert!(Formatter::new_with_config(&config.formatting).space_binary_operators);
+}
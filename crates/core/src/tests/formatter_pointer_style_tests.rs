@@ -0,0 +1,118 @@
+use crate::config::{FormattingConfig, PointerStyle};
+use crate::formatter::Formatter;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn format_with_style(source: &str, style: PointerStyle) -> String {
+    let config = FormattingConfig {
+        add_file_header: false,
+        pointer_style: Some(style),
+        ..Default::default()
+    };
+    let tu = Parser::new(Lexer::new(source)).parse();
+    Formatter::new_with_config(&config).format_tu(&tu)
+}
+
+#[test]
+fn test_pointer_style_var_decl() {
+    let source = "int*p;\n";
+    assert_eq!(format_with_style(source, PointerStyle::RightOfType), "int* p;\n");
+    assert_eq!(format_with_style(source, PointerStyle::LeftOfName), "int *p;\n");
+    assert_eq!(format_with_style(source, PointerStyle::Spaced), "int * p;\n");
+}
+
+#[test]
+fn test_pointer_style_qualified_layers_and_initializer() {
+    let source = "static char*const*names = 0;\n";
+    assert_eq!(format_with_style(source, PointerStyle::RightOfType), "static char* const* names = 0;\n");
+    assert_eq!(format_with_style(source, PointerStyle::LeftOfName), "static char *const *names = 0;\n");
+    assert_eq!(format_with_style(source, PointerStyle::Spaced), "static char * const * names = 0;\n");
+
+    // 初期化子の中の乗算は書き換えない
+    let source = "int total = a * b;\n";
+    assert_eq!(format_with_style(source, PointerStyle::LeftOfName), source);
+}
+
+#[test]
+fn test_pointer_style_function_return_type_and_parameters() {
+    let source = "char*\ncopy(char*dst, const char *  src, int buf[N * 2])\n{\n    return dst;\n}\n";
+    assert_eq!(
+        format_with_style(source, PointerStyle::RightOfType),
+        "char*\ncopy(char* dst, const char* src, int buf[N * 2])\n{\n    return dst;\n}\n"
+    );
+    assert_eq!(
+        format_with_style(source, PointerStyle::LeftOfName),
+        "char *\ncopy(char *dst, const char *src, int buf[N * 2])\n{\n    return dst;\n}\n"
+    );
+    assert_eq!(
+        format_with_style(source, PointerStyle::Spaced),
+        "char *\ncopy(char * dst, const char * src, int buf[N * 2])\n{\n    return dst;\n}\n"
+    );
+}
+
+#[test]
+fn test_pointer_style_uses_parsed_type_with_type_info() {
+    let tu = Parser::new(Lexer::new("int*p;\n")).parse();
+    let mut formatter = Formatter::new_with_options(false, true);
+    formatter.pointer_style = Some(PointerStyle::LeftOfName);
+    assert_eq!(formatter.format_tu(&tu), "int *p;\n");
+}
+
+#[test]
+fn test_pointer_style_unset_keeps_source() {
+    for source in ["int*p;\n", "int\nf(char*s)\n{\n    return 0;\n}\n"] {
+        let tu = Parser::new(Lexer::new(source)).parse();
+        assert_eq!(Formatter::new_no_header().format_tu(&tu), source);
+    }
+}
+
+#[test]
+fn test_pointer_style_const_qualified_pointer() {
+    let const_pointer = "int *const q;\n";
+    assert_eq!(format_with_style(const_pointer, PointerStyle::RightOfType), "int* const q;\n");
+    assert_eq!(format_with_style(const_pointer, PointerStyle::LeftOfName), "int *const q;\n");
+    assert_eq!(format_with_style(const_pointer, PointerStyle::Spaced), "int * const q;\n");
+
+    let pointer_to_const_pointer = "const char*const*argv;\n";
+    assert_eq!(format_with_style(pointer_to_const_pointer, PointerStyle::RightOfType), "const char* const* argv;\n");
+    assert_eq!(format_with_style(pointer_to_const_pointer, PointerStyle::LeftOfName), "const char *const *argv;\n");
+    assert_eq!(format_with_style(pointer_to_const_pointer, PointerStyle::Spaced), "const char * const * argv;\n");
+}
+
+#[test]
+fn test_pointer_style_struct_members() {
+    let source = "struct node {\n    struct node*next;\n    const char *const name;\n    int count;\n};\n";
+    assert_eq!(
+        format_with_style(source, PointerStyle::RightOfType),
+        "struct node {\n    struct node* next;\n    const char* const name;\n    int count;\n};\n"
+    );
+    assert_eq!(
+        format_with_style(source, PointerStyle::Spaced),
+        "struct node {\n    struct node * next;\n    const char * const name;\n    int count;\n};\n"
+    );
+}
+
+#[test]
+fn test_pointer_style_aligned_struct_members() {
+    let source = "struct node {\n    struct node *next;\n    int count;\n};\n";
+    let config = FormattingConfig {
+        add_file_header: false,
+        align_struct_members: true,
+        pointer_style: Some(PointerStyle::RightOfType),
+        ..Default::default()
+    };
+    let tu = Parser::new(Lexer::new(source)).parse();
+    assert_eq!(
+        Formatter::new_with_config(&config).format_tu(&tu),
+        "struct node {\n    struct node* next;\n    int          count;\n};\n"
+    );
+}
+
+#[test]
+fn test_pointer_style_function_body_locals() {
+    let source = "void f(void)\n{\n    char*p;\n    int *const q = 0;\n    int n = a * b;\n    if (n) {\n        char **argv;\n    }\n}\n";
+    assert_eq!(
+        format_with_style(source, PointerStyle::RightOfType),
+        "void f(void)\n{\n    char* p;\n    int* const q = 0;\n    int n = a * b;\n    if (n) {\n        char** argv;\n    }\n}\n"
+    );
+}
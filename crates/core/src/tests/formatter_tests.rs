@@ -52,7 +52,7 @@ fn test_formatter_format_tu_trims_leading_whitespace() {
     fn test_formatter_format_define_keeps_newline_only() {
         let span = Span { start_line: 0, start_column: 0, end_line: 0, end_column: 0, byte_start_idx: 0, byte_end_idx: 0 };
         let text = String::from("\t\r\n  #define Z 42\n");
-        let item = Item::Define { span, text: text.clone(), macro_name: "Z".into(), macro_value: "42".into(), trivia: Trivia::empty() };
+        let item = Item::Define { span, text: text.clone(), macro_name: "Z".into(), params: None, macro_value: "42".into(), trivia: Trivia::empty() };
         let tu = TranslationUnit { items: vec![item], leading_trivia: Trivia::empty() };
         let fmt = Formatter::new_no_header();
         let out = fmt.format_tu(&tu);
@@ -67,7 +67,10 @@ fn test_formatter_format_tu_trims_leading_whitespace() {
             text: String::from("  int x;"),
             var_name: String::from("x"),
             has_initializer: false,
+            initializer: None,
             var_type: None,
+            specifiers: Vec::new(),
+            attributes: Vec::new(),
             trivia: Trivia::empty(),
         };
         let tu = TranslationUnit { items: vec![item], leading_trivia: Trivia::empty() };
@@ -86,6 +89,7 @@ fn test_formatter_format_tu_trims_leading_whitespace() {
             has_typedef: false,
             variable_names: Vec::new(),
             members: Vec::new(),
+            attributes: Vec::new(),
             trivia: Trivia::empty(),
         };
         let tu = TranslationUnit { items: vec![item], leading_trivia: Trivia::empty() };
@@ -112,7 +116,10 @@ fn test_formatter_format_tu_trims_leading_whitespace() {
             text: String::from("int *ptr;"),
             var_name: String::from("ptr"),
             has_initializer: false,
+            initializer: None,
             var_type: Some(ty),
+            specifiers: Vec::new(),
+            attributes: Vec::new(),
             trivia: Trivia::empty(),
         };
         
@@ -143,7 +150,10 @@ fn test_formatter_format_tu_trims_leading_whitespace() {
             text: String::from("int *const ptr;"),
             var_name: String::from("ptr"),
             has_initializer: false,
+            initializer: None,
             var_type: Some(ty),
+            specifiers: Vec::new(),
+            attributes: Vec::new(),
             trivia: Trivia::empty(),
         };
         
@@ -162,7 +172,10 @@ fn test_formatter_format_tu_trims_leading_whitespace() {
             text: String::from("int *ptr;"),
             var_name: String::from("ptr"),
             has_initializer: false,
+            initializer: None,
             var_type: None,
+            specifiers: Vec::new(),
+            attributes: Vec::new(),
             trivia: Trivia::empty(),
         };
         
@@ -107,3 +107,132 @@ fn test_parser_function_with_nested_braces() {
         _ => panic!("Expected FunctionDecl item"),
     }
 }
+
+#[test]
+fn test_parser_knr_function_definition() {
+    let s = r#"int add(a, b)
+int a;
+char *b;
+{
+    return a;
+}
+int next;
+"#;
+    let lx = Lexer::new(s);
+    let mut parser = Parser::new(lx);
+    let tu = parser.parse();
+
+    // K&R の引数宣言が後続の宣言を壊さない
+    assert_eq!(tu.items.len(), 2);
+
+    match &tu.items[0] {
+        Item::FunctionDecl { function_name, parameters, body, .. } => {
+            assert_eq!(function_name, "add");
+            // 引数宣言の型で補われた ANSI 形式に正規化される
+            assert_eq!(parameters, "(int a, char *b)");
+            assert!(body.is_some());
+        }
+        other => panic!("Expected FunctionDecl item, got {:?}", other),
+    }
+    assert!(matches!(&tu.items[1], Item::VarDecl { var_name, .. } if var_name == "next"));
+}
+
+#[test]
+fn test_parser_knr_undeclared_parameter_is_int() {
+    let s = "long scale(value, factor, out) long value; int *out; { *out = value; return 0; }\n";
+    let lx = Lexer::new(s);
+    let mut parser = Parser::new(lx);
+    let tu = parser.parse();
+
+    assert_eq!(tu.items.len(), 1);
+    match &tu.items[0] {
+        Item::FunctionDecl { function_name, parameters, .. } => {
+            assert_eq!(function_name, "scale");
+            assert_eq!(parameters, "(long value, int factor, int *out)");
+        }
+        other => panic!("Expected FunctionDecl item, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parser_static_inline_function() {
+    let s = "static inline int f(void) {}\n";
+    let lx = Lexer::new(s);
+    let mut parser = Parser::new(lx);
+    let tu = parser.parse();
+
+    assert_eq!(tu.items.len(), 1);
+    match &tu.items[0] {
+        Item::FunctionDecl { return_type, function_name, storage_class, specifiers, body, .. } => {
+            assert_eq!(function_name, "f");
+            assert_eq!(storage_class.as_deref(), Some("static"));
+            assert_eq!(specifiers, &vec!["inline".to_string()]);
+            assert_eq!(return_type, "int");
+            assert!(body.is_some());
+        }
+        other => panic!("Expected FunctionDecl item, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parser_noreturn_function_and_thread_local_var() {
+    let s = "_Noreturn void fatal(void);\n_Thread_local int counter;\n";
+    let lx = Lexer::new(s);
+    let mut parser = Parser::new(lx);
+    let tu = parser.parse();
+
+    assert_eq!(tu.items.len(), 2);
+    match &tu.items[0] {
+        Item::FunctionDecl { return_type, function_name, specifiers, .. } => {
+            assert_eq!(function_name, "fatal");
+            assert_eq!(specifiers, &vec!["_Noreturn".to_string()]);
+            assert_eq!(return_type, "void");
+        }
+        other => panic!("Expected FunctionDecl item, got {:?}", other),
+    }
+    match &tu.items[1] {
+        Item::VarDecl { var_name, var_type, specifiers, .. } => {
+            assert_eq!(var_name, "counter");
+            assert_eq!(specifiers, &vec!["_Thread_local".to_string()]);
+            assert!(var_type.is_some());
+        }
+        other => panic!("Expected VarDecl item, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parser_unterminated_function_body() {
+    let s = "int main(void) {\n    int x = 1;\n    if (x) {\n        x = 2;\n    }\n    return x;\n";
+    let lx = Lexer::new(s);
+    let mut parser = Parser::new(lx);
+    let tu = parser.parse();
+
+    // 閉じ括弧がなくても入力の終わりまでを本体として関数定義を作る
+    assert_eq!(tu.items.len(), 1);
+    match &tu.items[0] {
+        Item::FunctionDecl { function_name, body: Some(body), .. } => {
+            assert_eq!(function_name, "main");
+            assert_eq!(body.len(), 3);
+        }
+        other => panic!("Expected FunctionDecl item with body, got {:?}", other),
+    }
+
+    // 開き括弧の位置を指すパース時の診断
+    let diagnostics = parser.take_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.to_string(), "CGH013");
+    assert_eq!(&s[diagnostics[0].span.byte_start_idx..diagnostics[0].span.byte_end_idx].trim(), &"{");
+}
+
+#[test]
+fn test_parser_unterminated_implicit_int_function_body() {
+    let s = "foo(void) {\n    return 0;\n";
+    let lx = Lexer::new(s);
+    let mut parser = Parser::new(lx);
+    let tu = parser.parse();
+
+    assert!(matches!(&tu.items[..], [Item::FunctionDecl { body: Some(_), .. }]));
+    let diagnostics = parser.take_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].span.start_line, 0);
+}
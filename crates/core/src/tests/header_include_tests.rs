@@ -158,3 +158,95 @@ fn include_paths_resolved_against_project_root() {
 
     assert!(parser.get_type_table().is_type_name("FROM_HEADER"));
 }
+
+#[test]
+fn test_typedef_from_included_header_used_in_var_decl() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("foo.h"), "typedef int Foo;\n").unwrap();
+
+    let source = "#include \"foo.h\"\nFoo x;\n";
+    let lexer = Lexer::new(source);
+    let mut parser = Parser::new(lexer);
+    parser.set_current_file_dir(temp.path());
+    let tu = parser.parse();
+
+    assert!(parser.take_diagnostics().is_empty());
+    let var = tu.items.iter().find_map(|item| match item {
+        crate::ast::Item::VarDecl { var_name, var_type, .. } => Some((var_name.clone(), var_type.clone())),
+        _ => None,
+    });
+    let (var_name, var_type) = var.expect("Foo x; should be parsed as VarDecl");
+    assert_eq!(var_name, "x");
+    assert_eq!(var_type.and_then(|t| t.alias), Some("Foo".to_string()));
+}
+
+#[test]
+fn test_include_cycle_terminates() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("a.h"), "#include \"b.h\"\ntypedef int TypeA;\n").unwrap();
+    fs::write(temp.path().join("b.h"), "#include \"a.h\"\ntypedef int TypeB;\n").unwrap();
+
+    let source = "#include \"a.h\"\n";
+    let lexer = Lexer::new(source);
+    let mut parser = Parser::new(lexer);
+    parser.set_current_file_dir(temp.path());
+    let _ = parser.parse();
+
+    assert!(parser.get_type_table().is_type_name("TypeA"));
+    assert!(parser.get_type_table().is_type_name("TypeB"));
+}
+
+#[test]
+fn test_missing_include_emits_warning() {
+    let temp = tempdir().unwrap();
+
+    let source = "#include \"missing.h\"\n#include <stdio.h>\n";
+    let lexer = Lexer::new(source);
+    let mut parser = Parser::new(lexer);
+    parser.set_current_file_dir(temp.path());
+    let _ = parser.parse();
+
+    // <...> 形式は警告しない
+    let diagnostics = parser.take_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, crate::diagnostics::DiagnosticSeverity::Warning);
+    assert!(diagnostics[0].message.contains("missing.h"));
+}
+
+#[test]
+fn test_resolve_includes_disabled() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("foo.h"), "typedef int Foo;\n").unwrap();
+
+    let config = PreprocessorConfig {
+        resolve_includes: false,
+        ..Default::default()
+    };
+    let source = "#include \"foo.h\"\n#include \"missing.h\"\n";
+    let lexer = Lexer::new(source);
+    let mut parser = Parser::new_with_config(lexer, config);
+    parser.set_current_file_dir(temp.path());
+    let _ = parser.parse();
+
+    assert!(!parser.get_type_table().is_type_name("Foo"));
+    assert!(parser.take_diagnostics().is_empty());
+}
+
+#[test]
+fn test_malformed_header_diagnostics_are_reported_at_include() {
+    let temp = tempdir().unwrap();
+    fs::write(temp.path().join("broken.h"), "typedef int Broken;\nvoid f(void) {\n").unwrap();
+
+    let source = "int x;\n#include \"broken.h\"\n";
+    let lexer = Lexer::new(source);
+    let mut parser = Parser::new(lexer);
+    parser.set_current_file_dir(temp.path());
+    let _ = parser.parse();
+
+    let diagnostics = parser.take_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, crate::diagnostics::DiagnosticSeverity::Error);
+    assert!(diagnostics[0].message.starts_with("broken.h:2:"), "{}", diagnostics[0].message);
+    let span = &diagnostics[0].span;
+    assert_eq!(&source[span.byte_start_idx..span.byte_end_idx].trim(), &"#include \"broken.h\"");
+}
@@ -3,6 +3,7 @@ mod tests {
     use crate::lexer::Lexer;
     use crate::parser::Parser;
     use crate::ast::Item;
+    use crate::config::PreprocessorConfig;
 
     #[test]
     fn test_parser_ifdef_simple() {
@@ -11,14 +12,16 @@ mod tests {
         let mut parser = Parser::new(lexer);
         let tu = parser.parse();
 
-        // 新しい入れ子構造: ConditionalBlockが1つ、その中にVarDeclとendifが含まれる
+        // ConditionalBlockが1つ、その中にVarDeclのみが含まれる（#endif は end_span で表す）
         assert_eq!(tu.items.len(), 1);
         
         // #ifdef
-        if let Item::ConditionalBlock { directive_type, condition, items, .. } = &tu.items[0] {
+        if let Item::ConditionalBlock { directive_type, condition, items, elif_branches, else_branch, start_span, end_span, .. } = &tu.items[0] {
             assert_eq!(directive_type, "ifdef");
             assert_eq!(condition, "DEBUG");
-            assert_eq!(items.len(), 2); // VarDecl + endif
+            assert_eq!(items.len(), 1);
+            assert!(elif_branches.is_empty());
+            assert!(else_branch.is_none());
             
             // 中身: variable declaration
             if let Item::VarDecl { var_name, .. } = &items[0] {
@@ -27,12 +30,9 @@ mod tests {
                 panic!("Expected VarDecl inside ifdef");
             }
             
-            // 中身: #endif
-            if let Item::ConditionalBlock { directive_type, .. } = &items[1] {
-                assert_eq!(directive_type, "endif");
-            } else {
-                panic!("Expected endif marker");
-            }
+            // #endif の位置
+            assert_eq!(start_span.start_line, 0);
+            assert_eq!(end_span.start_line, 2);
         } else {
             panic!("Expected ConditionalBlock for ifdef");
         }
@@ -45,25 +45,18 @@ mod tests {
         let mut parser = Parser::new(lexer);
         let tu = parser.parse();
 
-        // 新しい入れ子構造
         assert_eq!(tu.items.len(), 1);
         
         if let Item::ConditionalBlock { directive_type, condition, items, .. } = &tu.items[0] {
             assert_eq!(directive_type, "ifndef");
             assert_eq!(condition, "HEADER_H");
-            assert_eq!(items.len(), 2); // Define + endif
+            assert_eq!(items.len(), 1); // Define のみ
             
             if let Item::Define { macro_name, .. } = &items[0] {
                 assert_eq!(macro_name, "HEADER_H");
             } else {
                 panic!("Expected Define inside ifndef");
             }
-            
-            if let Item::ConditionalBlock { directive_type, .. } = &items[1] {
-                assert_eq!(directive_type, "endif");
-            } else {
-                panic!("Expected endif marker");
-            }
         } else {
             panic!("Expected ConditionalBlock for ifndef");
         }
@@ -76,13 +69,12 @@ mod tests {
         let mut parser = Parser::new(lexer);
         let tu = parser.parse();
 
-        // 新しい入れ子構造
         assert_eq!(tu.items.len(), 1);
         
         if let Item::ConditionalBlock { directive_type, condition, items, .. } = &tu.items[0] {
             assert_eq!(directive_type, "if");
             assert!(condition.contains("defined(FEATURE)"));
-            assert_eq!(items.len(), 2); // VarDecl + endif
+            assert_eq!(items.len(), 1); // VarDecl のみ
         } else {
             panic!("Expected ConditionalBlock for if");
         }
@@ -95,63 +87,54 @@ mod tests {
         let mut parser = Parser::new(lexer);
         let tu = parser.parse();
 
-        // 新しい入れ子構造: ifdef -> (items + elif -> (items + else -> (items + endif)))
+        // ブロックは1つで、#elif / #else はブロックのフィールドとして保持される
         assert_eq!(tu.items.len(), 1);
         
-        if let Item::ConditionalBlock { directive_type, condition, items, .. } = &tu.items[0] {
+        if let Item::ConditionalBlock { directive_type, condition, items, elif_branches, else_branch, .. } = &tu.items[0] {
             assert_eq!(directive_type, "ifdef");
             assert_eq!(condition, "WINDOWS");
-            assert_eq!(items.len(), 2); // VarDecl + elif block
+            assert_eq!(items.len(), 1); // VarDecl のみ
+            assert!(matches!(&items[0], Item::VarDecl { var_name, .. } if var_name == "os"));
             
-            // VarDecl
-            if let Item::VarDecl { var_name, .. } = &items[0] {
-                assert_eq!(var_name, "os");
-            } else {
-                panic!("Expected VarDecl in ifdef");
-            }
+            // elif branch
+            assert_eq!(elif_branches.len(), 1);
+            assert_eq!(elif_branches[0].condition, "defined(LINUX)");
+            assert_eq!(elif_branches[0].items.len(), 1);
+            assert!(matches!(&elif_branches[0].items[0], Item::VarDecl { var_name, .. } if var_name == "os"));
             
-            // elif block
-            if let Item::ConditionalBlock { directive_type, condition:_, items: elif_items, .. } = &items[1] {
-                assert_eq!(directive_type, "elif");
-                // conditionは空になっている（Lexerの実装による）
-                assert_eq!(elif_items.len(), 3); // VarDecl + else block + endif
-                
-                // VarDecl in elif
-                if let Item::VarDecl { var_name, .. } = &elif_items[0] {
-                    assert_eq!(var_name, "os");
-                } else {
-                    panic!("Expected VarDecl in elif");
-                }
-                
-                // else block
-                if let Item::ConditionalBlock { directive_type, items: else_items, .. } = &elif_items[1] {
-                    assert_eq!(directive_type, "else");
-                    assert_eq!(else_items.len(), 1); // VarDecl のみ（endifは elif の sibling）
-                    
-                    // VarDecl in else
-                    if let Item::VarDecl { var_name, .. } = &else_items[0] {
-                        assert_eq!(var_name, "os");
-                    } else {
-                        panic!("Expected VarDecl in else");
-                    }
-                } else {
-                    panic!("Expected else block");
-                }
-                
-                // endif at elif level
-                if let Item::ConditionalBlock { directive_type, .. } = &elif_items[2] {
-                    assert_eq!(directive_type, "endif");
-                } else {
-                    panic!("Expected endif marker at elif level");
-                }
-            } else {
-                panic!("Expected elif block");
-            }
+            // else branch
+            let else_items = else_branch.as_ref().expect("Expected else branch");
+            assert_eq!(else_items.len(), 1);
+            assert!(matches!(&else_items[0], Item::VarDecl { var_name, .. } if var_name == "os"));
         } else {
             panic!("Expected ConditionalBlock for ifdef");
         }
     }
 
+    #[test]
+    fn test_parser_if_else_has_no_synthetic_items() {
+        let input = "#if 0\nint a;\n#else\nint b;\n#endif\n";
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let tu = parser.parse();
+
+        assert_eq!(tu.items.len(), 1);
+        if let Item::ConditionalBlock { items, elif_branches, else_branch, else_span, condition_result, .. } = &tu.items[0] {
+            assert!(!condition_result);
+            assert_eq!(items.len(), 1);
+            assert!(elif_branches.is_empty());
+            assert_eq!(else_branch.as_ref().map(|b| b.len()), Some(1));
+            assert_eq!(else_span.as_ref().map(|s| s.start_line), Some(2));
+        } else {
+            panic!("Expected ConditionalBlock for if");
+        }
+
+        // 全アイテムを列挙しても、ブロック自身以外に ConditionalBlock は現れない
+        let blocks = tu.all_items().into_iter().filter(|item| matches!(item, Item::ConditionalBlock { .. })).count();
+        assert_eq!(blocks, 1);
+        assert_eq!(tu.all_items().len(), 3); // ブロック + int a + int b
+    }
+
     #[test]
     fn test_parser_nested_ifdef() {
         let input = "#ifdef OUTER\n#ifdef INNER\nint x = 1;\n#endif\n#endif\n";
@@ -159,19 +142,21 @@ mod tests {
         let mut parser = Parser::new(lexer);
         let tu = parser.parse();
 
-        // 新しい入れ子構造: outer ifdef -> (inner ifdef -> (VarDecl + inner endif) + outer endif)
+        // 入れ子構造: outer ifdef -> inner ifdef -> VarDecl
         assert_eq!(tu.items.len(), 1);
         
-        if let Item::ConditionalBlock { directive_type, condition, items, .. } = &tu.items[0] {
+        if let Item::ConditionalBlock { directive_type, condition, items, end_span: outer_end, .. } = &tu.items[0] {
             assert_eq!(directive_type, "ifdef");
             assert_eq!(condition, "OUTER");
-            assert_eq!(items.len(), 2); // inner ifdef block + outer endif
+            assert_eq!(items.len(), 1); // inner ifdef block のみ
+            assert_eq!(outer_end.start_line, 4);
             
             // inner ifdef
-            if let Item::ConditionalBlock { directive_type, condition, items: inner_items, .. } = &items[0] {
+            if let Item::ConditionalBlock { directive_type, condition, items: inner_items, end_span: inner_end, .. } = &items[0] {
                 assert_eq!(directive_type, "ifdef");
                 assert_eq!(condition, "INNER");
-                assert_eq!(inner_items.len(), 2); // VarDecl + inner endif
+                assert_eq!(inner_items.len(), 1); // VarDecl のみ
+                assert_eq!(inner_end.start_line, 3);
                 
                 // VarDecl
                 if let Item::VarDecl { var_name, .. } = &inner_items[0] {
@@ -179,25 +164,54 @@ mod tests {
                 } else {
                     panic!("Expected VarDecl");
                 }
-                
-                // inner endif
-                if let Item::ConditionalBlock { directive_type, .. } = &inner_items[1] {
-                    assert_eq!(directive_type, "endif");
-                } else {
-                    panic!("Expected inner endif");
-                }
             } else {
                 panic!("Expected inner ifdef");
             }
-            
-            // outer endif
-            if let Item::ConditionalBlock { directive_type, .. } = &items[1] {
-                assert_eq!(directive_type, "endif");
-            } else {
-                panic!("Expected outer endif");
-            }
         } else {
             panic!("Expected outer ifdef");
         }
     }
+
+    #[test]
+    fn test_parser_prune_inactive_branches_keeps_else() {
+        let input = "#if 0\nint a;\n#else\nint b;\n#endif\n";
+        let config = PreprocessorConfig {
+            prune_inactive_branches: true,
+            ..Default::default()
+        };
+        let mut parser = Parser::new_with_config(Lexer::new(input), config);
+        let tu = parser.parse();
+
+        // else 分岐のアイテムだけが残り、ConditionalBlock は取り除かれる
+        assert_eq!(tu.items.len(), 1);
+        assert!(matches!(&tu.items[0], Item::VarDecl { var_name, .. } if var_name == "b"));
+    }
+
+    #[test]
+    fn test_parser_prune_inactive_branches_nested_and_elif() {
+        let input = "#ifdef WINDOWS\nint a;\n#elif 1\n#if 0\nint b;\n#endif\nint c;\n#else\nint d;\n#endif\n";
+        let config = PreprocessorConfig {
+            prune_inactive_branches: true,
+            ..Default::default()
+        };
+        let mut parser = Parser::new_with_config(Lexer::new(input), config);
+        let tu = parser.parse();
+
+        let names: Vec<&str> = tu.items.iter().filter_map(|item| match item {
+            Item::VarDecl { var_name, .. } => Some(var_name.as_str()),
+            _ => None,
+        }).collect();
+        assert_eq!(names, vec!["c"]);
+        assert_eq!(tu.items.len(), 1);
+    }
+
+    #[test]
+    fn test_parser_default_keeps_all_branches() {
+        let input = "#if 0\nint a;\n#else\nint b;\n#endif\n";
+        let mut parser = Parser::new(Lexer::new(input));
+        let tu = parser.parse();
+
+        assert_eq!(tu.items.len(), 1);
+        assert!(matches!(&tu.items[0], Item::ConditionalBlock { .. }));
+    }
 }
@@ -0,0 +1,80 @@
+use crate::ast::{Initializer, Item};
+use crate::formatter::Formatter;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn parse_items(input: &str) -> Vec<Item> {
+    Parser::new(Lexer::new(input)).parse().items
+}
+
+#[test]
+fn test_global_array_initializer_list() {
+    let items = parse_items("int table[] = {1, 2, 3};\n");
+
+    assert_eq!(items.len(), 1);
+    let Item::VarDecl { var_name, has_initializer, initializer, .. } = &items[0] else {
+        panic!("Expected VarDecl");
+    };
+    assert_eq!(var_name, "table");
+    assert!(has_initializer);
+    let Some(Initializer::List { elements, .. }) = initializer else {
+        panic!("Expected initializer list, got {:?}", initializer);
+    };
+    let values: Vec<&str> = elements
+        .iter()
+        .map(|e| match e {
+            Initializer::Value { text, .. } => text.as_str(),
+            Initializer::List { .. } => panic!("Expected value"),
+        })
+        .collect();
+    assert_eq!(values, vec!["1", "2", "3"]);
+    assert_eq!(items[0].array_length(), Some(3));
+}
+
+#[test]
+fn test_nested_and_designated_initializers() {
+    let items = parse_items("int points[][2] = {{1, 2}, {[0] = 3, [1] = f(4, 5)},};\nint grid[2][2] = {{1, 0}, {0, 1}};\n");
+
+    let Item::VarDecl { var_name, initializer: Some(init), .. } = &items[0] else {
+        panic!("Expected VarDecl with initializer, got {:?}", items[0]);
+    };
+    assert_eq!(var_name, "points");
+    assert_eq!(init.element_count(), Some(2));
+    let Initializer::List { elements, .. } = init else { unreachable!() };
+    let Initializer::List { elements: designated, .. } = &elements[1] else {
+        panic!("Expected nested list");
+    };
+    assert!(matches!(&designated[1], Initializer::Value { text, .. } if text == "[1] = f(4, 5)"), "{:?}", designated);
+    assert_eq!(items[0].array_length(), Some(2));
+
+    // 明示した要素数は初期化子ではなく宣言から求める
+    assert_eq!(items[1].array_length(), Some(2));
+}
+
+#[test]
+fn test_scalar_initializer_and_typedef_array() {
+    let items = parse_items("typedef unsigned char u8;\nint count = max + 1;\nu8 buffer[4 * 4] = {0};\n");
+
+    let Item::VarDecl { var_name, initializer, .. } = &items[1] else {
+        panic!("Expected VarDecl");
+    };
+    // 初期化子の中の識別子を変数名と取り違えない
+    assert_eq!(var_name, "count");
+    assert!(matches!(initializer, Some(Initializer::Value { text, .. }) if text == "max + 1"));
+    assert_eq!(items[1].array_length(), None);
+
+    let Item::VarDecl { var_name, initializer, .. } = &items[2] else {
+        panic!("Expected VarDecl");
+    };
+    assert_eq!(var_name, "buffer");
+    assert_eq!(initializer.as_ref().and_then(Initializer::element_count), Some(1));
+    assert_eq!(items[2].array_length(), Some(16));
+}
+
+#[test]
+fn test_type_info_formatting_keeps_array_dimensions() {
+    let tu = Parser::new(Lexer::new("int table[3] = {1, 2, 3};\n")).parse();
+    let formatted = Formatter::new_with_options(false, true).format_tu(&tu);
+
+    assert!(formatted.contains("int table[3] = {1, 2, 3};"), "{}", formatted);
+}
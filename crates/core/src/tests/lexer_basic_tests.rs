@@ -46,3 +46,115 @@ fn test_basic_iteration_and_pos() {
         assert_eq!(got, vec!['a', 'é', 'b']);
         assert_eq!(lx.pos_index(), 3);
     }
+
+#[test]
+fn test_identical_sources_lex_to_equal_tokens() {
+    let source = "#define MAX (10)\nstatic const int x = 0x1F;\nfloat f = 1.5f;\n";
+
+    let mut first = Lexer::new(source);
+    let mut second = Lexer::new(source);
+    let mut count = 0;
+    loop {
+        let (a, b) = (first.next_token(), second.next_token());
+        assert_eq!(a, b);
+        if a.is_none() {
+            break;
+        }
+        count += 1;
+    }
+    assert!(count > 0);
+}
+
+#[test]
+fn test_tokens_can_be_deduplicated() {
+    use std::collections::HashSet;
+
+    // 同じ内容・同じ位置のトークンは重複として扱える
+    let tokens: Vec<_> = std::iter::repeat_with(|| Lexer::new("int x;").next_token().unwrap())
+        .take(3)
+        .collect();
+    let unique: HashSet<_> = tokens.into_iter().collect();
+    assert_eq!(unique.len(), 1);
+}
+
+#[test]
+fn test_identical_sources_parse_to_equal_items() {
+    fn assert_eq_impl<T: Eq>(_: &T) {}
+
+    let source = "typedef int MyInt;\nMyInt value;\nvoid foo(void) { double d = 1.5f; return; }\n";
+
+    let tu1 = crate::parser::Parser::new(Lexer::new(source)).parse();
+    let tu2 = crate::parser::Parser::new(Lexer::new(source)).parse();
+    assert_eq_impl(&tu1);
+    assert_eq!(tu1, tu2);
+}
+
+#[test]
+fn test_token_span_accessor() {
+    use crate::token::Token;
+
+    let s = "int x = 10;";
+    let mut lx = Lexer::new(s);
+
+    let mut tokens = Vec::new();
+    while let Some(token) = lx.next_token() {
+        tokens.push(token);
+    }
+
+    assert!(matches!(tokens[0], Token::Int(_)));
+    assert!(matches!(tokens[1], Token::Ident(_)));
+    assert!(matches!(tokens[2], Token::Equal(_)));
+    assert!(matches!(tokens[3], Token::NumberLiteral(_)));
+    assert!(matches!(tokens[4], Token::Semicolon(_)));
+
+    // 各トークンのスパンをパターンマッチせずに取得できる
+    let texts: Vec<&str> = tokens
+        .iter()
+        .map(|token| {
+            let span = token.span();
+            s[span.byte_start_idx..span.byte_end_idx].trim()
+        })
+        .collect();
+    assert_eq!(texts, vec!["int", "x", "=", "10", ";"]);
+
+    let last = tokens.pop().unwrap();
+    let span = last.span().clone();
+    assert_eq!(last.into_span(), span);
+}
+
+#[test]
+fn test_token_kind() {
+    use crate::span::Span;
+    use crate::token::{IntToken, Token, TokenKind};
+
+    let span = Span {
+        start_line: 0,
+        start_column: 0,
+        end_line: 0,
+        end_column: 3,
+        byte_start_idx: 0,
+        byte_end_idx: 3,
+    };
+    assert_eq!(Token::Int(IntToken { span }).kind(), TokenKind::Int);
+
+    // ペイロードに関係なく種類で比較できる
+    let mut lx = Lexer::new("x = y;");
+    let kinds: Vec<TokenKind> = std::iter::from_fn(|| lx.next_token()).map(|t| t.kind()).collect();
+    assert_eq!(kinds, vec![TokenKind::Ident, TokenKind::Equal, TokenKind::Ident, TokenKind::Semicolon]);
+}
+
+#[test]
+fn test_identifiers_with_same_name_share_storage() {
+    // 同じ名前の識別子はインターンされ、同じ文字列を共有する
+    let mut lx = Lexer::new("count = count + other;");
+    let names: Vec<std::sync::Arc<str>> = std::iter::from_fn(|| lx.next_token())
+        .filter_map(|t| match t {
+            crate::token::Token::Ident(ident) => Some(ident.name),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names.len(), 3);
+    assert_eq!(&*names[0], "count");
+    assert!(std::sync::Arc::ptr_eq(&names[0], &names[1]));
+    assert!(!std::sync::Arc::ptr_eq(&names[0], &names[2]));
+}
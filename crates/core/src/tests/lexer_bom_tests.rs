@@ -0,0 +1,93 @@
+use crate::ast::Item;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::Token;
+
+const BOM: &str = "\u{FEFF}";
+
+fn tokens(source: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(source);
+    std::iter::from_fn(|| lexer.next_token()).collect()
+}
+
+#[test]
+fn test_bom_yields_same_tokens_and_positions() {
+    let source = "int x;\nchar *p = \"a\";\n";
+    let with_bom = format!("{}{}", BOM, source);
+
+    let plain = tokens(source);
+    let bom = tokens(&with_bom);
+    assert_eq!(plain.len(), bom.len());
+    for (a, b) in plain.iter().zip(&bom) {
+        assert_eq!(a.kind(), b.kind());
+        let (sa, sb) = (a.span(), b.span());
+        assert_eq!(
+            (sa.start_line, sa.start_column, sa.end_line, sa.end_column),
+            (sb.start_line, sb.start_column, sb.end_line, sb.end_column)
+        );
+        // バイトオフセットは BOM を含む入力での位置なので、BOM の長さだけずれる
+        assert_eq!(sa.byte_start_idx + BOM.len(), sb.byte_start_idx);
+        assert_eq!(sa.byte_end_idx + BOM.len(), sb.byte_end_idx);
+        assert_eq!(&source[sa.byte_start_idx..sa.byte_end_idx], &with_bom[sb.byte_start_idx..sb.byte_end_idx]);
+    }
+}
+
+#[test]
+fn test_bom_is_not_part_of_first_token() {
+    let source = format!("{}int x;\n", BOM);
+    let first = tokens(&source).remove(0);
+    assert!(matches!(first, Token::Int(_)));
+    assert_eq!(first.span().byte_start_idx, BOM.len());
+}
+
+#[test]
+fn test_bom_keeps_function_body_positions() {
+    let source = "int f(void) { return 0; }\n";
+    let with_bom = format!("{}{}", BOM, source);
+
+    let body_span = |src: &str| {
+        let tu = Parser::new(Lexer::new(src)).parse();
+        let Some(Item::FunctionDecl { body: Some(body), .. }) = tu.items.first() else {
+            panic!("expected function definition");
+        };
+        let span = body[0].span().clone();
+        (span.start_line, span.start_column, span.end_line, span.end_column)
+    };
+    assert_eq!(body_span(source), body_span(&with_bom));
+}
+
+#[test]
+fn test_preserve_whitespace_emits_bom_as_first_token() {
+    let source = format!("{}int x;\n", BOM);
+    let mut lexer = Lexer::new(&source);
+    lexer.preserve_whitespace(true);
+
+    let first = lexer.next_token().unwrap();
+    let Token::Whitespace(ws) = &first else {
+        panic!("expected whitespace token, got {:?}", first);
+    };
+    assert_eq!(ws.text, BOM);
+    assert_eq!((first.span().byte_start_idx, first.span().byte_end_idx), (0, BOM.len()));
+
+    let second = lexer.next_token().unwrap();
+    assert!(matches!(second, Token::Int(_)));
+    assert_eq!((second.span().start_line, second.span().start_column), (0, 0));
+}
+
+#[test]
+fn test_preserve_whitespace_round_trip_with_bom() {
+    let examples = [
+        ("valid_code.c", include_str!("../../../../examples/valid_code.c")),
+        ("test_elif.c", include_str!("../../../../examples/test_elif.c")),
+    ];
+    for (path, source) in examples {
+        assert!(source.starts_with(BOM), "{} should start with a BOM", path);
+
+        let mut lexer = Lexer::new(source);
+        lexer.preserve_whitespace(true);
+        let text: String = std::iter::from_fn(|| lexer.next_token())
+            .map(|token| source[token.span().byte_start_idx..token.span().byte_end_idx].to_string())
+            .collect();
+        assert_eq!(text, source, "{}", path);
+    }
+}
@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+use std::time::Instant;
+
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::*;
+
+fn collect_tokens(mut lexer: Lexer) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+const SAMPLE: &str = "/* ヘッダー */\n#include <stdio.h>\n#define MAX(a, b) ((a) > (b) ? (a) : (b))\r\n\ntypedef unsigned char VU8;\nstatic int counter = 0;  // カウンター\n\nint add(int a, int b) {\n\treturn a + b;\n}\n";
+
+#[test]
+fn test_borrowed_and_owned_produce_identical_tokens() {
+    let owned = collect_tokens(Lexer::new(SAMPLE));
+    let borrowed = collect_tokens(Lexer::from_borrowed(SAMPLE));
+    assert!(!owned.is_empty());
+    assert_eq!(owned, borrowed);
+}
+
+#[test]
+fn test_borrowed_and_owned_identical_with_whitespace_preserved() {
+    let mut owned = Lexer::new(SAMPLE);
+    owned.preserve_whitespace(true);
+    let mut borrowed = Lexer::from_borrowed(SAMPLE);
+    borrowed.preserve_whitespace(true);
+    assert_eq!(collect_tokens(owned), collect_tokens(borrowed));
+}
+
+#[test]
+fn test_borrowed_lexer_does_not_copy_input() {
+    let lexer = Lexer::from_borrowed(SAMPLE);
+    assert!(matches!(lexer.input, Cow::Borrowed(_)));
+    assert_eq!(lexer.input.as_ptr(), SAMPLE.as_ptr());
+}
+
+#[test]
+fn test_borrowed_spans_index_into_caller_buffer() {
+    let mut lexer = Lexer::from_borrowed(SAMPLE);
+    while let Some(token) = lexer.next_token() {
+        if let Token::Define(DefineToken { span, macro_name, .. }) = token {
+            assert_eq!(macro_name, "MAX");
+            assert!(SAMPLE[span.byte_start_idx..span.byte_end_idx].starts_with("#define MAX(a, b)"));
+            return;
+        }
+    }
+    panic!("Define token not found");
+}
+
+#[test]
+fn test_parser_accepts_borrowed_lexer() {
+    let source = String::from("int x = 1;\n");
+    let mut parser = Parser::new(Lexer::from_borrowed(&source));
+    let tu = parser.parse();
+    assert_eq!(tu.items.len(), 1);
+}
+
+#[test]
+fn test_borrowed_lexing_large_input() {
+    // 大きな入力でも借用版と所有版が同じ結果になることを確認する（所要時間は参考値として出力）
+    let source = SAMPLE.repeat(2000);
+
+    let start = Instant::now();
+    let owned = collect_tokens(Lexer::new(&source));
+    let owned_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let borrowed = collect_tokens(Lexer::from_borrowed(&source));
+    let borrowed_elapsed = start.elapsed();
+
+    println!("owned: {:?}, borrowed: {:?} ({} bytes, {} tokens)", owned_elapsed, borrowed_elapsed, source.len(), borrowed.len());
+    assert_eq!(owned.len(), borrowed.len());
+    assert_eq!(owned.last(), borrowed.last());
+}
@@ -0,0 +1,52 @@
+use crate::lexer::Lexer;
+
+/// トークンの種類と行・列（バイトオフセットは除く）を列挙する
+fn token_positions(source: &str) -> Vec<(std::mem::Discriminant<crate::token::Token>, usize, usize, usize, usize)> {
+    let mut lexer = Lexer::new(source);
+    let mut positions = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        let span = token.span();
+        positions.push((
+            std::mem::discriminant(&token),
+            span.start_line,
+            span.start_column,
+            span.end_line,
+            span.end_column,
+        ));
+    }
+    positions
+}
+
+fn assert_same_positions(lf_source: &str) {
+    let crlf_source = lf_source.replace('\n', "\r\n");
+    assert_eq!(token_positions(lf_source), token_positions(&crlf_source));
+}
+
+#[test]
+fn test_crlf_statements_same_positions() {
+    assert_same_positions("int  value;\n  x = 1;\n  y = x + 2;\n");
+}
+
+#[test]
+fn test_crlf_line_comment_same_positions() {
+    assert_same_positions("int x; // comment\nint y; // another\n");
+}
+
+#[test]
+fn test_crlf_block_comment_and_directive_same_positions() {
+    assert_same_positions("#include <stdio.h>\n#define A 1\n/* multi\n line */\nint z;\n");
+}
+
+#[test]
+fn test_crlf_leading_newline_same_positions() {
+    assert_same_positions("\nvoid foo() {\n    return;\n}\n");
+}
+
+#[test]
+fn test_crlf_line_comment_excludes_carriage_return() {
+    let source = "// comment\r\nint x;\r\n";
+    let mut lexer = Lexer::new(source);
+    let token = lexer.next_token().unwrap();
+    let span = token.span();
+    assert_eq!(&source[span.byte_start_idx..span.byte_end_idx], "// comment");
+}
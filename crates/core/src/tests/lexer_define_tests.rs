@@ -8,7 +8,7 @@ fn test_lexer_define_simple() {
 
         while let Some(token) = lx.next_token() {
             match token {
-                Token::Define(DefineToken { macro_name, macro_value, span }) => {
+                Token::Define(DefineToken { macro_name, macro_value, span, .. }) => {
                     assert_eq!(macro_name, "MAX");
                     assert_eq!(macro_value, "10");
                     assert_eq!(&s[span.byte_start_idx..span.byte_end_idx], "#define MAX 10\n");
@@ -27,7 +27,7 @@ fn test_lexer_define_simple() {
 
         while let Some(token) = lx.next_token() {
             match token {
-                Token::Define(DefineToken { macro_name, macro_value, span }) => {
+                Token::Define(DefineToken { macro_name, macro_value, span, .. }) => {
                     assert_eq!(macro_name, "X");
                     assert_eq!(macro_value, "1");
                     assert_eq!(&s[span.byte_start_idx..span.byte_end_idx], "\t \r #define X 1\n");
@@ -46,7 +46,7 @@ fn test_lexer_define_simple() {
 
         let token1 = lx.next_token();
         match token1 {
-            Some(Token::Define(DefineToken { macro_name, macro_value, span })) => {
+            Some(Token::Define(DefineToken { macro_name, macro_value, span, .. })) => {
                 assert_eq!(macro_name, "A");
                 assert_eq!(macro_value, "B");
                 assert_eq!(&s[span.byte_start_idx..span.byte_end_idx], "#define A B\n");
@@ -64,3 +64,35 @@ fn test_lexer_define_simple() {
             _ => panic!("Expected Include token"),
         }
     }
+
+#[test]
+fn test_lexer_define_function_like() {
+    let s = "#define MAX(a,b) ((a)>(b)?(a):(b))\n";
+    let mut lx = Lexer::new(s);
+
+    match lx.next_token() {
+        Some(Token::Define(DefineToken { macro_name, params, macro_value, .. })) => {
+            assert_eq!(macro_name, "MAX");
+            assert_eq!(params, Some(vec!["a".to_string(), "b".to_string()]));
+            assert_eq!(macro_value, "((a)>(b)?(a):(b))");
+        }
+        _ => panic!("Expected Define token"),
+    }
+}
+
+#[test]
+fn test_lexer_define_space_before_paren_is_object_like() {
+    // マクロ名と ( の間に空白がある場合はオブジェクト形式マクロ
+    let s = "#define VALUE (1 + 2)\n";
+    let mut lx = Lexer::new(s);
+
+    match lx.next_token() {
+        Some(Token::Define(DefineToken { macro_name, params, macro_value, .. })) => {
+            assert_eq!(macro_name, "VALUE");
+            assert_eq!(params, None);
+            assert_eq!(macro_value, "(1 + 2)");
+        }
+        _ => panic!("Expected Define token"),
+    }
+}
+
@@ -67,3 +67,43 @@ int main() { return 0; }
         assert_eq!(typedef_count, 1);
         assert!(int_count >= 1); // at least one "int"
     }
+
+#[test]
+fn test_lexer_c99_type_keywords() {
+    let mut lexer = Lexer::new("_Bool _Complex _Imaginary");
+    assert!(matches!(lexer.next_token(), Some(Token::Bool(_))));
+    assert!(matches!(lexer.next_token(), Some(Token::Complex(_))));
+    assert!(matches!(lexer.next_token(), Some(Token::Imaginary(_))));
+    assert!(lexer.next_token().is_none());
+}
+
+#[test]
+fn test_lexer_disabled_keyword_is_identifier() {
+    let mut lx = Lexer::new("int restrict;\n");
+    lx.disable_keyword("restrict");
+
+    assert!(matches!(lx.next_token(), Some(Token::Int(_))));
+    match lx.next_token() {
+        Some(Token::Ident(IdentToken { name, .. })) => assert_eq!(&*name, "restrict"),
+        other => panic!("restrict should be an identifier, got {:?}", other),
+    }
+    assert!(matches!(lx.next_token(), Some(Token::Semicolon(_))));
+}
+
+#[test]
+fn test_lexer_restrict_is_keyword_by_default() {
+    let mut lx = Lexer::new("int restrict;\n");
+
+    assert!(matches!(lx.next_token(), Some(Token::Int(_))));
+    assert!(matches!(lx.next_token(), Some(Token::Restrict(_))));
+}
+
+#[test]
+fn test_lexer_disabled_keyword_leaves_others_intact() {
+    let mut lx = Lexer::new("_Atomic int restrict;\n");
+    lx.disable_keyword("_Atomic");
+
+    assert!(matches!(lx.next_token(), Some(Token::Ident(_))));
+    assert!(matches!(lx.next_token(), Some(Token::Int(_))));
+    assert!(matches!(lx.next_token(), Some(Token::Restrict(_))));
+}
@@ -8,7 +8,7 @@ fn test_lexer_define_line_continuation_backslash_n() {
 
     while let Some(token) = lx.next_token() {
         match token {
-            Token::Define(DefineToken { macro_name, macro_value, span: _ }) => {
+            Token::Define(DefineToken { macro_name, macro_value, .. }) => {
                 assert_eq!(macro_name, "LONG_MACRO");
                 // 行継続記号は削除される
                 assert_eq!(macro_value, "VALUE_ON_NEXT_LINE");
@@ -27,7 +27,7 @@ fn test_lexer_define_line_continuation_backslash_r_n() {
 
     while let Some(token) = lx.next_token() {
         match token {
-            Token::Define(DefineToken { macro_name, macro_value, span: _ }) => {
+            Token::Define(DefineToken { macro_name, macro_value, .. }) => {
                 assert_eq!(macro_name, "LONG_MACRO");
                 // 行継続記号は削除される
                 assert_eq!(macro_value, "VALUE_ON_NEXT_LINE");
@@ -46,7 +46,7 @@ fn test_lexer_define_multiple_line_continuation() {
 
     while let Some(token) = lx.next_token() {
         match token {
-            Token::Define(DefineToken { macro_name, macro_value, span: _ }) => {
+            Token::Define(DefineToken { macro_name, macro_value, .. }) => {
                 assert_eq!(macro_name, "MULTI");
                 // 行継続記号は削除される
                 assert_eq!(macro_value, "LINE1 LINE2 LINE3");
@@ -134,7 +134,7 @@ fn test_lexer_line_continuation_no_backslash() {
     let token2 = lx.next_token();
     match token2 {
         Some(Token::Ident(IdentToken { name, .. })) => {
-            assert_eq!(name, "LINE");
+            assert_eq!(&*name, "LINE");
         }
         _ => panic!("Expected Ident token for LINE"),
     }
@@ -77,7 +77,7 @@ fn test_lexer_numbers_with_identifiers() {
     // x
     match lexer.next_token() {
         Some(Token::Ident(IdentToken { name, .. })) => {
-            assert_eq!(name, "x");
+            assert_eq!(&*name, "x");
         }
         other => panic!("Expected Ident, got {:?}", other),
     }
@@ -238,7 +238,7 @@ fn test_lexer_float_in_code() {
     // pi
     match lexer.next_token() {
         Some(Token::Ident(IdentToken { name, .. })) => {
-            assert_eq!(name, "pi");
+            assert_eq!(&*name, "pi");
         }
         other => panic!("Expected Ident, got {:?}", other),
     }
@@ -394,3 +394,17 @@ fn test_lexer_integer_vs_float_with_large_l_suffix() {
         other => panic!("Expected FloatLiteral for '1e5L', got {:?}", other),
     }
 }
+
+#[test]
+fn test_number_literal_is_octal() {
+    let is_octal = |s: &str| match Lexer::new(s).next_token() {
+        Some(Token::NumberLiteral(literal)) => literal.is_octal(),
+        other => panic!("Expected NumberLiteral, got {:?}", other),
+    };
+    assert!(is_octal("010"));
+    assert!(is_octal("0755u"));
+    assert!(!is_octal("0"));
+    assert!(!is_octal("0u"));
+    assert!(!is_octal("0x10"));
+    assert!(!is_octal("10"));
+}
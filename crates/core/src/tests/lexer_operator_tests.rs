@@ -193,3 +193,29 @@ fn test_lexer_bitwise_or_vs_logical_or() {
     assert!(matches!(lexer.next_token(), Some(Token::PipePipe(_))));
     assert!(matches!(lexer.next_token(), Some(Token::Ident(_)))); // c
 }
+
+#[test]
+fn test_lexer_compound_assignment_operators() {
+    let input = "a += 1; b <<= 2; c >>= 3; d *= 4; e /= 5; f -= 6; g %= 7; h &= 8; i |= 9; j ^= 10;";
+    let mut lexer = Lexer::new(input);
+
+    let mut operators = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        match token {
+            Token::Ident(_) | Token::NumberLiteral(_) | Token::Semicolon(_) => {}
+            other => operators.push(other),
+        }
+    }
+
+    assert_eq!(operators.len(), 10);
+    assert!(matches!(operators[0], Token::PlusEqual(_)));
+    assert!(matches!(operators[1], Token::LeftShiftEqual(_)));
+    assert!(matches!(operators[2], Token::RightShiftEqual(_)));
+    assert!(matches!(operators[3], Token::AsteriskEqual(_)));
+    assert!(matches!(operators[4], Token::SlashEqual(_)));
+    assert!(matches!(operators[5], Token::MinusEqual(_)));
+    assert!(matches!(operators[6], Token::PercentEqual(_)));
+    assert!(matches!(operators[7], Token::AmpersandEqual(_)));
+    assert!(matches!(operators[8], Token::PipeEqual(_)));
+    assert!(matches!(operators[9], Token::CaretEqual(_)));
+}
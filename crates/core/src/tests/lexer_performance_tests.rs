@@ -0,0 +1,80 @@
+use std::time::{Duration, Instant};
+
+use crate::lexer::Lexer;
+
+/// 関数・構造体・プリプロセッサ・コメントを含む lines 行の C コードを生成する
+fn generate_source(lines: usize) -> String {
+    const BLOCK: [&str; 10] = [
+        "/* 関数 {i} */",
+        "#define VALUE_{i} ({i} + 1)",
+        "struct S{i} { int x; char name[16]; };",
+        "static int func_{i}(int a, int b) {",
+        "    // 加算",
+        "    if (a > b) {",
+        "        return a - b;",
+        "    }",
+        "    return a + b * VALUE_{i};",
+        "}",
+    ];
+
+    let mut input = String::new();
+    for line in 0..lines {
+        let template = BLOCK[line % BLOCK.len()];
+        input.push_str(&template.replace("{i}", &(line / BLOCK.len()).to_string()));
+        input.push('\n');
+    }
+    input
+}
+
+/// 入力全体をトークン化し、トークン数と所要時間を返す
+fn tokenize(input: &str) -> (usize, Duration) {
+    let start = Instant::now();
+    let mut lexer = Lexer::from_borrowed(input);
+    let mut count = 0;
+    while lexer.next_token().is_some() {
+        count += 1;
+    }
+    (count, start.elapsed())
+}
+
+#[test]
+fn test_generate_source_line_count() {
+    let source = generate_source(10_000);
+    assert_eq!(source.lines().count(), 10_000);
+}
+
+#[test]
+fn test_tokenize_10k_lines_within_time_bound() {
+    // デバッグビルドでも 1 秒未満で終わる規模。極端に遅くなった場合（二乗オーダーの処理の混入など）に検出する
+    let source = generate_source(10_000);
+    let (tokens, elapsed) = tokenize(&source);
+
+    // 10 行のブロックごとに 49 トークン
+    assert_eq!(tokens, 49_000);
+    assert!(
+        elapsed < Duration::from_secs(10),
+        "10,000 行のトークン化に {:?} かかりました",
+        elapsed
+    );
+}
+
+#[test]
+fn test_tokenize_time_grows_linearly() {
+    // 入力を 10 倍にしたときの所要時間の比を見る。線形なら約 10 倍、二乗なら約 100 倍になる
+    // 計測の揺らぎを抑えるため、それぞれ数回計測した最小値を使う
+    let small = generate_source(2_000);
+    let large = generate_source(20_000);
+    let best = |input: &str| (0..3).map(|_| tokenize(input).1).min().unwrap();
+
+    let small_elapsed = best(&small).max(Duration::from_micros(100));
+    let large_elapsed = best(&large);
+    let ratio = large_elapsed.as_secs_f64() / small_elapsed.as_secs_f64();
+
+    assert!(
+        ratio < 40.0,
+        "入力 10 倍で所要時間が {:.1} 倍になりました（{:?} → {:?}）",
+        ratio,
+        small_elapsed,
+        large_elapsed
+    );
+}
@@ -0,0 +1,86 @@
+use crate::lexer::Lexer;
+use crate::token::*;
+
+fn tokens(s: &str) -> Vec<Token> {
+    let mut lx = Lexer::new(s);
+    std::iter::from_fn(|| lx.next_token()).collect()
+}
+
+#[test]
+fn test_lexer_block_comment_inside_string() {
+    let s = "\"a /* b */ c\"";
+    let tokens = tokens(s);
+
+    assert_eq!(tokens.len(), 1);
+    match &tokens[0] {
+        Token::StringLiteral(StringLiteralToken { span, value }) => {
+            assert_eq!(value, "\"a /* b */ c\"");
+            assert_eq!(&s[span.byte_start_idx..span.byte_end_idx], s);
+        },
+        other => panic!("Expected StringLiteral, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_lexer_line_comment_inside_string() {
+    let tokens = tokens("url = \"http://x\"; y = 1;\n");
+
+    // "//" 以降が行コメントにならず、文字列の後のトークンも読める
+    assert!(tokens.iter().all(|t| !matches!(t, Token::LineComment(..))));
+    assert!(matches!(&tokens[2], Token::StringLiteral(StringLiteralToken { value, .. }) if value == "\"http://x\""));
+    assert!(matches!(&tokens[4], Token::Ident(ident) if &*ident.name == "y"));
+    assert_eq!(tokens.len(), 8);
+}
+
+#[test]
+fn test_lexer_comment_after_string() {
+    let tokens = tokens("s = \"/*\"; /* comment */ // \"not a string\"\n");
+
+    assert!(matches!(&tokens[2], Token::StringLiteral(StringLiteralToken { value, .. }) if value == "\"/*\""));
+    assert!(matches!(tokens[4], Token::BlockComment(..)));
+    assert!(matches!(tokens[5], Token::LineComment(..)));
+    assert_eq!(tokens.len(), 6);
+}
+
+#[test]
+fn test_lexer_string_with_escaped_quote() {
+    let tokens = tokens("\"say \\\"/*hi*/\\\"\\\\\" x");
+
+    assert!(matches!(&tokens[0], Token::StringLiteral(StringLiteralToken { value, .. }) if value == "\"say \\\"/*hi*/\\\"\\\\\""));
+    assert!(matches!(&tokens[1], Token::Ident(ident) if &*ident.name == "x"));
+}
+
+#[test]
+fn test_lexer_unterminated_string() {
+    let tokens = tokens("\"abc\nint x;\n");
+
+    assert!(matches!(tokens[0], Token::Error(..)));
+    assert!(matches!(tokens[1], Token::Int(..)));
+}
+
+#[test]
+fn test_lexer_double_quote_char_literal() {
+    let tokens = tokens("return c == '\"'; x = 1;\n");
+
+    // '"' は文字定数で、文字列リテラルを開始しない
+    assert!(matches!(&tokens[3], Token::CharLiteral(CharLiteralToken { value, .. }) if value == "'\"'"));
+    assert!(tokens.iter().all(|t| !matches!(t, Token::StringLiteral(..) | Token::Error(..))));
+    assert!(matches!(&tokens[5], Token::Ident(ident) if &*ident.name == "x"));
+}
+
+#[test]
+fn test_lexer_escaped_quote_char_literal() {
+    let tokens = tokens("c = '\\''; s = \"a\";\n");
+
+    assert!(matches!(&tokens[2], Token::CharLiteral(CharLiteralToken { value, .. }) if value == "'\\''"));
+    assert!(matches!(&tokens[4], Token::Ident(ident) if &*ident.name == "s"));
+    assert!(matches!(&tokens[6], Token::StringLiteral(StringLiteralToken { value, .. }) if value == "\"a\""));
+}
+
+#[test]
+fn test_lexer_comment_markers_inside_char_literal() {
+    let tokens = tokens("c = '/'; d = '*';\n");
+
+    assert!(tokens.iter().all(|t| !matches!(t, Token::LineComment(..) | Token::BlockComment(..))));
+    assert_eq!(tokens.iter().filter(|t| matches!(t, Token::CharLiteral(..))).count(), 2);
+}
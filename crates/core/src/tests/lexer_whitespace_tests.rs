@@ -0,0 +1,88 @@
+use crate::lexer::Lexer;
+use crate::token::Token;
+
+/// 空白保持モードで字句解析し、各トークンのテキストを返す
+fn token_texts(source: &str) -> Vec<String> {
+    let mut lexer = Lexer::new(source);
+    lexer.preserve_whitespace(true);
+
+    let mut texts = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        let span = token.span();
+        texts.push(source[span.byte_start_idx..span.byte_end_idx].to_string());
+    }
+    texts
+}
+
+#[test]
+fn test_preserve_whitespace_round_trip() {
+    let source = r#"/* header */
+#include <stdio.h>
+#define MAX 10
+
+static int counter = 0x10;  // counter
+
+int
+main(void)
+{
+	int x = counter + MAX;
+    return x >= 1 ? x : -x;
+}
+"#;
+
+    assert_eq!(token_texts(source).concat(), source);
+}
+
+#[test]
+fn test_preserve_whitespace_round_trip_crlf() {
+    let source = "int x;\r\n\r\n  x += 1; // c\r\n";
+    assert_eq!(token_texts(source).concat(), source);
+}
+
+#[test]
+fn test_preserve_whitespace_round_trip_form_feed_and_vertical_tab() {
+    let source = "int x;\n\x0c\n/* page 2 */\x0bint\x0cy; \x0b\n";
+    assert_eq!(token_texts(source).concat(), source);
+}
+
+#[test]
+fn test_form_feed_and_vertical_tab_are_whitespace_tokens() {
+    let mut lexer = Lexer::new("int\x0c\x0b x;");
+    lexer.preserve_whitespace(true);
+
+    let tokens: Vec<Token> = std::iter::from_fn(|| lexer.next_token()).collect();
+    assert_eq!(tokens.len(), 4);
+    match &tokens[1] {
+        Token::Whitespace(ws) => assert_eq!(ws.text, "\x0c\x0b "),
+        other => panic!("Expected Whitespace, got {:?}", other),
+    }
+    assert!(matches!(tokens[2], Token::Ident(_)));
+}
+
+#[test]
+fn test_preserve_whitespace_emits_whitespace_tokens() {
+    let mut lexer = Lexer::new("int  x;\n");
+    lexer.preserve_whitespace(true);
+
+    let tokens: Vec<Token> = std::iter::from_fn(|| lexer.next_token()).collect();
+    assert_eq!(tokens.len(), 5);
+    assert!(matches!(tokens[0], Token::Int(_)));
+    match &tokens[1] {
+        Token::Whitespace(ws) => assert_eq!(ws.text, "  "),
+        other => panic!("Expected Whitespace, got {:?}", other),
+    }
+    assert!(matches!(tokens[2], Token::Ident(_)));
+    assert!(matches!(tokens[3], Token::Semicolon(_)));
+    match &tokens[4] {
+        Token::Whitespace(ws) => assert_eq!(ws.text, "\n"),
+        other => panic!("Expected Whitespace, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_default_mode_has_no_whitespace_tokens() {
+    let mut lexer = Lexer::new("int  x;\n");
+    let tokens: Vec<Token> = std::iter::from_fn(|| lexer.next_token()).collect();
+    assert_eq!(tokens.len(), 3);
+    assert!(!tokens.iter().any(|t| matches!(t, Token::Whitespace(_))));
+}
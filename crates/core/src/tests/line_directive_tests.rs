@@ -0,0 +1,72 @@
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::ast::Item;
+use crate::token::*;
+use crate::diagnostics::{diagnose, DiagnosticConfig};
+
+#[test]
+fn test_lex_line_directive_with_file() {
+    let mut lexer = Lexer::new("#line 100 \"foo.c\"\n");
+    match lexer.next_token() {
+        Some(Token::Line(LineToken { line_number, file, .. })) => {
+            assert_eq!(line_number, 100);
+            assert_eq!(file.as_deref(), Some("foo.c"));
+        }
+        other => panic!("Expected Line token, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_lex_line_directive_without_file() {
+    let mut lexer = Lexer::new("#line 42\n");
+    match lexer.next_token() {
+        Some(Token::Line(LineToken { line_number, file, .. })) => {
+            assert_eq!(line_number, 42);
+            assert_eq!(file, None);
+        }
+        other => panic!("Expected Line token, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_line_directive_with_macro_is_not_line_token() {
+    // 行番号が数値でない場合は従来どおり生テキストとして残す
+    let mut lexer = Lexer::new("#line LINE_NO\n");
+    assert!(matches!(lexer.next_token(), Some(Token::Include(_))));
+}
+
+#[test]
+fn test_parse_line_directive_item() {
+    let mut parser = Parser::new(Lexer::new("#line 100 \"foo.c\"\nint counter;\n"));
+    let tu = parser.parse();
+
+    assert_eq!(tu.items.len(), 2);
+    match &tu.items[0] {
+        Item::LineDirective { line_number, file, text, .. } => {
+            assert_eq!(*line_number, 100);
+            assert_eq!(file.as_deref(), Some("foo.c"));
+            assert!(text.starts_with("#line 100"));
+        }
+        other => panic!("Expected LineDirective, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_diagnostic_after_line_directive_reports_logical_line() {
+    let source = "int first;\n#line 100 \"foo.c\"\nint counter;\nvoid v;\n";
+    let mut parser = Parser::new(Lexer::new(source));
+    let tu = parser.parse();
+
+    let config = DiagnosticConfig {
+        check_file_header: false,
+        ..Default::default()
+    };
+    let diags = diagnose(&tu, &config);
+    let void_var = diags.iter().find(|d| d.code.to_string() == "CGH101").expect("CGH101 expected");
+
+    // 物理行は 3 行目（0始まり）だが、#line 100 の次の次の行なので論理行は 101 行目（0始まりで 100）
+    assert_eq!(void_var.span.start_line, 3);
+    assert_eq!(tu.logical_position(void_var.span.start_line), (100, Some("foo.c")));
+    // #line より前の行はそのまま
+    assert_eq!(tu.logical_position(0), (0, None));
+}
@@ -1,23 +1,42 @@
 // Test modules organized by functionality
 
+mod span_tests;
 mod lexer_basic_tests;
+mod lexer_crlf_tests;
+mod lexer_bom_tests;
+mod lexer_whitespace_tests;
+mod lexer_borrowed_tests;
+mod lexer_performance_tests;
+mod token_index_tests;
+mod token_stream_tests;
 mod lexer_comment_tests;
 mod lexer_include_tests;
+mod line_directive_tests;
+mod error_directive_tests;
 mod lexer_define_tests;
 mod lexer_typedef_tests;
 mod lexer_keyword_tests;
+mod standard_tests;
 mod lexer_number_tests;
+mod lexer_string_tests;
 mod lexer_operator_tests;
 mod lexer_line_continuation_tests;
 mod lexer_error_tests;
 mod parser_tests;
 mod formatter_tests;
 mod formatter_indent_tests;
+mod formatter_item_tests;
+mod formatter_pointer_style_tests;
+mod formatter_include_sort_tests;
+mod formatter_operator_spacing_tests;
+mod formatter_blank_lines_tests;
 mod function_tests;
+mod attribute_tests;
 mod enum_tests;
 mod union_tests;
 mod extern_tests;
 mod ifdef_tests;
+mod symbol_tests;
 mod type_parser_tests;
 mod diagnostic_type_tests;
 mod diagnostic_macro_tests;
@@ -36,7 +55,73 @@ mod expression_binary_op_tests;
 mod expression_unary_tests;
 mod expression_cast_tests;
 mod expression_typedef_cast_tests;
+mod statement_pretty_tests;
+mod initializer_tests;
 mod type_table_tests;
 mod typedef_complex_tests;
 mod struct_cast_tests;
 mod scope_tests;
+mod analysis_tests;
+mod diagnostic_include_guard_tests;
+mod diagnostic_empty_loop_tests;
+#[cfg(feature = "serde")]
+mod serde_tests;
+mod diagnostic_function_length_tests;
+mod diagnostic_complexity_tests;
+mod diagnostic_nesting_depth_tests;
+mod diagnostic_const_pointer_tests;
+mod diagnostic_naming_tests;
+mod diagnostic_enum_tests;
+mod diagnostic_implicit_int_tests;
+mod diagnostic_empty_aggregate_tests;
+mod diagnostic_short_identifier_tests;
+mod diagnostic_dead_preprocessor_tests;
+mod diagnostic_macro_argument_tests;
+mod diagnostic_order_tests;
+mod diagnostic_include_order_tests;
+mod diagnostic_typedef_shadowing_tests;
+mod diagnostic_redundant_qualifier_tests;
+mod diagnostic_missing_prototype_tests;
+mod diagnostic_array_init_tests;
+mod diagnostic_plain_char_tests;
+mod diagnostic_declaration_order_tests;
+mod diagnostic_continuation_indent_tests;
+mod diagnostic_void_return_tests;
+mod diagnostic_unused_parameter_tests;
+mod diagnostic_unused_local_tests;
+mod diagnostic_use_before_declaration_tests;
+mod diagnostic_hex_unsigned_tests;
+mod diagnostic_octal_literal_tests;
+mod diagnostic_brace_style_tests;
+mod diagnostic_operator_spacing_tests;
+mod diagnostic_no_tabs_tests;
+mod diagnostic_packed_struct_tests;
+mod diagnostic_non_ascii_tests;
+mod diagnostic_config_builder_tests;
+mod editorconfig_tests;
+mod diagnostic_storage_class_tests;
+mod diagnostic_function_format_tests;
+
+use crate::diagnostics::{diagnose_with_source, Diagnostic, DiagnosticConfig, DiagnosticConfigBuilder};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+/// ソースを解析して診断し、診断コード `code` の診断だけを返す
+///
+/// デフォルト設定からファイルヘッダーのチェック（CGH001）を外し、`code` のチェックを有効にして診断する。
+/// 上限値や命名規則などの設定が必要なチェックは `diagnostics_with_config` を使う。
+fn diagnostics_with_code(source: &str, code: &str) -> Vec<Diagnostic> {
+    let builder = DiagnosticConfigBuilder::new().disable("CGH001").unwrap();
+    // 上限値を持つチェックと個別のフラグを持たないコードは enable できないため、設定を変えずに診断する
+    let builder = builder.clone().enable(code).unwrap_or(builder);
+    diagnostics_with_config(source, &builder.build(), code)
+}
+
+/// ソースを `config` で診断し、診断コード `code` の診断だけを返す
+fn diagnostics_with_config(source: &str, config: &DiagnosticConfig, code: &str) -> Vec<Diagnostic> {
+    let tu = Parser::new(Lexer::new(source)).parse();
+    diagnose_with_source(&tu, config, source)
+        .into_iter()
+        .filter(|d| d.code.to_string() == code)
+        .collect()
+}
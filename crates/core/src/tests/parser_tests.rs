@@ -312,3 +312,23 @@ fn test_parser_includes_produced_items() {
             _ => panic!("Expected StructDecl item"),
         }
     }
+
+#[test]
+fn test_parser_function_like_define() {
+    let s = "#define MAX(a, b) ((a)>(b)?(a):(b))\n";
+    let lx = Lexer::new(s);
+    let mut parser = Parser::new(lx);
+    let tu = parser.parse();
+
+    assert_eq!(tu.items.len(), 1);
+
+    match &tu.items[0] {
+        Item::Define { macro_name, params, macro_value, .. } => {
+            assert_eq!(macro_name, "MAX");
+            assert_eq!(params.as_deref(), Some(&["a".to_string(), "b".to_string()][..]));
+            assert_eq!(macro_value, "((a)>(b)?(a):(b))");
+        }
+        _ => panic!("Expected Define item"),
+    }
+    assert!(parser.defined_macros().contains_key("MAX"));
+}
@@ -0,0 +1,66 @@
+use crate::ast::TranslationUnit;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::span::Span;
+
+fn parse(source: &str) -> TranslationUnit {
+    let lexer = Lexer::new(source);
+    let mut parser = Parser::new(lexer);
+    parser.parse()
+}
+
+#[test]
+fn test_translation_unit_json_round_trip() {
+    let source = r#"// ファイルヘッダー
+#include <stdio.h>
+#define SIZE (4 * 2)
+
+#ifdef DEBUG
+int debug_flag;
+#endif
+
+typedef struct point {
+    int x;
+    int y;
+} Point;
+
+enum color { RED, GREEN = 2, BLUE };
+
+static const unsigned int *table;
+
+int add(int a, int b) {
+    int sum = a + b;
+    while (sum > 10) {
+        sum -= 1;
+    }
+    do {
+        sum++;
+    } while (sum < 0);
+    return sum;
+}
+"#;
+
+    let tu = parse(source);
+    let json = serde_json::to_string(&tu).unwrap();
+    let restored: TranslationUnit = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored, tu);
+}
+
+#[test]
+fn test_span_serializes_compactly() {
+    let span = Span {
+        start_line: 1,
+        start_column: 2,
+        end_line: 3,
+        end_column: 4,
+        byte_start_idx: 5,
+        byte_end_idx: 6,
+    };
+
+    let json = serde_json::to_string(&span).unwrap();
+    assert_eq!(json, "[1,2,3,4,5,6]");
+
+    let restored: Span = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, span);
+}
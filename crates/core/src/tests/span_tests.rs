@@ -0,0 +1,96 @@
+use crate::span::Span;
+
+fn span(start: (usize, usize), end: (usize, usize), bytes: (usize, usize)) -> Span {
+    Span {
+        start_line: start.0,
+        start_column: start.1,
+        end_line: end.0,
+        end_column: end.1,
+        byte_start_idx: bytes.0,
+        byte_end_idx: bytes.1,
+    }
+}
+
+#[test]
+fn test_span_contains_single_line_boundaries() {
+    // "int value;" の "value"（0行目 4〜9列）
+    let s = span((0, 4), (0, 9), (4, 9));
+
+    assert!(!s.contains(0, 3));
+    assert!(s.contains(0, 4));  // 開始位置は含む
+    assert!(s.contains(0, 8));
+    assert!(!s.contains(0, 9));  // 終了位置は含まない
+    assert!(!s.contains(1, 4));
+}
+
+#[test]
+fn test_span_contains_multi_line() {
+    let s = span((1, 10), (3, 2), (20, 45));
+
+    assert!(!s.contains(1, 9));
+    assert!(s.contains(1, 10));
+    assert!(s.contains(2, 0));  // 中間の行は列に関係なく含む
+    assert!(s.contains(2, 100));
+    assert!(s.contains(3, 1));
+    assert!(!s.contains(3, 2));
+    assert!(!s.contains(0, 50));
+}
+
+#[test]
+fn test_span_len_bytes() {
+    assert_eq!(span((0, 4), (0, 9), (4, 9)).len_bytes(), 5);
+    assert_eq!(Span::new(0, 0, 0, 0).len_bytes(), 0);
+}
+
+#[test]
+fn test_span_text() {
+    let source = "int value;\nchar *名前;\n";
+    assert_eq!(span((0, 4), (0, 9), (4, 9)).text(source), "value");
+
+    let start = source.find("名前").unwrap();
+    let name = span((1, 6), (1, 8), (start, start + "名前".len()));
+    assert_eq!(name.text(source), "名前");
+
+    // 範囲外や文字境界でない場合は空文字列
+    assert_eq!(span((0, 0), (0, 0), (100, 120)).text(source), "");
+    assert_eq!(span((1, 6), (1, 7), (start, start + 1)).text(source), "");
+}
+
+#[test]
+fn test_span_merge_adjacent() {
+    // "a + b" の "a" と "+ b"
+    let left = span((0, 0), (0, 1), (0, 1));
+    let right = span((0, 1), (0, 5), (1, 5));
+
+    let merged = left.merge(&right);
+    assert_eq!(merged, span((0, 0), (0, 5), (0, 5)));
+    // 引数の順序によらず同じ結果になる
+    assert_eq!(right.merge(&left), merged);
+}
+
+#[test]
+fn test_span_merge_overlapping() {
+    let first = span((0, 4), (2, 1), (4, 30));
+    let second = span((1, 0), (3, 6), (12, 44));
+
+    assert_eq!(first.merge(&second), span((0, 4), (3, 6), (4, 44)));
+}
+
+#[test]
+fn test_span_merge_contained() {
+    let outer = span((0, 0), (5, 0), (0, 80));
+    let inner = span((2, 4), (2, 9), (30, 35));
+
+    assert_eq!(outer.merge(&inner), outer);
+    assert_eq!(inner.merge(&outer), outer);
+}
+
+#[test]
+fn test_span_lsp_range_round_trip() {
+    let original = Span::new(3, 4, 5, 12);
+    let range = original.to_lsp_range();
+
+    // LSP と同じく 0 始まりのまま変換される
+    assert_eq!(range, (3, 4, 5, 12));
+    assert_eq!(Span::from_lsp_range(range), original);
+}
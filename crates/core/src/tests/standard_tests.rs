@@ -0,0 +1,78 @@
+use crate::analysis::analyze_with_preprocessor;
+use crate::ast::Item;
+use crate::config::{CStandard, PreprocessorConfig};
+use crate::diagnostics::{DiagnosticConfig, DiagnosticSeverity};
+use crate::lexer::Lexer;
+use crate::token::*;
+
+fn analyze_with_standard(source: &str, standard: Option<CStandard>) -> crate::analysis::AnalysisResult {
+    let preprocessor_config = PreprocessorConfig {
+        standard,
+        ..Default::default()
+    };
+    analyze_with_preprocessor(source, &DiagnosticConfig::default(), preprocessor_config)
+}
+
+fn standard_diagnostic_count(result: &crate::analysis::AnalysisResult) -> usize {
+    result.parse_diagnostics.iter().filter(|d| d.code.to_string() == "CGH050").count()
+}
+
+#[test]
+fn test_binary_literal_is_single_token() {
+    let mut lx = Lexer::new("0b101u");
+    match lx.next_token() {
+        Some(Token::NumberLiteral(NumberLiteralToken { value, .. })) => assert_eq!(value, "0b101u"),
+        other => panic!("expected number literal, got {:?}", other),
+    }
+    assert!(lx.next_token().is_none());
+}
+
+#[test]
+fn test_binary_literal_under_c89_is_diagnosed() {
+    let source = "int x = 0b101;\n";
+    let result = analyze_with_standard(source, Some(CStandard::C89));
+
+    let diags: Vec<_> = result.parse_diagnostics.iter().filter(|d| d.code.to_string() == "CGH050").collect();
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].severity, DiagnosticSeverity::Error);
+    assert!(diags[0].message.contains("0b101"));
+    assert_eq!(source[diags[0].span.byte_start_idx..diags[0].span.byte_end_idx].trim(), "0b101");
+}
+
+#[test]
+fn test_binary_literal_under_c23_parses() {
+    let result = analyze_with_standard("int x = 0b101;\n", Some(CStandard::C23));
+
+    assert_eq!(standard_diagnostic_count(&result), 0);
+    assert!(matches!(&result.translation_unit.items[0], Item::VarDecl { var_name, .. } if var_name == "x"));
+}
+
+#[test]
+fn test_binary_literal_without_standard_is_accepted() {
+    let result = analyze_with_standard("int x = 0b101;\n", None);
+    assert_eq!(standard_diagnostic_count(&result), 0);
+}
+
+#[test]
+fn test_binary_literal_in_function_body_under_c99_is_diagnosed() {
+    let result = analyze_with_standard("int f(void)\n{\n    return 0b11 + 0b1;\n}\n", Some(CStandard::C99));
+    assert_eq!(standard_diagnostic_count(&result), 2);
+}
+
+#[test]
+fn test_c89_disables_c99_keywords() {
+    let mut lx = Lexer::new("_Bool restrict;\n");
+    lx.set_standard(CStandard::C89);
+
+    assert!(matches!(lx.next_token(), Some(Token::Ident(_))));
+    assert!(matches!(lx.next_token(), Some(Token::Ident(_))));
+}
+
+#[test]
+fn test_c99_keeps_bool_and_disables_c11_keywords() {
+    let mut lx = Lexer::new("_Bool _Atomic;\n");
+    lx.set_standard(CStandard::C99);
+
+    assert!(matches!(lx.next_token(), Some(Token::Bool(_))));
+    assert!(matches!(lx.next_token(), Some(Token::Ident(_))));
+}
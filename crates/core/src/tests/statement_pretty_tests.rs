@@ -0,0 +1,41 @@
+use crate::ast::{Item, Statement};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn parse_body(input: &str) -> Vec<Statement> {
+    let tu = Parser::new(Lexer::new(input)).parse();
+    match &tu.items[0] {
+        Item::FunctionDecl { body: Some(body), .. } => body.clone(),
+        other => panic!("Expected function definition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_pretty_if_else() {
+    let body = parse_body("int f(int x) {\n    if (x > 0) {\n        return 1;\n    } else if (x < 0) {\n        return -1;\n    } else {\n        x = x * (2 + 3);\n    }\n    return 0;\n}\n");
+
+    assert_eq!(body.len(), 2);
+    assert_eq!(
+        body[0].pretty(),
+        "if (x > 0) { return 1; } else if (x < 0) { return -1; } else { x = x * (2 + 3); }"
+    );
+    assert_eq!(body[1].pretty(), "return 0;");
+}
+
+#[test]
+fn test_pretty_for_loop() {
+    let body = parse_body("void f(int n) {\n    int sum = 0;\n    for (int i = 0; i < n; i++) {\n        sum += i;\n    }\n    for (;;) {\n    }\n}\n");
+
+    assert_eq!(body.len(), 3);
+    assert_eq!(body[0].pretty(), "int sum = 0;");
+    assert_eq!(body[1].pretty(), "for (int i = 0; i < n; i++) { sum += i; }");
+    assert_eq!(body[2].pretty(), "for (;;) {}");
+}
+
+#[test]
+fn test_pretty_while_and_do_while() {
+    let body = parse_body("void f(int n) {\n    while (n) n--;\n    do {\n        n++;\n    } while (!n);\n}\n");
+
+    assert_eq!(body[0].pretty(), "while (n) { n--; }");
+    assert_eq!(body[1].pretty(), "do { n++; } while (!n);");
+}
@@ -0,0 +1,63 @@
+use crate::ast::SymbolKind;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+#[test]
+fn test_symbols_mixed_file() {
+    let input = r#"#define MAX_SIZE 16
+typedef unsigned int Count;
+typedef struct {
+    int x;
+} Point;
+struct Node {
+    int value;
+};
+union Data {
+    int i;
+    float f;
+} data;
+enum Color {
+    RED,
+    GREEN
+};
+int counter = 0;
+void reset(void);
+#ifdef DEBUG
+static void dump(void) {
+}
+#endif
+"#;
+    let tu = Parser::new(Lexer::new(input)).parse();
+    let symbols: Vec<(String, SymbolKind)> = tu.symbols().into_iter().map(|s| (s.name, s.kind)).collect();
+
+    let expected = vec![
+        ("MAX_SIZE", SymbolKind::Macro),
+        ("Count", SymbolKind::Typedef),
+        ("Point", SymbolKind::Typedef),
+        ("Node", SymbolKind::Struct),
+        ("Data", SymbolKind::Union),
+        ("data", SymbolKind::Variable),
+        ("Color", SymbolKind::Enum),
+        ("RED", SymbolKind::EnumVariant),
+        ("GREEN", SymbolKind::EnumVariant),
+        ("counter", SymbolKind::Variable),
+        ("reset", SymbolKind::Function),
+        ("dump", SymbolKind::Function),
+    ];
+    let expected: Vec<(String, SymbolKind)> = expected.into_iter().map(|(name, kind)| (name.to_string(), kind)).collect();
+    assert_eq!(symbols, expected);
+    assert_eq!(symbols.len(), 12);
+}
+
+#[test]
+fn test_symbol_spans() {
+    let input = "enum Mode {\n    MODE_A,\n    MODE_B\n};\nint value;\n";
+    let tu = Parser::new(Lexer::new(input)).parse();
+    let symbols = tu.symbols();
+
+    let mode_b = symbols.iter().find(|s| s.name == "MODE_B").unwrap();
+    assert_eq!(mode_b.span.start_line, 2);
+    let value = symbols.iter().find(|s| s.name == "value").unwrap();
+    assert_eq!(value.kind, SymbolKind::Variable);
+    assert_eq!(value.span.start_line, 4);
+}
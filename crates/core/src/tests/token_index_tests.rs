@@ -0,0 +1,52 @@
+use crate::lexer::Lexer;
+use crate::token::Token;
+use crate::token_index::TokenIndex;
+
+const SOURCE: &str = "int  value;\n  count = value + 1; // total\n";
+
+#[test]
+fn test_token_at_identifier() {
+    let index = TokenIndex::new(SOURCE);
+
+    // "value" は 0行目 5〜9列
+    for column in 5..10 {
+        match index.token_at(0, column) {
+            Some(Token::Ident(ident)) => assert_eq!(&*ident.name, "value"),
+            other => panic!("Expected Ident at column {}, got {:?}", column, other),
+        }
+    }
+
+    match index.token_at(1, 2) {
+        Some(Token::Ident(ident)) => assert_eq!(&*ident.name, "count"),
+        other => panic!("Expected Ident, got {:?}", other),
+    }
+    assert!(matches!(index.token_at(0, 0), Some(Token::Int(_))));
+    assert!(matches!(index.token_at(1, 21), Some(Token::LineComment(_))));
+}
+
+#[test]
+fn test_token_at_whitespace_returns_none() {
+    let index = TokenIndex::new(SOURCE);
+
+    assert!(index.token_at(0, 3).is_none());  // "int" と "value" の間
+    assert!(index.token_at(0, 4).is_none());
+    assert!(index.token_at(1, 0).is_none());  // 行頭のインデント
+    assert!(index.token_at(5, 0).is_none());  // 範囲外の行
+    assert!(index.token_at(0, 100).is_none());  // 範囲外の列
+}
+
+#[test]
+fn test_token_at_boundaries() {
+    let index = TokenIndex::new(SOURCE);
+
+    // 終了位置は含まない: "value" の直後はセミコロン
+    assert!(matches!(index.token_at(0, 10), Some(Token::Semicolon(_))));
+    // "int" の終了位置（3列目）は空白
+    assert!(index.token_at(0, 3).is_none());
+}
+
+#[test]
+fn test_lexer_token_at() {
+    assert!(matches!(Lexer::token_at(SOURCE, 1, 8), Some(Token::Equal(_))));
+    assert!(Lexer::token_at(SOURCE, 1, 7).is_none());
+}
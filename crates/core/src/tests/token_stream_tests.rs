@@ -0,0 +1,100 @@
+use crate::lexer::Lexer;
+use crate::token::Token;
+use crate::token_stream::TokenStream;
+
+/// `( 型名 )` の形のキャストを試しに読む（型名でなければ失敗）
+fn parse_cast_type(stream: &mut TokenStream) -> Option<&'static str> {
+    if !matches!(stream.next_token()?, Token::LeftParen(_)) {
+        return None;
+    }
+    let type_name = match stream.next_token()? {
+        Token::Int(_) => "int",
+        Token::Char(_) => "char",
+        _ => return None,
+    };
+    matches!(stream.next_token()?, Token::RightParen(_)).then_some(type_name)
+}
+
+#[test]
+fn test_failed_speculative_parse_restores_position() {
+    let mut lexer = Lexer::new("(a) + b");
+    let mut stream = TokenStream::new(&mut lexer);
+    let before = stream.peek_token().cloned();
+
+    assert_eq!(stream.speculate(parse_cast_type), None);
+
+    // 括弧から読み直せる
+    assert_eq!(stream.peek_token().cloned(), before);
+    assert!(matches!(stream.next_token(), Some(Token::LeftParen(_))));
+    assert!(matches!(stream.next_token(), Some(Token::Ident(ident)) if &*ident.name == "a"));
+}
+
+#[test]
+fn test_successful_speculative_parse_keeps_position() {
+    let mut lexer = Lexer::new("(int) x");
+    let mut stream = TokenStream::new(&mut lexer);
+
+    assert_eq!(stream.speculate(parse_cast_type), Some("int"));
+    assert!(matches!(stream.next_token(), Some(Token::Ident(ident)) if &*ident.name == "x"));
+    assert_eq!(stream.next_token(), None);
+}
+
+#[test]
+fn test_rewind_to_earlier_checkpoint() {
+    let mut lexer = Lexer::new("a = b + c;");
+    let mut stream = TokenStream::new(&mut lexer);
+    stream.next_token();
+    let mark = stream.checkpoint();
+    let expected: Vec<Token> = std::iter::from_fn(|| stream.next_token()).collect();
+
+    stream.rewind(mark);
+    let replayed: Vec<Token> = std::iter::from_fn(|| stream.next_token()).collect();
+    assert_eq!(replayed, expected);
+    assert_eq!(replayed.len(), 5);
+}
+
+#[test]
+fn test_consumed_tokens_are_dropped_without_checkpoint() {
+    let mut lexer = Lexer::new("a = b + c;");
+    let mut stream = TokenStream::new(&mut lexer);
+    for _ in 0..4 {
+        stream.next_token();
+    }
+    // 巻き戻す先がないので読み終えたトークンは残らない
+    assert_eq!(stream.buffered_len(), 0);
+    stream.peek_token();
+    assert_eq!(stream.buffered_len(), 1);
+}
+
+#[test]
+fn test_consumed_tokens_are_kept_until_checkpoint_released() {
+    let mut lexer = Lexer::new("a = b + c;");
+    let mut stream = TokenStream::new(&mut lexer);
+    let outer = stream.checkpoint();
+    stream.next_token();
+    let inner = stream.checkpoint();
+    stream.next_token();
+    stream.next_token();
+
+    // 内側を確定しても外側の位置へ戻れるようにトークンを残す
+    stream.commit(inner);
+    assert_eq!(stream.buffered_len(), 3);
+
+    stream.commit(outer);
+    assert_eq!(stream.buffered_len(), 0);
+    assert!(matches!(stream.next_token(), Some(Token::Plus(_))));
+}
+
+#[test]
+fn test_finish_returns_unread_tokens_to_lexer() {
+    let mut lexer = Lexer::new("(a) + b");
+    let mut stream = TokenStream::new(&mut lexer);
+    assert_eq!(stream.speculate(parse_cast_type), None);
+    stream.finish();
+
+    // 先読みしたトークンも含め、字句解析器から同じ順に読める
+    assert!(matches!(lexer.next_token(), Some(Token::LeftParen(_))));
+    assert!(matches!(lexer.next_token(), Some(Token::Ident(ident)) if &*ident.name == "a"));
+    assert!(matches!(lexer.next_token(), Some(Token::RightParen(_))));
+    assert!(matches!(lexer.next_token(), Some(Token::Plus(_))));
+}
@@ -95,3 +95,100 @@ fn test_var_decl_with_pointer_type() {
         _ => panic!("Expected VarDecl"),
     }
 }
+
+#[test]
+fn test_parse_bool_var_decl() {
+    let input = "_Bool flag;\nint next;\n";
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer);
+    let tu = parser.parse();
+
+    assert_eq!(tu.items.len(), 2);
+    match &tu.items[0] {
+        Item::VarDecl { var_name, var_type, .. } => {
+            assert_eq!(var_name, "flag");
+            let ty = var_type.as_ref().expect("Should have type info");
+            assert_eq!(ty.base_type, BaseType::Bool);
+            assert_eq!(ty.to_string(), "_Bool");
+        }
+        other => panic!("Expected VarDecl, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_complex_and_imaginary() {
+    let lexer = Lexer::new("_Complex");
+    let mut parser = Parser::new(lexer);
+    assert_eq!(parser.parse_type().expect("Should parse _Complex").base_type, BaseType::Complex);
+
+    let lexer = Lexer::new("const _Imaginary *");
+    let mut parser = Parser::new(lexer);
+    let ty = parser.parse_type().expect("Should parse _Imaginary");
+    assert_eq!(ty.base_type, BaseType::Imaginary);
+    assert_eq!(ty.pointer_level(), 1);
+}
+
+fn parse_base_type(input: &str) -> BaseType {
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer);
+    parser.parse_type().expect("Should parse type").base_type
+}
+
+#[test]
+fn test_parse_multi_token_integer_types() {
+    assert_eq!(parse_base_type("unsigned long long"), BaseType::UnsignedLongLong);
+    assert_eq!(parse_base_type("unsigned long long int"), BaseType::UnsignedLongLong);
+    assert_eq!(parse_base_type("long unsigned int"), BaseType::UnsignedLong);
+    assert_eq!(parse_base_type("long long"), BaseType::LongLong);
+    assert_eq!(parse_base_type("short int"), BaseType::Short);
+    assert_eq!(parse_base_type("unsigned short"), BaseType::UnsignedShort);
+    assert_eq!(parse_base_type("unsigned int"), BaseType::Unsigned);
+    assert_eq!(parse_base_type("signed int"), BaseType::Int);
+}
+
+#[test]
+fn test_parse_long_double_and_signed_char() {
+    assert_eq!(parse_base_type("long double"), BaseType::LongDouble);
+    assert_eq!(parse_base_type("signed char"), BaseType::SignedChar);
+    assert_eq!(parse_base_type("unsigned char"), BaseType::UnsignedChar);
+    assert_eq!(parse_base_type("char"), BaseType::Char);
+}
+
+#[test]
+fn test_multi_token_type_keeps_pointer_and_qualifiers() {
+    let lexer = Lexer::new("unsigned const long long *");
+    let mut parser = Parser::new(lexer);
+    let ty = parser.parse_type().expect("Should parse type");
+    assert_eq!(ty.base_type, BaseType::UnsignedLongLong);
+    assert!(ty.has_base_qualifier(TypeQualifier::Const));
+    assert_eq!(ty.pointer_level(), 1);
+}
+
+#[test]
+fn test_var_decl_with_multi_token_type() {
+    let input = "unsigned long long counter;\n";
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer);
+    let tu = parser.parse();
+
+    match &tu.items[0] {
+        Item::VarDecl { var_name, var_type, .. } => {
+            assert_eq!(var_name, "counter");
+            let ty = var_type.as_ref().expect("Should have type info");
+            assert_eq!(ty.base_type, BaseType::UnsignedLongLong);
+            assert_eq!(ty.to_string(), "unsigned long long");
+        }
+        other => panic!("Expected VarDecl, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_double_pointer_declarator() {
+    let mut parser = Parser::new(Lexer::new("int ** const *table"));
+
+    let (ty, name) = parser.parse_type_and_declarator().expect("Should parse declarator");
+
+    assert_eq!(name, "table");
+    assert_eq!(ty.pointer_level(), 3);
+    assert!(ty.pointer_layers[1].qualifiers.contains(&TypeQualifier::Const));
+}
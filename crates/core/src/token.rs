@@ -1,397 +1,543 @@
 use crate::span::Span;
+use std::sync::Arc;
 
 // 各トークン種類の構造体
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BlockCommentToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IncludeToken {
     pub span: Span,
     pub filename: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DefineToken {
     pub span: Span,
     pub macro_name: String,
+    pub params: Option<Vec<String>>,  // 関数形式マクロの引数（オブジェクト形式マクロは None）
     pub macro_value: String,
 }
 
-#[derive(Debug, Clone)]
+// #error / #warning ディレクティブ（字句解析エラーの ErrorToken とは別物）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErrorDirectiveToken {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WarningDirectiveToken {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LineToken {
+    pub span: Span,
+    pub line_number: usize,  // 次の行の論理行番号（1始まり）
+    pub file: Option<String>,  // 指定された論理ファイル名
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SemicolonToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EqualToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AsteriskToken {
     pub span: Span,
 }
 
 // 演算子トークン
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PlusToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MinusToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SlashToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PercentToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EqualEqualToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct NotEqualToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LessThanToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LessThanOrEqualToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GreaterThanToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GreaterThanOrEqualToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AmpersandToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AmpersandAmpersandToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PipeToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PipePipeToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CaretToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TildeToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExclamationToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LeftShiftToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RightShiftToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LeftBracketToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RightBracketToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct QuestionToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ColonToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CommaToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DotToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ArrowToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PlusPlusToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MinusMinusToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+// 複合代入演算子トークン
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AsteriskEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlusEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MinusEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PercentEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SlashEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AmpersandEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PipeEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CaretEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LeftShiftEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RightShiftEqualToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ErrorToken {
     pub span: Span,
     pub message: String,
 }
 
-#[derive(Debug, Clone)]
+// 空白・改行の連続（Lexer::preserve_whitespace が有効な場合のみ出力される）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WhitespaceToken {
+    pub span: Span,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IdentToken {
     pub span: Span,
-    pub name: String,
+    pub name: Arc<str>,  // 同じ Lexer 内の同名の識別子は同じ文字列を共有する
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AutoToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RegisterToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct StaticToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExternToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TypedefToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ConstToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct VolatileToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RestrictToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AtomicToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IntToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CharToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FloatToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DoubleToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct VoidToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LongToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ShortToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SignedToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UnsignedToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoolToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ComplexToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImaginaryToken {
+    pub span: Span,
+}
+
+// 関数指定子・スレッド記憶域指定子
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InlineToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NoreturnToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThreadLocalToken {
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct StructToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EnumToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UnionToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LeftBraceToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RightBraceToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LeftParenToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RightParenToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ReturnToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IfKeywordToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ElseKeywordToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct WhileToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ForToken {
     pub span: Span,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DoToken {
+    pub span: Span,
+}
+
 // 条件コンパイルディレクティブトークン
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IfdefToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IfndefToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IfToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ElifToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ElseToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EndifToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LineCommentToken {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct NumberLiteralToken {
     pub span: Span,
     pub value: String,  // "123", "0x1A", "0755" など元の文字列表現
 }
 
-#[derive(Debug, Clone)]
+impl NumberLiteralToken {
+    /// 先頭の 0 に続けて数字がある 8進数リテラルかどうか（"010" など。"0" や "0x10" は false）
+    pub fn is_octal(&self) -> bool {
+        let mut chars = self.value.chars();
+        chars.next() == Some('0') && chars.next().is_some_and(|c| c.is_ascii_digit())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FloatLiteralToken {
     pub span: Span,
     pub value: String,  // "1.5", "3.14f", "1e10", "2.5e-3L" など元の文字列表現
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CharLiteralToken {
+    pub span: Span,
+    pub value: String,  // 引用符を含む元の表記（'a' や '\n' など）
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StringLiteralToken {
+    pub span: Span,
+    pub value: String,  // 引用符を含む元の文字列表現（"hello\n" など）
+}
+
+/// 属性（`__attribute__((...))` または `[[...]]`）をまとめた1つのトークン
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributeToken {
+    pub span: Span,
+    pub attributes: Vec<String>,  // 括弧の中の属性をカンマで分けたもの（"packed", "aligned(4)" など）
+}
+
 // トークンの enum
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Token {
     BlockComment(BlockCommentToken),
     LineComment(LineCommentToken),
     Include(IncludeToken),
     Define(DefineToken),
+    Line(LineToken),
+    ErrorDirective(ErrorDirectiveToken),
+    WarningDirective(WarningDirectiveToken),
     Ifdef(IfdefToken),
     Ifndef(IfndefToken),
     If(IfToken),
@@ -403,6 +549,9 @@ pub enum Token {
     Asterisk(AsteriskToken),
     NumberLiteral(NumberLiteralToken),
     FloatLiteral(FloatLiteralToken),
+    StringLiteral(StringLiteralToken),
+    CharLiteral(CharLiteralToken),
+    Attribute(AttributeToken),
     
     // 演算子
     Plus(PlusToken),
@@ -433,6 +582,16 @@ pub enum Token {
     Arrow(ArrowToken),
     PlusPlus(PlusPlusToken),
     MinusMinus(MinusMinusToken),
+    AsteriskEqual(AsteriskEqualToken),
+    PlusEqual(PlusEqualToken),
+    MinusEqual(MinusEqualToken),
+    PercentEqual(PercentEqualToken),
+    SlashEqual(SlashEqualToken),
+    AmpersandEqual(AmpersandEqualToken),
+    PipeEqual(PipeEqualToken),
+    CaretEqual(CaretEqualToken),
+    LeftShiftEqual(LeftShiftEqualToken),
+    RightShiftEqual(RightShiftEqualToken),
     
     Ident(IdentToken),
     Auto(AutoToken),
@@ -453,6 +612,12 @@ pub enum Token {
     Short(ShortToken),
     Signed(SignedToken),
     Unsigned(UnsignedToken),
+    Bool(BoolToken),
+    Complex(ComplexToken),
+    Imaginary(ImaginaryToken),
+    Inline(InlineToken),
+    Noreturn(NoreturnToken),
+    ThreadLocal(ThreadLocalToken),
     Struct(StructToken),
     Enum(EnumToken),
     Union(UnionToken),
@@ -465,88 +630,533 @@ pub enum Token {
     ElseKeyword(ElseKeywordToken),
     While(WhileToken),
     For(ForToken),
+    Do(DoToken),
     Error(ErrorToken),
+    Whitespace(WhitespaceToken),
+}
+
+// トークンの種類（ペイロードを持たない）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    BlockComment,
+    LineComment,
+    Include,
+    Define,
+    Line,
+    ErrorDirective,
+    WarningDirective,
+    Ifdef,
+    Ifndef,
+    If,
+    Elif,
+    Else,
+    Endif,
+    Semicolon,
+    Equal,
+    Asterisk,
+    NumberLiteral,
+    FloatLiteral,
+    StringLiteral,
+    CharLiteral,
+    Attribute,
+    Plus,
+    Minus,
+    Slash,
+    Percent,
+    EqualEqual,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    Ampersand,
+    AmpersandAmpersand,
+    Pipe,
+    PipePipe,
+    Caret,
+    Tilde,
+    Exclamation,
+    LeftShift,
+    RightShift,
+    LeftBracket,
+    RightBracket,
+    Question,
+    Colon,
+    Comma,
+    Dot,
+    Arrow,
+    PlusPlus,
+    MinusMinus,
+    AsteriskEqual,
+    PlusEqual,
+    MinusEqual,
+    PercentEqual,
+    SlashEqual,
+    AmpersandEqual,
+    PipeEqual,
+    CaretEqual,
+    LeftShiftEqual,
+    RightShiftEqual,
+    Ident,
+    Auto,
+    Register,
+    Static,
+    Extern,
+    Typedef,
+    Const,
+    Volatile,
+    Restrict,
+    Atomic,
+    Int,
+    Char,
+    Float,
+    Double,
+    Void,
+    Long,
+    Short,
+    Signed,
+    Unsigned,
+    Bool,
+    Complex,
+    Imaginary,
+    Inline,
+    Noreturn,
+    ThreadLocal,
+    Struct,
+    Enum,
+    Union,
+    LeftBrace,
+    RightBrace,
+    LeftParen,
+    RightParen,
+    Return,
+    IfKeyword,
+    ElseKeyword,
+    While,
+    For,
+    Do,
+    Error,
+    Whitespace,
 }
 
 impl Token {
+    /// トークンの種類を取得
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            Token::BlockComment(_) => TokenKind::BlockComment,
+            Token::LineComment(_) => TokenKind::LineComment,
+            Token::Include(_) => TokenKind::Include,
+            Token::Define(_) => TokenKind::Define,
+            Token::Line(_) => TokenKind::Line,
+            Token::ErrorDirective(_) => TokenKind::ErrorDirective,
+            Token::WarningDirective(_) => TokenKind::WarningDirective,
+            Token::Ifdef(_) => TokenKind::Ifdef,
+            Token::Ifndef(_) => TokenKind::Ifndef,
+            Token::If(_) => TokenKind::If,
+            Token::Elif(_) => TokenKind::Elif,
+            Token::Else(_) => TokenKind::Else,
+            Token::Endif(_) => TokenKind::Endif,
+            Token::Semicolon(_) => TokenKind::Semicolon,
+            Token::Equal(_) => TokenKind::Equal,
+            Token::Asterisk(_) => TokenKind::Asterisk,
+            Token::NumberLiteral(_) => TokenKind::NumberLiteral,
+            Token::FloatLiteral(_) => TokenKind::FloatLiteral,
+            Token::StringLiteral(_) => TokenKind::StringLiteral,
+            Token::CharLiteral(_) => TokenKind::CharLiteral,
+            Token::Attribute(_) => TokenKind::Attribute,
+            Token::Plus(_) => TokenKind::Plus,
+            Token::Minus(_) => TokenKind::Minus,
+            Token::Slash(_) => TokenKind::Slash,
+            Token::Percent(_) => TokenKind::Percent,
+            Token::EqualEqual(_) => TokenKind::EqualEqual,
+            Token::NotEqual(_) => TokenKind::NotEqual,
+            Token::LessThan(_) => TokenKind::LessThan,
+            Token::LessThanOrEqual(_) => TokenKind::LessThanOrEqual,
+            Token::GreaterThan(_) => TokenKind::GreaterThan,
+            Token::GreaterThanOrEqual(_) => TokenKind::GreaterThanOrEqual,
+            Token::Ampersand(_) => TokenKind::Ampersand,
+            Token::AmpersandAmpersand(_) => TokenKind::AmpersandAmpersand,
+            Token::Pipe(_) => TokenKind::Pipe,
+            Token::PipePipe(_) => TokenKind::PipePipe,
+            Token::Caret(_) => TokenKind::Caret,
+            Token::Tilde(_) => TokenKind::Tilde,
+            Token::Exclamation(_) => TokenKind::Exclamation,
+            Token::LeftShift(_) => TokenKind::LeftShift,
+            Token::RightShift(_) => TokenKind::RightShift,
+            Token::LeftBracket(_) => TokenKind::LeftBracket,
+            Token::RightBracket(_) => TokenKind::RightBracket,
+            Token::Question(_) => TokenKind::Question,
+            Token::Colon(_) => TokenKind::Colon,
+            Token::Comma(_) => TokenKind::Comma,
+            Token::Dot(_) => TokenKind::Dot,
+            Token::Arrow(_) => TokenKind::Arrow,
+            Token::PlusPlus(_) => TokenKind::PlusPlus,
+            Token::MinusMinus(_) => TokenKind::MinusMinus,
+            Token::AsteriskEqual(_) => TokenKind::AsteriskEqual,
+            Token::PlusEqual(_) => TokenKind::PlusEqual,
+            Token::MinusEqual(_) => TokenKind::MinusEqual,
+            Token::PercentEqual(_) => TokenKind::PercentEqual,
+            Token::SlashEqual(_) => TokenKind::SlashEqual,
+            Token::AmpersandEqual(_) => TokenKind::AmpersandEqual,
+            Token::PipeEqual(_) => TokenKind::PipeEqual,
+            Token::CaretEqual(_) => TokenKind::CaretEqual,
+            Token::LeftShiftEqual(_) => TokenKind::LeftShiftEqual,
+            Token::RightShiftEqual(_) => TokenKind::RightShiftEqual,
+            Token::Ident(_) => TokenKind::Ident,
+            Token::Auto(_) => TokenKind::Auto,
+            Token::Register(_) => TokenKind::Register,
+            Token::Static(_) => TokenKind::Static,
+            Token::Extern(_) => TokenKind::Extern,
+            Token::Typedef(_) => TokenKind::Typedef,
+            Token::Const(_) => TokenKind::Const,
+            Token::Volatile(_) => TokenKind::Volatile,
+            Token::Restrict(_) => TokenKind::Restrict,
+            Token::Atomic(_) => TokenKind::Atomic,
+            Token::Int(_) => TokenKind::Int,
+            Token::Char(_) => TokenKind::Char,
+            Token::Float(_) => TokenKind::Float,
+            Token::Double(_) => TokenKind::Double,
+            Token::Void(_) => TokenKind::Void,
+            Token::Long(_) => TokenKind::Long,
+            Token::Short(_) => TokenKind::Short,
+            Token::Signed(_) => TokenKind::Signed,
+            Token::Unsigned(_) => TokenKind::Unsigned,
+            Token::Bool(_) => TokenKind::Bool,
+            Token::Complex(_) => TokenKind::Complex,
+            Token::Imaginary(_) => TokenKind::Imaginary,
+            Token::Inline(_) => TokenKind::Inline,
+            Token::Noreturn(_) => TokenKind::Noreturn,
+            Token::ThreadLocal(_) => TokenKind::ThreadLocal,
+            Token::Struct(_) => TokenKind::Struct,
+            Token::Enum(_) => TokenKind::Enum,
+            Token::Union(_) => TokenKind::Union,
+            Token::LeftBrace(_) => TokenKind::LeftBrace,
+            Token::RightBrace(_) => TokenKind::RightBrace,
+            Token::LeftParen(_) => TokenKind::LeftParen,
+            Token::RightParen(_) => TokenKind::RightParen,
+            Token::Return(_) => TokenKind::Return,
+            Token::IfKeyword(_) => TokenKind::IfKeyword,
+            Token::ElseKeyword(_) => TokenKind::ElseKeyword,
+            Token::While(_) => TokenKind::While,
+            Token::For(_) => TokenKind::For,
+            Token::Do(_) => TokenKind::Do,
+            Token::Error(_) => TokenKind::Error,
+            Token::Whitespace(_) => TokenKind::Whitespace,
+        }
+    }
+
     /// トークンのスパン情報を取得
-    pub fn span(&self) -> Span {
+    pub fn span(&self) -> &Span {
+        match self {
+            Token::BlockComment(t) => &t.span,
+            Token::LineComment(t) => &t.span,
+            Token::Include(t) => &t.span,
+            Token::Define(t) => &t.span,
+            Token::Line(t) => &t.span,
+            Token::ErrorDirective(t) => &t.span,
+            Token::WarningDirective(t) => &t.span,
+            Token::Ifdef(t) => &t.span,
+            Token::Ifndef(t) => &t.span,
+            Token::If(t) => &t.span,
+            Token::Elif(t) => &t.span,
+            Token::Else(t) => &t.span,
+            Token::Endif(t) => &t.span,
+            Token::Semicolon(t) => &t.span,
+            Token::Equal(t) => &t.span,
+            Token::Asterisk(t) => &t.span,
+            Token::NumberLiteral(t) => &t.span,
+            Token::FloatLiteral(t) => &t.span,
+            Token::StringLiteral(t) => &t.span,
+            Token::CharLiteral(t) => &t.span,
+            Token::Attribute(t) => &t.span,
+            Token::Plus(t) => &t.span,
+            Token::Minus(t) => &t.span,
+            Token::Slash(t) => &t.span,
+            Token::Percent(t) => &t.span,
+            Token::EqualEqual(t) => &t.span,
+            Token::NotEqual(t) => &t.span,
+            Token::LessThan(t) => &t.span,
+            Token::LessThanOrEqual(t) => &t.span,
+            Token::GreaterThan(t) => &t.span,
+            Token::GreaterThanOrEqual(t) => &t.span,
+            Token::Ampersand(t) => &t.span,
+            Token::AmpersandAmpersand(t) => &t.span,
+            Token::Pipe(t) => &t.span,
+            Token::PipePipe(t) => &t.span,
+            Token::Caret(t) => &t.span,
+            Token::Tilde(t) => &t.span,
+            Token::Exclamation(t) => &t.span,
+            Token::LeftShift(t) => &t.span,
+            Token::RightShift(t) => &t.span,
+            Token::LeftBracket(t) => &t.span,
+            Token::RightBracket(t) => &t.span,
+            Token::Question(t) => &t.span,
+            Token::Colon(t) => &t.span,
+            Token::Comma(t) => &t.span,
+            Token::Dot(t) => &t.span,
+            Token::Arrow(t) => &t.span,
+            Token::PlusPlus(t) => &t.span,
+            Token::MinusMinus(t) => &t.span,
+            Token::AsteriskEqual(t) => &t.span,
+            Token::PlusEqual(t) => &t.span,
+            Token::MinusEqual(t) => &t.span,
+            Token::PercentEqual(t) => &t.span,
+            Token::SlashEqual(t) => &t.span,
+            Token::AmpersandEqual(t) => &t.span,
+            Token::PipeEqual(t) => &t.span,
+            Token::CaretEqual(t) => &t.span,
+            Token::LeftShiftEqual(t) => &t.span,
+            Token::RightShiftEqual(t) => &t.span,
+            Token::Ident(t) => &t.span,
+            Token::Auto(t) => &t.span,
+            Token::Register(t) => &t.span,
+            Token::Static(t) => &t.span,
+            Token::Extern(t) => &t.span,
+            Token::Typedef(t) => &t.span,
+            Token::Const(t) => &t.span,
+            Token::Volatile(t) => &t.span,
+            Token::Restrict(t) => &t.span,
+            Token::Atomic(t) => &t.span,
+            Token::Int(t) => &t.span,
+            Token::Char(t) => &t.span,
+            Token::Float(t) => &t.span,
+            Token::Double(t) => &t.span,
+            Token::Void(t) => &t.span,
+            Token::Long(t) => &t.span,
+            Token::Short(t) => &t.span,
+            Token::Signed(t) => &t.span,
+            Token::Unsigned(t) => &t.span,
+            Token::Bool(t) => &t.span,
+            Token::Complex(t) => &t.span,
+            Token::Imaginary(t) => &t.span,
+            Token::Inline(t) => &t.span,
+            Token::Noreturn(t) => &t.span,
+            Token::ThreadLocal(t) => &t.span,
+            Token::Struct(t) => &t.span,
+            Token::Enum(t) => &t.span,
+            Token::Union(t) => &t.span,
+            Token::LeftBrace(t) => &t.span,
+            Token::RightBrace(t) => &t.span,
+            Token::LeftParen(t) => &t.span,
+            Token::RightParen(t) => &t.span,
+            Token::Return(t) => &t.span,
+            Token::IfKeyword(t) => &t.span,
+            Token::ElseKeyword(t) => &t.span,
+            Token::While(t) => &t.span,
+            Token::For(t) => &t.span,
+            Token::Do(t) => &t.span,
+            Token::Error(t) => &t.span,
+            Token::Whitespace(t) => &t.span,
+        }
+    }
+
+    /// トークンを消費してスパン情報を取り出す
+    pub fn into_span(self) -> Span {
+        match self {
+            Token::BlockComment(t) => t.span,
+            Token::LineComment(t) => t.span,
+            Token::Include(t) => t.span,
+            Token::Define(t) => t.span,
+            Token::Line(t) => t.span,
+            Token::ErrorDirective(t) => t.span,
+            Token::WarningDirective(t) => t.span,
+            Token::Ifdef(t) => t.span,
+            Token::Ifndef(t) => t.span,
+            Token::If(t) => t.span,
+            Token::Elif(t) => t.span,
+            Token::Else(t) => t.span,
+            Token::Endif(t) => t.span,
+            Token::Semicolon(t) => t.span,
+            Token::Equal(t) => t.span,
+            Token::Asterisk(t) => t.span,
+            Token::NumberLiteral(t) => t.span,
+            Token::FloatLiteral(t) => t.span,
+            Token::StringLiteral(t) => t.span,
+            Token::CharLiteral(t) => t.span,
+            Token::Attribute(t) => t.span,
+            Token::Plus(t) => t.span,
+            Token::Minus(t) => t.span,
+            Token::Slash(t) => t.span,
+            Token::Percent(t) => t.span,
+            Token::EqualEqual(t) => t.span,
+            Token::NotEqual(t) => t.span,
+            Token::LessThan(t) => t.span,
+            Token::LessThanOrEqual(t) => t.span,
+            Token::GreaterThan(t) => t.span,
+            Token::GreaterThanOrEqual(t) => t.span,
+            Token::Ampersand(t) => t.span,
+            Token::AmpersandAmpersand(t) => t.span,
+            Token::Pipe(t) => t.span,
+            Token::PipePipe(t) => t.span,
+            Token::Caret(t) => t.span,
+            Token::Tilde(t) => t.span,
+            Token::Exclamation(t) => t.span,
+            Token::LeftShift(t) => t.span,
+            Token::RightShift(t) => t.span,
+            Token::LeftBracket(t) => t.span,
+            Token::RightBracket(t) => t.span,
+            Token::Question(t) => t.span,
+            Token::Colon(t) => t.span,
+            Token::Comma(t) => t.span,
+            Token::Dot(t) => t.span,
+            Token::Arrow(t) => t.span,
+            Token::PlusPlus(t) => t.span,
+            Token::MinusMinus(t) => t.span,
+            Token::AsteriskEqual(t) => t.span,
+            Token::PlusEqual(t) => t.span,
+            Token::MinusEqual(t) => t.span,
+            Token::PercentEqual(t) => t.span,
+            Token::SlashEqual(t) => t.span,
+            Token::AmpersandEqual(t) => t.span,
+            Token::PipeEqual(t) => t.span,
+            Token::CaretEqual(t) => t.span,
+            Token::LeftShiftEqual(t) => t.span,
+            Token::RightShiftEqual(t) => t.span,
+            Token::Ident(t) => t.span,
+            Token::Auto(t) => t.span,
+            Token::Register(t) => t.span,
+            Token::Static(t) => t.span,
+            Token::Extern(t) => t.span,
+            Token::Typedef(t) => t.span,
+            Token::Const(t) => t.span,
+            Token::Volatile(t) => t.span,
+            Token::Restrict(t) => t.span,
+            Token::Atomic(t) => t.span,
+            Token::Int(t) => t.span,
+            Token::Char(t) => t.span,
+            Token::Float(t) => t.span,
+            Token::Double(t) => t.span,
+            Token::Void(t) => t.span,
+            Token::Long(t) => t.span,
+            Token::Short(t) => t.span,
+            Token::Signed(t) => t.span,
+            Token::Unsigned(t) => t.span,
+            Token::Bool(t) => t.span,
+            Token::Complex(t) => t.span,
+            Token::Imaginary(t) => t.span,
+            Token::Inline(t) => t.span,
+            Token::Noreturn(t) => t.span,
+            Token::ThreadLocal(t) => t.span,
+            Token::Struct(t) => t.span,
+            Token::Enum(t) => t.span,
+            Token::Union(t) => t.span,
+            Token::LeftBrace(t) => t.span,
+            Token::RightBrace(t) => t.span,
+            Token::LeftParen(t) => t.span,
+            Token::RightParen(t) => t.span,
+            Token::Return(t) => t.span,
+            Token::IfKeyword(t) => t.span,
+            Token::ElseKeyword(t) => t.span,
+            Token::While(t) => t.span,
+            Token::For(t) => t.span,
+            Token::Do(t) => t.span,
+            Token::Error(t) => t.span,
+            Token::Whitespace(t) => t.span,
+        }
+    }
+
+    /// トークンのスパン情報を変更する（字句解析器内部で使用）
+    pub(crate) fn span_mut(&mut self) -> &mut Span {
         match self {
-            Token::BlockComment(t) => t.span.clone(),
-            Token::LineComment(t) => t.span.clone(),
-            Token::Include(t) => t.span.clone(),
-            Token::Define(t) => t.span.clone(),
-            Token::Ifdef(t) => t.span.clone(),
-            Token::Ifndef(t) => t.span.clone(),
-            Token::If(t) => t.span.clone(),
-            Token::Elif(t) => t.span.clone(),
-            Token::Else(t) => t.span.clone(),
-            Token::Endif(t) => t.span.clone(),
-            Token::Semicolon(t) => t.span.clone(),
-            Token::Equal(t) => t.span.clone(),
-            Token::Asterisk(t) => t.span.clone(),
-            Token::NumberLiteral(t) => t.span.clone(),
-            Token::FloatLiteral(t) => t.span.clone(),
-            Token::Plus(t) => t.span.clone(),
-            Token::Minus(t) => t.span.clone(),
-            Token::Slash(t) => t.span.clone(),
-            Token::Percent(t) => t.span.clone(),
-            Token::EqualEqual(t) => t.span.clone(),
-            Token::NotEqual(t) => t.span.clone(),
-            Token::LessThan(t) => t.span.clone(),
-            Token::LessThanOrEqual(t) => t.span.clone(),
-            Token::GreaterThan(t) => t.span.clone(),
-            Token::GreaterThanOrEqual(t) => t.span.clone(),
-            Token::Ampersand(t) => t.span.clone(),
-            Token::AmpersandAmpersand(t) => t.span.clone(),
-            Token::Pipe(t) => t.span.clone(),
-            Token::PipePipe(t) => t.span.clone(),
-            Token::Caret(t) => t.span.clone(),
-            Token::Tilde(t) => t.span.clone(),
-            Token::Exclamation(t) => t.span.clone(),
-            Token::LeftShift(t) => t.span.clone(),
-            Token::RightShift(t) => t.span.clone(),
-            Token::LeftBracket(t) => t.span.clone(),
-            Token::RightBracket(t) => t.span.clone(),
-            Token::Question(t) => t.span.clone(),
-            Token::Colon(t) => t.span.clone(),
-            Token::Comma(t) => t.span.clone(),
-            Token::Dot(t) => t.span.clone(),
-            Token::Arrow(t) => t.span.clone(),
-            Token::PlusPlus(t) => t.span.clone(),
-            Token::MinusMinus(t) => t.span.clone(),
-            Token::Ident(t) => t.span.clone(),
-            Token::Auto(t) => t.span.clone(),
-            Token::Register(t) => t.span.clone(),
-            Token::Static(t) => t.span.clone(),
-            Token::Extern(t) => t.span.clone(),
-            Token::Typedef(t) => t.span.clone(),
-            Token::Const(t) => t.span.clone(),
-            Token::Volatile(t) => t.span.clone(),
-            Token::Restrict(t) => t.span.clone(),
-            Token::Atomic(t) => t.span.clone(),
-            Token::Int(t) => t.span.clone(),
-            Token::Char(t) => t.span.clone(),
-            Token::Float(t) => t.span.clone(),
-            Token::Double(t) => t.span.clone(),
-            Token::Void(t) => t.span.clone(),
-            Token::Long(t) => t.span.clone(),
-            Token::Short(t) => t.span.clone(),
-            Token::Signed(t) => t.span.clone(),
-            Token::Unsigned(t) => t.span.clone(),
-            Token::Struct(t) => t.span.clone(),
-            Token::Enum(t) => t.span.clone(),
-            Token::Union(t) => t.span.clone(),
-            Token::LeftBrace(t) => t.span.clone(),
-            Token::RightBrace(t) => t.span.clone(),
-            Token::LeftParen(t) => t.span.clone(),
-            Token::RightParen(t) => t.span.clone(),
-            Token::Return(t) => t.span.clone(),
-            Token::IfKeyword(t) => t.span.clone(),
-            Token::ElseKeyword(t) => t.span.clone(),
-            Token::While(t) => t.span.clone(),
-            Token::For(t) => t.span.clone(),
-            Token::Error(t) => t.span.clone(),
+            Token::BlockComment(t) => &mut t.span,
+            Token::LineComment(t) => &mut t.span,
+            Token::Include(t) => &mut t.span,
+            Token::Define(t) => &mut t.span,
+            Token::Line(t) => &mut t.span,
+            Token::ErrorDirective(t) => &mut t.span,
+            Token::WarningDirective(t) => &mut t.span,
+            Token::Ifdef(t) => &mut t.span,
+            Token::Ifndef(t) => &mut t.span,
+            Token::If(t) => &mut t.span,
+            Token::Elif(t) => &mut t.span,
+            Token::Else(t) => &mut t.span,
+            Token::Endif(t) => &mut t.span,
+            Token::Semicolon(t) => &mut t.span,
+            Token::Equal(t) => &mut t.span,
+            Token::Asterisk(t) => &mut t.span,
+            Token::NumberLiteral(t) => &mut t.span,
+            Token::FloatLiteral(t) => &mut t.span,
+            Token::StringLiteral(t) => &mut t.span,
+            Token::CharLiteral(t) => &mut t.span,
+            Token::Attribute(t) => &mut t.span,
+            Token::Plus(t) => &mut t.span,
+            Token::Minus(t) => &mut t.span,
+            Token::Slash(t) => &mut t.span,
+            Token::Percent(t) => &mut t.span,
+            Token::EqualEqual(t) => &mut t.span,
+            Token::NotEqual(t) => &mut t.span,
+            Token::LessThan(t) => &mut t.span,
+            Token::LessThanOrEqual(t) => &mut t.span,
+            Token::GreaterThan(t) => &mut t.span,
+            Token::GreaterThanOrEqual(t) => &mut t.span,
+            Token::Ampersand(t) => &mut t.span,
+            Token::AmpersandAmpersand(t) => &mut t.span,
+            Token::Pipe(t) => &mut t.span,
+            Token::PipePipe(t) => &mut t.span,
+            Token::Caret(t) => &mut t.span,
+            Token::Tilde(t) => &mut t.span,
+            Token::Exclamation(t) => &mut t.span,
+            Token::LeftShift(t) => &mut t.span,
+            Token::RightShift(t) => &mut t.span,
+            Token::LeftBracket(t) => &mut t.span,
+            Token::RightBracket(t) => &mut t.span,
+            Token::Question(t) => &mut t.span,
+            Token::Colon(t) => &mut t.span,
+            Token::Comma(t) => &mut t.span,
+            Token::Dot(t) => &mut t.span,
+            Token::Arrow(t) => &mut t.span,
+            Token::PlusPlus(t) => &mut t.span,
+            Token::MinusMinus(t) => &mut t.span,
+            Token::AsteriskEqual(t) => &mut t.span,
+            Token::PlusEqual(t) => &mut t.span,
+            Token::MinusEqual(t) => &mut t.span,
+            Token::PercentEqual(t) => &mut t.span,
+            Token::SlashEqual(t) => &mut t.span,
+            Token::AmpersandEqual(t) => &mut t.span,
+            Token::PipeEqual(t) => &mut t.span,
+            Token::CaretEqual(t) => &mut t.span,
+            Token::LeftShiftEqual(t) => &mut t.span,
+            Token::RightShiftEqual(t) => &mut t.span,
+            Token::Ident(t) => &mut t.span,
+            Token::Auto(t) => &mut t.span,
+            Token::Register(t) => &mut t.span,
+            Token::Static(t) => &mut t.span,
+            Token::Extern(t) => &mut t.span,
+            Token::Typedef(t) => &mut t.span,
+            Token::Const(t) => &mut t.span,
+            Token::Volatile(t) => &mut t.span,
+            Token::Restrict(t) => &mut t.span,
+            Token::Atomic(t) => &mut t.span,
+            Token::Int(t) => &mut t.span,
+            Token::Char(t) => &mut t.span,
+            Token::Float(t) => &mut t.span,
+            Token::Double(t) => &mut t.span,
+            Token::Void(t) => &mut t.span,
+            Token::Long(t) => &mut t.span,
+            Token::Short(t) => &mut t.span,
+            Token::Signed(t) => &mut t.span,
+            Token::Unsigned(t) => &mut t.span,
+            Token::Bool(t) => &mut t.span,
+            Token::Complex(t) => &mut t.span,
+            Token::Imaginary(t) => &mut t.span,
+            Token::Inline(t) => &mut t.span,
+            Token::Noreturn(t) => &mut t.span,
+            Token::ThreadLocal(t) => &mut t.span,
+            Token::Struct(t) => &mut t.span,
+            Token::Enum(t) => &mut t.span,
+            Token::Union(t) => &mut t.span,
+            Token::LeftBrace(t) => &mut t.span,
+            Token::RightBrace(t) => &mut t.span,
+            Token::LeftParen(t) => &mut t.span,
+            Token::RightParen(t) => &mut t.span,
+            Token::Return(t) => &mut t.span,
+            Token::IfKeyword(t) => &mut t.span,
+            Token::ElseKeyword(t) => &mut t.span,
+            Token::While(t) => &mut t.span,
+            Token::For(t) => &mut t.span,
+            Token::Do(t) => &mut t.span,
+            Token::Error(t) => &mut t.span,
+            Token::Whitespace(t) => &mut t.span,
         }
     }
 }
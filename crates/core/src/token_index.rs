@@ -0,0 +1,105 @@
+use crate::lexer::Lexer;
+use crate::token::Token;
+
+/// 位置（行・列）からトークンを引くための索引
+///
+/// 字句解析したトークン列を、前後の空白を除いた実際のバイト範囲で並べて保持し、
+/// 二分探索で位置に対応するトークンを求める。LSP のホバー・定義ジャンプなどで使用する。
+#[derive(Debug, Clone)]
+pub struct TokenIndex {
+    entries: Vec<TokenEntry>,
+    line_starts: Vec<usize>,  // 各行の先頭のバイトオフセット
+    source: String,
+}
+
+#[derive(Debug, Clone)]
+struct TokenEntry {
+    byte_start: usize,
+    byte_end: usize,
+    token: Token,
+}
+
+impl TokenIndex {
+    /// ソースコードを字句解析して索引を作る
+    pub fn new(source: &str) -> Self {
+        let mut lexer = Lexer::new(source);
+        let mut tokens = Vec::new();
+        while let Some(token) = lexer.next_token() {
+            tokens.push(token);
+        }
+        Self::from_tokens(source, tokens)
+    }
+
+    /// 字句解析済みのトークン列から索引を作る
+    pub fn from_tokens(source: &str, tokens: Vec<Token>) -> Self {
+        let mut entries: Vec<TokenEntry> = tokens
+            .into_iter()
+            .filter_map(|token| {
+                // トークンのスパンは直前の空白を含むことがあるため、空白を除いた範囲を使う
+                let span = token.span();
+                let text = source.get(span.byte_start_idx..span.byte_end_idx)?;
+                let leading = text.len() - text.trim_start().len();
+                let trailing = text.len() - text.trim_end().len();
+                if leading == text.len() {
+                    return None;
+                }
+                Some(TokenEntry {
+                    byte_start: span.byte_start_idx + leading,
+                    byte_end: span.byte_end_idx - trailing,
+                    token,
+                })
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.byte_start);
+
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(pos, _)| pos + 1))
+            .collect();
+
+        TokenIndex {
+            entries,
+            line_starts,
+            source: source.to_string(),
+        }
+    }
+
+    /// 指定した位置（0始まりの行・列）にあるトークンを返す
+    ///
+    /// トークンの開始位置は含み、終了位置は含まない。トークンの間の空白では `None` を返す。
+    pub fn token_at(&self, line: usize, column: usize) -> Option<&Token> {
+        let offset = self.byte_offset(line, column)?;
+        // offset 以前に始まる最後のトークン
+        let candidates = self.entries.partition_point(|entry| entry.byte_start <= offset);
+        let entry = &self.entries[candidates.checked_sub(1)?];
+        (offset < entry.byte_end).then_some(&entry.token)
+    }
+
+    /// 索引に含まれるトークン数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 行・列（文字単位）をバイトオフセットに変換する
+    fn byte_offset(&self, line: usize, column: usize) -> Option<usize> {
+        let line_start = *self.line_starts.get(line)?;
+        let line_end = self.line_starts.get(line + 1).map_or(self.source.len(), |next| next - 1);
+        let line_text = &self.source[line_start..line_end];
+        line_text
+            .char_indices()
+            .nth(column)
+            .map(|(pos, _)| line_start + pos)
+    }
+}
+
+impl Lexer<'_> {
+    /// ソースコード中の指定位置（0始まりの行・列）にあるトークンを返す
+    ///
+    /// 繰り返し検索する場合は `TokenIndex` を作って使い回すこと。
+    pub fn token_at(source: &str, line: usize, column: usize) -> Option<Token> {
+        TokenIndex::new(source).token_at(line, column).cloned()
+    }
+}
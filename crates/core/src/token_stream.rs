@@ -0,0 +1,112 @@
+use std::collections::VecDeque;
+
+use crate::lexer::Lexer;
+use crate::token::Token;
+
+/// 字句解析器を借用し、読み進めた位置へ巻き戻せるようにしたトークン列
+///
+/// 試しに解析して失敗したら元の位置に戻す（キャスト式と括弧式、宣言と式の判別など）ために使う。
+/// 字句解析器から読んだトークンはバッファに保持し、巻き戻した後はバッファから読み直す。
+/// 記録中の位置（`Mark`）がなくなった時点で、読み終えたトークンはバッファから捨てる。
+#[derive(Debug)]
+pub struct TokenStream<'l, 'src> {
+    lexer: &'l mut Lexer<'src>,
+    buffer: VecDeque<Token>,
+    start: usize,       // buffer の先頭のトークンの読み取り位置
+    position: usize,    // 次に返すトークンの読み取り位置
+    open_marks: usize,  // rewind / commit していない Mark の数
+}
+
+/// `TokenStream::checkpoint` で記録した読み取り位置
+///
+/// `rewind` か `commit` に渡すまでは、この位置以降のトークンをバッファに残す。
+#[derive(Debug, PartialEq, Eq)]
+#[must_use = "Mark は rewind か commit に渡す（渡さないと読み終えたトークンがバッファに残り続ける）"]
+pub struct Mark(usize);
+
+impl<'l, 'src> TokenStream<'l, 'src> {
+    pub fn new(lexer: &'l mut Lexer<'src>) -> Self {
+        TokenStream {
+            lexer,
+            buffer: VecDeque::new(),
+            start: 0,
+            position: 0,
+            open_marks: 0,
+        }
+    }
+
+    /// 次のトークンを読み進める
+    pub fn next_token(&mut self) -> Option<Token> {
+        self.peek_token()?;
+        let token = if self.open_marks == 0 {
+            // 巻き戻す先がないので、読んだトークンはバッファに残さない
+            self.start += 1;
+            self.buffer.pop_front()
+        } else {
+            self.buffer.get(self.position - self.start).cloned()
+        };
+        self.position += 1;
+        token
+    }
+
+    /// 次のトークンを読み進めずに返す
+    pub fn peek_token(&mut self) -> Option<&Token> {
+        let index = self.position - self.start;
+        if index == self.buffer.len() {
+            let token = self.lexer.next_token()?;
+            self.buffer.push_back(token);
+        }
+        self.buffer.get(index)
+    }
+
+    /// 現在の読み取り位置を記録する
+    pub fn checkpoint(&mut self) -> Mark {
+        self.open_marks += 1;
+        Mark(self.position)
+    }
+
+    /// `checkpoint` で記録した位置に戻る（以降は同じトークンをもう一度読む）
+    pub fn rewind(&mut self, mark: Mark) {
+        self.position = mark.0;
+        self.release_mark();
+    }
+
+    /// `checkpoint` で記録した位置に戻らないことを確定する（読み取り位置はそのまま）
+    pub fn commit(&mut self, mark: Mark) {
+        let Mark(_) = mark;
+        self.release_mark();
+    }
+
+    /// 試しに解析し、`None` を返した場合は解析前の位置に戻す
+    pub fn speculate<T>(&mut self, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let mark = self.checkpoint();
+        let result = parse(self);
+        if result.is_some() {
+            self.commit(mark);
+        } else {
+            self.rewind(mark);
+        }
+        result
+    }
+
+    /// バッファに保持しているトークンの数（読み終えて巻き戻しに備えているものも含む）
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// 読み残したトークンを字句解析器に戻して終了する（以降は字句解析器から続きを読める）
+    pub fn finish(mut self) {
+        let consumed = self.position - self.start;
+        for token in self.buffer.drain(consumed..).rev() {
+            self.lexer.unget_token(token);
+        }
+    }
+
+    fn release_mark(&mut self) {
+        self.open_marks -= 1;
+        if self.open_marks == 0 {
+            self.buffer.drain(..self.position - self.start);
+            self.start = self.position;
+        }
+    }
+}
@@ -1,14 +1,16 @@
 use crate::span::Span;
 
 /// コメントの種類
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Comment {
     Line { text: String, span: Span },
     Block { text: String, span: Span },
 }
 
 /// Trivia: 宣言に付随するコメント
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Trivia {
     pub leading: Vec<Comment>,   // 前置コメント（宣言の前）
     pub trailing: Vec<Comment>,  // 後置コメント（宣言の後、同じ行）
@@ -4,25 +4,36 @@
 //! - 基本型 (void, char, int, long, など)
 //! - 型修飾子 (const, volatile, restrict, atomic)
 //! - ポインタ層 (各層に修飾子を付与可能)
-//! - 完全な型表現 (基本型 + ポインタ層)
+//! - 配列の次元 (C99 の `[const 4]` のような括弧内の修飾子を含む)
+//! - 完全な型表現 (基本型 + ポインタ層 + 配列の次元)
 
 use crate::span::Span;
 
 /// C言語の基本型
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BaseType {
     Void,
     Char,
+    SignedChar,
+    UnsignedChar,
     Short,
+    UnsignedShort,
     Int,
     Long,
+    UnsignedLong,
     LongLong,
+    UnsignedLongLong,
     Float,
     Double,
     LongDouble,
+    /// `signed` 単独（パーサーは `signed` / `signed int` を Int として返す）
     Signed,
+    /// `unsigned` / `unsigned int`
     Unsigned,
     Bool,
+    Complex,    // _Complex
+    Imaginary,  // _Imaginary
     /// 構造体型（名前付きまたは匿名）
     /// Option<String>: Some(name) for named struct, None for anonymous
     Struct(Option<String>),
@@ -38,16 +49,23 @@ impl BaseType {
         match self {
             BaseType::Void => "void",
             BaseType::Char => "char",
+            BaseType::SignedChar => "signed char",
+            BaseType::UnsignedChar => "unsigned char",
             BaseType::Short => "short",
+            BaseType::UnsignedShort => "unsigned short",
             BaseType::Int => "int",
             BaseType::Long => "long",
+            BaseType::UnsignedLong => "unsigned long",
             BaseType::LongLong => "long long",
+            BaseType::UnsignedLongLong => "unsigned long long",
             BaseType::Float => "float",
             BaseType::Double => "double",
             BaseType::LongDouble => "long double",
             BaseType::Signed => "signed",
             BaseType::Unsigned => "unsigned",
             BaseType::Bool => "_Bool",
+            BaseType::Complex => "_Complex",
+            BaseType::Imaginary => "_Imaginary",
             BaseType::Struct(Some(name)) => return Box::leak(format!("struct {}", name).into_boxed_str()),
             BaseType::Struct(None) => "struct",
             BaseType::Union(Some(name)) => return Box::leak(format!("union {}", name).into_boxed_str()),
@@ -56,10 +74,50 @@ impl BaseType {
             BaseType::Enum(None) => "enum",
         }
     }
+
+    /// 組み合わせ可能な型指定子の並び（`unsigned long long int` など）を正規化した基本型にする
+    ///
+    /// 指定子の順序は問わない（`long unsigned` も `unsigned long` と同じ）。
+    /// 対象は signed / unsigned / short / long / int / char / double で、それ以外を含む場合や空の場合は None。
+    pub fn from_specifiers(specifiers: &[&str]) -> Option<BaseType> {
+        let count = |keyword: &str| specifiers.iter().filter(|s| **s == keyword).count();
+        if specifiers.is_empty()
+            || specifiers
+                .iter()
+                .any(|s| !matches!(*s, "signed" | "unsigned" | "short" | "long" | "int" | "char" | "double"))
+        {
+            return None;
+        }
+
+        let is_signed = count("signed") > 0;
+        let is_unsigned = count("unsigned") > 0;
+        let longs = count("long");
+        let base_type = if count("char") > 0 {
+            match (is_signed, is_unsigned) {
+                (true, _) => BaseType::SignedChar,
+                (_, true) => BaseType::UnsignedChar,
+                _ => BaseType::Char,
+            }
+        } else if count("double") > 0 {
+            if longs > 0 { BaseType::LongDouble } else { BaseType::Double }
+        } else if count("short") > 0 {
+            if is_unsigned { BaseType::UnsignedShort } else { BaseType::Short }
+        } else if longs >= 2 {
+            if is_unsigned { BaseType::UnsignedLongLong } else { BaseType::LongLong }
+        } else if longs == 1 {
+            if is_unsigned { BaseType::UnsignedLong } else { BaseType::Long }
+        } else if is_unsigned {
+            BaseType::Unsigned
+        } else {
+            BaseType::Int
+        };
+        Some(base_type)
+    }
 }
 
 /// C言語の型修飾子
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TypeQualifier {
     Const,
     Volatile,
@@ -85,7 +143,8 @@ impl TypeQualifier {
 /// - Layer 0 (最内層): qualifiers = []
 /// - Layer 1: qualifiers = [Const]
 /// - Layer 2 (最外層): qualifiers = [Volatile]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerLayer {
     /// このポインタ層に適用される修飾子
     pub qualifiers: Vec<TypeQualifier>,
@@ -128,6 +187,46 @@ impl PointerLayer {
     }
 }
 
+/// 配列宣言子の1次元分
+///
+/// 例: 引数 `int a[const static 4]` の場合:
+/// - size: Some("4")
+/// - qualifiers: [Const]
+/// - is_static: true
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArrayDimension {
+    /// 要素数の式（`[]` の場合は None）
+    pub size: Option<String>,
+    /// 括弧内の修飾子（C99、関数引数でのみ有効）
+    pub qualifiers: Vec<TypeQualifier>,
+    /// 括弧内に `static` があるか（C99、関数引数でのみ有効）
+    pub is_static: bool,
+    /// `[` から `]` までをカバーするSpan
+    pub span: Span,
+}
+
+impl ArrayDimension {
+    /// この次元が特定の修飾子を持つか確認
+    pub fn has_qualifier(&self, qualifier: TypeQualifier) -> bool {
+        self.qualifiers.contains(&qualifier)
+    }
+}
+
+impl std::fmt::Display for ArrayDimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts: Vec<&str> = Vec::new();
+        if self.is_static {
+            parts.push("static");
+        }
+        parts.extend(self.qualifiers.iter().map(|q| q.to_string()));
+        if let Some(size) = &self.size {
+            parts.push(size);
+        }
+        write!(f, "[{}]", parts.join(" "))
+    }
+}
+
 /// 完全なC言語の型表現
 ///
 /// 例: `const int *const *volatile ptr`
@@ -138,7 +237,8 @@ impl PointerLayer {
 ///     PointerLayer { qualifiers: [Const] },      // *const
 ///     PointerLayer { qualifiers: [Volatile] }    // *volatile (最外層)
 ///   ]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Type {
     /// 基本型 (int, char, など)
     pub base_type: BaseType,
@@ -148,6 +248,8 @@ pub struct Type {
     pub base_qualifiers: Vec<TypeQualifier>,
     /// ポインタ層 (最内層から順)
     pub pointer_layers: Vec<PointerLayer>,
+    /// 配列の次元 (宣言子に近い順、配列でなければ空)
+    pub array_dimensions: Vec<ArrayDimension>,
     /// 型全体をカバーするSpan
     pub span: Span,
 }
@@ -160,6 +262,7 @@ impl Type {
             alias: None,
             base_qualifiers: Vec::new(),
             pointer_layers: Vec::new(),
+            array_dimensions: Vec::new(),
             span,
         }
     }
@@ -175,6 +278,7 @@ impl Type {
             alias: None,
             base_qualifiers,
             pointer_layers: Vec::new(),
+            array_dimensions: Vec::new(),
             span,
         }
     }
@@ -191,6 +295,7 @@ impl Type {
             alias: None,
             base_qualifiers,
             pointer_layers,
+            array_dimensions: Vec::new(),
             span,
         }
     }
@@ -200,6 +305,11 @@ impl Type {
         !self.pointer_layers.is_empty()
     }
 
+    /// 配列型かどうか確認
+    pub fn is_array(&self) -> bool {
+        !self.array_dimensions.is_empty()
+    }
+
     /// ポインタ階層を取得 (非ポインタは0, *は1, **は2, など)
     pub fn pointer_level(&self) -> usize {
         self.pointer_layers.len()
@@ -210,36 +320,83 @@ impl Type {
         self.base_qualifiers.contains(&qualifier)
     }
 
-    /// 型を文字列表現に変換
-    pub fn to_string(&self) -> String {
-        let mut result = String::new();
+    /// 型そのもの（最上位）の修飾子
+    /// ポインタ型なら最外層のポインタの修飾子、それ以外は基本型の修飾子を返す
+    /// 例: `const int *` -> []、`int *const` -> [Const]、`const int` -> [Const]
+    pub fn top_level_qualifiers(&self) -> &[TypeQualifier] {
+        match self.pointer_layers.last() {
+            Some(layer) => &layer.qualifiers,
+            None => &self.base_qualifiers,
+        }
+    }
 
-        // 基本型修飾子
-        if !self.base_qualifiers.is_empty() {
-            let quals = self
-                .base_qualifiers
-                .iter()
-                .map(|q| q.to_string())
-                .collect::<Vec<_>>()
-                .join(" ");
-            result.push_str(&quals);
-            result.push(' ');
+    /// `level` 回間接参照した先の修飾子（0 は最上位の修飾子、pointer_level() で基本型の修飾子）
+    /// ポインタの段数より深い場合は None を返す
+    /// 例: `const int *` の pointee_qualifiers(1) -> Some([Const])
+    pub fn pointee_qualifiers(&self, level: usize) -> Option<&[TypeQualifier]> {
+        let index = self.pointer_layers.len().checked_sub(level)?;
+        match index {
+            0 => Some(&self.base_qualifiers),
+            _ => Some(&self.pointer_layers[index - 1].qualifiers),
         }
+    }
 
-        // 基本型
-        if let Some(alias) = &self.alias {
-            result.push_str(alias);
-        } else {
-            result.push_str(self.base_type.to_string());
+    /// 型そのもの（最上位）が const かどうか
+    pub fn is_const(&self) -> bool {
+        self.top_level_qualifiers().contains(&TypeQualifier::Const)
+    }
+
+    /// 型そのもの（最上位）が volatile かどうか
+    pub fn is_volatile(&self) -> bool {
+        self.top_level_qualifiers().contains(&TypeQualifier::Volatile)
+    }
+
+    /// 型を正規化したC言語の型表記に変換する（例: `const int *`、`int **`、`struct Foo *const`）
+    pub fn to_c_string(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+/// 基本型の修飾子 → 基本型（タグ名・typedef名を含む） → ポインタ層 の順に出力する
+/// 修飾子のないポインタ層は続けて書き（`**`）、修飾子付きの層の後は空白で区切る（`*const *`）
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for qualifier in &self.base_qualifiers {
+            write!(f, "{} ", qualifier.to_string())?;
         }
 
-        // ポインタ層
-        for layer in &self.pointer_layers {
-            result.push(' ');
-            result.push_str(&layer.to_string());
+        match (&self.alias, &self.base_type) {
+            (Some(alias), _) => write!(f, "{}", alias)?,
+            (None, BaseType::Struct(Some(name))) => write!(f, "struct {}", name)?,
+            (None, BaseType::Union(Some(name))) => write!(f, "union {}", name)?,
+            (None, BaseType::Enum(Some(name))) => write!(f, "enum {}", name)?,
+            (None, base_type) => write!(f, "{}", base_type.to_string())?,
         }
 
-        result
+        if !self.pointer_layers.is_empty() {
+            write!(f, " ")?;
+        }
+        for (i, layer) in self.pointer_layers.iter().enumerate() {
+            write!(f, "*")?;
+            if !layer.qualifiers.is_empty() {
+                let quals: Vec<&str> = layer.qualifiers.iter().map(|q| q.to_string()).collect();
+                write!(f, "{}", quals.join(" "))?;
+                if i + 1 < self.pointer_layers.len() {
+                    write!(f, " ")?;
+                }
+            }
+        }
+
+        // 配列の次元は抽象宣言子として続ける（`int [4]`、`int *[4]`、`int *const [4]`）
+        if !self.array_dimensions.is_empty()
+            && self.pointer_layers.last().is_none_or(|layer| !layer.qualifiers.is_empty())
+        {
+            write!(f, " ")?;
+        }
+        for dimension in &self.array_dimensions {
+            write!(f, "{}", dimension)?;
+        }
+        Ok(())
     }
 }
 
@@ -311,4 +468,87 @@ mod tests {
         assert!(ty.pointer_layers[1].has_qualifier(TypeQualifier::Volatile));
         assert_eq!(ty.to_string(), "const int *const *volatile");
     }
+
+    #[test]
+    fn test_qualifier_queries_pointer_to_const() {
+        // const int *: 指す先は const だがポインタ自体は const ではない
+        let span = Span::new(0, 0, 0, 0);
+        let ty = Type::with_pointers(BaseType::Int, vec![TypeQualifier::Const], vec![PointerLayer::new(span.clone())], span);
+
+        assert!(!ty.is_const());
+        assert!(!ty.is_volatile());
+        assert!(ty.top_level_qualifiers().is_empty());
+        assert_eq!(ty.pointee_qualifiers(0), Some(&[][..]));
+        assert_eq!(ty.pointee_qualifiers(1), Some(&[TypeQualifier::Const][..]));
+        assert_eq!(ty.pointee_qualifiers(2), None);
+    }
+
+    #[test]
+    fn test_qualifier_queries_const_pointer() {
+        // int *const: ポインタ自体が const で、指す先は const ではない
+        let span = Span::new(0, 0, 0, 0);
+        let layer = PointerLayer::with_qualifiers(vec![TypeQualifier::Const], span.clone());
+        let ty = Type::with_pointers(BaseType::Int, Vec::new(), vec![layer], span);
+
+        assert!(ty.is_const());
+        assert_eq!(ty.top_level_qualifiers(), &[TypeQualifier::Const]);
+        assert_eq!(ty.pointee_qualifiers(1), Some(&[][..]));
+    }
+
+    #[test]
+    fn test_qualifier_queries_non_pointer_and_multi_level() {
+        let span = Span::new(0, 0, 0, 0);
+        let ty = Type::with_base_qualifiers(BaseType::Int, vec![TypeQualifier::Const, TypeQualifier::Volatile], span.clone());
+        assert!(ty.is_const());
+        assert!(ty.is_volatile());
+        assert_eq!(ty.pointee_qualifiers(1), None);
+
+        // volatile int *const *: 最上位は修飾なし、1段先が const、2段先が volatile
+        let layers = vec![
+            PointerLayer::with_qualifiers(vec![TypeQualifier::Const], span.clone()),
+            PointerLayer::new(span.clone()),
+        ];
+        let ty = Type::with_pointers(BaseType::Int, vec![TypeQualifier::Volatile], layers, span);
+        assert!(!ty.is_const());
+        assert_eq!(ty.pointee_qualifiers(1), Some(&[TypeQualifier::Const][..]));
+        assert_eq!(ty.pointee_qualifiers(2), Some(&[TypeQualifier::Volatile][..]));
+    }
+
+    #[test]
+    fn test_display_const_int_pointer() {
+        let span = Span::new(0, 0, 0, 0);
+        let ty = Type::with_pointers(BaseType::Int, vec![TypeQualifier::Const], vec![PointerLayer::new(span.clone())], span);
+        assert_eq!(ty.to_c_string(), "const int *");
+        assert_eq!(format!("{}", ty), "const int *");
+    }
+
+    #[test]
+    fn test_display_double_pointer() {
+        let span = Span::new(0, 0, 0, 0);
+        let layers = vec![PointerLayer::new(span.clone()), PointerLayer::new(span.clone())];
+        let ty = Type::with_pointers(BaseType::Int, Vec::new(), layers, span);
+        assert_eq!(ty.to_c_string(), "int **");
+    }
+
+    #[test]
+    fn test_display_struct_tag_pointer() {
+        let span = Span::new(0, 0, 0, 0);
+        let ty = Type::with_pointers(BaseType::Struct(Some("Foo".to_string())), Vec::new(), vec![PointerLayer::new(span.clone())], span.clone());
+        assert_eq!(ty.to_c_string(), "struct Foo *");
+
+        let ty = Type::new(BaseType::Enum(Some("Color".to_string())), span);
+        assert_eq!(ty.to_c_string(), "enum Color");
+    }
+
+    #[test]
+    fn test_display_qualified_layer_followed_by_pointer() {
+        // int *const *
+        let span = Span::new(0, 0, 0, 0);
+        let layers = vec![
+            PointerLayer::with_qualifiers(vec![TypeQualifier::Const], span.clone()),
+            PointerLayer::new(span.clone()),
+        ];
+        let ty = Type::with_pointers(BaseType::Int, Vec::new(), layers, span);
+        assert_eq!(ty.to_c_string(), "int *const *");
+    }
 }
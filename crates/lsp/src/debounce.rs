@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tower_lsp::lsp_types::Url;
+
+/// 診断の再計算を間引くための、ドキュメントごとの世代管理
+///
+/// 変更のたびに `schedule` で世代を進め、`wait` で一定時間待ったあとも最新の世代であれば実行する。
+/// 待っている間に新しい変更が来た実行は取り消され、最後の変更だけが診断される。
+/// 世代は全ドキュメントの通し番号なので、`forget` したあとに開き直しても古い実行の世代とは重ならない。
+#[derive(Debug)]
+pub struct Debouncer {
+    delay: Duration,
+    generations: Mutex<HashMap<Url, u64>>,
+    next_generation: AtomicU64,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Debouncer {
+            delay,
+            generations: Mutex::new(HashMap::new()),
+            next_generation: AtomicU64::new(1),
+        }
+    }
+
+    /// ドキュメントの新しい実行を予約し、その世代を返す（それまでの実行は古くなる）
+    pub fn schedule(&self, uri: &Url) -> u64 {
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+        self.generations.lock().unwrap().insert(uri.clone(), generation);
+        generation
+    }
+
+    /// 遅延時間だけ待ち、その時点でもこの世代が最新なら true を返す
+    pub async fn wait(&self, uri: &Url, generation: u64) -> bool {
+        tokio::time::sleep(self.delay).await;
+        self.is_latest(uri, generation)
+    }
+
+    /// この世代がドキュメントの最新の実行かどうか（診断の公開直前の確認に使う）
+    pub fn is_latest(&self, uri: &Url, generation: u64) -> bool {
+        self.generations.lock().unwrap().get(uri) == Some(&generation)
+    }
+
+    /// ドキュメントの世代を破棄する（予約済みの実行はすべて取り消される）
+    pub fn forget(&self, uri: &Url) {
+        self.generations.lock().unwrap().remove(uri);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    const DELAY: Duration = Duration::from_millis(200);
+
+    /// 変更ごとに予約と待機を行い、最新のままなら公開したバージョンを記録する
+    fn edit(debouncer: &Arc<Debouncer>, published: &Arc<Mutex<Vec<u32>>>, uri: &Url, version: u32) -> tokio::task::JoinHandle<()> {
+        let generation = debouncer.schedule(uri);
+        let (debouncer, published, uri) = (debouncer.clone(), published.clone(), uri.clone());
+        tokio::spawn(async move {
+            if debouncer.wait(&uri, generation).await {
+                published.lock().unwrap().push(version);
+            }
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rapid_edits_publish_once() {
+        let debouncer = Arc::new(Debouncer::new(DELAY));
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let uri = Url::parse("file:///project/main.c").unwrap();
+
+        let mut handles = Vec::new();
+        for version in 1..=5 {
+            handles.push(edit(&debouncer, &published, &uri, version));
+            tokio::time::advance(Duration::from_millis(50)).await;
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        // 最後の変更だけが公開される
+        assert_eq!(*published.lock().unwrap(), vec![5]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_spaced_edits_and_documents_publish_separately() {
+        let debouncer = Arc::new(Debouncer::new(DELAY));
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let first = Url::parse("file:///project/a.c").unwrap();
+        let second = Url::parse("file:///project/b.c").unwrap();
+
+        edit(&debouncer, &published, &first, 1).await.unwrap();
+        let a = edit(&debouncer, &published, &first, 2);
+        let b = edit(&debouncer, &published, &second, 3);
+        a.await.unwrap();
+        b.await.unwrap();
+
+        // 遅延より間隔の空いた変更と、別ドキュメントの変更はそれぞれ公開される
+        let mut versions = published.lock().unwrap().clone();
+        versions.sort();
+        assert_eq!(versions, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_newer_schedule_invalidates_older_generation() {
+        let debouncer = Debouncer::new(DELAY);
+        let uri = Url::parse("file:///project/main.c").unwrap();
+
+        let old = debouncer.schedule(&uri);
+        let new = debouncer.schedule(&uri);
+        assert!(!debouncer.is_latest(&uri, old));
+        assert!(debouncer.is_latest(&uri, new));
+    }
+
+    #[test]
+    fn test_forget_cancels_pending_generation() {
+        let debouncer = Debouncer::new(DELAY);
+        let uri = Url::parse("file:///project/main.c").unwrap();
+
+        let old = debouncer.schedule(&uri);
+        debouncer.forget(&uri);
+        assert!(!debouncer.is_latest(&uri, old));
+
+        // 開き直したあとの世代は、破棄前の世代と重ならない
+        let reopened = debouncer.schedule(&uri);
+        assert_ne!(old, reopened);
+        assert!(!debouncer.is_latest(&uri, old));
+    }
+}
@@ -1,19 +1,42 @@
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
-use coding_guide_helper_core::{Lexer, Parser, diagnose, DiagnosticSeverity, LoadedProjectConfig};
+use coding_guide_helper_core::{Lexer, Parser, analyze_with_preprocessor, DiagnosticSeverity, LoadedProjectConfig, Span, TranslationUnit};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
-#[derive(Debug)]
+mod debounce;
+mod symbol_index;
+use debounce::Debouncer;
+use symbol_index::{span_to_range, SymbolIndex};
+
+/// 編集が止まってから診断を再計算するまでの待ち時間
+const DIAGNOSTICS_DEBOUNCE: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone)]
 struct Backend {
     client: Client,
     config: Arc<RwLock<LoadedProjectConfig>>,
+    symbol_index: Arc<RwLock<SymbolIndex>>,  // 開いたファイルのシンボル（workspace/symbol 用）
+    debouncer: Arc<Debouncer>,  // ドキュメントごとの診断の間引き
+    documents: Arc<RwLock<HashMap<Url, String>>>,  // 開いているドキュメントの最新のテキスト
+    pull_diagnostics: Arc<AtomicBool>,  // クライアントが textDocument/diagnostic で診断を取得するかどうか
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
     async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        // プル型の診断に対応したクライアントには診断をプッシュしない（二重に表示されるため）
+        let pull = params
+            .capabilities
+            .text_document
+            .as_ref()
+            .is_some_and(|text_document| text_document.diagnostic.is_some());
+        self.pull_diagnostics.store(pull, Ordering::Relaxed);
+
         // ワークスペースルートから設定を読み込む
         if let Some(workspace_folders) = params.workspace_folders {
             if let Some(folder) = workspace_folders.first() {
@@ -45,6 +68,14 @@ impl LanguageServer for Backend {
                     TextDocumentSyncKind::FULL,
                 )),
                 document_formatting_provider: Some(OneOf::Left(true)),
+                diagnostic_provider: Some(DiagnosticServerCapabilities::Options(DiagnosticOptions {
+                    identifier: Some("coding-guide-helper".to_string()),
+                    // #include したヘッダーの内容が診断に影響する
+                    inter_file_dependencies: true,
+                    workspace_diagnostics: false,
+                    ..Default::default()
+                })),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
                 ..ServerCapabilities::default()
             },
         })
@@ -78,6 +109,14 @@ impl LanguageServer for Backend {
         }
     }
 
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        // 閉じたドキュメントのバッファ・予約済みの診断・シンボルを破棄する
+        let uri = params.text_document.uri;
+        self.documents.write().await.remove(&uri);
+        self.debouncer.forget(&uri);
+        self.symbol_index.write().await.remove(&uri);
+    }
+
     async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
         let uri = params.text_document.uri;
         
@@ -94,7 +133,7 @@ impl LanguageServer for Backend {
                 }
                 let tu = parser.parse();
                 
-                let formatter = coding_guide_helper_core::Formatter::new();
+                let formatter = coding_guide_helper_core::Formatter::new_with_config(&config.config.formatting);
                 let formatted = formatter.format_tu(&tu);
                 
                 // 全体を置換するTextEditを返す
@@ -120,21 +159,79 @@ impl LanguageServer for Backend {
         
         Ok(None)
     }
+
+    async fn diagnostic(&self, params: DocumentDiagnosticParams) -> Result<DocumentDiagnosticReportResult> {
+        let uri = params.text_document.uri;
+        // 開いているドキュメントはバッファの内容を、それ以外はファイルの内容を診断する
+        let cached = self.documents.read().await.get(&uri).cloned();
+        let text = cached.or_else(|| uri.to_file_path().ok().and_then(|path| std::fs::read_to_string(path).ok()));
+        let items = match text {
+            Some(text) => self.compute_diagnostics(&uri, &text).await,
+            None => Vec::new(),
+        };
+
+        Ok(DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(
+            RelatedFullDocumentDiagnosticReport {
+                related_documents: None,
+                full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                    result_id: None,
+                    items,
+                },
+            },
+        )))
+    }
+
+    async fn symbol(&self, params: WorkspaceSymbolParams) -> Result<Option<Vec<SymbolInformation>>> {
+        let index = self.symbol_index.read().await;
+        Ok(Some(index.search(&params.query)))
+    }
 }
 
 impl Backend {
+    fn new(client: Client, config: LoadedProjectConfig) -> Self {
+        Backend {
+            client,
+            config: Arc::new(RwLock::new(config)),
+            symbol_index: Arc::new(RwLock::new(SymbolIndex::new())),
+            debouncer: Arc::new(Debouncer::new(DIAGNOSTICS_DEBOUNCE)),
+            documents: Arc::new(RwLock::new(HashMap::new())),
+            pull_diagnostics: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 診断の再計算を予約する
+    ///
+    /// 予約は通知を受け取った順に行うため、最後に届いたテキストの診断だけが公開される。
     async fn on_change(&self, uri: Url, text: String) {
+        self.documents.write().await.insert(uri.clone(), text.clone());
+        let generation = self.debouncer.schedule(&uri);
+        let backend = self.clone();
+        tokio::spawn(async move {
+            if backend.debouncer.wait(&uri, generation).await {
+                backend.diagnose_and_publish(uri, text, generation).await;
+            }
+        });
+    }
+
+    async fn diagnose_and_publish(&self, uri: Url, text: String, generation: u64) {
+        // プル型のクライアントでも、workspace/symbol 用のシンボルはここで更新する
+        let lsp_diagnostics = self.compute_diagnostics(&uri, &text).await;
+
+        // 解析中に新しい変更が来ていれば、古いテキストの診断は公開しない
+        if !self.debouncer.is_latest(&uri, generation) || self.pull_diagnostics.load(Ordering::Relaxed) {
+            return;
+        }
+        self.client
+            .publish_diagnostics(uri, lsp_diagnostics, None)
+            .await;
+    }
+
+    /// テキストを解析して LSP の診断に変換する（プッシュ・プル両方の診断で共有）
+    async fn compute_diagnostics(&self, uri: &Url, text: &str) -> Vec<Diagnostic> {
         // パースして診断を実行
         let config = self.config.read().await;
 
-        let mut parser = Parser::new_with_config(Lexer::new(&text), config.to_preprocessor_config());
         let source_path = uri.to_file_path().ok();
-        if let Some(path) = &source_path {
-            if let Some(parent) = path.parent() {
-                parser.set_current_file_dir(parent);
-            }
-        }
-        let tu = parser.parse();
         
         // プロジェクト設定から診断設定を取得
         let diag_config = config.to_diagnostic_config_with_path(source_path.as_ref());
@@ -151,10 +248,16 @@ impl Backend {
             )
             .await;
         
-        let diagnostics = diagnose(&tu, &diag_config);
+        let result = analyze_with_preprocessor(text, &diag_config, config.to_preprocessor_config());
+        self.symbol_index
+            .write()
+            .await
+            .update(uri, result.translation_unit.symbols());
+        let tu = &result.translation_unit;
+        let diagnostics: Vec<_> = result.parse_diagnostics.iter().chain(&result.diagnostics).collect();
         
         // LSP Diagnosticに変換
-        let lsp_diagnostics: Vec<Diagnostic> = diagnostics
+        diagnostics
             .iter()
             .map(|diag| {
                 let severity = match diag.severity {
@@ -165,31 +268,48 @@ impl Backend {
                 };
                 
                 Diagnostic {
-                    range: Range {
-                        start: Position {
-                            line: diag.span.start_line as u32,
-                            character: diag.span.start_column as u32,
-                        },
-                        end: Position {
-                            line: diag.span.end_line as u32,
-                            character: diag.span.end_column as u32,
-                        },
-                    },
+                    range: span_to_range(&diag.span),
                     severity,
                     code: Some(NumberOrString::String(diag.code.to_string())),
                     source: Some("coding-guide-helper".to_string()),
                     message: diag.message.clone(),
+                    related_information: (!diag.related.is_empty()).then(|| {
+                        diag.related
+                            .iter()
+                            .map(|(span, message)| DiagnosticRelatedInformation {
+                                location: related_location(tu, uri, span),
+                                message: message.clone(),
+                            })
+                            .collect()
+                    }),
                     ..Default::default()
                 }
             })
-            .collect();
-        
-        self.client
-            .publish_diagnostics(uri, lsp_diagnostics, None)
-            .await;
+            .collect()
     }
 }
 
+/// 関連する位置を LSP の Location に変換する
+///
+/// `#line` でファイル名が指定された範囲の位置はそのファイル（相対パスは診断対象のディレクトリ基準）の
+/// 論理行で、それ以外は診断対象のドキュメント内の位置で表す。
+fn related_location(tu: &TranslationUnit, uri: &Url, span: &Span) -> Location {
+    let (logical_line, file) = tu.logical_position(span.start_line);
+    let file_uri = file.and_then(|file| {
+        let base = uri.to_file_path().ok()?;
+        Url::from_file_path(base.parent()?.join(file)).ok()
+    });
+    let Some(file_uri) = file_uri else {
+        return Location { uri: uri.clone(), range: span_to_range(span) };
+    };
+
+    let to_logical = |line: u32| (line as usize + logical_line).saturating_sub(span.start_line) as u32;
+    let mut range = span_to_range(span);
+    range.start.line = to_logical(range.start.line);
+    range.end.line = to_logical(range.end.line);
+    Location { uri: file_uri, range }
+}
+
 #[tokio::main]
 async fn main() {
     env_logger::init();
@@ -197,11 +317,166 @@ async fn main() {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(|client| Backend { 
-        client,
-        config: Arc::new(RwLock::new(LoadedProjectConfig::find_and_load_with_root(
-            std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")),
-        ))),
+    let (service, socket) = LspService::new(|client| {
+        Backend::new(
+            client,
+            LoadedProjectConfig::find_and_load_with_root(
+                std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")),
+            ),
+        )
     });
     Server::new(stdin, stdout, socket).serve(service).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coding_guide_helper_core::ProjectConfig;
+
+    fn backend_service(project_root: std::path::PathBuf) -> (LspService<Backend>, tower_lsp::ClientSocket) {
+        LspService::new(|client| {
+            Backend::new(
+                client,
+                LoadedProjectConfig {
+                    config: ProjectConfig::default(),
+                    project_root,
+                },
+            )
+        })
+    }
+
+    fn symbol_params(query: &str) -> WorkspaceSymbolParams {
+        WorkspaceSymbolParams {
+            query: query.to_string(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        }
+    }
+
+    fn pull_diagnostics(report: DocumentDiagnosticReportResult) -> Vec<Diagnostic> {
+        match report {
+            DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(full)) => {
+                full.full_document_diagnostic_report.items
+            }
+            other => panic!("Expected full report, got {:?}", other),
+        }
+    }
+
+    fn diagnostic_params(uri: &Url) -> DocumentDiagnosticParams {
+        DocumentDiagnosticParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            identifier: None,
+            previous_result_id: None,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pull_diagnostics_uses_cached_buffer() {
+        let project_root = std::env::temp_dir();
+        let (service, _socket) = backend_service(project_root.clone());
+        let backend = service.inner();
+        let uri = Url::from_file_path(project_root.join("pull_diagnostics_test.c")).unwrap();
+
+        // ディスク上にないファイルでも、開いたバッファの内容を診断する
+        backend.on_change(uri.clone(), "foo(void) {\n}\n".to_string()).await;
+        let items = pull_diagnostics(backend.diagnostic(diagnostic_params(&uri)).await.unwrap());
+
+        let implicit_int = items
+            .iter()
+            .find(|d| d.code == Some(NumberOrString::String("CGH026".to_string())))
+            .expect("CGH026 should be reported");
+        assert_eq!(implicit_int.range.start.line, 0);
+        assert_eq!(implicit_int.source.as_deref(), Some("coding-guide-helper"));
+    }
+
+    #[tokio::test]
+    async fn test_initialize_detects_pull_diagnostics_capability() {
+        let (service, _socket) = backend_service(std::env::temp_dir());
+        let backend = service.inner();
+
+        backend.initialize(InitializeParams::default()).await.unwrap();
+        assert!(!backend.pull_diagnostics.load(Ordering::Relaxed));
+
+        let params = InitializeParams {
+            capabilities: ClientCapabilities {
+                text_document: Some(TextDocumentClientCapabilities {
+                    diagnostic: Some(DiagnosticClientCapabilities::default()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        backend.initialize(params).await.unwrap();
+        assert!(backend.pull_diagnostics.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_did_close_discards_document_state() {
+        let project_root = std::env::temp_dir();
+        let (service, _socket) = backend_service(project_root.clone());
+        let backend = service.inner();
+        let uri = Url::from_file_path(project_root.join("did_close_test.c")).unwrap();
+        let text = "int closed_document_value;\n".to_string();
+
+        backend.on_change(uri.clone(), text.clone()).await;
+        backend.compute_diagnostics(&uri, &text).await;
+        let symbols = backend.symbol(symbol_params("closed_document_value")).await.unwrap().unwrap();
+        assert_eq!(symbols.len(), 1);
+
+        backend
+            .did_close(DidCloseTextDocumentParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+            })
+            .await;
+        assert!(!backend.documents.read().await.contains_key(&uri));
+        assert!(!backend.debouncer.is_latest(&uri, 1));
+        let symbols = backend.symbol(symbol_params("closed_document_value")).await.unwrap().unwrap();
+        assert!(symbols.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_related_information_uses_line_directive_file() {
+        let project_root = std::env::temp_dir();
+        let (service, _socket) = backend_service(project_root.clone());
+        let uri = Url::from_file_path(project_root.join("related_location_test.c")).unwrap();
+        let text = "#line 10 \"defs.h\"\n#define SIZE 1\n#line 1 \"related_location_test.c\"\n#define SIZE 2\n";
+
+        let items = service.inner().compute_diagnostics(&uri, text).await;
+        let redefinition = items
+            .iter()
+            .find(|d| d.code == Some(NumberOrString::String("CGH015".to_string())))
+            .expect("CGH015 should be reported");
+        let related = &redefinition.related_information.as_ref().unwrap()[0];
+        assert_eq!(related.location.uri, Url::from_file_path(project_root.join("defs.h")).unwrap());
+        assert_eq!(related.location.range.start.line, 9);
+    }
+
+    #[tokio::test]
+    async fn test_related_information_in_same_document_uses_document_uri() {
+        let project_root = std::env::temp_dir();
+        let (service, _socket) = backend_service(project_root.clone());
+        let uri = Url::from_file_path(project_root.join("related_same_file_test.c")).unwrap();
+        let text = "#define SIZE 1\n#define SIZE 2\n";
+
+        let items = service.inner().compute_diagnostics(&uri, text).await;
+        let redefinition = items
+            .iter()
+            .find(|d| d.code == Some(NumberOrString::String("CGH015".to_string())))
+            .expect("CGH015 should be reported");
+        let related = &redefinition.related_information.as_ref().unwrap()[0];
+        assert_eq!(related.location.uri, uri);
+        assert_eq!(related.location.range.start.line, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pull_diagnostics_unknown_document_is_empty() {
+        let (service, _socket) = backend_service(std::env::temp_dir());
+        let uri = Url::parse("file:///nonexistent/missing.c").unwrap();
+
+        let items = pull_diagnostics(service.inner().diagnostic(diagnostic_params(&uri)).await.unwrap());
+        assert!(items.is_empty());
+    }
+}
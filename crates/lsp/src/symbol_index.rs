@@ -0,0 +1,158 @@
+use coding_guide_helper_core::{Span, Symbol, SymbolKind as CoreSymbolKind};
+use std::collections::HashMap;
+use tower_lsp::lsp_types::{Location, Position, Range, SymbolInformation, SymbolKind, Url};
+
+/// 開いたファイルのシンボルを名前で引けるようにしたワークスペース全体の索引
+///
+/// did_open / did_change のたびに `update` でそのファイルの分を入れ替える。
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    by_name: HashMap<String, Vec<(Url, Symbol)>>,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ファイルのシンボルを登録する（以前に登録したそのファイルのシンボルは取り除く）
+    pub fn update(&mut self, uri: &Url, symbols: Vec<Symbol>) {
+        self.remove(uri);
+        for symbol in symbols {
+            self.by_name
+                .entry(symbol.name.clone())
+                .or_default()
+                .push((uri.clone(), symbol));
+        }
+    }
+
+    /// ファイルのシンボルを索引から取り除く
+    pub fn remove(&mut self, uri: &Url) {
+        self.by_name.retain(|_, entries| {
+            entries.retain(|(entry_uri, _)| entry_uri != uri);
+            !entries.is_empty()
+        });
+    }
+
+    /// クエリに一致するシンボルを一致度の高い順に返す
+    ///
+    /// 大文字小文字を区別せず、完全一致 → 前方一致 → 部分一致 → あいまい一致
+    /// （クエリの文字が順番どおりに現れる）の順に並べる。空のクエリはすべてのシンボルに一致する。
+    pub fn search(&self, query: &str) -> Vec<SymbolInformation> {
+        let query = query.to_lowercase();
+        let mut matches: Vec<(u8, &String, &Url, &Symbol)> = self
+            .by_name
+            .iter()
+            .filter_map(|(name, entries)| match_rank(&name.to_lowercase(), &query).map(|rank| (rank, name, entries)))
+            .flat_map(|(rank, name, entries)| entries.iter().map(move |(uri, symbol)| (rank, name, uri, symbol)))
+            .collect();
+        matches.sort_by(|a, b| {
+            (a.0, a.1, a.2.as_str(), a.3.span.start_line).cmp(&(b.0, b.1, b.2.as_str(), b.3.span.start_line))
+        });
+
+        matches
+            .into_iter()
+            .map(|(_, _, uri, symbol)| to_symbol_information(uri, symbol))
+            .collect()
+    }
+}
+
+/// 名前がクエリに一致する場合、一致の種類（小さいほど良い）を返す
+fn match_rank(name: &str, query: &str) -> Option<u8> {
+    if name == query {
+        Some(0)
+    } else if name.starts_with(query) {
+        Some(1)
+    } else if name.contains(query) {
+        Some(2)
+    } else {
+        let mut chars = name.chars();
+        query.chars().all(|q| chars.any(|c| c == q)).then_some(3)
+    }
+}
+
+#[allow(deprecated)] // SymbolInformation::deprecated は非推奨だが構造体の初期化に必要
+fn to_symbol_information(uri: &Url, symbol: &Symbol) -> SymbolInformation {
+    SymbolInformation {
+        name: symbol.name.clone(),
+        kind: lsp_symbol_kind(symbol.kind),
+        tags: None,
+        deprecated: None,
+        location: Location {
+            uri: uri.clone(),
+            range: span_to_range(&symbol.span),
+        },
+        container_name: None,
+    }
+}
+
+/// LSP には typedef・共用体に当たる種類がないため、近いものに対応付ける
+fn lsp_symbol_kind(kind: CoreSymbolKind) -> SymbolKind {
+    match kind {
+        CoreSymbolKind::Function => SymbolKind::FUNCTION,
+        CoreSymbolKind::Variable => SymbolKind::VARIABLE,
+        CoreSymbolKind::Struct | CoreSymbolKind::Union => SymbolKind::STRUCT,
+        CoreSymbolKind::Enum => SymbolKind::ENUM,
+        CoreSymbolKind::Typedef => SymbolKind::CLASS,
+        CoreSymbolKind::EnumVariant => SymbolKind::ENUM_MEMBER,
+        CoreSymbolKind::Macro => SymbolKind::CONSTANT,
+    }
+}
+
+/// コアの Span を LSP の Range に変換する（診断・シンボルで共通）
+pub fn span_to_range(span: &Span) -> Range {
+    let (start_line, start_character, end_line, end_character) = span.to_lsp_range();
+    Range {
+        start: Position::new(start_line, start_character),
+        end: Position::new(end_line, end_character),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coding_guide_helper_core::{Lexer, Parser};
+
+    fn symbols_of(source: &str) -> Vec<Symbol> {
+        Parser::new(Lexer::new(source)).parse().symbols()
+    }
+
+    #[test]
+    fn test_search_finds_symbol_in_second_document() {
+        let first = Url::parse("file:///project/a.c").unwrap();
+        let second = Url::parse("file:///project/b.c").unwrap();
+        let mut index = SymbolIndex::new();
+        index.update(&first, symbols_of("int counter;\nvoid start(void) {\n}\n"));
+        index.update(&second, symbols_of("#define BUFFER_SIZE 64\nvoid uart_send(int value) {\n}\n"));
+
+        let results = index.search("uart_send");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].location.uri, second);
+        assert_eq!(results[0].kind, SymbolKind::FUNCTION);
+        assert_eq!(results[0].location.range.start.line, 1);
+    }
+
+    #[test]
+    fn test_search_substring_and_fuzzy_ranking() {
+        let uri = Url::parse("file:///project/a.c").unwrap();
+        let mut index = SymbolIndex::new();
+        index.update(&uri, symbols_of("int uart_send;\nint send;\nint sensor_data;\nint disable_uart;\n"));
+
+        let names: Vec<String> = index.search("SEND").into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["send", "uart_send", "sensor_data"]);
+
+        let names: Vec<String> = index.search("sdt").into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["sensor_data"]);
+    }
+
+    #[test]
+    fn test_update_replaces_previous_symbols() {
+        let uri = Url::parse("file:///project/a.c").unwrap();
+        let mut index = SymbolIndex::new();
+        index.update(&uri, symbols_of("int old_name;\n"));
+        index.update(&uri, symbols_of("int new_name;\n"));
+
+        assert!(index.search("old_name").is_empty());
+        assert_eq!(index.search("new_name").len(), 1);
+    }
+}